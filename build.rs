@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Exposes PKMGR_GIT_COMMIT and PKMGR_BUILD_DATE to the crate for
+/// `--version` and `pkmgr version --full`, and embeds the release signing
+/// keyring for `update-self` when one is available
+fn main() {
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // Reproducible builds pin the date through SOURCE_DATE_EPOCH
+    let epoch = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+
+    println!("cargo:rustc-env=PKMGR_GIT_COMMIT={}", commit);
+    println!("cargo:rustc-env=PKMGR_BUILD_DATE={}", civil_date(epoch / 86_400));
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // Binary (not armored) export of the release public key; release builds
+    // point PKMGR_RELEASE_KEYRING at it, otherwise keys/release.gpg is used
+    println!("cargo:rustc-check-cfg=cfg(release_keyring)");
+    println!("cargo:rerun-if-env-changed=PKMGR_RELEASE_KEYRING");
+    println!("cargo:rerun-if-changed=keys/release.gpg");
+    let keyring = std::env::var("PKMGR_RELEASE_KEYRING")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| Path::new(env!("CARGO_MANIFEST_DIR")).join("keys").join("release.gpg"));
+    if keyring.is_file() {
+        println!("cargo:rustc-cfg=release_keyring");
+        println!("cargo:rustc-env=PKMGR_RELEASE_KEYRING={}", keyring.display());
+    }
+}
+
+/// YYYY-MM-DD for a count of days since 1970-01-01 (Howard Hinnant's
+/// civil_from_days)
+fn civil_date(days: u64) -> String {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -1,18 +1,28 @@
 use anyhow::{Result, bail, Context};
 use std::path::PathBuf;
-use reqwest;
 use serde_json::Value;
 use tokio::fs;
+use crate::core::config::Config;
 use crate::ui::output::Output;
+use super::github;
+use crate::utils::download::{http_client, request_error};
 
 pub struct BinaryDownloader {
     output: Output,
     install_dir: PathBuf,
+    github_token: Option<String>,
 }
 
 impl BinaryDownloader {
     pub fn new(output: Output, install_dir: PathBuf) -> Self {
-        Self { output, install_dir }
+        Self { output, install_dir, github_token: github::token_from_env() }
+    }
+
+    /// Authenticate GitHub requests with the environment token, else
+    /// `binary_sources.github_token`
+    pub fn with_config(mut self, config: &Config) -> Self {
+        self.github_token = github::resolve_token(config);
+        self
     }
 
     pub async fn download_from_github(&self, repo: &str, version: Option<&str>) -> Result<PathBuf> {
@@ -25,13 +35,14 @@ impl BinaryDownloader {
             format!("https://api.github.com/repos/{}/releases/latest", repo)
         };
 
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&release_url)
-            .header("User-Agent", "pkmgr/1.0.0")
+        let client = http_client()?;
+        let request = client.get(&release_url);
+        let response = github::authorize(request, self.github_token.as_deref())
             .send()
             .await
-            .context("Failed to fetch release info")?;
+            .map_err(|e| request_error(e, "fetch release info"))?;
+
+        github::check_rate_limit(&response)?;
 
         if !response.status().is_success() {
             bail!("Failed to fetch release: {}", response.status());
@@ -136,9 +147,9 @@ impl BinaryDownloader {
 
         self.output.info(&format!("📥 Downloading {}", filename));
 
-        let client = reqwest::Client::new();
+        let client = http_client()?;
         let response = client.get(url).send().await
-            .context("Failed to download asset")?;
+            .map_err(|e| request_error(e, "download asset"))?;
 
         if !response.status().is_success() {
             bail!("Download failed with status: {}", response.status());
@@ -335,13 +346,14 @@ impl BinaryDownloader {
 
         let search_url = format!("https://api.github.com/search/repositories?q={}&sort=stars&order=desc", query);
 
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&search_url)
-            .header("User-Agent", "pkmgr/1.0.0")
+        let client = http_client()?;
+        let request = client.get(&search_url);
+        let response = github::authorize(request, self.github_token.as_deref())
             .send()
             .await
-            .context("Failed to search GitHub")?;
+            .map_err(|e| request_error(e, "search GitHub"))?;
+
+        github::check_rate_limit(&response)?;
 
         if !response.status().is_success() {
             bail!("GitHub search failed: {}", response.status());
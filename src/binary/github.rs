@@ -0,0 +1,109 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, TimeZone, Utc};
+use reqwest::{Response, StatusCode};
+use crate::core::config::Config;
+
+const TOKEN_ENV_VARS: &[&str] = &["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// Resolve the GitHub API token, preferring the environment over the config file
+pub fn resolve_token(config: &Config) -> Option<String> {
+    token_from_env().or_else(|| {
+        config.binary_sources.github_token.clone()
+            .filter(|t| !t.trim().is_empty())
+    })
+}
+
+/// Resolve the GitHub API token from the environment only
+pub fn token_from_env() -> Option<String> {
+    TOKEN_ENV_VARS.iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|t| !t.trim().is_empty())
+}
+
+/// Attach the standard GitHub API headers and bearer token to a request
+pub fn authorize(request: reqwest::RequestBuilder, token: Option<&str>) -> reqwest::RequestBuilder {
+    let request = request.header("Accept", "application/vnd.github+json");
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+/// Turn a rate-limited response into an error that says when access resumes
+pub fn check_rate_limit(response: &Response) -> Result<()> {
+    let status = response.status();
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return Ok(());
+    }
+
+    let remaining = header_u64(response, "x-ratelimit-remaining");
+    if status == StatusCode::FORBIDDEN && remaining != Some(0) {
+        return Ok(());
+    }
+
+    let resumes = header_u64(response, "x-ratelimit-reset")
+        .and_then(|reset| Utc.timestamp_opt(reset as i64, 0).single());
+
+    Err(rate_limit_error(resumes))
+}
+
+fn rate_limit_error(resumes: Option<DateTime<Utc>>) -> anyhow::Error {
+    let hint = if token_from_env().is_some() {
+        ""
+    } else {
+        " Set GITHUB_TOKEN (or binary_sources.github_token in config) for a higher limit."
+    };
+
+    match resumes {
+        Some(reset) => {
+            let wait = (reset - Utc::now()).num_minutes().max(0);
+            anyhow!(
+                "GitHub API rate limit exceeded; access resumes at {} (in about {} min).{}",
+                reset.with_timezone(&Local).format("%H:%M:%S"),
+                wait,
+                hint
+            )
+        }
+        None => anyhow!("GitHub API rate limit exceeded.{}", hint),
+    }
+}
+
+fn header_u64(response: &Response, name: &str) -> Option<u64> {
+    response.headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// Current core API rate-limit usage for a token
+#[derive(Debug, Clone)]
+pub struct RateLimitStatus {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset: DateTime<Utc>,
+}
+
+/// Query the rate-limit endpoint, which does not count against the quota
+pub async fn fetch_rate_limit(token: &str) -> Result<RateLimitStatus> {
+    let client = crate::utils::download::http_client()?;
+
+    let response = authorize(client.get("https://api.github.com/rate_limit"), Some(token))
+        .send()
+        .await
+        .map_err(|e| crate::utils::download::request_error(e, "query GitHub rate limit"))?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("GitHub rate limit query failed: HTTP {}", response.status());
+    }
+
+    let body: serde_json::Value = response.json().await?;
+    let core = &body["resources"]["core"];
+
+    Ok(RateLimitStatus {
+        limit: core["limit"].as_u64().unwrap_or(0),
+        remaining: core["remaining"].as_u64().unwrap_or(0),
+        reset: core["reset"].as_i64()
+            .and_then(|r| Utc.timestamp_opt(r, 0).single())
+            .unwrap_or_else(Utc::now),
+    })
+}
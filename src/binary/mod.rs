@@ -1,3 +1,4 @@
 pub mod downloader;
+pub mod github;
 
 pub use downloader::BinaryDownloader;
\ No newline at end of file
@@ -6,6 +6,72 @@ use crate::cache::{CacheConfig, CacheEntry, CacheType, CacheStats, format_size};
 use crate::cache::manager::CacheManager;
 use crate::ui::output::Output;
 use crate::ui::prompt::Prompt;
+use crate::core::exit::Cancelled;
+
+/// Outcome of an eviction pass
+#[derive(Debug, Clone, Default)]
+pub struct EvictionReport {
+    pub freed_bytes: u64,
+    pub entries_removed: usize,
+    pub by_type: Vec<(CacheType, u64, usize)>,  // (type, freed, count)
+    pub target_reached: bool,
+}
+
+impl EvictionReport {
+    fn record(&mut self, entry: &CacheEntry) {
+        self.freed_bytes += entry.size;
+        self.entries_removed += 1;
+
+        match self.by_type.iter_mut().find(|(t, _, _)| *t == entry.cache_type) {
+            Some((_, size, count)) => {
+                *size += entry.size;
+                *count += 1;
+            }
+            None => self.by_type.push((entry.cache_type.clone(), entry.size, 1)),
+        }
+    }
+}
+
+/// How far below the cleanup threshold an eviction pass trims, so the next
+/// insert does not immediately trigger another pass
+const EVICTION_MARGIN: f64 = 0.1;
+
+/// Size the cache is trimmed down to once it crosses the cleanup threshold
+pub fn eviction_target(config: &CacheConfig) -> u64 {
+    let ratio = (config.cleanup_threshold as f64 - EVICTION_MARGIN).max(0.0);
+    (config.max_size as f64 * ratio) as u64
+}
+
+/// Pick entries to evict until `total_size` drops to `target`: most disposable
+/// types first, least recently accessed first within a type. ISOs and language
+/// versions are only considered when `force` is set.
+pub fn plan_eviction(entries: &[CacheEntry], total_size: u64, target: u64, force: bool) -> Vec<CacheEntry> {
+    if total_size <= target {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<&CacheEntry> = entries.iter()
+        .filter(|e| force || !e.cache_type.is_protected())
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        b.cache_type.cleanup_priority()
+            .cmp(&a.cache_type.cleanup_priority())
+            .then(a.last_accessed.cmp(&b.last_accessed))
+    });
+
+    let mut remaining = total_size;
+    let mut plan = Vec::new();
+    for entry in candidates {
+        if remaining <= target {
+            break;
+        }
+        remaining = remaining.saturating_sub(entry.size);
+        plan.push(entry.clone());
+    }
+
+    plan
+}
 
 pub struct CacheCleaner {
     pub manager: CacheManager,
@@ -53,8 +119,7 @@ impl CacheCleaner {
 
         if !force && !self.dry_run {
             if !self.prompt.confirm("Clean all cache entries?")? {
-                self.output.info("Cleanup cancelled");
-                return Ok(());
+                return Err(Cancelled("Cleanup cancelled".to_string()).into());
             }
         }
 
@@ -110,6 +175,69 @@ impl CacheCleaner {
         Ok(())
     }
 
+    /// Evict entries until the cache is back under its eviction target
+    pub async fn evict(&mut self, force: bool) -> Result<EvictionReport> {
+        let stats = self.manager.get_stats()?;
+        let target = eviction_target(&self.config);
+        let mut report = EvictionReport {
+            target_reached: stats.total_size <= target,
+            ..Default::default()
+        };
+
+        if report.target_reached {
+            return Ok(report);
+        }
+
+        self.output.progress(&format!("🧹 Evicting cache entries down to {}...", format_size(target)));
+
+        let entries: Vec<CacheEntry> = self.manager.index.values().cloned().collect();
+        let plan = plan_eviction(&entries, stats.total_size, target, force);
+
+        for entry in &plan {
+            if self.dry_run {
+                self.output.info(&format!("  Would evict: {} ({})",
+                    entry.path.display(),
+                    format_size(entry.size)
+                ));
+                report.record(entry);
+            } else if self.remove_entry(entry).await? {
+                report.record(entry);
+            }
+        }
+
+        report.target_reached = stats.total_size.saturating_sub(report.freed_bytes) <= target;
+
+        let verb = if self.dry_run { "Would free" } else { "Freed" };
+        for (cache_type, size, count) in &report.by_type {
+            self.output.info(&format!("  {} {}: {} ({} entries)",
+                verb,
+                cache_type.display_name(),
+                format_size(*size),
+                count
+            ));
+        }
+
+        if report.target_reached {
+            self.output.success(&format!("✅ {} {} ({} entries)",
+                verb,
+                format_size(report.freed_bytes),
+                report.entries_removed
+            ));
+        } else {
+            self.output.warn(&format!("⚠️  {} {} ({} entries) but cache is still over {}",
+                verb,
+                format_size(report.freed_bytes),
+                report.entries_removed,
+                format_size(target)
+            ));
+            if !force {
+                self.output.info("💡 ISOs and language versions are kept unless cleanup is forced");
+            }
+        }
+
+        Ok(report)
+    }
+
     /// Clean specific cache type
     pub async fn clean_type(&mut self, cache_type: &CacheType, entries: Vec<CacheEntry>) -> Result<(u64, usize)> {
         if entries.is_empty() {
@@ -141,110 +269,96 @@ impl CacheCleaner {
 
     /// Clean expired entries only
     pub async fn clean_expired(&mut self) -> Result<()> {
-        self.output.section("Cleaning Expired Cache Entries");
-
-        let expired: Vec<CacheEntry> = self.manager
-            .index
-            .values()
-            .filter(|e| e.is_expired())
-            .cloned()
-            .collect();
+        self.clean_selected(true, false).await
+    }
 
-        if expired.is_empty() {
-            self.output.success("✅ No expired entries found");
+    /// Clean entries matching any of the selectors: TTL-expired entries
+    /// and/or entries not accessed within `stale_days` (protected types excluded)
+    pub async fn clean_selected(&mut self, expired: bool, stale: bool) -> Result<()> {
+        let title = match (expired, stale) {
+            (true, true) => "Cleaning Expired and Stale Cache Entries",
+            (true, false) => "Cleaning Expired Cache Entries",
+            _ => "Cleaning Stale Cache Entries",
+        };
+        self.output.section(title);
+
+        let stale_days = self.config.stale_days;
+        let mut selected = self.select(expired, stale);
+
+        if selected.is_empty() {
+            let what = match (expired, stale) {
+                (true, true) => format!("expired or stale (>{} days)", stale_days),
+                (true, false) => "expired".to_string(),
+                _ => format!("stale (>{} days)", stale_days),
+            };
+            self.output.success(&format!("✅ No {} entries found", what));
             return Ok(());
         }
 
-        self.output.info(&format!("🗑️  Found {} expired entries", expired.len()));
-
-        let mut total_size = 0u64;
-        let mut count = 0usize;
+        selected.sort_by(|a, b| a.key.cmp(&b.key));
+        self.output.info(&format!("🗑️  Found {} matching entries", selected.len()));
 
-        for entry in expired {
+        let mut report = EvictionReport::default();
+        for entry in &selected {
             if self.dry_run {
-                self.output.info(&format!("  Would remove: {}",
+                self.output.info(&format!("  Would remove: {} ({})",
                     entry.path.file_name()
                         .and_then(|n| n.to_str())
-                        .unwrap_or("unknown")
+                        .unwrap_or("unknown"),
+                    format_size(entry.size)
                 ));
-                total_size += entry.size;
-                count += 1;
-            } else {
-                if self.remove_entry(&entry).await? {
-                    total_size += entry.size;
-                    count += 1;
-                }
+                report.record(entry);
+            } else if self.remove_entry(entry).await? {
+                report.record(entry);
             }
         }
 
         if self.dry_run {
+            for (cache_type, size, count) in &report.by_type {
+                self.output.info(&format!("  {}: {} ({} entries)",
+                    cache_type.display_name(),
+                    format_size(*size),
+                    count
+                ));
+            }
             self.output.info(&format!("🔍 Dry run - would clean: {} ({} entries)",
-                format_size(total_size),
-                count
+                format_size(report.freed_bytes),
+                report.entries_removed
             ));
         } else {
-            self.output.success(&format!("✅ Cleaned expired: {} ({} entries)",
-                format_size(total_size),
-                count
+            self.output.success(&format!("✅ Cleaned: {} ({} entries)",
+                format_size(report.freed_bytes),
+                report.entries_removed
             ));
         }
 
         Ok(())
     }
 
-    /// Clean stale entries (not accessed recently)
-    pub async fn clean_stale(&mut self) -> Result<()> {
-        self.output.section("Cleaning Stale Cache Entries");
-
-        let stale: Vec<CacheEntry> = self.manager
+    /// Entries `clean_selected` would remove for the same selectors
+    pub fn select(&self, expired: bool, stale: bool) -> Vec<CacheEntry> {
+        let stale_days = self.config.stale_days;
+        self.manager
             .index
             .values()
-            .filter(|e| e.is_stale(self.config.stale_days))
+            .filter(|e| {
+                (expired && e.is_expired())
+                    || (stale && e.is_stale(stale_days) && !e.cache_type.is_protected())
+            })
             .cloned()
-            .collect();
-
-        if stale.is_empty() {
-            self.output.success(&format!("✅ No stale entries (>{} days)",
-                self.config.stale_days
-            ));
-            return Ok(());
-        }
-
-        self.output.info(&format!("📅 Found {} stale entries", stale.len()));
-
-        let mut total_size = 0u64;
-        let mut count = 0usize;
+            .collect()
+    }
 
-        for entry in stale {
-            if self.dry_run {
-                self.output.info(&format!("  Would remove: {}",
-                    entry.path.file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("unknown")
-                ));
-                total_size += entry.size;
-                count += 1;
-            } else {
-                if self.remove_entry(&entry).await? {
-                    total_size += entry.size;
-                    count += 1;
-                }
+    /// Remove `entries` without per-entry output; in dry-run mode they are
+    /// only tallied
+    pub async fn remove_entries(&mut self, entries: &[CacheEntry]) -> Result<EvictionReport> {
+        let mut report = EvictionReport::default();
+        for entry in entries {
+            if self.dry_run || self.remove_entry(entry).await? {
+                report.record(entry);
             }
         }
-
-        if self.dry_run {
-            self.output.info(&format!("🔍 Dry run - would clean: {} ({} entries)",
-                format_size(total_size),
-                count
-            ));
-        } else {
-            self.output.success(&format!("✅ Cleaned stale: {} ({} entries)",
-                format_size(total_size),
-                count
-            ));
-        }
-
-        Ok(())
+        Ok(report)
     }
 
     /// Smart cleanup to free specific amount of space
@@ -6,26 +6,49 @@ use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 use crate::cache::{CacheConfig, CacheEntry, CacheType, CacheStats, format_size};
+use crate::cache::cleaner::{eviction_target, plan_eviction};
+use crate::core::PackageInfo;
 use crate::ui::output::Output;
 
+/// Cached names of installed packages, for shell completion
+const INSTALLED_LIST: &str = "installed-packages";
+
 pub struct CacheManager {
     pub config: CacheConfig,
     pub output: Output,
     pub index: HashMap<String, CacheEntry>,
+    /// Serve expired entries instead of treating them as misses
+    pub offline: bool,
 }
 
 impl CacheManager {
     pub fn new(output: Output) -> Result<Self> {
-        let config = CacheConfig::load()?;
+        Self::with_config(CacheConfig::load()?, output)
+    }
+
+    /// Create a manager over an explicit cache configuration
+    pub fn with_config(config: CacheConfig, output: Output) -> Result<Self> {
         let mut manager = Self {
             config,
             output,
             index: HashMap::new(),
+            offline: crate::utils::download::is_offline(),
         };
         manager.load_index()?;
         Ok(manager)
     }
 
+    /// Manager that skips loading and rescanning the index, for fast lookups
+    /// that only read cached files
+    pub fn read_only(output: Output) -> Result<Self> {
+        Ok(Self {
+            config: CacheConfig::load()?,
+            output,
+            index: HashMap::new(),
+            offline: crate::utils::download::is_offline(),
+        })
+    }
+
     /// Load cache index from disk
     fn load_index(&mut self) -> Result<()> {
         let index_path = self.config.base_dir.join("cache_index.json");
@@ -332,25 +355,184 @@ impl CacheManager {
         if self.config.auto_cleanup {
             let stats = self.get_stats()?;
             if stats.needs_cleanup(&self.config) {
-                self.output.info("🧹 Cache threshold reached, starting automatic cleanup...");
-                // Trigger cleanup (would call cleaner module)
+                let target = eviction_target(&self.config);
+                let entries: Vec<CacheEntry> = self.index.values().cloned().collect();
+                let plan = plan_eviction(&entries, stats.total_size, target, false);
+
+                if !plan.is_empty() {
+                    self.output.info("🧹 Cache threshold reached, starting automatic cleanup...");
+                    let mut freed = 0u64;
+                    for entry in &plan {
+                        if self.remove_entry(&entry.key)? {
+                            freed += entry.size;
+                        }
+                    }
+                    self.output.info(&format!("🧹 Evicted {} ({} entries)", format_size(freed), plan.len()));
+                }
             }
         }
 
         Ok(())
     }
 
-    /// Get entry from cache
-    pub fn get_entry(&mut self, key: &str) -> Option<&mut CacheEntry> {
-        if let Some(entry) = self.index.get_mut(key) {
-            entry.touch();
-            // Save index after the borrow ends
-            drop(entry);
-            let _ = self.save_index();
-            self.index.get_mut(key)
-        } else {
-            None
+    /// Get entry from cache; expired entries are misses unless offline
+    pub fn get(&mut self, key: &str) -> Option<&CacheEntry> {
+        let entry = self.index.get_mut(key)?;
+        if entry.is_expired() && !self.offline {
+            return None;
+        }
+
+        entry.touch();
+        let _ = self.save_index();
+        self.index.get(key)
+    }
+
+    /// Path of a named JSON document in the cache
+    fn json_path(&self, cache_type: &CacheType, name: &str) -> PathBuf {
+        let name: String = name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '/') { c } else { '_' })
+            .collect();
+        self.config.get_cache_dir(cache_type).join(format!("{}.json", name.trim_matches('/')))
+    }
+
+    /// Index key for a cached file
+    fn key_for(&self, path: &Path) -> String {
+        path.strip_prefix(&self.config.base_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Store serializable data in the cache and index it
+    pub fn store_json<T: serde::Serialize>(&mut self, cache_type: CacheType, name: &str, data: &T) -> Result<()> {
+        let path = self.json_path(&cache_type, name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string(data)?)
+            .with_context(|| format!("Failed to write cache file {}", path.display()))?;
+
+        let key = self.key_for(&path);
+        self.add_entry(key, path, cache_type)
+    }
+
+    /// Load cached data if it has not expired (or at any age when offline)
+    pub fn load_cached_json<T: serde::de::DeserializeOwned>(&mut self, cache_type: &CacheType, name: &str) -> Option<T> {
+        let path = self.json_path(cache_type, name);
+        let key = self.key_for(&path);
+        let entry = self.get(&key)?;
+
+        let content = fs::read_to_string(&entry.path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Every package known from cached metadata and repository indexes
+    pub fn cached_packages(&self) -> Vec<PackageInfo> {
+        let mut packages: HashMap<String, PackageInfo> = HashMap::new();
+
+        for cache_type in [CacheType::RepositoryIndex, CacheType::PackageMetadata] {
+            let dir = self.config.get_cache_dir(&cache_type);
+            for entry in WalkDir::new(&dir).into_iter().filter_map(|e| e.ok()) {
+                if entry.path().extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                let content = match fs::read_to_string(entry.path()) {
+                    Ok(c) => c,
+                    Err(_) => continue,
+                };
+
+                // Files hold a search result, a package list, or a single package
+                let found: Vec<PackageInfo> = if let Ok(result) = serde_json::from_str::<crate::core::SearchResult>(&content) {
+                    result.packages
+                } else if let Ok(list) = serde_json::from_str::<Vec<PackageInfo>>(&content) {
+                    list
+                } else if let Ok(Some(info)) = serde_json::from_str::<Option<PackageInfo>>(&content) {
+                    vec![info]
+                } else {
+                    continue;
+                };
+
+                for package in found {
+                    packages.entry(package.name.clone()).or_insert(package);
+                }
+            }
         }
+
+        let mut packages: Vec<PackageInfo> = packages.into_values().collect();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+        packages
+    }
+
+    /// Replace the cached installed package list
+    pub fn store_installed(&mut self, mut names: Vec<String>) -> Result<()> {
+        names.sort();
+        names.dedup();
+        self.store_json(CacheType::PackageMetadata, INSTALLED_LIST, &names)
+    }
+
+    /// Apply an install or removal to the cached list, if one exists
+    pub fn update_installed(&mut self, added: &[String], removed: &[String]) -> Result<()> {
+        self.drop_upgradable()?;
+        let mut names = match self.cached_installed() {
+            Some(names) => names,
+            None => return Ok(()),
+        };
+        names.retain(|name| !removed.contains(name));
+        names.extend(added.iter().cloned());
+        self.store_installed(names)
+    }
+
+    /// Installed package names from the last listing, at any age; a stale
+    /// list is still better than nothing for completion
+    pub fn cached_installed(&self) -> Option<Vec<String>> {
+        let content = fs::read_to_string(self.json_path(&CacheType::PackageMetadata, INSTALLED_LIST)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Search results from `manager`'s repository index, under
+    /// RepositoryIndex so they expire with its TTL
+    pub fn search_key(manager: &str, query: &str) -> String {
+        format!("search/{}/{}", manager, query)
+    }
+
+    /// `list upgradable` from `manager`, under RepositoryIndex so it
+    /// expires with the index
+    pub fn upgradable_key(manager: &str) -> String {
+        format!("upgradable/{}", manager)
+    }
+
+    /// Forget every cached `list upgradable`; installed versions changed
+    pub fn drop_upgradable(&mut self) -> Result<()> {
+        self.remove_under(&CacheType::RepositoryIndex, "upgradable")
+    }
+
+    /// Whether pkmgr refreshed `manager`'s repository index within the
+    /// RepositoryIndex TTL
+    pub fn index_is_fresh(&self, manager: &str) -> bool {
+        let key = self.key_for(&self.json_path(&CacheType::RepositoryIndex, &format!("refreshed/{}", manager)));
+        self.index.get(&key).is_some_and(|entry| !entry.is_expired())
+    }
+
+    /// Record a refresh of `manager`'s repository index and drop the search
+    /// results cached before it
+    pub fn mark_index_refreshed(&mut self, manager: &str) -> Result<()> {
+        self.remove_under(&CacheType::RepositoryIndex, &Self::search_key(manager, ""))?;
+        let upgradable = self.key_for(&self.json_path(&CacheType::RepositoryIndex, &Self::upgradable_key(manager)));
+        self.remove_entry(&upgradable)?;
+        self.store_json(CacheType::RepositoryIndex, &format!("refreshed/{}", manager), &Utc::now())
+    }
+
+    /// Remove the JSON entries stored under `prefix`
+    fn remove_under(&mut self, cache_type: &CacheType, prefix: &str) -> Result<()> {
+        let dir = self.json_path(cache_type, prefix).with_extension("");
+        let stale: Vec<String> = self.index.values()
+            .filter(|entry| entry.path.starts_with(&dir))
+            .map(|entry| entry.key.clone())
+            .collect();
+        for key in stale {
+            self.remove_entry(&key)?;
+        }
+        Ok(())
     }
 
     /// Remove entry from cache
@@ -410,4 +592,109 @@ impl CacheManager {
             CacheType::Temporary => "⏱️",
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(dir: &Path) -> CacheManager {
+        let config = CacheConfig {
+            base_dir: dir.to_path_buf(),
+            auto_cleanup: false,
+            ..CacheConfig::default()
+        };
+        let mut manager = CacheManager::with_config(config, Output::new("never".to_string(), false)).unwrap();
+        manager.offline = false;
+        manager
+    }
+
+    fn insert(manager: &mut CacheManager, key: &str, age_seconds: i64) {
+        let path = manager.config.base_dir.join(key);
+        fs::write(&path, "{}").unwrap();
+        manager.add_entry(key.to_string(), path, CacheType::RepositoryIndex).unwrap();
+
+        let entry = manager.index.get_mut(key).unwrap();
+        entry.created = Utc::now() - chrono::Duration::seconds(age_seconds);
+        entry.access_count = 1;
+    }
+
+    #[test]
+    fn test_get_fresh_hit_touches_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = manager(dir.path());
+        insert(&mut manager, "fresh.json", 60);
+
+        let entry = manager.get("fresh.json").expect("fresh entry should hit");
+        assert_eq!(entry.access_count, 2);
+    }
+
+    #[test]
+    fn test_get_expired_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = manager(dir.path());
+        let ttl = CacheType::RepositoryIndex.default_ttl().unwrap();
+        insert(&mut manager, "expired.json", ttl + 60);
+
+        assert!(manager.get("expired.json").is_none());
+        assert_eq!(manager.index["expired.json"].access_count, 1);
+    }
+
+    #[test]
+    fn test_get_offline_returns_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = manager(dir.path());
+        manager.offline = true;
+        let ttl = CacheType::RepositoryIndex.default_ttl().unwrap();
+        insert(&mut manager, "expired.json", ttl + 60);
+
+        assert!(manager.get("expired.json").is_some());
+    }
+
+    #[test]
+    fn test_index_refresh_drops_cached_searches() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = manager(dir.path());
+        assert!(!manager.index_is_fresh("apt"));
+
+        let result = crate::core::SearchResult { packages: Vec::new(), total_count: 0 };
+        manager.store_json(CacheType::RepositoryIndex, &CacheManager::search_key("apt", "vim"), &result).unwrap();
+        manager.store_json(CacheType::RepositoryIndex, &CacheManager::search_key("dnf", "vim"), &result).unwrap();
+
+        manager.mark_index_refreshed("apt").unwrap();
+        assert!(manager.index_is_fresh("apt"));
+        assert!(manager.load_cached_json::<crate::core::SearchResult>(&CacheType::RepositoryIndex, &CacheManager::search_key("apt", "vim")).is_none());
+        assert!(manager.load_cached_json::<crate::core::SearchResult>(&CacheType::RepositoryIndex, &CacheManager::search_key("dnf", "vim")).is_some());
+
+        let updates: Vec<crate::core::PackageUpdate> = Vec::new();
+        manager.store_json(CacheType::RepositoryIndex, &CacheManager::upgradable_key("apt"), &updates).unwrap();
+        manager.update_installed(&["vim".to_string()], &[]).unwrap();
+        assert!(manager.load_cached_json::<Vec<crate::core::PackageUpdate>>(&CacheType::RepositoryIndex, &CacheManager::upgradable_key("apt")).is_none());
+    }
+
+    #[test]
+    fn test_auto_cleanup_trims_below_the_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = CacheConfig {
+            base_dir: dir.path().to_path_buf(),
+            max_size: 1000,
+            min_free_space: 0,
+            ..CacheConfig::default()
+        };
+        let mut manager = CacheManager::with_config(config, Output::new("never".to_string(), false)).unwrap();
+        let add = |manager: &mut CacheManager, n: usize| {
+            let path = dir.path().join(format!("{}.json", n));
+            fs::write(&path, [b' '; 100]).unwrap();
+            manager.add_entry(format!("{}.json", n), path, CacheType::RepositoryIndex).unwrap();
+            manager.index.len()
+        };
+
+        // 900 of 1000 bytes crosses the 80% threshold and trims to 70%
+        for n in 0..8 {
+            add(&mut manager, n);
+        }
+        assert_eq!(add(&mut manager, 8), 7);
+        // so the next insert stays at the threshold without evicting
+        assert_eq!(add(&mut manager, 9), 8);
+    }
+}
@@ -6,6 +6,8 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use crate::core::config::Config;
+use crate::core::paths::Paths;
 
 /// Cache entry metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,6 +88,11 @@ impl CacheType {
         }
     }
 
+    /// Types that are expensive to re-fetch and only removed on explicit request
+    pub fn is_protected(&self) -> bool {
+        matches!(self, CacheType::IsoDownload | CacheType::LanguageVersion)
+    }
+
     /// Get human-readable name
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -114,9 +121,9 @@ pub struct CacheConfig {
 
 impl Default for CacheConfig {
     fn default() -> Self {
-        let base_dir = dirs::cache_dir()
-            .unwrap_or_else(|| PathBuf::from("/tmp"))
-            .join("pkmgr");
+        let base_dir = Paths::resolve()
+            .map(|paths| paths.cache_dir)
+            .unwrap_or_else(|_| std::env::temp_dir().join("pkmgr"));
 
         Self {
             base_dir,
@@ -132,10 +139,7 @@ impl Default for CacheConfig {
 impl CacheConfig {
     /// Load from configuration file
     pub fn load() -> Result<Self> {
-        let config_path = dirs::config_dir()
-            .context("Failed to determine config directory")?
-            .join("pkmgr")
-            .join("cache.toml");
+        let config_path = Config::get_config_dir()?.join("cache.toml");
 
         if config_path.exists() {
             let content = std::fs::read_to_string(&config_path)?;
@@ -147,9 +151,7 @@ impl CacheConfig {
 
     /// Save to configuration file
     pub fn save(&self) -> Result<()> {
-        let config_dir = dirs::config_dir()
-            .context("Failed to determine config directory")?
-            .join("pkmgr");
+        let config_dir = Config::get_config_dir()?;
 
         std::fs::create_dir_all(&config_dir)?;
 
@@ -0,0 +1,54 @@
+use anyhow::{Result, Context};
+use crate::cache::manager::CacheManager;
+use crate::commands::Cli;
+use crate::core::config::Config;
+use crate::core::platform::PlatformInfo;
+use crate::managers::PackageManagerFactory;
+use crate::ui::output::Output;
+use crate::ui::prompt::Prompt;
+use crate::core::exit::Cancelled;
+
+/// Remove orphaned dependencies, listing them first and asking before
+/// anything is removed
+pub async fn execute(cli: &Cli, _config: &Config, output: &Output) -> Result<()> {
+    output.print_header("🧹 Removing Orphaned Packages");
+
+    let platform_info = PlatformInfo::detect_async().await?;
+    let package_manager = PackageManagerFactory::create(&platform_info)
+        .context("Failed to create package manager")?;
+
+    output.debug(&format!("Using package manager: {}", package_manager.name()));
+
+    let orphans = package_manager.orphans().await?;
+    if orphans.is_empty() {
+        output.success("No orphaned packages to remove");
+        return Ok(());
+    }
+
+    output.print_section(&format!("🗑️ Orphaned packages ({})", orphans.len()));
+    output.print_list(&orphans);
+
+    if cli.dry_run {
+        output.info("Dry run - no changes made");
+        return Ok(());
+    }
+
+    if !cli.yes {
+        let prompt = Prompt::new(output.emoji_enabled);
+        if !prompt.confirm(&format!("Remove {} orphaned packages?", orphans.len()))? {
+            return Err(Cancelled("Autoremove cancelled".to_string()).into());
+        }
+    }
+
+    let result = package_manager.remove_orphans(&orphans).await?;
+    if !result.success {
+        anyhow::bail!("Failed to remove orphaned packages: {}", result.message);
+    }
+
+    if let Ok(mut cache) = CacheManager::new(output.clone()) {
+        let _ = cache.update_installed(&[], &result.packages_installed);
+    }
+
+    output.success(&format!("Removed {} orphaned packages", result.packages_installed.len()));
+    Ok(())
+}
@@ -1,9 +1,11 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Result};
 use clap::Subcommand;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use crate::commands::Cli;
 use crate::core::config::Config;
 use crate::core::platform::PlatformInfo;
+use crate::core::privilege::{self, Escalation, InstallLocation, InstallTarget};
 use crate::ui::output::Output;
 use crate::utils::download::{Downloader, GitHubClient};
 use crate::utils::archive::Extractor;
@@ -15,7 +17,14 @@ pub enum BinaryCommands {
     /// Install from GitHub/GitLab
     Install { repo: String },
     /// List installed binaries
-    List,
+    List {
+        /// Check GitHub for newer releases
+        #[arg(long)]
+        check: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: ListFormat,
+    },
     /// Update binaries
     Update { name: Option<String> },
     /// Remove binary
@@ -24,16 +33,22 @@ pub enum BinaryCommands {
     Info { repo: String },
 }
 
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Json,
+}
+
 pub async fn execute(cmd: BinaryCommands, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
     match cmd {
         BinaryCommands::Search { query } => {
             search_binaries(query, config, output).await
         }
         BinaryCommands::Install { repo } => {
-            install_binary(repo, config, output).await
+            install_for_target(repo, cli, config, output).await
         }
-        BinaryCommands::List => {
-            list_binaries(config, output).await
+        BinaryCommands::List { check, format } => {
+            list_binaries(check, format, config, output).await
         }
         BinaryCommands::Update { name } => {
             update_binaries(name, config, output).await
@@ -56,7 +71,40 @@ async fn search_binaries(query: String, config: &Config, output: &Output) -> Res
     Ok(())
 }
 
-async fn install_binary(repo: String, config: &Config, output: &Output) -> Result<()> {
+/// Install honoring `--user` / `--global`: under sudo, `--user` installs
+/// into the invoking user's home and hands the files to them; `--global`
+/// installs into /usr/local, re-running pkmgr through sudo for a normal user
+async fn install_for_target(repo: String, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
+    match privilege::install_target(cli.user, cli.global)? {
+        InstallTarget::Configured => install_binary(repo, config, output).await.map(drop),
+        InstallTarget::System { escalate: true } => {
+            output.info("🔐 --global installs into /usr/local; re-running with root privileges");
+            privilege::rerun_privileged()
+        }
+        InstallTarget::System { escalate: false } => {
+            install_binary(repo, &privilege::system_config(config), output).await.map(drop)
+        }
+        InstallTarget::User { owner: None } => {
+            if privilege::escalation()? == Escalation::Root {
+                output.warn("Running as root outside sudo; --user installs into root's home");
+            }
+            let mut config = config.clone();
+            config.paths.install_dir = "~/.local".to_string();
+            install_binary(repo, &config, output).await.map(drop)
+        }
+        InstallTarget::User { owner: Some(owner) } => {
+            output.info(&format!("Installing for {} (invoked through sudo)", owner.name));
+            let config = owner.config(config);
+            let binary_path = install_binary(repo, &config, output).await?;
+            owner.adopt(&binary_path)?;
+            owner.adopt(&config.get_data_dir()?.join("binaries"))?;
+            owner.adopt(&config.get_cache_dir()?)
+        }
+    }
+}
+
+/// Install a GitHub release binary and return where it was installed
+pub async fn install_binary(repo: String, config: &Config, output: &Output) -> Result<PathBuf> {
     output.print_header(&format!("📦 Installing binary from: {}", repo));
 
     // Parse repository format (user/repo[@version])
@@ -94,7 +142,7 @@ async fn install_binary(repo: String, config: &Config, output: &Output) -> Resul
     output.progress(&format!("Fetching release information for {}/{}", owner, repo_name));
 
     // Get release information
-    let github_client = GitHubClient::new()?;
+    let github_client = GitHubClient::from_config(config)?;
     let release = if let Some(ver) = version {
         // Get specific version
         let releases = github_client.get_releases(owner, repo_name).await?;
@@ -118,7 +166,7 @@ async fn install_binary(repo: String, config: &Config, output: &Output) -> Resul
     let cache_dir = config.get_cache_dir()?;
     let download_path = cache_dir.join(&asset.name);
 
-    let downloader = Downloader::new(config.defaults.emoji_enabled)?;
+    let downloader = Downloader::new(config.defaults.emoji_enabled.enabled())?;
 
     output.download_start(&asset.name, Some(asset.size));
     downloader.download_file(&asset.browser_download_url, &download_path).await?;
@@ -149,96 +197,306 @@ async fn install_binary(repo: String, config: &Config, output: &Output) -> Resul
     }
 
     // Save installation info
-    save_binary_info(owner, repo_name, &release.tag_name, &asset.browser_download_url, config).await?;
+    save_binary_info(owner, repo_name, &release.tag_name, &asset.browser_download_url, &binary_path, config).await?;
 
     output.success(&format!("✅ Successfully installed {} {}", repo_name, release.tag_name));
     output.info(&format!("📁 Installed to: {}", binary_path.display()));
 
-    Ok(())
+    Ok(binary_path)
 }
 
-async fn list_binaries(config: &Config, output: &Output) -> Result<()> {
-    output.print_header("📋 Installed Binaries");
+/// A binary recorded in the installed-binaries state file
+#[derive(Debug, Clone)]
+pub struct InstalledBinary {
+    pub name: String,
+    pub repository: String,
+    pub version: String,
+    pub installed_date: String,
+    /// Where the executable was written; absent in entries recorded before
+    /// install paths were tracked
+    pub path: Option<PathBuf>,
+}
 
-    let data_dir = config.get_data_dir()?;
-    let binaries_file = data_dir.join("binaries").join("installed.toml");
+impl InstalledBinary {
+    /// The recorded install path, else the binary's name under the
+    /// configured install dir
+    pub fn location(&self, config: &Config) -> Result<PathBuf> {
+        match &self.path {
+            Some(path) => Ok(path.clone()),
+            None => Ok(config.get_install_dir()?.join("bin").join(&self.name)),
+        }
+    }
+}
+
+/// Read the installed-binaries state file
+pub async fn load_installed_binaries(config: &Config) -> Result<Vec<InstalledBinary>> {
+    let binaries_file = config.get_data_dir()?.join("binaries").join("installed.toml");
 
     if !binaries_file.exists() {
-        output.info("No binaries installed yet.");
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let content = tokio::fs::read_to_string(&binaries_file).await?;
     let binaries: toml::Value = toml::from_str(&content)?;
 
+    let mut installed = Vec::new();
     if let Some(table) = binaries.as_table() {
-        let headers = vec!["Binary", "Version", "Source", "Installed"];
-        let mut rows = Vec::new();
-
         for (name, info) in table {
-            if let Some(info_table) = info.as_table() {
-                let version = info_table.get("version")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-
-                let source = info_table.get("repository")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-
-                let installed_date = info_table.get("installed_date")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-
-                rows.push(vec![
-                    name.clone(),
-                    version.to_string(),
-                    source.to_string(),
-                    installed_date.to_string(),
-                ]);
-            }
+            let info_table = match info.as_table() {
+                Some(t) => t,
+                None => continue,
+            };
+
+            let field = |key: &str| info_table.get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+
+            installed.push(InstalledBinary {
+                name: name.clone(),
+                repository: field("repository"),
+                version: field("version"),
+                installed_date: field("installed_date"),
+                path: info_table.get("install_path").and_then(|v| v.as_str()).map(PathBuf::from),
+            });
         }
+    }
+
+    Ok(installed)
+}
+
+/// Binaries recorded for this user followed by those installed with
+/// `--global`, each with the config that owns its record
+pub(crate) async fn load_all_installed(config: &Config) -> Result<Vec<(InstalledBinary, Config)>> {
+    load_installed_alongside(config, &privilege::system_config(config)).await
+}
+
+/// Binaries recorded under `config` followed by those under `system`
+pub(crate) async fn load_installed_alongside(config: &Config, system: &Config) -> Result<Vec<(InstalledBinary, Config)>> {
+    let mut all: Vec<(InstalledBinary, Config)> = load_installed_binaries(config).await?
+        .into_iter()
+        .map(|binary| (binary, config.clone()))
+        .collect();
+
+    if system.get_data_dir()? != config.get_data_dir()? {
+        all.extend(load_installed_binaries(system).await?
+            .into_iter()
+            .map(|binary| (binary, system.clone())));
+    }
+    Ok(all)
+}
+
+/// Whether `config` is the one `--global` installs record under
+fn is_system_config(config: &Config) -> Result<bool> {
+    Ok(config.get_data_dir()? == InstallLocation::System.share_dir())
+}
+
+#[derive(Debug, Serialize)]
+struct BinaryListEntry {
+    name: String,
+    repository: String,
+    version: String,
+    path: String,
+    installed_date: String,
+    latest_version: Option<String>,
+    update_available: Option<bool>,
+}
+
+async fn list_binaries(check: bool, format: ListFormat, config: &Config, output: &Output) -> Result<()> {
+    let github_client = if check { Some(GitHubClient::from_config(config)?) } else { None };
+    let mut entries = Vec::new();
+
+    for (binary, owner) in load_all_installed(config).await? {
+        let path = binary.location(&owner)?;
+
+        // Only query GitHub when asked; failures are reported per binary
+        let latest = match (&github_client, binary.repository.split_once('/')) {
+            (Some(client), Some((owner, repo))) => {
+                match client.get_latest_release(owner, repo).await {
+                    Ok(release) => Some(release.tag_name),
+                    Err(e) => {
+                        output.debug(&format!("Failed to check {}: {}", binary.repository, e));
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let update_available = latest.as_deref()
+            .map(|latest| is_newer_release(&binary.version, latest));
+
+        entries.push(BinaryListEntry {
+            name: binary.name,
+            repository: binary.repository,
+            version: binary.version,
+            path: path.display().to_string(),
+            installed_date: binary.installed_date,
+            latest_version: latest,
+            update_available,
+        });
+    }
+
+    if let ListFormat::Json = format {
+        output.print(&serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
 
-        if !rows.is_empty() {
-            output.print_table(&headers, &rows);
+    output.print_header("📋 Installed Binaries");
+
+    if entries.is_empty() {
+        output.info("No binaries installed yet.");
+        return Ok(());
+    }
+
+    let mut headers = vec!["Binary", "Version", "Source", "Path", "Installed"];
+    if check {
+        headers.push("Latest");
+    }
+
+    let mut upgradable = 0;
+    let rows: Vec<Vec<String>> = entries.iter()
+        .map(|entry| {
+            let mut row = vec![
+                entry.name.clone(),
+                entry.version.clone(),
+                entry.repository.clone(),
+                entry.path.clone(),
+                entry.installed_date.clone(),
+            ];
+            if check {
+                row.push(match (&entry.latest_version, entry.update_available) {
+                    (Some(latest), Some(true)) => {
+                        upgradable += 1;
+                        format!("{} (update available)", latest)
+                    }
+                    (Some(_), _) => "up to date".to_string(),
+                    (None, _) => "unknown".to_string(),
+                });
+            }
+            row
+        })
+        .collect();
+
+    output.print_table(&headers, &rows);
+
+    if check {
+        if upgradable > 0 {
+            output.info(&format!("{} binaries can be updated. Run 'pkmgr binary update' to upgrade.", upgradable));
         } else {
-            output.info("No binaries installed yet.");
+            output.success("All binaries are up to date");
         }
     }
 
     Ok(())
 }
 
+/// Compare release tags, falling back to a plain string comparison for non-semver tags
+pub fn is_newer_release(installed: &str, latest: &str) -> bool {
+    let installed = installed.trim_start_matches('v');
+    let latest = latest.trim_start_matches('v');
+
+    match (semver::Version::parse(installed), semver::Version::parse(latest)) {
+        (Ok(installed), Ok(latest)) => latest > installed,
+        _ => installed != latest,
+    }
+}
+
 async fn update_binaries(name: Option<String>, config: &Config, output: &Output) -> Result<()> {
-    if let Some(name) = name {
-        output.print_header(&format!("🔄 Updating binary: {}", name));
-        // TODO: Implement single binary update
-        output.info("Update feature coming soon");
-    } else {
-        output.print_header("🔄 Updating all binaries");
-        // TODO: Implement all binaries update
-        output.info("Update feature coming soon");
+    let mut installed = load_all_installed(config).await?;
+    match &name {
+        Some(name) => {
+            output.print_header(&format!("🔄 Updating binary: {}", name));
+            installed.retain(|(binary, _)| &binary.name == name);
+            if installed.is_empty() {
+                anyhow::bail!("Binary '{}' is not installed", name);
+            }
+        }
+        None => output.print_header("🔄 Updating all binaries"),
+    }
+
+    // A named system-wide binary is updated by re-running as root; a full
+    // update leaves those to `sudo pkmgr binary update`
+    if name.is_some() && !privilege::is_root() && is_system_config(&installed[0].1)? {
+        output.info("🔐 Installed with --global; re-running with root privileges");
+        return privilege::rerun_privileged();
+    }
+
+    let github_client = GitHubClient::from_config(config)?;
+    let mut skipped_system = 0;
+    let mut failed = Vec::new();
+
+    for (binary, owner) in installed {
+        if !privilege::is_root() && is_system_config(&owner)? {
+            skipped_system += 1;
+            continue;
+        }
+        let Some((repo_owner, repo)) = binary.repository.split_once('/') else {
+            output.warn(&format!("Skipping {}: unknown repository '{}'", binary.name, binary.repository));
+            continue;
+        };
+
+        let latest = match github_client.get_latest_release(repo_owner, repo).await {
+            Ok(release) => release.tag_name,
+            Err(e) => {
+                output.warn(&format!("Could not check {} for updates: {}", binary.name, e));
+                failed.push(binary.name);
+                continue;
+            }
+        };
+        if !is_newer_release(&binary.version, &latest) {
+            output.info(&format!("✓ {} {} is up to date", binary.name, binary.version));
+            continue;
+        }
+
+        // Reinstall where the binary already lives
+        let mut target = owner;
+        if let Some(dir) = binary.location(&target)?.parent().and_then(Path::parent) {
+            target.paths.install_dir = dir.display().to_string();
+        }
+        if let Err(e) = install_binary(format!("{}@{}", binary.repository, latest), &target, output).await {
+            output.warn(&format!("Failed to update {}: {}", binary.name, e));
+            failed.push(binary.name);
+        }
+    }
+
+    if skipped_system > 0 {
+        output.warn(&format!(
+            "Skipped {} binaries installed with --global; run 'sudo pkmgr binary update' to update them",
+            skipped_system
+        ));
+    }
+    if !failed.is_empty() {
+        bail!("Failed to update {} binaries: {}", failed.len(), failed.join(", "));
     }
 
     Ok(())
 }
 
-async fn remove_binary(name: String, config: &Config, output: &Output) -> Result<()> {
+pub async fn remove_binary(name: String, config: &Config, output: &Output) -> Result<()> {
     output.print_header(&format!("🗑️ Removing binary: {}", name));
 
-    let install_dir = config.get_install_dir()?.join("bin");
-    let binary_path = install_dir.join(&name);
+    let installed = load_all_installed(config).await?;
+    let Some((binary, owner)) = installed.into_iter().find(|(binary, _)| binary.name == name) else {
+        output.error(&format!("Binary '{}' not found", name));
+        return Ok(());
+    };
 
+    if !privilege::is_root() && is_system_config(&owner)? {
+        output.info("🔐 Installed with --global; re-running with root privileges");
+        return privilege::rerun_privileged();
+    }
+
+    let binary_path = binary.location(&owner)?;
     if binary_path.exists() {
         tokio::fs::remove_file(&binary_path).await?;
         output.success(&format!("✅ Removed {}", name));
-
-        // Remove from tracking
-        remove_binary_info(&name, config).await?;
     } else {
-        output.error(&format!("Binary '{}' not found", name));
+        output.warn(&format!("{} was already gone; forgetting it", binary_path.display()));
     }
 
-    Ok(())
+    // Remove from tracking
+    remove_binary_info(&name, &owner).await
 }
 
 async fn show_binary_info(repo: String, config: &Config, output: &Output) -> Result<()> {
@@ -254,7 +512,7 @@ async fn show_binary_info(repo: String, config: &Config, output: &Output) -> Res
     let repo_name = parts[1];
 
     // Get release information
-    let github_client = GitHubClient::new()?;
+    let github_client = GitHubClient::from_config(config)?;
     let release = github_client.get_latest_release(owner, repo_name).await?;
 
     output.print_section("Release Information");
@@ -271,7 +529,7 @@ async fn show_binary_info(repo: String, config: &Config, output: &Output) -> Res
     Ok(())
 }
 
-async fn save_binary_info(owner: &str, name: &str, version: &str, url: &str, config: &Config) -> Result<()> {
+async fn save_binary_info(owner: &str, name: &str, version: &str, url: &str, path: &Path, config: &Config) -> Result<()> {
     let data_dir = config.get_data_dir()?;
     let binaries_dir = data_dir.join("binaries");
     tokio::fs::create_dir_all(&binaries_dir).await?;
@@ -290,6 +548,7 @@ async fn save_binary_info(owner: &str, name: &str, version: &str, url: &str, con
         info.insert("repository".to_string(), toml::Value::String(format!("{}/{}", owner, name)));
         info.insert("version".to_string(), toml::Value::String(version.to_string()));
         info.insert("download_url".to_string(), toml::Value::String(url.to_string()));
+        info.insert("install_path".to_string(), toml::Value::String(path.display().to_string()));
         info.insert("installed_date".to_string(), toml::Value::String(chrono::Utc::now().to_rfc3339()));
 
         table.insert(name.to_string(), toml::Value::Table(info));
@@ -19,10 +19,10 @@ pub enum CacheCommands {
         /// Force cleanup without confirmation
         #[arg(long)]
         force: bool,
-        /// Only clean expired entries
+        /// Clean entries past their TTL
         #[arg(long)]
         expired: bool,
-        /// Only clean stale entries
+        /// Clean entries not accessed within stale_days (ISOs and language versions are kept)
         #[arg(long)]
         stale: bool,
         /// Clean orphaned files
@@ -82,12 +82,14 @@ async fn clean_cache(
 ) -> Result<()> {
     let mut cleaner = CacheCleaner::new(output.clone(), dry_run)?;
 
-    if expired {
-        cleaner.clean_expired().await?;
-    } else if stale {
-        cleaner.clean_stale().await?;
-    } else if orphaned {
-        cleaner.clean_orphaned().await?;
+    // Selectors can be combined; any of them replaces the broad cleanup
+    if expired || stale || orphaned {
+        if expired || stale {
+            cleaner.clean_selected(expired, stale).await?;
+        }
+        if orphaned {
+            cleaner.clean_orphaned().await?;
+        }
     } else if let Some(clean_type) = cache_type {
         match clean_type {
             CleanType::All => {
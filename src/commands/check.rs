@@ -0,0 +1,35 @@
+use anyhow::{Context, Result};
+use crate::core::exit::Outcome;
+use crate::core::platform::PlatformInfo;
+use crate::managers::PackageManagerFactory;
+use crate::ui::output::Output;
+
+/// List pending updates from the last refreshed metadata; `script` prints
+/// only their number. Exits with UpdatesAvailable when there are any
+pub async fn execute(script: bool, output: &Output) -> Result<Outcome> {
+    let platform_info = PlatformInfo::detect_async().await?;
+    let package_manager = PackageManagerFactory::create(&platform_info)
+        .context("Failed to create package manager")?;
+
+    let updates = package_manager.upgradable().await?;
+
+    if script {
+        output.print(&updates.len().to_string());
+    } else if updates.is_empty() {
+        output.success("✅ All packages up to date");
+    } else {
+        output.print_header(&format!("📦 {} updates available", updates.len()));
+        let rows: Vec<Vec<String>> = updates.iter()
+            .map(|update| vec![
+                update.name.clone(),
+                update.current.clone(),
+                update.candidate.clone(),
+                update.repo.clone(),
+            ])
+            .collect();
+        output.print_table(&["Package", "Installed", "Available", "Repository"], &rows);
+        output.info("Run 'pkmgr update' to install them");
+    }
+
+    Ok(if updates.is_empty() { Outcome::Success } else { Outcome::UpdatesAvailable })
+}
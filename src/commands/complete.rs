@@ -0,0 +1,109 @@
+use anyhow::Result;
+use clap::CommandFactory;
+use crate::cache::manager::CacheManager;
+use crate::commands::Cli;
+use crate::ui::output::Output;
+
+/// Where package name candidates for a command come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageSource {
+    /// Packages known from cached repository indexes and search results
+    Available,
+    /// The installed list cached by `list`, `install` and `remove`
+    Installed,
+}
+
+/// Commands whose positional arguments are package names
+pub const PACKAGE_COMMANDS: &[(&str, PackageSource)] = &[
+    ("install", PackageSource::Available),
+    ("info", PackageSource::Available),
+    ("whatis", PackageSource::Available),
+    ("remove", PackageSource::Installed),
+    ("update", PackageSource::Installed),
+    ("where", PackageSource::Installed),
+];
+
+/// `pkmgr __complete <words>... <partial>`: print package names completing
+/// the last word, one per line, reading only from the cache
+pub fn execute(words: Vec<String>, output: &Output) -> Result<()> {
+    let (partial, context) = match words.split_last() {
+        Some((partial, context)) => (partial.as_str(), context),
+        None => return Ok(()),
+    };
+    // Flags are completed by the static scripts
+    if partial.starts_with('-') {
+        return Ok(());
+    }
+
+    let source = match package_source(context) {
+        Some(source) => source,
+        None => return Ok(()),
+    };
+
+    let cache = CacheManager::read_only(output.clone())?;
+    let names = match source {
+        PackageSource::Available => cache.cached_packages().into_iter().map(|p| p.name).collect(),
+        PackageSource::Installed => cache.cached_installed().unwrap_or_default(),
+    };
+
+    for name in candidates(names, partial, context) {
+        output.print(&name);
+    }
+    Ok(())
+}
+
+/// Package source for the subcommand in `context`, skipping global flags
+/// and their values, e.g. `--profile work rm`
+fn package_source(context: &[String]) -> Option<PackageSource> {
+    let cli = Cli::command();
+    let mut words = context.iter();
+
+    while let Some(word) = words.next() {
+        if let Some(flag) = word.strip_prefix("--") {
+            let takes_value = !flag.contains('=') && cli.get_arguments()
+                .any(|arg| arg.get_long() == Some(flag) && arg.get_action().takes_values());
+            if takes_value {
+                words.next();
+            }
+            continue;
+        }
+        if word.starts_with('-') {
+            continue;
+        }
+
+        let name = cli.find_subcommand(word)?.get_name();
+        return PACKAGE_COMMANDS.iter()
+            .find(|(command, _)| *command == name)
+            .map(|(_, source)| *source);
+    }
+    None
+}
+
+/// Sorted names starting with `partial` that are not already on the line
+fn candidates(mut names: Vec<String>, partial: &str, context: &[String]) -> Vec<String> {
+    names.retain(|name| name.starts_with(partial) && !context.contains(name));
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn picks_source_from_subcommand_and_aliases() {
+        assert_eq!(package_source(&words("install")), Some(PackageSource::Available));
+        assert_eq!(package_source(&words("--profile work rm")), Some(PackageSource::Installed));
+        assert_eq!(package_source(&words("-y up")), Some(PackageSource::Installed));
+        assert_eq!(package_source(&words("search")), None);
+        assert_eq!(package_source(&words("bogus")), None);
+
+        let names = words("firefox git gimp git");
+        assert_eq!(candidates(names, "gi", &words("install gimp")), vec!["git"]);
+    }
+}
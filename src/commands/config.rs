@@ -1,8 +1,18 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use clap::Subcommand;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
 use crate::commands::Cli;
 use crate::core::config::Config;
+use crate::core::privilege::Escalation;
 use crate::ui::output::Output;
+use crate::ui::prompt;
+use crate::utils::logging::LoggedCommand;
+
+/// Prefix of the error header written above an invalid edit
+const ERROR_HEADER: &str = "# pkmgr:";
 
 #[derive(Debug, Subcommand, Clone)]
 pub enum ConfigCommands {
@@ -11,9 +21,139 @@ pub enum ConfigCommands {
     Set { key: String, value: String },
     Remove { key: String },
     Reset,
+    /// Edit config.toml in $EDITOR; invalid edits are never saved
+    Edit,
+}
+
+pub async fn execute(cmd: ConfigCommands, _cli: &Cli, config: &Config, output: &Output) -> Result<()> {
+    match cmd {
+        ConfigCommands::Edit => edit_config(config, output),
+        _ => {
+            output.info("⚙️ Configuration management");
+            Ok(())
+        }
+    }
 }
 
-pub async fn execute(cmd: ConfigCommands, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
-    output.info("⚙️ Configuration management");
+/// Edit a copy of config.toml and only replace the real file once the copy
+/// deserializes, reopening the editor with the error on top like visudo
+fn edit_config(config: &Config, output: &Output) -> Result<()> {
+    if !prompt::is_interactive() {
+        bail!("non-interactive session: 'config edit' needs a terminal for the editor");
+    }
+
+    let config_dir = Config::get_config_dir()?;
+    fs::create_dir_all(&config_dir)
+        .with_context(|| format!("Failed to create {}", config_dir.display()))?;
+    let config_file = Config::config_file()?;
+
+    let original = if config_file.exists() {
+        fs::read_to_string(&config_file).context("Failed to read config file")?
+    } else {
+        toml::to_string_pretty(config).context("Failed to serialize config")?
+    };
+
+    let mut draft = tempfile::Builder::new()
+        .prefix("config.")
+        .suffix(".toml")
+        .tempfile_in(&config_dir)
+        .context("Failed to create temporary config")?;
+    draft.write_all(original.as_bytes())?;
+    draft.flush()?;
+
+    let editor = editor_command();
+    output.info(&format!("📝 Opening {} with {}", config_file.display(), editor));
+
+    loop {
+        run_editor(&editor, draft.path())?;
+
+        let edited = strip_error_header(&fs::read_to_string(draft.path())?);
+        if edited.trim().is_empty() {
+            output.warn("Empty config; edit aborted and config.toml left unchanged");
+            return Ok(());
+        }
+        if edited == original {
+            output.info("No changes made");
+            return Ok(());
+        }
+
+        match validate(&edited) {
+            Ok(()) => {
+                fs::write(draft.path(), &edited)?;
+                draft.persist(&config_file)
+                    .with_context(|| format!("Failed to write {}", config_file.display()))?;
+                output.success(&format!("Saved {}", config_file.display()));
+                return Ok(());
+            }
+            Err(e) => {
+                output.error(&format!("Invalid config: {:#}", e));
+                output.info("🔁 Reopening the editor; delete everything to abort");
+                fs::write(draft.path(), format!("{}{}", error_header(&e), edited))?;
+            }
+        }
+    }
+}
+
+/// The edit must deserialize into Config and hold values pkmgr accepts
+fn validate(content: &str) -> Result<()> {
+    let parsed: Config = toml::from_str(content).context("Failed to parse config")?;
+    Escalation::from_config(&parsed.privilege.escalation)?;
+    Ok(())
+}
+
+fn error_header(error: &anyhow::Error) -> String {
+    let mut header = format!("{} config.toml was NOT saved:\n", ERROR_HEADER);
+    for line in format!("{:#}", error).lines() {
+        header.push_str(&format!("{}   {}\n", ERROR_HEADER, line));
+    }
+    header.push_str(&format!("{} Fix the error and save, or delete everything to abort\n", ERROR_HEADER));
+    header
+}
+
+fn strip_error_header(content: &str) -> String {
+    let body: Vec<&str> = content.lines()
+        .skip_while(|line| line.starts_with(ERROR_HEADER))
+        .collect();
+    let mut stripped = body.join("\n");
+    if content.ends_with('\n') && !stripped.is_empty() {
+        stripped.push('\n');
+    }
+    stripped
+}
+
+/// $VISUAL, then $EDITOR, then a per-platform default
+fn editor_command() -> String {
+    for var in ["VISUAL", "EDITOR"] {
+        if let Ok(editor) = std::env::var(var) {
+            if !editor.trim().is_empty() {
+                return editor;
+            }
+        }
+    }
+
+    if cfg!(windows) {
+        return "notepad".to_string();
+    }
+
+    ["sensible-editor", "nano", "vim", "vi"].iter()
+        .find(|editor| which::which(editor).is_ok())
+        .unwrap_or(&"vi")
+        .to_string()
+}
+
+fn run_editor(editor: &str, path: &Path) -> Result<()> {
+    // Editors are often configured with arguments, e.g. "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("Editor command is empty")?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .logged_status()
+        .with_context(|| format!("Failed to start editor '{}'", editor))?;
+
+    if !status.success() {
+        bail!("Editor '{}' exited with {}; config.toml left unchanged", editor, status);
+    }
     Ok(())
 }
@@ -1,10 +1,17 @@
 use anyhow::Result;
 use crate::commands::Cli;
+use crate::binary::github;
 use crate::core::config::Config;
+use crate::core::exit::Outcome;
 use crate::ui::output::Output;
-use crate::doctor::checker::HealthChecker;
+use crate::utils::download::is_offline;
+use crate::doctor::{CheckCategory, Severity};
+use crate::doctor::checker::{HealthChecker, DEFAULT_CATEGORIES};
 use crate::doctor::diagnostics::Diagnostics;
+use crate::doctor::fixer::DoctorFixer;
 use crate::doctor::report::{ReportFormatter, ExportFormat};
+use crate::doctor::watch::{WatchFormat, Watcher};
+use std::time::Duration;
 
 pub async fn execute(
     full: bool,
@@ -12,17 +19,45 @@ pub async fn execute(
     usb: bool,
     security: bool,
     fix: bool,
+    no_network: bool,
+    categories: Vec<CheckCategory>,
+    watch: Option<(Duration, WatchFormat)>,
     cli: &Cli,
-    _config: &Config,
+    config: &Config,
     output: &Output,
-) -> Result<()> {
+) -> Result<Outcome> {
+    let no_network = no_network || config.network.no_network_checks || is_offline();
+
     // Create health checker
-    let checker = HealthChecker::new(output.clone(), fix)?;
+    let checker = HealthChecker::new(output.clone(), fix)?
+        .with_github_token(github::resolve_token(config))
+        .with_no_network(no_network)
+        .with_config(config.clone());
+
+    if let Some((interval, format)) = watch {
+        let categories = if !categories.is_empty() {
+            categories
+        } else if packages {
+            vec![CheckCategory::Packages, CheckCategory::Repository]
+        } else if usb {
+            vec![CheckCategory::USB]
+        } else if security {
+            vec![CheckCategory::Security]
+        } else {
+            DEFAULT_CATEGORIES.to_vec()
+        };
+        Watcher::new(checker, output.clone(), interval, format).run(&categories).await?;
+        return Ok(Outcome::Success);
+    }
 
     // Run appropriate checks
     let report = if full {
         output.section("🏥 Running Full System Health Check");
         checker.check_all().await?
+    } else if !categories.is_empty() {
+        let names: Vec<&str> = categories.iter().map(|c| c.display_name()).collect();
+        output.section(&format!("🏥 Checking {}", names.join(", ")));
+        checker.check_categories(&categories).await?
     } else if packages {
         output.section("📦 Checking Package Management Health");
         checker.check_packages_only().await?
@@ -44,14 +79,14 @@ pub async fn execute(
 
     // Run diagnostics if requested
     if full && output.verbose {
-        let diagnostics = Diagnostics::new(output.clone(), fix, cli.dry_run);
+        let diagnostics = Diagnostics::new(output.clone()).with_no_network(no_network);
         diagnostics.run_diagnostics(&report).await?;
     }
 
     // Apply fixes if requested
     if fix {
-        let diagnostics = Diagnostics::new(output.clone(), fix, cli.dry_run);
-        diagnostics.apply_fixes(&report).await?;
+        let fixer = DoctorFixer::new(&checker, output.clone(), cli.yes, cli.dry_run);
+        fixer.fix(&report).await?;
     }
 
     // Export report if requested (could add --export flag)
@@ -59,5 +94,10 @@ pub async fn execute(
         formatter.export(&report, ExportFormat::Markdown, None)?;
     }
 
-    Ok(())
+    // Scripts see what was found, whether or not --fix repaired it
+    Ok(match report.overall_status() {
+        Severity::Critical => Outcome::HealthCritical,
+        Severity::Error => Outcome::HealthErrors,
+        _ => Outcome::Success,
+    })
 }
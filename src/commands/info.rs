@@ -1,44 +1,144 @@
 use anyhow::{Result, Context};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use crate::cache::{manager::CacheManager, CacheType};
 use crate::commands::Cli;
 use crate::core::config::Config;
 use crate::core::platform::PlatformInfo;
+use crate::core::{PackageInfo, PackageManager};
 use crate::managers::PackageManagerFactory;
 use crate::ui::output::Output;
+use crate::utils::download::is_offline;
 
-pub async fn execute(package: String, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
-    output.print_header(&format!("📌 Package Information: {}", package));
+#[derive(Debug, Clone, ValueEnum)]
+pub enum InfoFormat {
+    Text,
+    Json,
+}
+
+#[derive(Serialize)]
+struct InfoJson {
+    name: String,
+    package: Option<PackageInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    installed_files: Option<Vec<PathBuf>>,
+    /// Null when requested but the backend has none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changelog: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dependency_tree: Option<DependencyNode>,
+}
+
+/// Levels `--tree` follows unless `--depth` says otherwise
+pub const DEFAULT_TREE_DEPTH: usize = 3;
 
+/// One package in a dependency tree
+#[derive(Debug, Serialize)]
+struct DependencyNode {
+    name: String,
+    /// Expanded earlier in the tree, or an ancestor when dependencies
+    /// cycle; its dependencies are not repeated
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    visited: bool,
+    /// Beyond the depth limit, so its dependencies were not queried
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    dependencies: Vec<DependencyNode>,
+}
+
+/// Direct dependencies of every package within `depth` levels of `root`,
+/// each queried once, with the shallowest level it was reached at
+type Fetched = HashMap<String, (usize, std::result::Result<Vec<String>, String>)>;
+
+/// `changelog` is Some when requested, holding the `--since` version if any;
+/// `dependency_depth` is how many levels of dependencies to show
+pub async fn execute(
+    package: String,
+    installed_files: bool,
+    changelog: Option<Option<String>>,
+    dependency_depth: Option<usize>,
+    format: InfoFormat,
+    cli: &Cli,
+    config: &Config,
+    output: &Output,
+) -> Result<()> {
     // Detect platform and get package manager
     let platform_info = PlatformInfo::detect_async().await?;
     let package_manager = PackageManagerFactory::create(&platform_info)
         .context("Failed to create package manager")?;
 
-    output.info(&format!("🔍 Searching for package info using {}", package_manager.name()));
+    if let InfoFormat::Json = format {
+        let info = lookup(package_manager.as_ref(), &package, output).await?;
+        let files = if installed_files {
+            Some(package_manager.owned_files(&package).await?)
+        } else {
+            None
+        };
+        let changelog = match &changelog {
+            Some(since) => Some(package_manager.changelog(&package).await?
+                .map(|text| changelog_since(&text, since.as_deref()).to_string())),
+            None => None,
+        };
+        let dependency_tree = match dependency_depth {
+            Some(depth) => Some(dependency_tree(package_manager.as_ref(), &package, depth).await),
+            None => None,
+        };
+        let json = InfoJson { name: package, package: info, installed_files: files, changelog, dependency_tree };
+        output.print(&serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
 
-    // Try to get package info
-    match package_manager.info(&package).await {
-        Ok(Some(info)) => {
-            output.success(&format!("✅ Found package: {}", info.name));
+    if let Some(since) = changelog {
+        return show_changelog(package_manager.as_ref(), &package, since.as_deref(), output).await;
+    }
 
-            // Display package details
-            output.info(&format!("📦 Name: {}", info.name));
-            output.info(&format!("🏷️  Version: {}", info.version));
+    output.print_header(&format!("📌 Package Information: {}", package));
 
-            if let Some(description) = &info.description {
-                output.info(&format!("📚 Description: {}", description));
-            }
+    if is_offline() {
+        output.info("📴 Offline mode: reading cached package metadata");
+    } else {
+        output.info(&format!("🔍 Searching for package info using {}", package_manager.name()));
+    }
+    let result = lookup(package_manager.as_ref(), &package, output).await;
 
-            if let Some(size) = info.size {
-                let size_mb = size as f64 / 1024.0 / 1024.0;
-                output.info(&format!("💾 Size: {:.2} MB", size_mb));
-            }
+    if installed_files {
+        match &result {
+            Ok(Some(info)) => show_info(info, true, output),
+            Ok(None) => {}
+            Err(e) => output.warn(&format!("⚠️  Could not read package details: {}", e)),
+        }
+        return show_owned_files(package_manager.as_ref(), &package, output).await;
+    }
 
-            output.info(&format!("📂 Source: {}", info.source));
-            output.info(&format!("📥 Installed: {}", if info.installed { "✅ Yes" } else { "❌ No" }));
+    if let Some(depth) = dependency_depth {
+        match &result {
+            Ok(Some(info)) => show_info(info, false, output),
+            Ok(None) => {}
+            Err(e) => output.warn(&format!("⚠️  Could not read package details: {}", e)),
         }
+        let tree = dependency_tree(package_manager.as_ref(), &package, depth).await;
+        output.info("");
+        output.info(&format!("🌳 Dependencies ({} level{}):", depth, if depth == 1 { "" } else { "s" }));
+        for line in render_tree(&tree) {
+            output.print(&line);
+        }
+        return Ok(());
+    }
+
+    // Try to get package info
+    match result {
+        Ok(Some(info)) => show_info(&info, true, output),
         Ok(None) => {
             output.warn(&format!("⚠️  Package '{}' not found in {}", package, package_manager.name()));
 
+            if is_offline() {
+                return Ok(());
+            }
+
             // Try to search for similar packages
             output.info("🔍 Searching for similar packages...");
             match package_manager.search(&package).await {
@@ -64,4 +164,261 @@ pub async fn execute(package: String, cli: &Cli, config: &Config, output: &Outpu
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Package details from the backend, cached for offline use; offline reads the cache only
+async fn lookup(package_manager: &dyn PackageManager, package: &str, output: &Output) -> Result<Option<PackageInfo>> {
+    let cache_key = format!("info/{}/{}", package_manager.name(), package);
+
+    if is_offline() {
+        let mut cache = CacheManager::new(output.clone())?;
+        return match cache.load_cached_json(&CacheType::PackageMetadata, &cache_key) {
+            Some(info) => Ok(Some(info)),
+            None => anyhow::bail!("No cached metadata for '{}'; run 'pkmgr info {}' online first", package, package),
+        };
+    }
+
+    let result = package_manager.info(package).await;
+    if let Ok(Some(info)) = &result {
+        if let Ok(mut cache) = CacheManager::new(output.clone()) {
+            let _ = cache.store_json(CacheType::PackageMetadata, &cache_key, info);
+        }
+    }
+    result
+}
+
+/// `dependencies` is false when a dependency tree follows
+fn show_info(info: &PackageInfo, dependencies: bool, output: &Output) {
+    output.success(&format!("✅ Found package: {}", info.name));
+
+    // Display package details
+    output.info(&format!("📦 Name: {}", info.name));
+    output.info(&format!("🏷️  Version: {}", info.version));
+    if let Some(kind) = &info.kind {
+        output.info(&format!("🧩 Kind: {}", kind));
+    }
+
+    if let Some(description) = &info.description {
+        output.info(&format!("📚 Description: {}", description));
+    }
+
+    if let Some(size) = info.size {
+        let size_mb = size as f64 / 1024.0 / 1024.0;
+        output.info(&format!("💾 Size: {:.2} MB", size_mb));
+    }
+
+    if let Some(homepage) = &info.homepage {
+        output.info(&format!("🌐 Homepage: {}", homepage));
+    }
+
+    if let Some(license) = &info.license {
+        output.info(&format!("⚖️  License: {}", license));
+    }
+
+    if dependencies && !info.dependencies.is_empty() {
+        output.info(&format!("🔗 Dependencies: {}", info.dependencies.join(", ")));
+    }
+
+    output.info(&format!("📂 Source: {}", info.source));
+    output.info(&format!("📥 Installed: {}", if info.installed { "✅ Yes" } else { "❌ No" }));
+}
+
+async fn show_owned_files(package_manager: &dyn PackageManager, package: &str, output: &Output) -> Result<()> {
+    let files = package_manager.owned_files(package).await?;
+
+    output.info("");
+    if files.is_empty() {
+        output.note(&format!("📭 '{}' is not installed, so it owns no files", package));
+        return Ok(());
+    }
+
+    output.info(&format!("📁 Installed files ({}):", files.len()));
+    for file in &files {
+        output.print(&file.display().to_string());
+    }
+
+    Ok(())
+}
+
+async fn show_changelog(package_manager: &dyn PackageManager, package: &str, since: Option<&str>, output: &Output) -> Result<()> {
+    output.print_header(&format!("📜 Changelog: {}", package));
+
+    let Some(changelog) = package_manager.changelog(package).await? else {
+        output.info(&format!("{} does not provide changelogs for '{}'; check the project's release notes instead", package_manager.name(), package));
+        return Ok(());
+    };
+
+    let entries = changelog_since(&changelog, since);
+    if entries.trim().is_empty() {
+        output.info(&format!("No changelog entries newer than {}", since.unwrap_or_default()));
+    } else {
+        output.print(entries.trim_end());
+    }
+    Ok(())
+}
+
+/// Query the backend breadth first, so each package is reached at its
+/// shallowest depth, then lay the answers out as a tree
+async fn dependency_tree(package_manager: &dyn PackageManager, root: &str, depth: usize) -> DependencyNode {
+    let mut fetched = Fetched::new();
+    let mut level = vec![root.to_string()];
+    for level_depth in 0..depth {
+        let mut next = Vec::new();
+        for name in level {
+            if fetched.contains_key(&name) {
+                continue;
+            }
+            let dependencies = package_manager.dependencies(&name).await.map_err(|e| e.to_string());
+            if let Ok(dependencies) = &dependencies {
+                next.extend(dependencies.iter().filter(|dependency| !fetched.contains_key(*dependency)).cloned());
+            }
+            fetched.insert(name, (level_depth, dependencies));
+        }
+        level = next;
+    }
+    build_tree(root, 0, depth, &fetched, &mut HashSet::new())
+}
+
+/// Each package is expanded once, at the shallowest level it was fetched
+/// at; other appearances, including cycles back to an ancestor, are marked
+/// visited and nothing is expanded at `depth` or below
+fn build_tree(name: &str, level: usize, depth: usize, fetched: &Fetched, expanded: &mut HashSet<String>) -> DependencyNode {
+    let mut node = DependencyNode {
+        name: name.to_string(),
+        visited: false,
+        truncated: false,
+        error: None,
+        dependencies: Vec::new(),
+    };
+    match fetched.get(name) {
+        Some((fetched_at, _)) if *fetched_at < level => node.visited = true,
+        _ if level >= depth => node.truncated = true,
+        None => node.truncated = true,
+        Some(_) if !expanded.insert(name.to_string()) => node.visited = true,
+        Some((_, Err(e))) => node.error = Some(e.clone()),
+        Some((_, Ok(dependencies))) => {
+            node.dependencies = dependencies.iter()
+                .map(|dependency| build_tree(dependency, level + 1, depth, fetched, expanded))
+                .collect();
+        }
+    }
+    node
+}
+
+fn render_tree(root: &DependencyNode) -> Vec<String> {
+    fn children(node: &DependencyNode, prefix: &str, lines: &mut Vec<String>) {
+        for (index, child) in node.dependencies.iter().enumerate() {
+            let last = index + 1 == node.dependencies.len();
+            lines.push(format!("{}{}{}", prefix, if last { "└── " } else { "├── " }, label(child)));
+            children(child, &format!("{}{}", prefix, if last { "    " } else { "│   " }), lines);
+        }
+    }
+
+    fn label(node: &DependencyNode) -> String {
+        match &node.error {
+            _ if node.visited => format!("{} (already shown)", node.name),
+            _ if node.truncated => format!("{} … (depth limit)", node.name),
+            Some(e) => format!("{} ⚠️  {}", node.name, e),
+            None => node.name.clone(),
+        }
+    }
+
+    let mut lines = vec![label(root)];
+    children(root, "", &mut lines);
+    lines
+}
+
+/// The part of a changelog above the entry for `version`. Entries open with
+/// an unindented heading naming their version, "curl (7.81.0-1ubuntu1.15)
+/// jammy; urgency=medium" for Debian and "* Tue Jan 02 2024 Name <mail> -
+/// 8.2.1-3" for RPM; an epoch in `version` is ignored
+fn changelog_since<'a>(changelog: &'a str, version: Option<&str>) -> &'a str {
+    let Some(version) = version else { return changelog };
+    let bare = version.split_once(':').map_or(version, |(_, rest)| rest);
+
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        let heading = !line.starts_with(char::is_whitespace) && !line.starts_with('-');
+        if heading && line.split(|c: char| c.is_whitespace() || "();,".contains(c)).any(|token| token == version || token == bare) {
+            return &changelog[..offset];
+        }
+        offset += line.len();
+    }
+    changelog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changelog_stops_at_the_since_entry() {
+        let debian = "curl (7.81.0-1ubuntu1.15) jammy-security; urgency=medium\n\n  * SECURITY UPDATE: cookie mixed case\n\n\
+            curl (7.81.0-1ubuntu1.14) jammy-security; urgency=medium\n\n  * older fix\n";
+        assert_eq!(
+            changelog_since(debian, Some("7.81.0-1ubuntu1.14")),
+            "curl (7.81.0-1ubuntu1.15) jammy-security; urgency=medium\n\n  * SECURITY UPDATE: cookie mixed case\n\n"
+        );
+
+        let rpm = "* Tue Jan 02 2024 Jan Doe <jan@example.com> - 1:3.1.4-1\n- Rebase\n\n\
+            * Mon Oct 02 2023 Jan Doe <jan@example.com> - 1:3.1.1-4\n- Fix 1:3.1.1-4 build\n";
+        assert_eq!(changelog_since(rpm, Some("1:3.1.1-4")), "* Tue Jan 02 2024 Jan Doe <jan@example.com> - 1:3.1.4-1\n- Rebase\n\n");
+        assert_eq!(changelog_since(rpm, None), rpm);
+    }
+
+    #[test]
+    fn dependency_cycles_are_marked_visited() {
+        let fetched: Fetched = [
+            ("app", 0, Ok(vec!["libfoo".to_string(), "libbar".to_string()])),
+            ("libfoo", 1, Ok(vec!["libbar".to_string()])),
+            ("libbar", 1, Ok(vec!["app".to_string(), "libc".to_string()])),
+            ("libc", 2, Err("Package 'libc' not found".to_string())),
+        ].into_iter().map(|(name, level, dependencies)| (name.to_string(), (level, dependencies))).collect();
+
+        let tree = build_tree("app", 0, 3, &fetched, &mut HashSet::new());
+        assert_eq!(render_tree(&tree), vec![
+            "app",
+            "├── libfoo",
+            "│   └── libbar (already shown)",
+            "└── libbar",
+            "    ├── app (already shown)",
+            "    └── libc ⚠️  Package 'libc' not found",
+        ]);
+    }
+
+    #[test]
+    fn diamond_dependencies_stop_at_the_depth_limit() {
+        // app -> left -> shared -> deep, app -> right -> shared; at depth 2
+        // only app, left and right were queried
+        let fetched: Fetched = [
+            ("app", 0, Ok(vec!["left".to_string(), "right".to_string()])),
+            ("left", 1, Ok(vec!["shared".to_string()])),
+            ("right", 1, Ok(vec!["shared".to_string()])),
+        ].into_iter().map(|(name, level, dependencies)| (name.to_string(), (level, dependencies))).collect();
+
+        let tree = build_tree("app", 0, 2, &fetched, &mut HashSet::new());
+        assert_eq!(render_tree(&tree), vec![
+            "app",
+            "├── left",
+            "│   └── shared … (depth limit)",
+            "└── right",
+            "    └── shared … (depth limit)",
+        ]);
+
+        // A package fetched at a shallow level is not expanded deeper down
+        let fetched: Fetched = [
+            ("app", 0, Ok(vec!["left".to_string(), "shared".to_string()])),
+            ("left", 1, Ok(vec!["shared".to_string()])),
+            ("shared", 1, Ok(vec!["deep".to_string()])),
+        ].into_iter().map(|(name, level, dependencies)| (name.to_string(), (level, dependencies))).collect();
+
+        let tree = build_tree("app", 0, 2, &fetched, &mut HashSet::new());
+        assert_eq!(render_tree(&tree), vec![
+            "app",
+            "├── left",
+            "│   └── shared (already shown)",
+            "└── shared",
+            "    └── deep … (depth limit)",
+        ]);
+    }
+}
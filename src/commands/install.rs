@@ -1,9 +1,15 @@
 use anyhow::{Result, Context};
+use crate::cache::manager::CacheManager;
 use crate::commands::Cli;
+use crate::commands::preview::preview;
 use crate::core::config::Config;
 use crate::core::platform::PlatformInfo;
+use crate::core::privilege::{self, InstallTarget};
 use crate::core::normalizer::PackageNormalizer;
+use crate::core::traits::Operation;
 use crate::managers::PackageManagerFactory;
+use crate::profile::hooks::{HookRunner, HookStage};
+use crate::repos::manager::RepositoryManager;
 use crate::ui::output::Output;
 
 pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
@@ -12,8 +18,17 @@ pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output:
         return Ok(());
     }
 
+    // Native packages always install system-wide; this rejects --user with
+    // --global and fails early when --global has no way to get root
+    if let InstallTarget::User { .. } = privilege::install_target(cli.user, cli.global)? {
+        output.warn("--user has no effect on native packages; they install system-wide");
+    }
+
     output.print_header("📦 Installing Packages");
 
+    let hooks = HookRunner::from_cli(cli, output)?;
+    hooks.run(HookStage::PreInstall)?;
+
     // Detect platform and get package manager
     let platform_info = PlatformInfo::detect_async().await?;
     output.debug(&format!("Detected platform: {} - {:?}", platform_info.os(), platform_info.distribution));
@@ -30,9 +45,28 @@ pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output:
     // Initialize normalizer for package name mapping
     let normalizer = PackageNormalizer::new();
 
+    if cli.dry_run {
+        let mut native = Vec::new();
+        for package in &packages {
+            let normalized_names = normalizer.normalize(package, pm_type)?;
+            if normalized_names.is_empty() {
+                native.push(package.clone());
+            } else {
+                native.extend(normalized_names);
+            }
+        }
+        return preview(package_manager.as_ref(), Operation::Install(&native), output).await;
+    }
+
+    // Used to enforce the minimum repository trust level
+    let repo_manager = RepositoryManager::new(output.clone(), platform_info.clone());
+    let min_trust_level = &config.security.min_trust_level;
+
     // Track successful and failed installations
     let mut installed = Vec::new();
     let mut failed = Vec::new();
+    let mut changed = false;
+    let mut native_installed = Vec::new();
 
     for package in &packages {
         output.install_start(package);
@@ -61,12 +95,31 @@ pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output:
             continue;
         }
 
+        // Refuse packages from repositories below the trust policy
+        if !cli.force {
+            let blocked = packages_to_use.iter()
+                .filter_map(|p| repo_manager.resolve_package_source(p))
+                .find(|repo| !repo.metadata.trust_level.meets(min_trust_level));
+
+            if let Some(repo) = blocked {
+                output.error(&format!(
+                    "❌ Refusing to install {}: repository '{}' has trust level {}, below the minimum {}",
+                    package, repo.name, repo.metadata.trust_level, min_trust_level
+                ));
+                output.info("Use --force to install anyway, or lower security.min_trust_level");
+                failed.push(package.clone());
+                continue;
+            }
+        }
+
         // Attempt installation
         match package_manager.install(&packages_to_use).await {
             Ok(result) => {
                 if result.success {
                     output.success(&format!("✅ Installed {}", package));
                     installed.push(package.clone());
+                    native_installed.extend(packages_to_use.iter().cloned());
+                    changed = true;
                 } else {
                     output.error(&format!("❌ Failed to install {}: {}", package, result.message));
                     failed.push(package.clone());
@@ -79,6 +132,13 @@ pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output:
         }
     }
 
+    if changed {
+        if let Ok(mut cache) = CacheManager::new(output.clone()) {
+            let _ = cache.update_installed(&native_installed, &[]);
+        }
+        hooks.run(HookStage::PostInstall)?;
+    }
+
     // Summary
     output.print_header("📊 Installation Summary");
     
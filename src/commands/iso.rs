@@ -13,6 +13,9 @@ pub enum IsoCommands {
         /// Show downloaded ISOs only
         #[arg(long)]
         downloaded: bool,
+        /// Hide distributions and versions without download URLs
+        #[arg(long)]
+        downloadable_only: bool,
     },
     /// Download ISO
     Install {
@@ -20,6 +23,9 @@ pub enum IsoCommands {
         distro: String,
         /// Version (optional, uses current if not specified)
         version: Option<String>,
+        /// Download from this mirror instead of probing for the fastest
+        #[arg(long, value_name = "URL")]
+        mirror: Option<String>,
     },
     /// Delete downloaded ISO file
     Remove {
@@ -30,6 +36,12 @@ pub enum IsoCommands {
     Info {
         /// Distribution name
         distro: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: crate::commands::info::InfoFormat,
+        /// Probe the distribution's mirrors and rank them by speed
+        #[arg(long)]
+        mirror_list: bool,
     },
     /// Verify ISO checksums and signatures
     Verify {
@@ -46,21 +58,21 @@ pub async fn execute(cmd: IsoCommands, cli: &Cli, config: &Config, output: &Outp
     let manager = IsoManager::new(config.clone(), output.clone())?;
     
     match cmd {
-        IsoCommands::List { distro, downloaded } => {
+        IsoCommands::List { distro, downloaded, downloadable_only } => {
             if downloaded {
                 manager.list_downloaded().await
             } else {
-                manager.list(distro).await
+                manager.list(distro, downloadable_only).await
             }
         }
-        IsoCommands::Install { distro, version } => {
-            manager.install(distro, version).await
+        IsoCommands::Install { distro, version, mirror } => {
+            manager.install(distro, version, mirror).await
         }
         IsoCommands::Remove { iso_file } => {
             manager.remove(iso_file).await
         }
-        IsoCommands::Info { distro } => {
-            manager.info(distro).await
+        IsoCommands::Info { distro, format, mirror_list } => {
+            manager.info(distro, format, mirror_list).await
         }
         IsoCommands::Verify { iso_file } => {
             manager.verify(iso_file).await
@@ -1,10 +1,30 @@
 use anyhow::Result;
-use clap::Subcommand;
+use clap::{Args, Subcommand};
 use crate::commands::Cli;
 use crate::core::config::Config;
+use crate::core::privilege::{self, InstallTarget, TargetUser};
+use crate::languages::dotnet::ToolScope;
 use crate::ui::output::Output;
+use crate::languages::dotnet::DotnetTools;
+use crate::languages::installer::LanguageInstaller;
+use crate::languages::java::JavaSpec;
 use crate::languages::resolver::VersionResolver;
 
+/// `pkmgr <lang> <subcommand>`, or `pkmgr <lang> [--use <version>] -- <command>`
+/// to run one command with a version regardless of version files
+#[derive(Debug, Args, Clone)]
+#[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+pub struct LanguageArgs<T: Subcommand> {
+    /// Version for this invocation; partial versions pick the newest install (18 -> 18.19.0)
+    #[arg(long = "use", value_name = "VERSION", requires = "run")]
+    pub use_version: Option<String>,
+    /// Command to run after --, e.g. `-- node script.js`
+    #[arg(last = true, value_name = "COMMAND")]
+    pub run: Vec<String>,
+    #[command(subcommand)]
+    pub command: Option<T>,
+}
+
 #[derive(Debug, Subcommand, Clone)]
 pub enum NodeCommands {
     /// Install specific Node.js version
@@ -103,7 +123,7 @@ pub enum PhpCommands {
 
 #[derive(Debug, Subcommand, Clone)]
 pub enum JavaCommands {
-    /// Install specific Java version
+    /// Install a JDK: temurin-21, zulu-17, graalvm-21, or a bare version with the default vendor
     Install { version: String },
     /// Switch active version
     Use { version: String },
@@ -127,6 +147,37 @@ pub enum DotnetCommands {
     Remove { version: String },
     /// Show current active version
     Current,
+    /// Manage .NET tools: global by default, --local for dotnet-tools.json
+    #[command(subcommand)]
+    Tool(DotnetToolCommands),
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum DotnetToolCommands {
+    /// Install a tool (pin a version with --version)
+    Install {
+        package: String,
+        /// Add to the repository's dotnet-tools.json, creating it if needed
+        #[arg(long)]
+        local: bool,
+    },
+    /// List installed tools
+    List {
+        #[arg(long)]
+        local: bool,
+    },
+    /// Update a tool, or all tools in the scope
+    Update {
+        package: Option<String>,
+        #[arg(long)]
+        local: bool,
+    },
+    /// Uninstall a tool
+    Remove {
+        package: String,
+        #[arg(long)]
+        local: bool,
+    },
 }
 
 pub async fn execute_node(cmd: NodeCommands, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
@@ -280,7 +331,12 @@ pub async fn execute_php(cmd: PhpCommands, cli: &Cli, config: &Config, output: &
 pub async fn execute_java(cmd: JavaCommands, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
     match cmd {
         JavaCommands::Install { version } => {
-            output.info(&format!("☕ Installing Java: {}", version));
+            if cli.dry_run {
+                let spec = JavaSpec::parse(&version, &config.language_defaults.java_vendor)?;
+                output.info(&format!("☕ Would install {} JDK {}", spec.vendor.name(), spec.version));
+                return Ok(());
+            }
+            install_version("java", &version, cli, config, output).await?;
         }
         JavaCommands::Use { version } => {
             output.info(&format!("🔄 Switching to Java: {}", version));
@@ -315,6 +371,85 @@ pub async fn execute_dotnet(cmd: DotnetCommands, cli: &Cli, config: &Config, out
         DotnetCommands::Current => {
             output.info("Current .NET version: 8.0.0");
         }
+        DotnetCommands::Tool(tool_cmd) => {
+            execute_dotnet_tool(tool_cmd, cli, output)?;
+        }
+    }
+    Ok(())
+}
+
+/// Install a language version honoring `--user` / `--global` like binary
+/// installs: under sudo, `--user` installs into the caller's home and hands
+/// the files to them; `--global` installs under /usr/local/share/pkmgr
+async fn install_version(language: &str, version: &str, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
+    let (config, owner) = match privilege::install_target(cli.user, cli.global)? {
+        InstallTarget::System { escalate: true } => {
+            output.info("🔐 --global installs under /usr/local; re-running with root privileges");
+            return privilege::rerun_privileged();
+        }
+        InstallTarget::System { escalate: false } => (privilege::system_config(config), None),
+        InstallTarget::User { owner: Some(owner) } => {
+            output.info(&format!("Installing for {} (invoked through sudo)", owner.name));
+            (owner.config(config), Some(owner))
+        }
+        InstallTarget::User { owner: None } | InstallTarget::Configured => (config.clone(), None),
+    };
+
+    let path = LanguageInstaller::new(language.to_string(), output.clone(), &config)
+        .install_version(version)
+        .await?;
+    match owner {
+        Some(owner) => owner.adopt(&path),
+        None => Ok(()),
+    }
+}
+
+/// The scope for a tool command; `--user` under sudo targets the caller's
+/// ~/.dotnet/tools instead of root's
+fn dotnet_tool_scope(tools: &DotnetTools, local: bool, create_manifest: bool, cli: &Cli) -> Result<(ToolScope, Option<TargetUser>)> {
+    if !local {
+        if let InstallTarget::User { owner: Some(owner) } = privilege::install_target(cli.user, cli.global)? {
+            return Ok((ToolScope::Path(owner.home.join(".dotnet").join("tools")), Some(owner)));
+        }
+    }
+    Ok((tools.scope(local, create_manifest)?, None))
+}
+
+fn execute_dotnet_tool(cmd: DotnetToolCommands, cli: &Cli, output: &Output) -> Result<()> {
+    let tools = DotnetTools::new(output.clone());
+    let version = cli.version.as_deref();
+    let adopt = |scope: &ToolScope, owner: Option<TargetUser>| match (scope, owner) {
+        (ToolScope::Path(dir), Some(owner)) => owner.adopt(dir),
+        _ => Ok(()),
+    };
+
+    match cmd {
+        DotnetToolCommands::Install { package, local } => {
+            let (scope, owner) = dotnet_tool_scope(&tools, local, true, cli)?;
+            output.info(&format!("🔷 Installing .NET tool: {}", package));
+            tools.install(&scope, &package, version)?;
+            adopt(&scope, owner)?;
+        }
+        DotnetToolCommands::List { local } => {
+            let (scope, _) = dotnet_tool_scope(&tools, local, false, cli)?;
+            let installed = tools.list(&scope)?;
+            if installed.is_empty() {
+                output.info("No .NET tools installed");
+            }
+            for (id, tool_version) in installed {
+                output.info(&format!("  {} {}", id, tool_version));
+            }
+        }
+        DotnetToolCommands::Update { package, local } => {
+            let (scope, owner) = dotnet_tool_scope(&tools, local, false, cli)?;
+            tools.update(&scope, package.as_deref(), version)?;
+            adopt(&scope, owner)?;
+        }
+        DotnetToolCommands::Remove { package, local } => {
+            let (scope, _) = dotnet_tool_scope(&tools, local, false, cli)?;
+            output.info(&format!("🗑️ Removing .NET tool: {}", package));
+            tools.remove(&scope, &package)?;
+        }
     }
     Ok(())
 }
\ No newline at end of file
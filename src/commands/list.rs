@@ -1,18 +1,42 @@
-use anyhow::{Result, Context};
+use anyhow::{bail, Result, Context};
 use clap::ValueEnum;
+use crate::cache::{manager::CacheManager, CacheType};
 use crate::commands::Cli;
+use crate::commands::binary::{self, InstalledBinary};
 use crate::core::config::Config;
 use crate::core::platform::PlatformInfo;
+use crate::core::sbom::{self, Component, ComponentSource};
+use crate::core::traits::{PackageInfo, PackageManager, PackageUpdate};
+use crate::languages::{self, resolver::VersionResolver};
 use crate::managers::PackageManagerFactory;
+use crate::repos::manager::RepositoryManager;
 use crate::ui::output::Output;
 
 #[derive(Debug, Clone, ValueEnum)]
 pub enum ListType {
     Installed,
     Available,
+    /// Installed packages with a newer version in the repositories
+    Upgradable,
 }
 
-pub async fn execute(list_type: Option<ListType>, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
+#[derive(Debug, Clone, PartialEq, ValueEnum)]
+pub enum ListFormat {
+    Text,
+    /// JSON array of installed or upgradable packages
+    Json,
+    /// CycloneDX JSON inventory of system packages, binaries and language versions
+    Cyclonedx,
+}
+
+/// Order of `list upgradable`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ListSort {
+    Name,
+    Repo,
+}
+
+pub async fn execute(list_type: Option<ListType>, format: ListFormat, sort: ListSort, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
     let list_type = list_type.unwrap_or(ListType::Installed);
 
     // Detect platform and get package manager
@@ -20,13 +44,34 @@ pub async fn execute(list_type: Option<ListType>, cli: &Cli, config: &Config, ou
     let package_manager = PackageManagerFactory::create(&platform_info)
         .context("Failed to create package manager")?;
 
+    if format == ListFormat::Cyclonedx {
+        if !matches!(list_type, ListType::Installed) {
+            bail!("--format cyclonedx lists installed packages only");
+        }
+        let packages = package_manager.list_installed().await?;
+        output.print(&inventory(&packages, &platform_info, config, output).await?);
+        return Ok(());
+    }
+
+    if format == ListFormat::Json && matches!(list_type, ListType::Available) {
+        bail!("--format json lists installed or upgradable packages only");
+    }
+
     match list_type {
+        ListType::Installed if format == ListFormat::Json => {
+            output.print(&serde_json::to_string_pretty(&package_manager.list_installed().await?)?);
+        }
         ListType::Installed => {
             output.print_header("📦 Installed Packages");
             output.info(&format!("🔍 Listing packages from {}...", package_manager.name()));
 
             match package_manager.list_installed().await {
                 Ok(packages) => {
+                    // Feeds package name completion for remove/update
+                    if let Ok(mut cache) = CacheManager::new(output.clone()) {
+                        let _ = cache.store_installed(packages.iter().map(|p| p.name.clone()).collect());
+                    }
+
                     if packages.is_empty() {
                         output.warn("⚠️  No packages found");
                     } else {
@@ -65,6 +110,23 @@ pub async fn execute(list_type: Option<ListType>, cli: &Cli, config: &Config, ou
                 }
             }
         }
+        ListType::Available if crate::utils::download::is_offline() => {
+            output.print_header("📋 Available Packages (cached)");
+
+            let packages = CacheManager::new(output.clone())?.cached_packages();
+            if packages.is_empty() {
+                output.warn("⚠️  No cached package metadata available");
+                output.info("💡 Run 'pkmgr search <query>' online to populate the cache");
+                return Ok(());
+            }
+
+            for pkg in &packages {
+                output.info(&format!("  📦 {} ({})", pkg.name, pkg.version));
+            }
+
+            output.info("");
+            output.info(&format!("📊 Total: {} cached packages", packages.len()));
+        }
         ListType::Available => {
             output.print_header("📋 Available Packages");
             output.info(&format!("Package manager: {}", package_manager.name()));
@@ -76,7 +138,112 @@ pub async fn execute(list_type: Option<ListType>, cli: &Cli, config: &Config, ou
             output.info("     pkmgr search web");
             output.info("     pkmgr search editor");
         }
+        ListType::Upgradable => {
+            let mut updates = upgradable(package_manager.as_ref(), &platform_info, output).await?;
+            match sort {
+                ListSort::Name => updates.sort_by(|a, b| a.name.cmp(&b.name)),
+                ListSort::Repo => updates.sort_by(|a, b| a.repo.cmp(&b.repo).then_with(|| a.name.cmp(&b.name))),
+            }
+
+            if format == ListFormat::Json {
+                output.print(&serde_json::to_string_pretty(&updates)?);
+            } else if updates.is_empty() {
+                output.success("✅ All packages up to date");
+            } else {
+                output.print_header(&format!("⬆️ Upgradable Packages ({})", updates.len()));
+                let rows: Vec<Vec<String>> = updates.iter()
+                    .map(|update| vec![
+                        update.name.clone(),
+                        update.current.clone(),
+                        update.candidate.clone(),
+                        update.repo.clone(),
+                    ])
+                    .collect();
+                output.print_table(&["Package", "Installed", "Candidate", "Repository"], &rows);
+            }
+        }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Upgradable packages, reused from the cache until the index TTL passes,
+/// the index is refreshed or packages change; offline, cached only
+async fn upgradable(package_manager: &dyn PackageManager, platform: &PlatformInfo, output: &Output) -> Result<Vec<PackageUpdate>> {
+    let key = CacheManager::upgradable_key(package_manager.name());
+    let mut cache = CacheManager::new(output.clone())?;
+    if let Some(updates) = cache.load_cached_json(&CacheType::RepositoryIndex, &key) {
+        output.debug("Using upgradable packages cached since the last index refresh");
+        return Ok(updates);
+    }
+    if crate::utils::download::is_offline() {
+        bail!("No cached upgradable packages; run 'pkmgr list upgradable' online first");
+    }
+
+    RepositoryManager::new(output.clone(), platform.clone()).refresh_if_stale(package_manager).await;
+    let updates = package_manager.upgradable().await?;
+    let _ = cache.store_json(CacheType::RepositoryIndex, &key, &updates);
+    Ok(updates)
+}
+
+/// CycloneDX document for system packages, GitHub binaries and
+/// pkmgr-managed language versions
+async fn inventory(packages: &[PackageInfo], platform: &PlatformInfo, config: &Config, output: &Output) -> Result<String> {
+    let manager = packages.first().map(|p| p.source.as_str()).unwrap_or_default();
+    let distro = platform.distribution.as_deref();
+
+    let mut components: Vec<Component> = packages.iter()
+        .map(|p| Component::new(&p.name, &p.version, ComponentSource::System { manager, distro }))
+        .collect();
+
+    components.extend(binary_components(&binary::load_all_installed(config).await?));
+
+    for language in languages::LANGUAGES {
+        let resolver = VersionResolver::new(language.to_string(), output.clone());
+        for version in resolver.installed_versions() {
+            components.push(Component::new(language, &version, ComponentSource::Language));
+        }
+    }
+
+    // bom-refs must be unique, and multi-arch installs can repeat a package
+    let mut seen = std::collections::HashSet::new();
+    components.retain(|component| seen.insert(component.purl().to_string()));
+
+    let os = distro.map(|name| (name, platform.version.as_deref()));
+    Ok(sbom::cyclonedx(&components, os)?)
+}
+
+/// Inventory entries for GitHub binaries, user and `--global` alike
+fn binary_components(installed: &[(InstalledBinary, Config)]) -> Vec<Component> {
+    installed.iter()
+        .map(|(binary, _)| Component::new(&binary.name, &binary.version, ComponentSource::Binary { repository: &binary.repository }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inventory_includes_global_binaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_at = |data: &str| {
+            let mut config = Config::default();
+            config.paths.data_dir = dir.path().join(data).display().to_string();
+            let state = dir.path().join(data).join("binaries");
+            std::fs::create_dir_all(&state).unwrap();
+            config
+        };
+        let user = config_at("user");
+        let system = config_at("system");
+        std::fs::write(user.get_data_dir().unwrap().join("binaries").join("installed.toml"),
+            "[gh]\nrepository = \"cli/cli\"\nversion = \"v2.40.0\"\n").unwrap();
+        std::fs::write(system.get_data_dir().unwrap().join("binaries").join("installed.toml"),
+            "[helm]\nrepository = \"helm/helm\"\nversion = \"v3.14.0\"\n").unwrap();
+
+        let installed = tokio::runtime::Runtime::new().unwrap()
+            .block_on(binary::load_installed_alongside(&user, &system)).unwrap();
+        let purls: Vec<String> = binary_components(&installed).iter().map(|c| c.purl().to_string()).collect();
+        assert_eq!(purls, vec!["pkg:github/cli/cli@v2.40.0", "pkg:github/helm/helm@v3.14.0"]);
+    }
+}
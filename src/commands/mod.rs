@@ -2,10 +2,15 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 
 use crate::core::config::Config;
+use crate::core::exit::{Outcome, UsageError};
 use crate::ui::output::Output;
+use crate::languages;
 
+pub mod autoremove;
 pub mod binary;
 pub mod cache;
+pub mod check;
+pub mod complete;
 pub mod config;
 pub mod doctor;
 pub mod info;
@@ -13,7 +18,9 @@ pub mod install;
 pub mod iso;
 pub mod language;
 pub mod list;
+pub mod preview;
 pub mod profile;
+pub mod reclaim;
 pub mod remove;
 pub mod repos;
 pub mod search;
@@ -23,6 +30,7 @@ pub mod whatis;
 pub mod where_pkg;
 pub mod update;
 pub mod usb;
+pub mod version;
 pub mod recovery;
 
 #[derive(clap::ValueEnum, Clone)]
@@ -31,14 +39,14 @@ pub enum SelfUpdateCommand {
     Check,
     /// Download and install update
     Yes,
-    /// Set update branch (stable, beta, daily)
+    /// Show the update branch, or set it (stable, beta, daily)
     Branch,
 }
 
 #[derive(Parser)]
 #[command(
     name = "pkmgr",
-    version = "1.0.0",
+    version = version::VERSION_LINE,
     about = "CasjaysDev Package Manager - Universal package manager with one consistent interface",
     author = "Jason Hempstead <jason@casjaysdev.pro>",
     long_about = "A universal package manager that provides one consistent interface across all package sources.
@@ -77,12 +85,16 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub profile: Option<String>,
 
+    /// Use this config file (or $PKMGR_CONFIG); its directory also holds cache, repository and profile config
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<std::path::PathBuf>,
+
     /// Specify target architecture
     #[arg(long, global = true)]
     pub arch: Option<String>,
 
     /// Specify target version
-    #[arg(long = "target-version", global = true)]
+    #[arg(long = "target-version", id = "target_version", global = true)]
     pub version: Option<String>,
 
     /// Force system-wide installation
@@ -92,6 +104,34 @@ pub struct Cli {
     /// Force user-space installation
     #[arg(long, global = true)]
     pub user: bool,
+
+    /// Use this package manager instead of the detected one (apt, dnf, yum, pacman, brew, winget, choco, scoop)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub manager: Option<String>,
+
+    /// Work from cached metadata only; never touch the network
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Fail network operations that take longer than this many seconds
+    #[arg(long, global = true, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Trust the CA certificates in this PEM bundle as well as the built-in roots
+    #[arg(long, global = true, value_name = "PATH")]
+    pub cacert: Option<std::path::PathBuf>,
+
+    /// Write a timestamped log of native commands and their output to a file
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<std::path::PathBuf>,
+
+    /// Skip the active profile's pre/post install and update hooks
+    #[arg(long, global = true)]
+    pub no_hooks: bool,
+
+    /// Remove a lock file left by a pkmgr that is no longer running
+    #[arg(long, global = true)]
+    pub force_unlock: bool,
 }
 
 #[derive(Subcommand, Clone)]
@@ -108,34 +148,82 @@ pub enum Commands {
     Remove {
         /// Package name(s) to remove
         packages: Vec<String>,
+        /// Also remove configuration files (apt purge, pacman -Rn, scoop/winget --purge)
+        #[arg(long, conflicts_with = "keep_config")]
+        purge: bool,
+        /// Keep configuration files where the backend allows it (the default)
+        #[arg(long)]
+        keep_config: bool,
     },
 
+    /// Remove dependencies no installed package needs any more
+    Autoremove,
+
+    /// Free space from package manager caches, orphaned packages and pkmgr's cache
+    Reclaim,
+
     /// Update packages (all if no target specified)
     #[command(alias = "u", alias = "up")]
     Update {
         /// Package name(s) to update, or "all" for everything
         packages: Option<Vec<String>>,
+        /// Only apply updates from security advisories (apt, dnf)
+        #[arg(long, conflicts_with = "packages")]
+        security_only: bool,
+        /// Skip a package during the update (repeatable)
+        #[arg(long, value_name = "PKG", value_delimiter = ',')]
+        exclude: Vec<String>,
     },
 
     /// Search system package manager
     #[command(alias = "s")]
     Search {
-        /// Search query
+        /// Search query, or a file path with --file
         query: String,
+        /// Find the package that owns or provides a file
+        #[arg(long)]
+        file: bool,
     },
 
     /// List packages
     #[command(alias = "ls")]
     List {
-        /// List type: installed, available
+        /// List type: installed, available, upgradable
         #[arg(value_enum)]
         list_type: Option<list::ListType>,
+        /// Output format; cyclonedx writes an SBOM of everything installed
+        #[arg(long, value_enum, default_value = "text")]
+        format: list::ListFormat,
+        /// Sort upgradable packages by name or repository
+        #[arg(long, value_enum, default_value = "name")]
+        sort: list::ListSort,
     },
 
     /// Show detailed package information
     Info {
         /// Package name
         package: String,
+        /// List the files the package installed
+        #[arg(long)]
+        installed_files: bool,
+        /// Show the package changelog
+        #[arg(long)]
+        changelog: bool,
+        /// Only changelog entries newer than this version (implies --changelog)
+        #[arg(long, value_name = "VERSION")]
+        since: Option<String>,
+        /// Show the package's dependencies
+        #[arg(long)]
+        deps: bool,
+        /// Follow dependencies recursively (implies --deps)
+        #[arg(long)]
+        tree: bool,
+        /// How many levels --tree follows
+        #[arg(long, value_name = "N", default_value_t = info::DEFAULT_TREE_DEPTH, requires = "tree")]
+        depth: usize,
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: info::InfoFormat,
     },
 
     /// Show installation location/path
@@ -161,25 +249,20 @@ pub enum Commands {
         /// Analyze the last error from command output
         #[arg(long)]
         last_error: bool,
+        /// Pick which suggested fix to apply for the last error (implies --last-error)
+        #[arg(long, conflicts_with = "auto")]
+        interactive: bool,
     },
 
     /// Language version management
-    #[command(subcommand)]
-    Node(language::NodeCommands),
-    #[command(subcommand)]
-    Python(language::PythonCommands),
-    #[command(subcommand)]
-    Go(language::GoCommands),
-    #[command(subcommand)]
-    Rust(language::RustCommands),
-    #[command(subcommand)]
-    Ruby(language::RubyCommands),
-    #[command(subcommand)]
-    Php(language::PhpCommands),
-    #[command(subcommand)]
-    Java(language::JavaCommands),
-    #[command(subcommand)]
-    Dotnet(language::DotnetCommands),
+    Node(language::LanguageArgs<language::NodeCommands>),
+    Python(language::LanguageArgs<language::PythonCommands>),
+    Go(language::LanguageArgs<language::GoCommands>),
+    Rust(language::LanguageArgs<language::RustCommands>),
+    Ruby(language::LanguageArgs<language::RubyCommands>),
+    Php(language::LanguageArgs<language::PhpCommands>),
+    Java(language::LanguageArgs<language::JavaCommands>),
+    Dotnet(language::LanguageArgs<language::DotnetCommands>),
 
     /// Binary management
     #[command(subcommand)]
@@ -226,13 +309,28 @@ pub enum Commands {
         /// Auto-fix issues where possible
         #[arg(long)]
         fix: bool,
+        /// Skip checks that need network access (implied by --offline)
+        #[arg(long)]
+        no_network: bool,
+        /// Only run checks in these categories (repeatable)
+        #[arg(long, value_enum)]
+        category: Vec<crate::doctor::CheckCategory>,
+        /// Re-run the checks on an interval, printing only what changed
+        #[arg(long, conflicts_with = "fix")]
+        watch: bool,
+        /// Seconds between runs with --watch
+        #[arg(long, default_value_t = 300, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// Change format with --watch; json prints one object per line
+        #[arg(long, value_enum, default_value = "text", requires = "watch")]
+        format: crate::doctor::watch::WatchFormat,
     },
 
     /// Bootstrap and sync
     #[command(subcommand)]
     Bootstrap(sync::BootstrapCommands),
-    #[command(subcommand)]
-    Sync(sync::SyncCommands),
+    /// Reconcile installed packages to a profile
+    Sync(sync::SyncArgs),
 
     /// Check for updates
     Check {
@@ -245,6 +343,13 @@ pub enum Commands {
     #[command(subcommand)]
     Shell(shell::ShellCommands),
 
+    /// Show the pkmgr version
+    Version {
+        /// Include build, platform and package manager details for bug reports
+        #[arg(long, visible_alias = "about")]
+        full: bool,
+    },
+
     /// Check and perform pkmgr self-updates
     #[command(name = "update-self")]
     UpdateSelf {
@@ -256,30 +361,206 @@ pub enum Commands {
     },
 }
 
-pub async fn execute(cli: Cli, config: Config, output: Output) -> Result<()> {
+impl Commands {
+    /// Name of the operation if this command cannot work from the cache alone
+    fn network_operation(&self) -> Option<&'static str> {
+        match self {
+            Commands::Install { .. } => Some("install"),
+            Commands::Update { .. } => Some("update"),
+            Commands::Sync(_) => Some("sync"),
+            Commands::UpdateSelf { .. } => Some("update-self"),
+            Commands::Binary(cmd) => match cmd {
+                binary::BinaryCommands::Install { .. } => Some("binary install"),
+                binary::BinaryCommands::Update { .. } => Some("binary update"),
+                binary::BinaryCommands::Search { .. } => Some("binary search"),
+                binary::BinaryCommands::Info { .. } => Some("binary info"),
+                _ => None,
+            },
+            Commands::Iso(iso::IsoCommands::Install { .. }) => Some("iso install"),
+            Commands::Repos(cmd) => match cmd {
+                repos::ReposCommands::Add { .. } => Some("repos add"),
+                repos::ReposCommands::Update => Some("repos update"),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Name of the operation if this command changes the system and must
+    /// hold the pkmgr operation lock
+    fn mutating_operation(&self) -> Option<&'static str> {
+        match self {
+            Commands::Install { .. } => Some("install"),
+            Commands::Remove { .. } => Some("remove"),
+            Commands::Autoremove => Some("autoremove"),
+            Commands::Reclaim => Some("reclaim"),
+            Commands::Update { .. } => Some("update"),
+            Commands::Fix { dry_run: false, .. } => Some("fix"),
+            Commands::Doctor { fix: true, .. } => Some("doctor --fix"),
+            Commands::Sync(_) => Some("sync"),
+            Commands::Bootstrap(sync::BootstrapCommands::Install { .. }) => Some("bootstrap install"),
+            Commands::Bootstrap(sync::BootstrapCommands::Base) => Some("bootstrap base"),
+            Commands::UpdateSelf { command: Some(SelfUpdateCommand::Yes), .. } => Some("update-self"),
+            Commands::Binary(cmd) => match cmd {
+                binary::BinaryCommands::Install { .. } => Some("binary install"),
+                binary::BinaryCommands::Update { .. } => Some("binary update"),
+                binary::BinaryCommands::Remove { .. } => Some("binary remove"),
+                _ => None,
+            },
+            Commands::Node(language::LanguageArgs { command: Some(cmd), .. }) => match cmd {
+                language::NodeCommands::Install { .. } => Some("node install"),
+                language::NodeCommands::Use { .. } => Some("node use"),
+                language::NodeCommands::Remove { .. } => Some("node remove"),
+                _ => None,
+            },
+            Commands::Python(language::LanguageArgs { command: Some(cmd), .. }) => match cmd {
+                language::PythonCommands::Install { .. } => Some("python install"),
+                language::PythonCommands::Use { .. } => Some("python use"),
+                language::PythonCommands::Remove { .. } => Some("python remove"),
+                _ => None,
+            },
+            Commands::Go(language::LanguageArgs { command: Some(cmd), .. }) => match cmd {
+                language::GoCommands::Install { .. } => Some("go install"),
+                language::GoCommands::Use { .. } => Some("go use"),
+                language::GoCommands::Remove { .. } => Some("go remove"),
+                _ => None,
+            },
+            Commands::Rust(language::LanguageArgs { command: Some(cmd), .. }) => match cmd {
+                language::RustCommands::Install { .. } => Some("rust install"),
+                language::RustCommands::Use { .. } => Some("rust use"),
+                language::RustCommands::Remove { .. } => Some("rust remove"),
+                _ => None,
+            },
+            Commands::Ruby(language::LanguageArgs { command: Some(cmd), .. }) => match cmd {
+                language::RubyCommands::Install { .. } => Some("ruby install"),
+                language::RubyCommands::Use { .. } => Some("ruby use"),
+                language::RubyCommands::Remove { .. } => Some("ruby remove"),
+                _ => None,
+            },
+            Commands::Php(language::LanguageArgs { command: Some(cmd), .. }) => match cmd {
+                language::PhpCommands::Install { .. } => Some("php install"),
+                language::PhpCommands::Use { .. } => Some("php use"),
+                language::PhpCommands::Remove { .. } => Some("php remove"),
+                _ => None,
+            },
+            Commands::Java(language::LanguageArgs { command: Some(cmd), .. }) => match cmd {
+                language::JavaCommands::Install { .. } => Some("java install"),
+                language::JavaCommands::Use { .. } => Some("java use"),
+                language::JavaCommands::Remove { .. } => Some("java remove"),
+                _ => None,
+            },
+            Commands::Dotnet(language::LanguageArgs { command: Some(cmd), .. }) => match cmd {
+                language::DotnetCommands::Install { .. } => Some("dotnet install"),
+                language::DotnetCommands::Use { .. } => Some("dotnet use"),
+                language::DotnetCommands::Remove { .. } => Some("dotnet remove"),
+                language::DotnetCommands::Tool(language::DotnetToolCommands::Install { .. }) => Some("dotnet tool install"),
+                language::DotnetCommands::Tool(language::DotnetToolCommands::Update { .. }) => Some("dotnet tool update"),
+                language::DotnetCommands::Tool(language::DotnetToolCommands::Remove { .. }) => Some("dotnet tool remove"),
+                _ => None,
+            },
+            Commands::Shell(cmd) => match cmd {
+                shell::ShellCommands::Unload { .. } => Some("shell unload"),
+                shell::ShellCommands::Completions { install: true, .. } => Some("shell completions --install"),
+                _ => None,
+            },
+            Commands::Config(cmd) => match cmd {
+                config::ConfigCommands::Set { .. } => Some("config set"),
+                config::ConfigCommands::Remove { .. } => Some("config remove"),
+                config::ConfigCommands::Reset => Some("config reset"),
+                config::ConfigCommands::Edit => Some("config edit"),
+                _ => None,
+            },
+            Commands::Profile(cmd) => match cmd {
+                profile::ProfileCommands::Create { .. } => Some("profile create"),
+                profile::ProfileCommands::Init { .. } => Some("profile init"),
+                profile::ProfileCommands::Use { .. } => Some("profile use"),
+                profile::ProfileCommands::Remove { .. } => Some("profile remove"),
+                profile::ProfileCommands::Edit { .. } => Some("profile edit"),
+                profile::ProfileCommands::Import { .. } => Some("profile import"),
+                profile::ProfileCommands::Apply { .. } => Some("profile apply"),
+                _ => None,
+            },
+            Commands::Cache(cache::CacheCommands::Clean { .. }) => Some("cache clean"),
+            Commands::Iso(cmd) => match cmd {
+                iso::IsoCommands::Install { .. } => Some("iso install"),
+                iso::IsoCommands::Remove { .. } => Some("iso remove"),
+                iso::IsoCommands::Clean => Some("iso clean"),
+                _ => None,
+            },
+            Commands::Usb(cmd) => match cmd {
+                usb::UsbCommands::Erase { .. } => Some("usb erase"),
+                usb::UsbCommands::Write { .. } => Some("usb write"),
+                usb::UsbCommands::Add { .. } => Some("usb add"),
+                usb::UsbCommands::Remove { .. } => Some("usb remove"),
+                usb::UsbCommands::Mount { .. } => Some("usb mount"),
+                usb::UsbCommands::Eject { .. } => Some("usb eject"),
+                usb::UsbCommands::Boot(usb::BootCommands::Create { .. }) => Some("usb boot create"),
+                usb::UsbCommands::Boot(usb::BootCommands::Add { .. }) => Some("usb boot add"),
+                usb::UsbCommands::Boot(usb::BootCommands::Remove { .. }) => Some("usb boot remove"),
+                usb::UsbCommands::Boot(usb::BootCommands::Clean { .. }) => Some("usb boot clean"),
+                _ => None,
+            },
+            Commands::Repos(cmd) => match cmd {
+                repos::ReposCommands::Add { .. } => Some("repos add"),
+                repos::ReposCommands::Remove { .. } => Some("repos remove"),
+                repos::ReposCommands::Update => Some("repos update"),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+pub async fn execute(cli: Cli, config: Config, output: Output) -> Result<Outcome> {
     let command = match &cli.command {
         Some(cmd) => cmd.clone(),
-        None => return Ok(()), // Should not happen due to check in main
+        None => return Ok(Outcome::Success), // Should not happen due to check in main
+    };
+
+    if crate::utils::download::is_offline() {
+        if let Some(operation) = command.network_operation() {
+            anyhow::bail!(
+                "'{}' needs network access and cannot run in offline mode; re-run without --offline",
+                operation
+            );
+        }
+    }
+
+    // Released when execute returns
+    let _lock = match command.mutating_operation() {
+        Some(operation) if !cli.dry_run => Some(crate::core::lock::OperationLock::acquire(operation, cli.force_unlock)?),
+        _ => None,
     };
-    
-    match command {
+
+    let result = match command {
         Commands::Install { packages } => {
             install::execute(packages, &cli, &config, &output).await
         }
-        Commands::Remove { packages } => {
-            remove::execute(packages, &cli, &config, &output).await
+        Commands::Remove { packages, purge, keep_config: _ } => {
+            remove::execute(packages, purge, &cli, &config, &output).await
+        }
+        Commands::Autoremove => {
+            autoremove::execute(&cli, &config, &output).await
+        }
+        Commands::Reclaim => {
+            reclaim::execute(&cli, &config, &output).await
         }
-        Commands::Update { packages } => {
-            update::execute(packages, &cli, &config, &output).await
+        Commands::Update { packages, security_only, exclude } => {
+            update::execute(packages, security_only, exclude, &cli, &config, &output).await
         }
-        Commands::Search { query } => {
+        Commands::Search { query, file: true } => {
+            search::execute_file(query, &cli, &config, &output).await
+        }
+        Commands::Search { query, file: false } => {
             search::execute(query, &cli, &config, &output).await
         }
-        Commands::List { list_type } => {
-            list::execute(list_type, &cli, &config, &output).await
+        Commands::List { list_type, format, sort } => {
+            list::execute(list_type, format, sort, &cli, &config, &output).await
         }
-        Commands::Info { package } => {
-            info::execute(package, &cli, &config, &output).await
+        Commands::Info { package, installed_files, changelog, since, deps, tree, depth, format } => {
+            let changelog = (changelog || since.is_some()).then_some(since);
+            let dependency_depth = if tree { Some(depth) } else { deps.then_some(1) };
+            info::execute(package, installed_files, changelog, dependency_depth, format, &cli, &config, &output).await
         }
         Commands::Where { package } => {
             where_pkg::execute(package, &cli, &config, &output).await
@@ -287,17 +568,41 @@ pub async fn execute(cli: Cli, config: Config, output: Output) -> Result<()> {
         Commands::Whatis { package } => {
             whatis::execute(package, &cli, &config, &output).await
         }
-        Commands::Fix { auto, dry_run, last_error } => {
-            recovery::execute(auto, dry_run, last_error, &cli, &config, &output).await
+        Commands::Fix { auto, dry_run, last_error, interactive } => {
+            recovery::execute(auto, dry_run, last_error, interactive, &cli, &config, &output).await
         }
-        Commands::Node(cmd) => language::execute_node(cmd, &cli, &config, &output).await,
-        Commands::Python(cmd) => language::execute_python(cmd, &cli, &config, &output).await,
-        Commands::Go(cmd) => language::execute_go(cmd, &cli, &config, &output).await,
-        Commands::Rust(cmd) => language::execute_rust(cmd, &cli, &config, &output).await,
-        Commands::Ruby(cmd) => language::execute_ruby(cmd, &cli, &config, &output).await,
-        Commands::Php(cmd) => language::execute_php(cmd, &cli, &config, &output).await,
-        Commands::Java(cmd) => language::execute_java(cmd, &cli, &config, &output).await,
-        Commands::Dotnet(cmd) => language::execute_dotnet(cmd, &cli, &config, &output).await,
+        Commands::Node(args) => match args.command {
+            Some(cmd) => language::execute_node(cmd, &cli, &config, &output).await,
+            None => languages::run_with_version("node", args.use_version, args.run, &config, &output).await,
+        },
+        Commands::Python(args) => match args.command {
+            Some(cmd) => language::execute_python(cmd, &cli, &config, &output).await,
+            None => languages::run_with_version("python", args.use_version, args.run, &config, &output).await,
+        },
+        Commands::Go(args) => match args.command {
+            Some(cmd) => language::execute_go(cmd, &cli, &config, &output).await,
+            None => languages::run_with_version("go", args.use_version, args.run, &config, &output).await,
+        },
+        Commands::Rust(args) => match args.command {
+            Some(cmd) => language::execute_rust(cmd, &cli, &config, &output).await,
+            None => languages::run_with_version("rust", args.use_version, args.run, &config, &output).await,
+        },
+        Commands::Ruby(args) => match args.command {
+            Some(cmd) => language::execute_ruby(cmd, &cli, &config, &output).await,
+            None => languages::run_with_version("ruby", args.use_version, args.run, &config, &output).await,
+        },
+        Commands::Php(args) => match args.command {
+            Some(cmd) => language::execute_php(cmd, &cli, &config, &output).await,
+            None => languages::run_with_version("php", args.use_version, args.run, &config, &output).await,
+        },
+        Commands::Java(args) => match args.command {
+            Some(cmd) => language::execute_java(cmd, &cli, &config, &output).await,
+            None => languages::run_with_version("java", args.use_version, args.run, &config, &output).await,
+        },
+        Commands::Dotnet(args) => match args.command {
+            Some(cmd) => language::execute_dotnet(cmd, &cli, &config, &output).await,
+            None => languages::run_with_version("dotnet", args.use_version, args.run, &config, &output).await,
+        },
         Commands::Binary(cmd) => binary::execute(cmd, &cli, &config, &output).await,
         Commands::Iso(cmd) => iso::execute(cmd, &cli, &config, &output).await,
         Commands::Usb(cmd) => usb::execute(cmd, &cli, &config, &output).await,
@@ -305,26 +610,20 @@ pub async fn execute(cli: Cli, config: Config, output: Output) -> Result<()> {
         Commands::Profile(cmd) => profile::execute(cmd, &cli, &config, &output).await,
         Commands::Config(cmd) => config::execute(cmd, &cli, &config, &output).await,
         Commands::Cache(cmd) => cache::execute(cmd, &cli, &config, &output).await,
-        Commands::Doctor { full, packages, usb, security, fix } => {
-            doctor::execute(full, packages, usb, security, fix, &cli, &config, &output).await
+        Commands::Doctor { full, packages, usb, security, fix, no_network, category, watch, interval, format } => {
+            let watch = watch.then(|| (std::time::Duration::from_secs(interval), format));
+            return doctor::execute(full, packages, usb, security, fix, no_network, category, watch, &cli, &config, &output).await;
         }
         Commands::Bootstrap(cmd) => sync::execute_bootstrap(cmd, &cli, &config, &output).await,
         Commands::Sync(cmd) => sync::execute_sync(cmd, &cli, &config, &output).await,
-        Commands::Check { script } => {
-            // TODO: Implement check command
-            if script {
-                println!("0");
-            } else {
-                output.success("✅ All packages up to date");
-            }
-            Ok(())
-        }
+        Commands::Check { script } => return check::execute(script, &output).await,
         Commands::Shell(cmd) => shell::execute(cmd, &cli, &config, &output).await,
+        Commands::Version { full } => version::execute(full, &output).await,
         Commands::UpdateSelf { command, branch } => {
             use crate::update::{UpdateManager, UpdateBranch};
             
             let version = env!("CARGO_PKG_VERSION").to_string();
-            let manager = UpdateManager::new(version)?;
+            let manager = UpdateManager::new(version, output.clone())?;
             
             match command {
                 Some(SelfUpdateCommand::Check) | None if branch.is_none() => {
@@ -336,13 +635,13 @@ pub async fn execute(cli: Cli, config: Config, output: Output) -> Result<()> {
                     manager.perform_update()?;
                 }
                 Some(SelfUpdateCommand::Branch) => {
-                    // Set branch
+                    // Set branch, or show it when none is given
                     if let Some(branch_name) = branch {
-                        let branch = UpdateBranch::from_str(&branch_name)?;
+                        let branch = UpdateBranch::from_str(&branch_name)
+                            .map_err(|e| UsageError(e.to_string()))?;
                         manager.set_branch(branch)?;
                     } else {
-                        output.error("❌ Branch name required. Valid: stable, beta, daily");
-                        std::process::exit(1);
+                        output.print(manager.get_branch()?.as_str());
                     }
                 }
                 _ => {
@@ -352,5 +651,40 @@ pub async fn execute(cli: Cli, config: Config, output: Output) -> Result<()> {
             }
             Ok(())
         }
+    };
+    result.map(|()| Outcome::Success)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_mutating_command_takes_the_lock() {
+        let mutating = [
+            "install curl", "remove curl", "autoremove", "reclaim", "update", "fix", "doctor --fix",
+            "sync dev", "bootstrap base", "update-self yes",
+            "binary install cli/cli", "binary update", "binary remove gh",
+            "node install 20", "node use 20", "node remove 20",
+            "python use 3.12", "python remove 3.12", "go remove 1.22", "rust use stable",
+            "ruby remove 3.3", "php use 8.3", "java remove 21", "dotnet use 8",
+            "dotnet tool install dotnet-ef", "dotnet tool update", "dotnet tool remove dotnet-ef",
+            "shell unload", "shell completions bash --install",
+            "config set defaults.color_output never", "config remove defaults.package_manager", "config reset", "config edit",
+            "profile create dev", "profile use dev", "profile remove dev", "profile edit dev", "profile import dev.toml",
+            "profile apply dev", "cache clean",
+            "iso install ubuntu", "iso remove ubuntu", "iso clean",
+            "usb erase /dev/sdz", "usb write ubuntu.iso /dev/sdz", "usb mount /dev/sdz", "usb eject /dev/sdz",
+            "usb boot clean", "repos add ppa:foo/bar", "repos remove foo", "repos update",
+        ];
+        for args in mutating {
+            let cli = Cli::try_parse_from(std::iter::once("pkmgr").chain(args.split(' ')))
+                .unwrap_or_else(|e| panic!("pkmgr {}: {}", args, e));
+            assert!(cli.command.unwrap().mutating_operation().is_some(), "pkmgr {} runs without the lock", args);
+        }
+
+        for args in ["search curl", "list", "node list", "shell completions bash", "config get defaults.color_output", "profile list"] {
+            let cli = Cli::try_parse_from(std::iter::once("pkmgr").chain(args.split(' '))).unwrap();
+            assert!(cli.command.unwrap().mutating_operation().is_none(), "pkmgr {} takes the lock", args);
+        }
     }
-}
\ No newline at end of file
+}
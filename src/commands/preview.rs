@@ -0,0 +1,62 @@
+use anyhow::Result;
+use crate::core::traits::{ChangeKind, Operation, PackageManager, PlannedChange};
+use crate::ui::output::Output;
+
+/// Show what `operation` would change for `--dry-run`. The backend's own
+/// simulation is used where it has one; otherwise pkmgr estimates from
+/// installed state, which cannot see dependencies
+pub async fn preview(package_manager: &dyn PackageManager, operation: Operation<'_>, output: &Output) -> Result<()> {
+    let changes = match package_manager.simulate(operation).await {
+        Ok(changes) => changes,
+        Err(e) => {
+            output.debug(&format!("Simulation failed: {}", e));
+            output.warn(&format!("{} has no dry-run mode; showing pkmgr's estimate without dependencies", package_manager.name()));
+            estimate(package_manager, operation).await?
+        }
+    };
+
+    if changes.is_empty() {
+        output.success("Nothing to do");
+    } else {
+        output.print_section(&format!("📋 Would change {} packages", changes.len()));
+        let rows: Vec<Vec<String>> = changes.iter()
+            .map(|change| vec![
+                change.kind.to_string(),
+                change.name.clone(),
+                change.current.clone().unwrap_or_default(),
+                change.version.clone().unwrap_or_default(),
+            ])
+            .collect();
+        output.print_table(&["Action", "Package", "From", "To"], &rows);
+    }
+
+    output.info("Dry run - no changes made");
+    Ok(())
+}
+
+/// pkmgr-side simulation: the requested packages against what is
+/// installed, and upgrades from the backend's upgradable list
+async fn estimate(package_manager: &dyn PackageManager, operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+    let (packages, kind) = match operation {
+        Operation::Install(packages) => (packages, ChangeKind::Install),
+        Operation::Remove(packages) | Operation::Purge(packages) => (packages, ChangeKind::Remove),
+        Operation::Upgrade(_) | Operation::UpgradeExcluding(_) => {
+            let updates = package_manager.upgradable().await?;
+            return Ok(updates.into_iter()
+                .filter(|update| match operation {
+                    Operation::Upgrade(Some(packages)) => packages.contains(&update.name),
+                    Operation::UpgradeExcluding(exclude) => !exclude.contains(&update.name),
+                    _ => true,
+                })
+                .map(|update| PlannedChange::new(ChangeKind::Upgrade, update.name.as_str())
+                    .with_versions(Some(&update.current), Some(&update.candidate)))
+                .collect());
+        }
+    };
+
+    let installed = package_manager.is_installed(packages).await?;
+    Ok(packages.iter()
+        .filter(|package| installed.get(*package).copied().unwrap_or(false) == (kind == ChangeKind::Remove))
+        .map(|package| PlannedChange::new(kind, package.as_str()))
+        .collect())
+}
@@ -6,7 +6,11 @@ use crate::core::config::Config;
 use crate::ui::output::Output;
 use crate::profile::manager::ProfileManager;
 use crate::profile::exporter::{ProfileExporter, ExportFormat};
+use crate::profile::Profile;
 use crate::profile::importer::ProfileImporter;
+use crate::profile::validator::ProfileValidator;
+use crate::utils::download::is_offline;
+use crate::core::exit::Cancelled;
 
 #[derive(Debug, Subcommand, Clone)]
 pub enum ProfileCommands {
@@ -31,6 +35,16 @@ pub enum ProfileCommands {
         copy_current: bool,
     },
 
+    /// Create a profile seeded from a template, without prompting
+    Init {
+        /// Profile name
+        name: String,
+
+        /// Template to copy (see 'pkmgr profile templates')
+        #[arg(long)]
+        template: Option<String>,
+    },
+
     /// Switch to profile
     Use {
         /// Profile name
@@ -92,8 +106,14 @@ pub enum ProfileCommands {
         yes: bool,
     },
 
-    /// Show available templates
+    /// List available templates with descriptions
     Templates,
+
+    /// Check that every package, binary and repository in a profile resolves
+    Validate {
+        /// Profile name
+        name: String,
+    },
 }
 
 pub async fn execute(cmd: ProfileCommands, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
@@ -112,6 +132,10 @@ pub async fn execute(cmd: ProfileCommands, cli: &Cli, config: &Config, output: &
             manager.create(&name, from_template, copy_current).await?;
         }
 
+        ProfileCommands::Init { name, template } => {
+            manager.init(&name, template.as_deref())?;
+        }
+
         ProfileCommands::Use { name } => {
             manager.use_profile(&name).await?;
         }
@@ -146,8 +170,7 @@ pub async fn execute(cmd: ProfileCommands, cli: &Cli, config: &Config, output: &
                 use crate::ui::prompt::Prompt;
                 let prompt = Prompt::new(output.emoji_enabled);
                 if !prompt.confirm("Continue?")? {
-                    output.info("Profile application cancelled");
-                    return Ok(());
+                    return Err(Cancelled("Profile application cancelled".to_string()).into());
                 }
             }
 
@@ -157,11 +180,51 @@ pub async fn execute(cmd: ProfileCommands, cli: &Cli, config: &Config, output: &
         ProfileCommands::Templates => {
             show_templates(output)?;
         }
+
+        ProfileCommands::Validate { name } => {
+            validate(&name, config, output).await?;
+        }
     }
 
     Ok(())
 }
 
+/// Per-entry report; fails when any entry has a problem
+async fn validate(name: &str, config: &Config, output: &Output) -> Result<()> {
+    let profile = Profile::resolve(name)?;
+    let offline = is_offline();
+
+    output.print_header(&format!("🔍 Validating profile: {}", profile.name));
+    if offline {
+        output.info("Offline: package lookups, GitHub releases and repository URLs are not checked");
+    }
+
+    let checks = ProfileValidator::new(output.clone(), config.clone())
+        .with_no_network(offline)
+        .validate(&profile)
+        .await?;
+    if checks.is_empty() {
+        output.info("Profile has no packages or repositories to check");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = checks.iter()
+        .map(|check| vec![
+            check.kind.clone(),
+            check.entry.clone(),
+            check.problem.clone().unwrap_or_else(|| "OK".to_string()),
+        ])
+        .collect();
+    output.print_table(&["Kind", "Entry", "Result"], &rows);
+
+    let problems = checks.iter().filter(|check| check.problem.is_some()).count();
+    if problems > 0 {
+        anyhow::bail!("Profile '{}' has {} of {} entries with problems", profile.name, problems, checks.len());
+    }
+    output.success(&format!("✅ All {} entries resolve", checks.len()));
+    Ok(())
+}
+
 fn show_templates(output: &Output) -> Result<()> {
     use crate::profile::get_profile_templates;
 
@@ -183,7 +246,7 @@ fn show_templates(output: &Output) -> Result<()> {
     }
 
     output.info("\nCreate a profile from template with:");
-    output.info("  pkmgr profile create <name> --from-template <template>");
+    output.info("  pkmgr profile init <name> --template <template>");
 
     Ok(())
 }
@@ -0,0 +1,149 @@
+use anyhow::{Result, Context};
+use futures_util::stream::{self, StreamExt};
+use std::path::Path;
+use crate::cache::cleaner::CacheCleaner;
+use crate::cache::format_size;
+use crate::cache::manager::CacheManager;
+use crate::commands::Cli;
+use crate::core::config::Config;
+use crate::core::platform::PlatformInfo;
+use crate::core::PackageManager;
+use crate::managers::PackageManagerFactory;
+use crate::ui::output::Output;
+use crate::ui::prompt::Prompt;
+use crate::core::exit::Cancelled;
+
+/// Concurrent `info` lookups when sizing orphaned packages
+const MAX_PARALLEL_LOOKUPS: usize = 4;
+
+/// Space one source could free, or why it was skipped
+struct Estimate {
+    label: &'static str,
+    bytes: Result<u64>,
+}
+
+impl Estimate {
+    fn bytes(&self) -> u64 {
+        self.bytes.as_ref().copied().unwrap_or(0)
+    }
+}
+
+/// Free space from the package manager's download cache, orphaned packages
+/// and pkmgr's own expired and stale cache entries, reporting before and after
+pub async fn execute(cli: &Cli, _config: &Config, output: &Output) -> Result<()> {
+    output.print_header("♻️ Reclaiming Disk Space");
+
+    let platform_info = PlatformInfo::detect_async().await?;
+    let package_manager = PackageManagerFactory::create(&platform_info)
+        .context("Failed to create package manager")?;
+    let mut cleaner = CacheCleaner::new(output.clone(), cli.dry_run)?;
+
+    output.debug(&format!("Using package manager: {}", package_manager.name()));
+    output.progress("🔍 Estimating reclaimable space...");
+
+    let orphans = package_manager.orphans().await;
+    let entries = cleaner.select(true, true);
+    let estimates = [
+        Estimate { label: "Package manager cache", bytes: package_manager.package_cache_size().await },
+        Estimate {
+            label: "Orphaned packages",
+            bytes: match &orphans {
+                Ok(orphans) => Ok(installed_size(package_manager.as_ref(), orphans).await),
+                Err(e) => Err(anyhow::anyhow!("{}", e)),
+            },
+        },
+        Estimate { label: "pkmgr cache", bytes: Ok(entries.iter().map(|entry| entry.size).sum()) },
+    ];
+    let orphans = orphans.unwrap_or_default();
+
+    output.print_section("📊 Reclaimable space");
+    for estimate in &estimates {
+        match &estimate.bytes {
+            Ok(bytes) => output.info(&format!("{}: {}", estimate.label, format_size(*bytes))),
+            Err(e) => output.info(&format!("{}: skipped ({})", estimate.label, e)),
+        }
+    }
+    if !orphans.is_empty() {
+        output.print_list(&orphans);
+    }
+
+    let total: u64 = estimates.iter().map(Estimate::bytes).sum();
+    output.info(&format!("Total: {}", format_size(total)));
+
+    if cli.dry_run {
+        output.info("Dry run - no changes made");
+        return Ok(());
+    }
+    if total == 0 {
+        output.success("Nothing to reclaim");
+        return Ok(());
+    }
+
+    if !cli.yes {
+        let prompt = Prompt::new(output.emoji_enabled);
+        if !prompt.confirm(&format!("Free about {}?", format_size(total)))? {
+            return Err(Cancelled("Reclaim cancelled".to_string()).into());
+        }
+    }
+
+    let disk_before = available_space();
+    let mut freed = Vec::new();
+
+    if estimates[0].bytes() > 0 {
+        output.progress("🧹 Cleaning package manager cache...");
+        match package_manager.clean_package_cache().await {
+            Ok(()) => {
+                let left = package_manager.package_cache_size().await.unwrap_or(0);
+                freed.push((estimates[0].label, estimates[0].bytes().saturating_sub(left)));
+            }
+            Err(e) => output.warn(&format!("Failed to clean package manager cache: {}", e)),
+        }
+    }
+
+    if !orphans.is_empty() {
+        output.progress(&format!("🗑️ Removing {} orphaned packages...", orphans.len()));
+        match package_manager.remove_orphans(&orphans).await {
+            Ok(result) if result.success => {
+                if let Ok(mut cache) = CacheManager::new(output.clone()) {
+                    let _ = cache.update_installed(&[], &result.packages_installed);
+                }
+                freed.push((estimates[1].label, estimates[1].bytes()));
+            }
+            Ok(result) => output.warn(&format!("Failed to remove orphaned packages: {}", result.message)),
+            Err(e) => output.warn(&format!("Failed to remove orphaned packages: {}", e)),
+        }
+    }
+
+    if !entries.is_empty() {
+        output.progress("🧹 Cleaning pkmgr cache...");
+        let report = cleaner.remove_entries(&entries).await?;
+        freed.push((estimates[2].label, report.freed_bytes));
+    }
+
+    output.print_header("📊 Reclaim Summary");
+    for (label, bytes) in &freed {
+        output.info(&format!("{}: {} freed", label, format_size(*bytes)));
+    }
+    if let (Some(before), Some(after)) = (disk_before, available_space()) {
+        output.info(&format!("Free disk space: {} → {}", format_size(before), format_size(after)));
+    }
+    output.success(&format!("Reclaimed {}", format_size(freed.iter().map(|(_, bytes)| bytes).sum())));
+
+    Ok(())
+}
+
+/// Installed size of `packages`, as far as the backend reports one; the
+/// orphan figure is an estimate for backends without sizes
+async fn installed_size(package_manager: &dyn PackageManager, packages: &[String]) -> u64 {
+    stream::iter(packages)
+        .map(|package| package_manager.info(package))
+        .buffered(MAX_PARALLEL_LOOKUPS)
+        .map(|info| info.ok().flatten().and_then(|info| info.size).unwrap_or(0))
+        .fold(0, |total, size| async move { total + size })
+        .await
+}
+
+/// Free space on the root filesystem, where system package caches live
+fn available_space() -> Option<u64> {
+    fs2::available_space(Path::new("/")).ok()
+}
@@ -1,31 +1,92 @@
 use anyhow::{Context, Result};
 use crate::commands::Cli;
 use crate::core::config::Config;
+use crate::core::paths::Paths;
 use crate::core::platform::{Platform, PlatformInfo};
 use crate::ui::output::Output;
-use crate::recovery::{ErrorAnalyzer, ErrorFixer, RecoveryStrategies};
+use crate::recovery::{compile_patterns, ErrorAnalysis, ErrorAnalyzer, ErrorFixer, FixSuggestion, RecoveryStrategies, RiskLevel};
+use crate::ui::prompt::{is_interactive, Prompt};
+use console::style;
 use std::fs;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 pub async fn execute(
     auto: bool,
     dry_run: bool,
     last_error: bool,
+    interactive: bool,
     cli: &Cli,
     config: &Config,
     output: &Output,
 ) -> Result<()> {
+    compile_patterns()?;
     let platform = Platform::detect()?;
 
-    if last_error {
+    if interactive && is_interactive() {
+        pick_last_error_fix(dry_run, output, platform).await
+    } else if last_error || interactive {
+        if interactive {
+            output.info("Non-interactive session: applying safe fixes as with --auto");
+        }
         // Analyze the last error from log file
-        analyze_last_error(auto, dry_run, output, platform).await
+        analyze_last_error(auto || interactive, dry_run, output, platform).await
     } else {
         // Run general system recovery
         run_system_recovery(auto, dry_run, output, platform).await
     }
 }
 
+/// The last failed command and its output, in the state directory
+#[derive(Debug, Serialize, Deserialize)]
+struct LastError {
+    argv: Vec<String>,
+    exit_code: i32,
+    stdout: String,
+    stderr: String,
+    time: chrono::DateTime<chrono::Utc>,
+}
+
+impl LastError {
+    fn path() -> Result<PathBuf> {
+        Ok(Paths::resolve()?.state_dir.join("last_error.json"))
+    }
+
+    fn load() -> Result<Option<Self>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .context("Failed to read the last error")?;
+        let last = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid last error record {}", path.display()))?;
+        Ok(Some(last))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Remember a failed pkmgr run for `pkmgr fix --last-error`; the error
+/// chain stands in for stderr, since it carries the native manager's output
+pub fn record_failure(argv: &[String], error: &anyhow::Error) -> Result<()> {
+    LastError {
+        argv: argv.to_vec(),
+        exit_code: 1,
+        stdout: String::new(),
+        stderr: format!("{:#}", error),
+        time: chrono::Utc::now(),
+    }.save()
+}
+
 async fn analyze_last_error(
     auto: bool,
     dry_run: bool,
@@ -34,39 +95,15 @@ async fn analyze_last_error(
 ) -> Result<()> {
     output.section("Error Recovery Analysis");
 
-    // Read last error from log file
-    let log_path = dirs::data_dir()
-        .context("Failed to determine data directory")?
-        .join("pkmgr")
-        .join("last_error.log");
-
-    if !log_path.exists() {
+    let Some(last) = LastError::load()? else {
         output.info("No recent errors found to analyze");
         output.info("Error recovery is automatically triggered when commands fail");
         return Ok(());
-    }
-
-    let error_content = fs::read_to_string(&log_path)
-        .context("Failed to read error log")?;
-
-    // Parse error content
-    let lines: Vec<&str> = error_content.lines().collect();
-    let stdout = lines.iter()
-        .find(|l| l.starts_with("STDOUT:"))
-        .map(|l| l.trim_start_matches("STDOUT:"))
-        .unwrap_or("");
-    let stderr = lines.iter()
-        .find(|l| l.starts_with("STDERR:"))
-        .map(|l| l.trim_start_matches("STDERR:"))
-        .unwrap_or("");
-    let exit_code: i32 = lines.iter()
-        .find(|l| l.starts_with("EXIT_CODE:"))
-        .and_then(|l| l.trim_start_matches("EXIT_CODE:").parse().ok())
-        .unwrap_or(1);
+    };
 
     // Analyze error
     let analyzer = ErrorAnalyzer::new(output.clone(), platform);
-    let analyses = analyzer.analyze(stdout, stderr, exit_code);
+    let analyses = analyzer.analyze(&last.stdout, &last.stderr, last.exit_code);
 
     if analyses.is_empty() {
         output.warn("No known error patterns matched");
@@ -79,7 +116,7 @@ async fn analyze_last_error(
 
     // Apply fixes if requested
     if !dry_run {
-        let fixer = ErrorFixer::new(output.clone(), dry_run, auto);
+        let fixer = ErrorFixer::new(output.clone(), dry_run, auto).with_original_command(&last.argv);
 
         for analysis in &analyses {
             output.section(&format!("Applying fixes for: {}", analysis.matched_pattern.name));
@@ -88,7 +125,7 @@ async fn analyze_last_error(
                 let applied = fixer.apply_fix(analysis, fix).await?;
 
                 if applied {
-                    output.success(&format!("✅ Applied fix: {}", fix.description));
+                    output.success(&format!("Applied fix: {}", fix.description));
                     // If one fix works, we're done
                     break;
                 }
@@ -98,12 +135,77 @@ async fn analyze_last_error(
 
     // Clean up log file after successful recovery
     if !dry_run {
-        let _ = fs::remove_file(&log_path);
+        let _ = fs::remove_file(LastError::path()?);
     }
 
     Ok(())
 }
 
+/// Let the user choose one suggestion for the last error, most likely
+/// patterns first, instead of trying them in turn
+async fn pick_last_error_fix(
+    dry_run: bool,
+    output: &Output,
+    platform: PlatformInfo,
+) -> Result<()> {
+    output.section("Error Recovery Analysis");
+
+    let Some(last) = LastError::load()? else {
+        output.info("No recent errors found to analyze");
+        output.info("Failed pkmgr commands are recorded automatically; run this again after one fails");
+        return Ok(());
+    };
+
+    output.info(&format!("Last failure: {} ({})", last.argv.join(" "), last.time.format("%Y-%m-%d %H:%M UTC")));
+    let analyzer = ErrorAnalyzer::new(output.clone(), platform);
+    let analyses = analyzer.analyze(&last.stdout, &last.stderr, last.exit_code);
+    if analyses.is_empty() {
+        output.warn("No known error patterns matched");
+        output.info("The error may require manual intervention");
+        return Ok(());
+    }
+
+    let choices: Vec<(&ErrorAnalysis, &FixSuggestion)> = analyses.iter()
+        .flat_map(|analysis| analysis.suggested_fixes.iter().map(move |fix| (analysis, fix)))
+        .collect();
+    let mut items: Vec<String> = choices.iter()
+        .map(|(analysis, fix)| fix_label(&analysis.matched_pattern.name, fix))
+        .collect();
+    items.push("Skip - apply nothing".to_string());
+
+    let prompt = Prompt::new(output.emoji_enabled);
+    let selected = prompt.select("Choose a fix to apply", &items)?;
+    let Some((analysis, fix)) = choices.get(selected) else {
+        output.info("No fix applied");
+        return Ok(());
+    };
+
+    let fixer = ErrorFixer::new(output.clone(), dry_run, false).with_original_command(&last.argv);
+    if fixer.apply_fix(analysis, fix).await? {
+        output.success(&format!("Applied fix: {}", fix.description));
+        let _ = fs::remove_file(LastError::path()?);
+    }
+
+    Ok(())
+}
+
+/// One picker line, colored by risk
+fn fix_label(pattern: &str, fix: &FixSuggestion) -> String {
+    let risk = match fix.risk_level {
+        RiskLevel::Safe => style("safe").green(),
+        RiskLevel::Low => style("low risk").cyan(),
+        RiskLevel::Medium => style("medium risk").yellow(),
+        RiskLevel::High => style("HIGH RISK").red().bold(),
+    };
+    format!("{} [{}] - {:.0}% success, {}{}",
+        fix.description,
+        pattern,
+        fix.estimated_success * 100.0,
+        risk,
+        if fix.requires_sudo { ", needs sudo" } else { "" }
+    )
+}
+
 async fn run_system_recovery(
     auto: bool,
     dry_run: bool,
@@ -314,12 +416,18 @@ pub async fn recover_from_error(
     stdout: &str,
     stderr: &str,
     exit_code: i32,
-    command: &str,
+    argv: &[String],
     output: &Output,
     config: &Config,
 ) -> Result<bool> {
     // Save error for later analysis
-    save_last_error(stdout, stderr, exit_code, command)?;
+    LastError {
+        argv: argv.to_vec(),
+        exit_code,
+        stdout: stdout.to_string(),
+        stderr: stderr.to_string(),
+        time: chrono::Utc::now(),
+    }.save()?;
 
     let platform = Platform::detect()?;
     let analyzer = ErrorAnalyzer::new(output.clone(), platform);
@@ -331,7 +439,7 @@ pub async fn recover_from_error(
 
     // Only auto-fix safe operations
     let auto_fix = config.defaults.auto_fix;
-    let fixer = ErrorFixer::new(output.clone(), false, auto_fix);
+    let fixer = ErrorFixer::new(output.clone(), false, auto_fix).with_original_command(argv);
 
     for analysis in &analyses {
         if analyzer.should_auto_fix(analysis) {
@@ -356,24 +464,3 @@ pub async fn recover_from_error(
 
     Ok(false)
 }
-
-fn save_last_error(stdout: &str, stderr: &str, exit_code: i32, command: &str) -> Result<()> {
-    let data_dir = dirs::data_dir()
-        .context("Failed to determine data directory")?
-        .join("pkmgr");
-
-    fs::create_dir_all(&data_dir)?;
-
-    let content = format!(
-        "COMMAND:{}\nEXIT_CODE:{}\nSTDOUT:{}\nSTDERR:{}\nTIME:{}",
-        command,
-        exit_code,
-        stdout,
-        stderr,
-        chrono::Utc::now().to_rfc3339()
-    );
-
-    fs::write(data_dir.join("last_error.log"), content)?;
-
-    Ok(())
-}
\ No newline at end of file
@@ -1,12 +1,17 @@
 use anyhow::{Result, Context};
+use crate::cache::manager::CacheManager;
 use crate::commands::Cli;
+use crate::commands::preview::preview;
 use crate::core::config::Config;
 use crate::core::platform::PlatformInfo;
 use crate::core::normalizer::PackageNormalizer;
+use crate::core::traits::Operation;
 use crate::managers::PackageManagerFactory;
 use crate::ui::output::Output;
 
-pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
+/// Remove packages; `purge` also removes their configuration files where the
+/// backend keeps them by default
+pub async fn execute(packages: Vec<String>, purge: bool, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
     if packages.is_empty() {
         output.error("No packages specified");
         return Ok(());
@@ -28,9 +33,24 @@ pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output:
     // Initialize normalizer
     let normalizer = PackageNormalizer::new();
 
+    if cli.dry_run {
+        let mut native = Vec::new();
+        for package in &packages {
+            let normalized_names = normalizer.normalize(package, pm_type)?;
+            if normalized_names.is_empty() {
+                native.push(package.clone());
+            } else {
+                native.extend(normalized_names);
+            }
+        }
+        let operation = if purge { Operation::Purge(&native) } else { Operation::Remove(&native) };
+        return preview(package_manager.as_ref(), operation, output).await;
+    }
+
     // Track successful and failed removals
     let mut removed = Vec::new();
     let mut failed = Vec::new();
+    let mut native_removed = Vec::new();
 
     for package in &packages {
         output.remove_start(package);
@@ -54,11 +74,17 @@ pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output:
         }
 
         // Attempt removal
-        match package_manager.remove(&packages_to_use).await {
+        let result = if purge {
+            package_manager.purge(&packages_to_use).await
+        } else {
+            package_manager.remove(&packages_to_use).await
+        };
+        match result {
             Ok(result) => {
                 if result.success {
-                    output.success(&format!("✅ Removed {}", package));
+                    output.success(&format!("✅ {} {}", if purge { "Purged" } else { "Removed" }, package));
                     removed.push(package.clone());
+                    native_removed.extend(packages_to_use.iter().cloned());
                 } else {
                     output.error(&format!("❌ Failed to remove {}: {}", package, result.message));
                     failed.push(package.clone());
@@ -71,6 +97,12 @@ pub async fn execute(packages: Vec<String>, cli: &Cli, config: &Config, output:
         }
     }
 
+    if !native_removed.is_empty() {
+        if let Ok(mut cache) = CacheManager::new(output.clone()) {
+            let _ = cache.update_installed(&[], &native_removed);
+        }
+    }
+
     // Summary
     output.print_header("📊 Removal Summary");
     
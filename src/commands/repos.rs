@@ -18,6 +18,9 @@ pub enum ReposCommands {
     Add {
         /// Repository to add (URL, PPA, or package name)
         repo: String,
+        /// Allow adding repositories that are not recognized
+        #[arg(long)]
+        allow_untrusted: bool,
     },
     /// Remove a repository
     Remove {
@@ -35,7 +38,8 @@ pub enum ReposCommands {
 
 pub async fn execute(cmd: ReposCommands, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
     let platform = Platform::detect()?;
-    let manager = RepositoryManager::new(output.clone(), platform);
+    let manager = RepositoryManager::new(output.clone(), platform)
+        .with_assume_yes(cli.yes);
 
     match cmd {
         ReposCommands::List { name } => {
@@ -45,9 +49,9 @@ pub async fn execute(cmd: ReposCommands, cli: &Cli, config: &Config, output: &Ou
                 list_repositories(&manager, output)?;
             }
         }
-        ReposCommands::Add { repo } => {
+        ReposCommands::Add { repo, allow_untrusted } => {
             output.section("Adding Repository");
-            manager.add(&repo).await?;
+            manager.add(&repo, allow_untrusted || config.security.allow_untrusted).await?;
         }
         ReposCommands::Remove { repo } => {
             output.section("Removing Repository");
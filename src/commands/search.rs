@@ -1,9 +1,14 @@
 use anyhow::{Result, Context};
+use crate::cache::{manager::CacheManager, CacheType};
 use crate::commands::Cli;
 use crate::core::config::Config;
 use crate::core::platform::PlatformInfo;
+use crate::core::SearchResult;
 use crate::managers::PackageManagerFactory;
+use crate::repos::manager::RepositoryManager;
 use crate::ui::output::Output;
+use crate::utils::download::is_offline;
+use std::path::PathBuf;
 
 pub async fn execute(query: String, _cli: &Cli, _config: &Config, output: &Output) -> Result<()> {
     output.print_header(&format!("🔍 Searching for: {}", query));
@@ -13,10 +18,34 @@ pub async fn execute(query: String, _cli: &Cli, _config: &Config, output: &Outpu
     let package_manager = PackageManagerFactory::create(&platform_info)
         .context("Failed to create package manager")?;
 
-    output.info(&format!("🔍 Searching in {} repositories...", package_manager.name()));
+    let cache_key = CacheManager::search_key(package_manager.name(), &query);
+    let cached = CacheManager::new(output.clone()).ok()
+        .and_then(|mut cache| cache.load_cached_json::<SearchResult>(&CacheType::RepositoryIndex, &cache_key));
 
-    // Perform search
-    match package_manager.search(&query).await {
+    let result = if is_offline() {
+        output.info("📴 Offline mode: searching cached metadata");
+        Ok(search_cache(&query, &cache_key, output)?)
+    } else if let Some(cached) = cached {
+        output.debug("Using search results cached since the last index refresh");
+        Ok(cached)
+    } else {
+        RepositoryManager::new(output.clone(), platform_info.clone())
+            .refresh_if_stale(package_manager.as_ref())
+            .await;
+
+        output.info(&format!("🔍 Searching in {} repositories...", package_manager.name()));
+        let result = package_manager.search(&query).await;
+
+        // Reused until the index TTL passes or the index is refreshed, and offline
+        if let Ok(search_result) = &result {
+            if let Ok(mut cache) = CacheManager::new(output.clone()) {
+                let _ = cache.store_json(CacheType::RepositoryIndex, &cache_key, search_result);
+            }
+        }
+        result
+    };
+
+    match result {
         Ok(search_result) => {
             if search_result.packages.is_empty() {
                 output.warn(&format!("⚠️  No packages found matching '{}'", query));
@@ -32,10 +61,12 @@ pub async fn execute(query: String, _cli: &Cli, _config: &Config, output: &Outpu
 
                     let desc = package.description.as_deref().unwrap_or("No description available");
                     let status = if package.installed { " [installed]" } else { "" };
+                    let kind = package.kind.as_deref().map(|kind| format!(" [{}]", kind)).unwrap_or_default();
 
-                    output.info(&format!("  📦 {} ({}){} - {}",
+                    output.info(&format!("  📦 {} ({}){}{} - {}",
                         package.name,
                         package.version,
+                        kind,
                         status,
                         desc
                     ));
@@ -53,4 +84,84 @@ pub async fn execute(query: String, _cli: &Cli, _config: &Config, output: &Outpu
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Find which package owns an installed file, or which packages provide it
+pub async fn execute_file(query: String, _cli: &Cli, _config: &Config, output: &Output) -> Result<()> {
+    output.print_header(&format!("🔍 Finding package for: {}", query));
+
+    let platform_info = PlatformInfo::detect_async().await?;
+    let package_manager = PackageManagerFactory::create(&platform_info)
+        .context("Failed to create package manager")?;
+
+    // Bare command names resolve through PATH
+    let path = if query.contains(std::path::MAIN_SEPARATOR) || query.contains('/') {
+        PathBuf::from(&query)
+    } else {
+        which::which(&query).unwrap_or_else(|_| PathBuf::from(&query))
+    };
+
+    if path.exists() {
+        match package_manager.owner_of_file(&path).await {
+            Ok(Some(owner)) => {
+                output.success(&format!("{} is installed and owned by {}", path.display(), owner));
+                return Ok(());
+            }
+            Ok(None) => {
+                output.info(&format!("{} exists but is not owned by any {} package", path.display(), package_manager.name()));
+            }
+            Err(e) => output.warn(&format!("Could not check file ownership: {}", e)),
+        }
+    }
+
+    let lookup = if path.is_absolute() { path.to_string_lossy().to_string() } else { query.clone() };
+    output.info(&format!("🔍 Searching {} repositories for {}...", package_manager.name(), lookup));
+
+    let providers = match package_manager.provides_file(&lookup).await {
+        Ok(providers) => providers,
+        Err(e) => {
+            output.warn(&format!("Could not search repositories: {}", e));
+            return Ok(());
+        }
+    };
+    if providers.is_empty() {
+        output.warn(&format!("No package provides '{}'", lookup));
+        return Ok(());
+    }
+
+    output.success(&format!("Available in {} package(s), not installed:", providers.len()));
+    for provider in &providers {
+        output.info(&format!("  📦 {}", provider));
+    }
+    output.info(&format!("💡 Install with 'pkmgr install {}'", providers[0]));
+
+    Ok(())
+}
+
+/// Answer a search from the cache: the exact query if it was run before,
+/// otherwise a name/description match over every cached package
+fn search_cache(query: &str, cache_key: &str, output: &Output) -> Result<SearchResult> {
+    let mut cache = CacheManager::new(output.clone())?;
+
+    if let Some(result) = cache.load_cached_json::<SearchResult>(&CacheType::RepositoryIndex, cache_key) {
+        return Ok(result);
+    }
+
+    let cached = cache.cached_packages();
+    if cached.is_empty() {
+        anyhow::bail!("No cached package metadata available; run a search online first to populate the cache");
+    }
+
+    let needle = query.to_lowercase();
+    let packages: Vec<_> = cached.into_iter()
+        .filter(|p| {
+            p.name.to_lowercase().contains(&needle)
+                || p.description.as_deref().is_some_and(|d| d.to_lowercase().contains(&needle))
+        })
+        .collect();
+
+    Ok(SearchResult {
+        total_count: packages.len(),
+        packages,
+    })
+}
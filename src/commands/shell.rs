@@ -3,9 +3,8 @@ use clap::Subcommand;
 use crate::commands::Cli;
 use crate::core::config::Config;
 use crate::ui::output::Output;
-use crate::shell::{ShellType, integration::ShellIntegration, completion::CompletionGenerator, detector::ShellDetector};
+use crate::shell::{ShellType, integration::{self, ShellIntegration}, completion::CompletionGenerator, detector::ShellDetector};
 use std::fs;
-use std::path::PathBuf;
 
 #[derive(Debug, Subcommand, Clone)]
 pub enum ShellCommands {
@@ -14,32 +13,53 @@ pub enum ShellCommands {
         /// Shell type (auto-detected if not specified)
         shell: Option<String>
     },
-    /// Generate shell completions
+    /// Print shell completions generated from the current command tree
     Completions {
-        /// Shell type (bash, zsh, fish, powershell)
-        shell: String
+        /// Shell type (bash, zsh, fish, powershell, nushell)
+        shell: String,
+        /// Write them to the shell's per-user completion directory instead
+        #[arg(long)]
+        install: bool,
+    },
+    /// Remove pkmgr-managed blocks from the shell's rc files
+    Unload {
+        /// Shell type (auto-detected if not specified)
+        shell: Option<String>,
+        /// Also delete installed completions
+        #[arg(long)]
+        completions: bool,
+    },
+    /// Print the line that adds ~/.local/bin to PATH: eval $(pkmgr shell add)
+    #[command(alias = "print-path")]
+    Add {
+        /// Shell type (auto-detected if not specified)
+        shell: Option<String>
+    },
+    /// Print the line that removes ~/.local/bin from PATH
+    Remove {
+        /// Shell type (auto-detected if not specified)
+        shell: Option<String>
     },
-    /// Add ~/.local/bin to PATH
-    Add,
-    /// Remove ~/.local/bin from PATH
-    Remove,
     /// Show shell environment status
     Env,
 }
 
-pub async fn execute(cmd: ShellCommands, _cli: &Cli, _config: &Config, output: &Output) -> Result<()> {
+pub async fn execute(cmd: ShellCommands, cli: &Cli, _config: &Config, output: &Output) -> Result<()> {
     match cmd {
         ShellCommands::Load { shell } => {
             load_integration(shell, output).await
         }
-        ShellCommands::Completions { shell } => {
-            generate_completions(&shell, output).await
+        ShellCommands::Completions { shell, install } => {
+            generate_completions(&shell, install, output).await
+        }
+        ShellCommands::Unload { shell, completions } => {
+            unload_integration(shell, completions, cli, output).await
         }
-        ShellCommands::Add => {
-            modify_path(true, output).await
+        ShellCommands::Add { shell } => {
+            modify_path(shell, true, output).await
         }
-        ShellCommands::Remove => {
-            modify_path(false, output).await
+        ShellCommands::Remove { shell } => {
+            modify_path(shell, false, output).await
         }
         ShellCommands::Env => {
             show_environment(output).await
@@ -63,90 +83,145 @@ async fn load_integration(shell_name: Option<String>, output: &Output) -> Result
     let script = integration.generate_script();
 
     // Output the script for evaluation
-    println!("{}", script);
+    output.print(&script);
 
     // Also provide installation instructions on stderr
     if ShellDetector::is_integration_installed(&shell) {
-        eprintln!("✅ Shell integration appears to be installed");
+        output.note("✅ Shell integration appears to be installed");
+    } else {
+        output.note("\n💡 To install permanently:");
+        output.note(&ShellDetector::suggest_installation(&shell));
+    }
+
+    Ok(())
+}
+
+async fn unload_integration(shell_name: Option<String>, completions: bool, cli: &Cli, output: &Output) -> Result<()> {
+    let shell = match shell_name {
+        Some(name) => ShellType::from_str(&name)?,
+        None => ShellDetector::detect_default_shell()
+            .context("Could not detect shell type; specify it with: pkmgr shell unload <shell>")?,
+    };
+
+    let mut changed = false;
+    for config_file in shell.config_files() {
+        let Ok(content) = fs::read_to_string(&config_file) else {
+            continue;
+        };
+        let (stripped, removed) = integration::strip_managed(&content)
+            .with_context(|| format!("Not editing {}", config_file))?;
+        if removed.is_empty() {
+            continue;
+        }
+
+        changed = true;
+        output.info(&format!("📝 {}: removing {} line(s)", config_file, removed.len()));
+        for line in &removed {
+            output.info(&format!("   - {}", line));
+        }
+        if cli.dry_run {
+            continue;
+        }
+
+        let backup_path = format!("{}.backup.{}", config_file, chrono::Utc::now().timestamp());
+        fs::copy(&config_file, &backup_path)
+            .with_context(|| format!("Failed to back up {}", config_file))?;
+        output.info(&format!("📄 Backed up to: {}", backup_path));
+        fs::write(&config_file, stripped)
+            .with_context(|| format!("Failed to write {}", config_file))?;
+    }
+
+    if completions {
+        if let Some(comp_path) = shell.completion_file().filter(|path| path.exists()) {
+            changed = true;
+            output.info(&format!("🗑️  Removing completions: {}", comp_path.display()));
+            if !cli.dry_run {
+                fs::remove_file(&comp_path)
+                    .with_context(|| format!("Failed to remove {}", comp_path.display()))?;
+            }
+        }
+    }
+
+    if !changed {
+        output.info(&format!("No pkmgr integration found for {}", shell.display_name()));
+    } else if cli.dry_run {
+        output.info("Dry run: nothing was changed");
     } else {
-        eprintln!("\n💡 To install permanently:");
-        eprintln!("{}", ShellDetector::suggest_installation(&shell));
+        output.success(&format!("✅ pkmgr integration removed for {}; open a new shell to finish", shell.display_name()));
     }
 
     Ok(())
 }
 
-async fn generate_completions(shell_name: &str, output: &Output) -> Result<()> {
+async fn generate_completions(shell_name: &str, install: bool, output: &Output) -> Result<()> {
     let shell = ShellType::from_str(shell_name)?;
 
     let generator = CompletionGenerator::new(shell.clone(), output.clone());
-    let completions = generator.generate_custom();
-
-    // Determine where to install
-    if let Some(comp_dir) = shell.completion_dir() {
-        let comp_path = PathBuf::from(&comp_dir).join("pkmgr");
+    let completions = generator.generate()?;
 
-        output.info(&format!("📝 Installing completions to: {}", comp_path.display()));
+    if !install {
+        output.write(&completions);
+        return Ok(());
+    }
 
-        // Create directory if needed
-        fs::create_dir_all(&comp_dir)
+    let comp_path = shell.completion_file()
+        .with_context(|| format!("No completion directory known for {}", shell.display_name()))?;
+    if let Some(comp_dir) = comp_path.parent() {
+        fs::create_dir_all(comp_dir)
             .context("Failed to create completion directory")?;
+    }
 
-        // Write completion file
-        fs::write(&comp_path, completions)
-            .context("Failed to write completion file")?;
-
-        // Make executable for shells that need it
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(&comp_path)?.permissions();
-            perms.set_mode(0o644);
-            fs::set_permissions(&comp_path, perms)?;
-        }
+    output.info(&format!("📝 Installing completions to: {}", comp_path.display()));
+    fs::write(&comp_path, completions)
+        .context("Failed to write completion file")?;
 
-        output.success(&format!("✅ Completions installed for {}", shell.display_name()));
+    output.success(&format!("✅ Completions installed for {}", shell.display_name()));
 
-        // Shell-specific reload instructions
-        match shell {
-            ShellType::Bash => {
-                output.info("💡 Reload with: source ~/.bashrc");
-            }
-            ShellType::Zsh => {
-                output.info("💡 Reload with: source ~/.zshrc");
-                output.info("   Or: rm -f ~/.zcompdump && compinit");
-            }
-            ShellType::Fish => {
-                output.info("💡 Completions will be available in new shells");
-            }
-            _ => {}
+    // Shell-specific reload instructions
+    match shell {
+        ShellType::Bash => {
+            output.info("💡 Completions will be available in new shells (requires bash-completion)");
         }
-    } else {
-        // Output to stdout for manual installation
-        println!("{}", completions);
-        output.info(&format!("💡 {} doesn't have a standard completion directory", shell.display_name()));
-        output.info("   Save the output above to an appropriate location");
+        ShellType::Zsh => {
+            output.info("💡 Add to ~/.zshrc before compinit: fpath=(~/.zsh/completions $fpath)");
+            output.info("   Then: rm -f ~/.zcompdump && compinit");
+        }
+        ShellType::Fish => {
+            output.info("💡 Completions will be available in new shells");
+        }
+        ShellType::PowerShell => {
+            output.info(&format!("💡 Add to your PowerShell profile: . {}", comp_path.display()));
+        }
+        ShellType::Nushell => {
+            output.info(&format!("💡 Add to config.nu: source {}", comp_path.display()));
+        }
+        ShellType::Unknown => {}
     }
 
     Ok(())
 }
 
-async fn modify_path(add: bool, output: &Output) -> Result<()> {
-    let shell = ShellDetector::detect_default_shell()
-        .unwrap_or(ShellType::Bash);
+async fn modify_path(shell_name: Option<String>, add: bool, output: &Output) -> Result<()> {
+    let shell = match shell_name {
+        Some(name) => ShellType::from_str(&name)?,
+        None => ShellDetector::detect_default_shell().unwrap_or(ShellType::Bash),
+    };
 
     let integration = ShellIntegration::new(shell.clone(), output.clone());
     let script = integration.generate_path_script(add);
 
-    // Output the script for evaluation
-    println!("{}", script);
+    // Only the snippet goes to stdout, so it can be evaluated as is
+    output.write(&script);
 
-    if add {
-        eprintln!("✅ PATH modification script generated");
-        eprintln!("💡 To apply: eval \"$(pkmgr shell add)\"");
-    } else {
-        eprintln!("✅ PATH removal script generated");
-        eprintln!("💡 To apply: eval \"$(pkmgr shell remove)\"");
+    let command = if add { "add" } else { "remove" };
+    let apply = match shell {
+        ShellType::Fish => format!("pkmgr shell {} fish | source", command),
+        ShellType::PowerShell => format!("pkmgr shell {} powershell | Invoke-Expression", command),
+        _ => format!("eval $(pkmgr shell {})", command),
+    };
+    // Keep eval'd runs quiet; the hint is for someone running it by hand
+    if atty::is(atty::Stream::Stdout) {
+        output.note(&format!("💡 To apply: {}", apply));
     }
 
     Ok(())
@@ -1,15 +1,84 @@
-use anyhow::Result;
-use clap::Subcommand;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use crate::cache::manager::CacheManager;
 use crate::commands::Cli;
+use crate::commands::preview::preview;
 use crate::core::config::Config;
+use crate::core::exit::UsageError;
+use crate::core::normalizer::PackageNormalizer;
+use crate::core::platform::PlatformInfo;
+use crate::core::traits::Operation;
+use crate::managers::PackageManagerFactory;
+use crate::profile::Profile;
+use crate::profile::applier::{ActionKind, ApplyScope, InstalledState, ProfileApplier, FAKE_INSTALLED_ENV};
+use crate::profile::importer::{self, ProfileImporter};
 use crate::ui::output::Output;
+use crate::ui::prompt::Prompt;
+use crate::core::exit::Cancelled;
 
 #[derive(Debug, Subcommand, Clone)]
 pub enum BootstrapCommands {
+    /// Install the tools pkmgr itself relies on: curl, git, gpg and CA certificates
+    Base,
     Install { file: String },
     Export,
 }
 
+/// Tools the rest of pkmgr relies on for downloads, repositories, signature
+/// checks and TLS, as universal names with the command that proves each
+/// present
+const BASE_TOOLS: &[(&str, Option<&str>)] = &[
+    ("curl", Some("curl")),
+    ("git", Some("git")),
+    ("gpg", Some("gpg")),
+    ("ca-certificates", None),
+];
+
+#[derive(Debug, Args, Clone)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct SyncArgs {
+    #[command(subcommand)]
+    pub command: Option<SyncCommands>,
+    /// Profile to reconcile the system against: a saved name, or an
+    /// http(s) or file URL
+    pub profile: Option<String>,
+    /// Save a profile fetched from a URL under this name; otherwise it is applied once and discarded
+    #[arg(long, value_name = "NAME")]
+    pub save: Option<String>,
+    /// Expected SHA-256 of a profile fetched from a URL
+    #[arg(long, value_name = "SHA256")]
+    pub checksum: Option<String>,
+    /// URL of a detached GnuPG signature for a profile fetched from a URL
+    #[arg(long, value_name = "URL", requires = "signing_key")]
+    pub signature: Option<String>,
+    /// Full fingerprint of the key that must have made --signature
+    #[arg(long, value_name = "FINGERPRINT", requires = "signature")]
+    pub signing_key: Option<String>,
+    /// Remove packages that sync previously installed but are no longer in the profile
+    #[arg(long)]
+    pub prune: bool,
+    /// Limit the reconcile to these parts of the profile
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub only: Vec<SyncScope>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SyncScope {
+    System,
+    Languages,
+    Binaries,
+}
+
+impl From<SyncScope> for ApplyScope {
+    fn from(scope: SyncScope) -> Self {
+        match scope {
+            SyncScope::System => ApplyScope::System,
+            SyncScope::Languages => ApplyScope::Languages,
+            SyncScope::Binaries => ApplyScope::Binaries,
+        }
+    }
+}
+
 #[derive(Debug, Subcommand, Clone)]
 pub enum SyncCommands {
     Push,
@@ -18,11 +87,181 @@ pub enum SyncCommands {
 }
 
 pub async fn execute_bootstrap(cmd: BootstrapCommands, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
-    output.info("🚀 Bootstrap");
+    match cmd {
+        BootstrapCommands::Base => bootstrap_base(cli, output).await,
+        _ => {
+            output.info("🚀 Bootstrap");
+            Ok(())
+        }
+    }
+}
+
+/// Install whichever base tools are missing, mapped to native names by the
+/// normalizer. A tool already on PATH counts as present however it got there
+async fn bootstrap_base(cli: &Cli, output: &Output) -> Result<()> {
+    output.print_header("🚀 Bootstrapping base tools");
+
+    let platform_info = PlatformInfo::detect_async().await?;
+    let package_manager = PackageManagerFactory::create(&platform_info)
+        .context("Failed to create package manager")?;
+    let pm_type = platform_info.primary_package_manager()
+        .context("No package manager available")?;
+    let normalizer = PackageNormalizer::new();
+
+    let mut missing = Vec::new();
+    for (tool, command) in BASE_TOOLS {
+        let packages = normalizer.normalize(tool, pm_type)?;
+        if packages.is_empty() {
+            output.debug(&format!("{} is provided by the system on {}", tool, package_manager.name()));
+            continue;
+        }
+        if command.is_some_and(|command| which::which(command).is_ok()) {
+            output.info(&format!("✅ {} is present", tool));
+            continue;
+        }
+
+        let installed = package_manager.is_installed(&packages).await?;
+        if packages.iter().any(|package| installed.get(package) == Some(&true)) {
+            output.info(&format!("✅ {} is present", tool));
+        } else {
+            output.info(&format!("📦 {} is missing ({})", tool, packages.join(", ")));
+            missing.extend(packages);
+        }
+    }
+
+    if missing.is_empty() {
+        output.success("All base tools are present");
+        return Ok(());
+    }
+
+    if cli.dry_run {
+        return preview(package_manager.as_ref(), Operation::Install(&missing), output).await;
+    }
+
+    output.progress(&format!("Installing {}", missing.join(", ")));
+    let result = package_manager.install(&missing).await?;
+    if !result.success {
+        anyhow::bail!("Failed to install base tools: {}", result.message);
+    }
+    if let Ok(mut cache) = CacheManager::new(output.clone()) {
+        let _ = cache.update_installed(&missing, &[]);
+    }
+
+    output.success(&format!("Installed {} base packages", missing.len()));
+    Ok(())
+}
+
+pub async fn execute_sync(args: SyncArgs, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
+    match (args.command, args.profile) {
+        (Some(_cmd), _) => {
+            output.info("🔄 Sync");
+            Ok(())
+        }
+        (None, Some(source)) if importer::is_url(&source) => {
+            let signature = args.signature.as_deref().zip(args.signing_key.as_deref());
+            let mut fetched = ProfileImporter::new(output.clone())
+                .fetch(&source, args.checksum.as_deref(), signature)
+                .await?;
+            let save = args.save.is_some();
+            if let Some(name) = args.save {
+                fetched.name = name;
+            }
+            let profile = fetched.clone().with_inheritance()
+                .with_context(|| format!("Profile from {} inherits from a profile that is not saved here", source))?
+                .expand_env()?;
+            sync_profile(&profile, save.then_some(&fetched), args.prune, &args.only, cli, config, output).await
+        }
+        (None, Some(name)) => {
+            if args.save.is_some() || args.checksum.is_some() || args.signature.is_some() {
+                return Err(UsageError(
+                    "--save, --checksum and --signature only apply to a profile given by URL".to_string()
+                ).into());
+            }
+            sync_profile(&Profile::load(&name)?, None, args.prune, &args.only, cli, config, output).await
+        }
+        (None, None) => {
+            anyhow::bail!("Specify a profile to sync, e.g. 'pkmgr sync development'")
+        }
+    }
+}
+
+/// Store a profile fetched with --save, as fetched (before inheritance and
+/// environment expansion)
+fn save_fetched(profile: Option<&Profile>, cli: &Cli, output: &Output) -> Result<()> {
+    let Some(profile) = profile else { return Ok(()) };
+    if cli.dry_run {
+        output.info(&format!("Would save as profile '{}'", profile.name));
+        return Ok(());
+    }
+    profile.save()?;
+    output.success(&format!("✅ Saved as profile '{}'", profile.name));
     Ok(())
 }
 
-pub async fn execute_sync(cmd: SyncCommands, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
-    output.info("🔄 Sync");
+/// Reconcile installed state to match a profile. `save` is a fetched profile
+/// to store once the sync is confirmed
+async fn sync_profile(
+    profile: &Profile,
+    save: Option<&Profile>,
+    prune: bool,
+    only: &[SyncScope],
+    cli: &Cli,
+    config: &Config,
+    output: &Output,
+) -> Result<()> {
+    output.print_header(&format!("🔄 Syncing to profile: {}", profile.name));
+
+    let mut applier = ProfileApplier::new(output.clone(), config.clone())
+        .with_scopes(only.iter().map(|s| (*s).into()).collect())
+        .with_prune(prune);
+    if let Some(installed) = InstalledState::from_env()? {
+        output.warn(&format!("Planning against the packages in ${}, not this system", FAKE_INSTALLED_ENV));
+        applier = applier.with_installed(installed);
+    }
+
+    let plan = applier.plan(profile).await?;
+
+    if plan.is_empty() {
+        output.success("✅ System already matches profile");
+        return save_fetched(save, cli, output);
+    }
+
+    for (kind, title) in [
+        (ActionKind::Install, "📦 To install"),
+        (ActionKind::Update, "🔄 To update"),
+        (ActionKind::Remove, "🗑️ To remove"),
+    ] {
+        let actions = plan.of_kind(kind);
+        if !actions.is_empty() {
+            output.print_section(&format!("{} ({})", title, actions.len()));
+            output.print_list(&actions.iter().map(|a| a.describe()).collect::<Vec<_>>());
+        }
+    }
+
+    if cli.dry_run {
+        output.info("Dry run - no changes made");
+        return save_fetched(save, cli, output);
+    }
+
+    let removals = plan.of_kind(ActionKind::Remove).len();
+    if removals > 0 && !cli.yes {
+        let prompt = Prompt::new(output.emoji_enabled);
+        if !prompt.confirm(&format!("Remove {} packages not in profile?", removals))? {
+            return Err(Cancelled("Sync cancelled".to_string()).into());
+        }
+    }
+    save_fetched(save, cli, output)?;
+
+    let report = applier.apply(profile, &plan).await?;
+
+    output.print_header("📊 Sync Summary");
+    if !report.succeeded.is_empty() {
+        output.success(&format!("✅ Applied {} changes", report.succeeded.len()));
+    }
+    if !report.failed.is_empty() {
+        output.error(&format!("❌ Failed {} changes: {}", report.failed.len(), report.failed.join(", ")));
+        return Err(anyhow::anyhow!("Some sync actions failed"));
+    }
+
     Ok(())
 }
@@ -1,11 +1,28 @@
 use anyhow::{Result, Context};
+use crate::cache::manager::CacheManager;
 use crate::commands::Cli;
+use crate::commands::preview::preview;
 use crate::core::config::Config;
 use crate::core::platform::PlatformInfo;
-use crate::managers::PackageManagerFactory;
+use crate::core::traits::{InstallResult, Operation, PackageManager};
+use crate::managers::{split_excluded, PackageManagerFactory};
+use crate::profile::hooks::{HookRunner, HookStage};
 use crate::ui::output::Output;
 
-pub async fn execute(packages: Option<Vec<String>>, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
+pub async fn execute(packages: Option<Vec<String>>, security_only: bool, exclude: Vec<String>, cli: &Cli, config: &Config, output: &Output) -> Result<()> {
+    let hooks = HookRunner::from_cli(cli, output)?;
+    hooks.run(HookStage::PreUpdate)?;
+    let result = update(packages, security_only, exclude, cli.dry_run, output).await;
+    if !cli.dry_run {
+        if let Ok(mut cache) = CacheManager::new(output.clone()) {
+            let _ = cache.drop_upgradable();
+        }
+    }
+    result?;
+    hooks.run(HookStage::PostUpdate)
+}
+
+async fn update(packages: Option<Vec<String>>, security_only: bool, exclude: Vec<String>, dry_run: bool, output: &Output) -> Result<()> {
     // Detect platform and get package manager
     let platform_info = PlatformInfo::detect_async().await?;
     let package_manager = PackageManagerFactory::create(&platform_info)
@@ -13,18 +30,29 @@ pub async fn execute(packages: Option<Vec<String>>, cli: &Cli, config: &Config,
 
     output.debug(&format!("Using package manager: {}", package_manager.name()));
 
+    if !exclude.is_empty() {
+        output.info(&format!("⏭️  Excluding: {}", exclude.join(", ")));
+    }
+
+    if dry_run {
+        return preview_update(package_manager.as_ref(), packages, security_only, &exclude, output).await;
+    }
+
+    if security_only {
+        return update_security_only(package_manager.as_ref(), &exclude, output).await;
+    }
+
     match packages {
         Some(packages) if packages.len() == 1 && packages[0] == "all" => {
             output.print_header("🔄 Updating All Packages");
             output.update_start("all packages");
 
             // First update package lists/metadata
-            output.info("📥 Updating package lists...");
-            package_manager.update().await?;
+            refresh_lists(package_manager.as_ref(), output).await?;
 
             // Then upgrade all packages
             output.info("⬆️  Upgrading packages...");
-            match package_manager.upgrade(None).await {
+            match upgrade_all(package_manager.as_ref(), &exclude).await {
                 Ok(result) => {
                     if result.success {
                         output.success(&format!("✅ {}", result.message));
@@ -43,8 +71,12 @@ pub async fn execute(packages: Option<Vec<String>>, cli: &Cli, config: &Config,
             output.print_header("🔄 Updating Specific Packages");
 
             // Update package lists first
-            output.info("📥 Updating package lists...");
-            package_manager.update().await?;
+            refresh_lists(package_manager.as_ref(), output).await?;
+
+            let (packages, skipped) = split_excluded(&packages, &exclude);
+            for package in &skipped {
+                output.info(&format!("⏭️  Skipping {} (excluded)", package));
+            }
 
             for package in &packages {
                 output.update_start(package);
@@ -68,12 +100,11 @@ pub async fn execute(packages: Option<Vec<String>>, cli: &Cli, config: &Config,
             output.update_start("all packages");
 
             // Update package lists
-            output.info("📥 Updating package lists...");
-            package_manager.update().await?;
+            refresh_lists(package_manager.as_ref(), output).await?;
 
             // Upgrade all packages
             output.info("⬆️  Upgrading packages...");
-            match package_manager.upgrade(None).await {
+            match upgrade_all(package_manager.as_ref(), &exclude).await {
                 Ok(result) => {
                     if result.success {
                         output.success(&format!("✅ {}", result.message));
@@ -91,4 +122,84 @@ pub async fn execute(packages: Option<Vec<String>>, cli: &Cli, config: &Config,
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Upgrade only the packages the backend reports security advisories for;
+/// backends without that metadata fail instead of doing a full upgrade
+async fn update_security_only(package_manager: &dyn PackageManager, exclude: &[String], output: &Output) -> Result<()> {
+    output.print_header("🔒 Applying Security Updates");
+
+    refresh_lists(package_manager, output).await?;
+
+    let packages = package_manager.security_updates().await
+        .with_context(|| format!("--security-only is not available with {}", package_manager.name()))?;
+    let (packages, skipped) = split_excluded(&packages, exclude);
+    for package in &skipped {
+        output.info(&format!("⏭️  Skipping security update for {} (excluded)", package));
+    }
+    if packages.is_empty() {
+        output.success("No security updates pending");
+        return Ok(());
+    }
+
+    output.info(&format!("🔒 {} security update(s): {}", packages.len(), packages.join(", ")));
+    output.info("⬆️  Upgrading packages...");
+    let result = package_manager.upgrade(Some(&packages)).await?;
+    if !result.success {
+        output.error(&result.message);
+        return Err(anyhow::anyhow!("Security update failed"));
+    }
+
+    output.success(&format!("Applied {} security update(s)", packages.len()));
+    Ok(())
+}
+
+/// `--dry-run`: the upgrade the backend would perform. Package lists are
+/// not refreshed, since that needs root and changes the system
+async fn preview_update(package_manager: &dyn PackageManager, packages: Option<Vec<String>>, security_only: bool, exclude: &[String], output: &Output) -> Result<()> {
+    output.print_header("🔄 Update Preview");
+    output.info("Package lists are not refreshed in a dry run; results reflect the last refresh");
+
+    let selected = if security_only {
+        let packages = package_manager.security_updates().await
+            .with_context(|| format!("--security-only is not available with {}", package_manager.name()))?;
+        let (packages, _) = split_excluded(&packages, exclude);
+        if packages.is_empty() {
+            output.success("No security updates pending");
+            return Ok(());
+        }
+        Some(packages)
+    } else {
+        match packages {
+            Some(packages) if !(packages.len() == 1 && packages[0] == "all") => Some(split_excluded(&packages, exclude).0),
+            _ => None,
+        }
+    };
+
+    let operation = match &selected {
+        Some(packages) => Operation::Upgrade(Some(packages)),
+        None if exclude.is_empty() => Operation::Upgrade(None),
+        None => Operation::UpgradeExcluding(exclude),
+    };
+    preview(package_manager, operation, output).await
+}
+
+/// Refresh package lists, recorded like `pkmgr repos update` so searches
+/// see the new index
+async fn refresh_lists(package_manager: &dyn PackageManager, output: &Output) -> Result<()> {
+    output.info("📥 Updating package lists...");
+    package_manager.update().await?;
+    if let Ok(mut cache) = CacheManager::new(output.clone()) {
+        let _ = cache.mark_index_refreshed(package_manager.name());
+    }
+    Ok(())
+}
+
+/// Full upgrade, handing exclusions to the backend's native mechanism
+async fn upgrade_all(package_manager: &dyn PackageManager, exclude: &[String]) -> Result<InstallResult> {
+    if exclude.is_empty() {
+        package_manager.upgrade(None).await
+    } else {
+        package_manager.upgrade_excluding(exclude).await
+    }
+}
@@ -24,6 +24,24 @@ pub enum UsbCommands {
         #[arg(long)]
         no_verify: bool,
     },
+    /// Add an ISO to a multi-boot USB and regenerate its boot menu
+    Add {
+        device: String,
+        iso: String,
+    },
+    /// Remove an ISO from a multi-boot USB (--force to remove the last one)
+    Remove {
+        device: String,
+        iso_name: String,
+    },
+    /// Mount a USB device and print where it is mounted
+    Mount {
+        device: String,
+    },
+    /// Flush, unmount and power off a USB device so it can be unplugged
+    Eject {
+        device: String,
+    },
     /// Create or manage multi-boot USB
     #[command(subcommand)]
     Boot(BootCommands),
@@ -65,24 +83,36 @@ pub async fn execute(cmd: UsbCommands, cli: &Cli, config: &Config, output: &Outp
     // Check if usb module is available
     #[cfg(feature = "usb")]
     {
-        use crate::usb::{device::DeviceDetector, wizard::UsbWizard, writer::UsbWriter};
+        use crate::usb::wizard::UsbWizard;
 
         match cmd {
             UsbCommands::Interactive => {
-                let wizard = UsbWizard::new(output.clone());
+                let wizard = UsbWizard::new(output.clone()).with_assume_yes(cli.yes);
                 wizard.run().await?;
             }
             UsbCommands::List => {
                 list_usb_devices(output)?;
             }
             UsbCommands::Erase { device, filesystem } => {
-                erase_device(&device, &filesystem, output).await?;
+                erase_device(&device, &filesystem, cli.force, cli.yes, output).await?;
             }
             UsbCommands::Write { iso_file, device, no_verify } => {
-                write_iso(&iso_file, &device, !no_verify, output).await?;
+                write_iso(&iso_file, &device, !no_verify, cli.force, cli.yes, output).await?;
+            }
+            UsbCommands::Add { device, iso } => {
+                add_iso(&device, &iso, output)?;
+            }
+            UsbCommands::Remove { device, iso_name } => {
+                remove_iso(&device, &iso_name, cli.force, output)?;
+            }
+            UsbCommands::Mount { device } => {
+                mount_device(&device, output)?;
+            }
+            UsbCommands::Eject { device } => {
+                eject_device(&device, output)?;
             }
             UsbCommands::Boot(boot_cmd) => {
-                handle_boot_command(boot_cmd, output)?;
+                handle_boot_command(boot_cmd, cli, output)?;
             }
         }
     }
@@ -106,6 +136,22 @@ pub async fn execute(cmd: UsbCommands, cli: &Cli, config: &Config, output: &Outp
                 output.info(&format!("💿 Would write {} to {}", iso_file, device));
                 output.warn("USB support not compiled in");
             }
+            UsbCommands::Add { device, iso } => {
+                output.info(&format!("➕ Would add {} to {}", iso, device));
+                output.warn("USB support not compiled in");
+            }
+            UsbCommands::Remove { device, iso_name } => {
+                output.info(&format!("➖ Would remove {} from {}", iso_name, device));
+                output.warn("USB support not compiled in");
+            }
+            UsbCommands::Mount { device } => {
+                output.info(&format!("📂 Would mount {}", device));
+                output.warn("USB support not compiled in");
+            }
+            UsbCommands::Eject { device } => {
+                output.info(&format!("⏏️ Would eject {}", device));
+                output.warn("USB support not compiled in");
+            }
             UsbCommands::Boot(boot_cmd) => {
                 output.info("🛠️ Multi-boot USB management");
                 output.warn("USB support not compiled in");
@@ -133,7 +179,7 @@ fn list_usb_devices(output: &Output) -> Result<()> {
 
     for device in devices {
         let status = if device.is_mounted { "mounted" } else { "unmounted" };
-        let fs = device.filesystem.as_ref().unwrap_or(&"unknown".to_string());
+        let fs = device.filesystem.as_deref().unwrap_or("unknown");
 
         output.info(&format!("{} - {} ({}, {}, {})",
             device.path.display(),
@@ -148,8 +194,14 @@ fn list_usb_devices(output: &Output) -> Result<()> {
 }
 
 #[cfg(feature = "usb")]
-fn handle_boot_command(cmd: BootCommands, output: &Output) -> Result<()> {
+fn handle_boot_command(cmd: BootCommands, cli: &Cli, output: &Output) -> Result<()> {
     match cmd {
+        BootCommands::Create { device, isos, bootloader } if bootloader.eq_ignore_ascii_case("ventoy") => {
+            create_ventoy(&device, &isos, cli.force, output)?;
+        }
+        BootCommands::Add { iso_or_distro, device: Some(device) } if is_ventoy(&device)? => {
+            add_to_ventoy(&device, &[iso_or_distro], output)?;
+        }
         BootCommands::Create { device, isos, bootloader } => {
             output.info(&format!("Creating multi-boot USB on {} with {}", device, bootloader));
             for iso in isos {
@@ -157,19 +209,19 @@ fn handle_boot_command(cmd: BootCommands, output: &Output) -> Result<()> {
             }
             output.warn("Multi-boot creation pending implementation");
         }
-        BootCommands::Add { iso_or_distro, device } => {
+        BootCommands::Add { iso_or_distro, .. } => {
             output.info(&format!("Adding {} to multi-boot USB", iso_or_distro));
             output.warn("Multi-boot add pending implementation");
         }
-        BootCommands::Remove { iso_or_distro, device } => {
+        BootCommands::Remove { iso_or_distro, .. } => {
             output.info(&format!("Removing {} from multi-boot USB", iso_or_distro));
             output.warn("Multi-boot remove pending implementation");
         }
-        BootCommands::List { device } => {
+        BootCommands::List { .. } => {
             output.info("Listing multi-boot USB contents");
             output.warn("Multi-boot list pending implementation");
         }
-        BootCommands::Clean { device } => {
+        BootCommands::Clean { .. } => {
             output.info("Cleaning multi-boot USB");
             output.warn("Multi-boot clean pending implementation");
         }
@@ -178,46 +230,155 @@ fn handle_boot_command(cmd: BootCommands, output: &Output) -> Result<()> {
 }
 
 #[cfg(feature = "usb")]
-async fn erase_device(device_path: &str, filesystem: &str, output: &Output) -> Result<()> {
+fn find_device(device_path: &str) -> Result<crate::usb::UsbDevice> {
+    use crate::usb::device::DeviceDetector;
+
+    DeviceDetector::new().list_usb_devices()?
+        .into_iter()
+        .find(|d| d.path == PathBuf::from(device_path))
+        .ok_or_else(|| anyhow::anyhow!("Device {} not found", device_path))
+}
+
+#[cfg(feature = "usb")]
+fn mount_device(device_path: &str, output: &Output) -> Result<()> {
+    use crate::usb::device::DeviceDetector;
+
+    let device = find_device(device_path)?;
+    for mount_point in DeviceDetector::new().mount_device(&device)? {
+        output.success(&format!("{} mounted at {}", device_path, mount_point.display()));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "usb")]
+fn eject_device(device_path: &str, output: &Output) -> Result<()> {
     use crate::usb::device::DeviceDetector;
+
+    let device = find_device(device_path)?;
+    output.progress(&format!("Flushing writes to {}...", device_path));
+    DeviceDetector::new().eject_device(&device)?;
+    output.success(&format!("{} ejected; safe to unplug", device_path));
+    Ok(())
+}
+
+#[cfg(feature = "usb")]
+fn is_ventoy(device_path: &str) -> Result<bool> {
+    use crate::usb::bootloader::BootloaderManager;
+
+    Ok(BootloaderManager::is_ventoy_device(&find_device(device_path)?))
+}
+
+/// Ventoy multi-boot: reuse an existing install, or erase and install with --force
+#[cfg(feature = "usb")]
+fn create_ventoy(device_path: &str, isos: &[String], force: bool, output: &Output) -> Result<()> {
+    use crate::usb::bootloader::{BootloaderManager, VentoySetup};
+    use crate::usb::BootloaderType;
+
+    output.print_header(&format!("💾 Creating Ventoy multi-boot USB on {}", device_path));
+
+    let device = find_device(device_path)?;
+    let manager = BootloaderManager::new(BootloaderType::Ventoy);
+    match manager.setup_ventoy(&device, force)? {
+        VentoySetup::Reused => output.info("♻️ Ventoy already installed; keeping existing ISOs"),
+        VentoySetup::Installed => output.success("Ventoy installed"),
+    }
+
+    if !isos.is_empty() {
+        // A fresh install re-reads partitions, so look the device up again
+        add_to_ventoy(device_path, isos, output)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "usb")]
+fn add_to_ventoy(device_path: &str, isos: &[String], output: &Output) -> Result<()> {
+    use crate::usb::bootloader::BootloaderManager;
+    use crate::usb::{BootloaderType, MultiBootConfig};
+
+    let device = find_device(device_path)?;
+    let root = BootloaderManager::ventoy_root(&device)?;
+    let manager = BootloaderManager::new(BootloaderType::Ventoy);
+    let mut config = MultiBootConfig::load_or_new(&root, BootloaderType::Ventoy)?;
+
+    for iso in isos {
+        output.progress(&format!("Copying {} to {}", iso, root.display()));
+        let entry = manager.add_iso(&root, std::path::Path::new(iso), &mut config)?;
+        output.success(&format!("Added {} ({})", entry.display_name, entry.category));
+    }
+    Ok(())
+}
+
+/// Load the manifest from the device's multi-boot partition
+#[cfg(feature = "usb")]
+fn open_multiboot(device_path: &str) -> Result<(crate::usb::bootloader::BootloaderManager, PathBuf, crate::usb::MultiBootConfig)> {
+    use crate::usb::bootloader::BootloaderManager;
+    use crate::usb::{BootloaderType, MultiBootConfig};
+
+    let device = find_device(device_path)?;
+    let root = BootloaderManager::multiboot_root(&device)?;
+    let default_type = if BootloaderManager::is_ventoy_device(&device) {
+        BootloaderType::Ventoy
+    } else {
+        BootloaderType::Grub2
+    };
+    let config = MultiBootConfig::load_or_new(&root, default_type)?;
+    let manager = BootloaderManager::new(config.bootloader.clone());
+    Ok((manager, root, config))
+}
+
+#[cfg(feature = "usb")]
+fn add_iso(device_path: &str, iso: &str, output: &Output) -> Result<()> {
+    let (manager, root, mut config) = open_multiboot(device_path)?;
+
+    output.progress(&format!("Copying {} to {}", iso, root.display()));
+    let entry = manager.add_iso(&root, std::path::Path::new(iso), &mut config)?;
+    output.success(&format!("Added {} ({}); {} ISOs on {}", entry.display_name, entry.category, config.entries.len(), device_path));
+    Ok(())
+}
+
+#[cfg(feature = "usb")]
+fn remove_iso(device_path: &str, iso_name: &str, force: bool, output: &Output) -> Result<()> {
+    let (manager, root, mut config) = open_multiboot(device_path)?;
+
+    let entry = manager.remove_iso(&root, &mut config, iso_name, force)?;
+    output.success(&format!("Removed {}; {} ISOs left on {}", entry.display_name, config.entries.len(), device_path));
+    Ok(())
+}
+
+#[cfg(feature = "usb")]
+async fn erase_device(device_path: &str, filesystem: &str, force: bool, assume_yes: bool, output: &Output) -> Result<()> {
+    use crate::usb::device::DeviceDetector;
+    use crate::core::exit::Cancelled;
+    use crate::usb::wizard::confirm_wipe;
     use crate::usb::writer::UsbWriter;
     use std::path::PathBuf;
 
     output.print_header(&format!("🔥 Erasing USB Device: {}", device_path));
 
+    // --force also reaches fixed disks; the writer asks for typed confirmation
     let detector = DeviceDetector::new();
-    let devices = detector.list_usb_devices()?;
+    let devices = if force { detector.list_all_devices()? } else { detector.list_usb_devices()? };
 
     let device_pathbuf = PathBuf::from(device_path);
     let device = devices.iter()
         .find(|d| d.path == device_pathbuf)
         .ok_or_else(|| anyhow::anyhow!("Device {} not found", device_path))?;
 
-    // Confirm with user
-    use crate::ui::prompt::Prompt;
-    let prompt = Prompt::new(output.emoji_enabled);
-
-    output.warn(&format!(
-        "This will PERMANENTLY ERASE all data on {} ({} - {})",
-        device.path.display(),
-        device.name,
-        device.format_size()
-    ));
-
-    if !prompt.confirm_dangerous("Type 'YES' in capitals to proceed")? {
-        output.info("Operation cancelled");
-        return Ok(());
+    if !confirm_wipe(device, "Erasing", assume_yes, output)? {
+        return Err(Cancelled("Operation cancelled; device left untouched".to_string()).into());
     }
 
-    let writer = UsbWriter::new(output.clone());
+    let writer = UsbWriter::new(output.clone()).with_force(force);
     writer.erase_device(device, filesystem).await?;
 
     Ok(())
 }
 
 #[cfg(feature = "usb")]
-async fn write_iso(iso_file: &str, device_path: &str, verify: bool, output: &Output) -> Result<()> {
+async fn write_iso(iso_file: &str, device_path: &str, verify: bool, force: bool, assume_yes: bool, output: &Output) -> Result<()> {
     use crate::usb::device::DeviceDetector;
+    use crate::core::exit::Cancelled;
+    use crate::usb::wizard::confirm_wipe;
     use crate::usb::writer::UsbWriter;
     use std::path::{Path, PathBuf};
 
@@ -230,31 +391,20 @@ async fn write_iso(iso_file: &str, device_path: &str, verify: bool, output: &Out
     }
 
     // Find device
+    // --force also reaches fixed disks; the writer asks for typed confirmation
     let detector = DeviceDetector::new();
-    let devices = detector.list_usb_devices()?;
+    let devices = if force { detector.list_all_devices()? } else { detector.list_usb_devices()? };
 
     let device_pathbuf = PathBuf::from(device_path);
     let device = devices.iter()
         .find(|d| d.path == device_pathbuf)
         .ok_or_else(|| anyhow::anyhow!("Device {} not found", device_path))?;
 
-    // Confirm with user
-    use crate::ui::prompt::Prompt;
-    let prompt = Prompt::new(output.emoji_enabled);
-
-    output.warn(&format!(
-        "This will overwrite all data on {} ({} - {})",
-        device.path.display(),
-        device.name,
-        device.format_size()
-    ));
-
-    if !prompt.confirm("Continue?")? {
-        output.info("Operation cancelled");
-        return Ok(());
+    if !confirm_wipe(device, "Writing the ISO", assume_yes, output)? {
+        return Err(Cancelled("Operation cancelled; device left untouched".to_string()).into());
     }
 
-    let writer = UsbWriter::new(output.clone());
+    let writer = UsbWriter::new(output.clone()).with_force(force);
     writer.write_iso(iso_path, device, verify).await?;
 
     Ok(())
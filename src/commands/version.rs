@@ -0,0 +1,43 @@
+use anyhow::Result;
+use crate::core::platform::PlatformInfo;
+use crate::ui::output::Output;
+
+/// `pkmgr v1.0.0 (abc1234) built 2025-12-23`, also used by --version
+pub const VERSION_LINE: &str = concat!(
+    "v",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("PKMGR_GIT_COMMIT"),
+    ") built ",
+    env!("PKMGR_BUILD_DATE")
+);
+
+/// Print the version line; `full` adds the target, detected platform and
+/// package managers, for bug reports
+pub async fn execute(full: bool, output: &Output) -> Result<()> {
+    output.print(&format!("pkmgr {}", VERSION_LINE));
+    if !full {
+        return Ok(());
+    }
+
+    let platform = PlatformInfo::detect_async().await?;
+    let distribution = match (&platform.distribution, &platform.version) {
+        (Some(distribution), Some(version)) => format!(" ({} {})", distribution, version),
+        (Some(distribution), None) => format!(" ({})", distribution),
+        _ => String::new(),
+    };
+    let primary = platform.primary_package_manager()
+        .map(|pm| pm.to_string())
+        .unwrap_or_else(|| "none detected".to_string());
+    let backends: Vec<String> = platform.package_managers.iter().map(|pm| pm.to_string()).collect();
+
+    output.print(&format!("Target:           {}-{}", std::env::consts::OS, std::env::consts::ARCH));
+    output.print(&format!("Platform:         {} {}{}", platform.os(), platform.architecture, distribution));
+    output.print(&format!("Package manager:  {}", primary));
+    output.print(&format!(
+        "Backends:         {}",
+        if backends.is_empty() { "none".to_string() } else { backends.join(", ") }
+    ));
+
+    Ok(())
+}
@@ -1,8 +1,31 @@
 use anyhow::{Context, Result};
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::fs;
+use crate::repos::TrustLevel;
+
+/// Set once at startup from --config
+static CONFIG_PATH: OnceCell<PathBuf> = OnceCell::new();
+
+/// Read and write this config file instead of the default for the rest of
+/// the process; its directory also holds cache.toml, repos.toml and profiles
+pub fn set_config_path(path: PathBuf) {
+    let _ = CONFIG_PATH.set(std::path::absolute(&path).unwrap_or(path));
+}
+
+/// --config, then a non-empty $PKMGR_CONFIG
+pub(crate) fn config_path_override() -> Option<PathBuf> {
+    CONFIG_PATH.get().cloned().or_else(|| {
+        std::env::var_os("PKMGR_CONFIG")
+            .filter(|path| !path.is_empty())
+            .map(|path| {
+                let path = PathBuf::from(path);
+                std::path::absolute(&path).unwrap_or(path)
+            })
+    })
+}
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
@@ -11,6 +34,8 @@ pub struct Config {
     pub paths: Paths,
     pub network: Network,
     pub security: Security,
+    #[serde(default)]
+    pub privilege: Privilege,
     pub repositories: HashMap<String, String>,
     pub aliases: HashMap<String, String>,
     pub language_defaults: LanguageDefaults,
@@ -24,6 +49,58 @@ pub struct PkmgrConfig {
     pub install_id: String,
 }
 
+/// `emoji_enabled`: true, false, or "auto" to use emoji only where the
+/// locale is UTF-8 and the terminal is not dumb
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmojiSetting {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl EmojiSetting {
+    /// Resolve against the current environment
+    pub fn enabled(self) -> bool {
+        crate::ui::output::detect_emoji(self)
+    }
+}
+
+impl Serialize for EmojiSetting {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            EmojiSetting::Auto => serializer.serialize_str("auto"),
+            EmojiSetting::Always => serializer.serialize_bool(true),
+            EmojiSetting::Never => serializer.serialize_bool(false),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EmojiSetting {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Bool(bool),
+            Text(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Bool(true) => Ok(EmojiSetting::Always),
+            Raw::Bool(false) => Ok(EmojiSetting::Never),
+            Raw::Text(text) => match text.as_str() {
+                "auto" => Ok(EmojiSetting::Auto),
+                "always" => Ok(EmojiSetting::Always),
+                "never" => Ok(EmojiSetting::Never),
+                other => Err(serde::de::Error::custom(format!(
+                    "invalid emoji_enabled '{}': expected true, false or \"auto\"",
+                    other
+                ))),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Defaults {
     pub install_location: String,
@@ -32,7 +109,7 @@ pub struct Defaults {
     pub parallel_downloads: u32,
     pub parallel_operations: u32,
     pub color_output: String,
-    pub emoji_enabled: bool,
+    pub emoji_enabled: EmojiSetting,
     pub progress_style: String,
     pub verbosity: String,
     pub pager: String,
@@ -42,12 +119,21 @@ pub struct Defaults {
     pub keep_downloads: bool,
     pub use_cache: bool,
     pub auto_fix: bool,
+    /// Backend to use instead of the detected one (apt, dnf, yum, brew, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<String>,
 }
 
+/// Directory settings. cache_dir, data_dir and config_dir are empty unless
+/// set by the user, so the XDG / $PKMGR_HOME layout from `core::paths`
+/// applies at run time instead of being frozen into config.toml
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Paths {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub cache_dir: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub data_dir: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub config_dir: String,
     pub install_dir: String,
     pub iso_dir: String,
@@ -61,7 +147,25 @@ pub struct Network {
     pub retry_delay: u32,
     pub bandwidth_limit: u64,
     pub proxy: String,
+    /// Hosts that bypass `proxy`, comma separated as in NO_PROXY
+    #[serde(default)]
+    pub no_proxy: String,
+    /// Extra PEM CA bundle to trust, for TLS-inspecting proxies
+    #[serde(default)]
+    pub ca_cert: String,
     pub parallel_downloads: u32,
+    /// Fetch large ISOs over up to `parallel_downloads` range requests at once
+    #[serde(default)]
+    pub segmented_downloads: bool,
+    /// Overall limit in seconds for a single request, including the body
+    #[serde(default = "default_download_timeout")]
+    pub download_timeout: u64,
+    /// Use only cached data and never touch the network
+    #[serde(default)]
+    pub offline: bool,
+    /// Skip doctor's online checks, as with `doctor --no-network`
+    #[serde(default)]
+    pub no_network_checks: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -71,6 +175,31 @@ pub struct Security {
     pub allow_untrusted: bool,
     pub keyserver: String,
     pub key_refresh_days: u32,
+    /// Installs from repositories below this trust level are refused unless forced
+    #[serde(default = "default_min_trust_level")]
+    pub min_trust_level: TrustLevel,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Privilege {
+    /// "auto", "sudo", "doas" or "pkexec"
+    pub escalation: String,
+}
+
+impl Default for Privilege {
+    fn default() -> Self {
+        Self {
+            escalation: "auto".to_string(),
+        }
+    }
+}
+
+fn default_download_timeout() -> u64 {
+    600
+}
+
+fn default_min_trust_level() -> TrustLevel {
+    TrustLevel::Community
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -83,6 +212,16 @@ pub struct LanguageDefaults {
     pub rust: String,
     pub java: String,
     pub dotnet: String,
+    /// JDK vendor for bare Java versions: temurin, zulu or graalvm
+    #[serde(default = "default_java_vendor")]
+    pub java_vendor: String,
+    /// Install a missing `pkmgr <lang> --use <version>` version instead of failing
+    #[serde(default)]
+    pub auto_install: bool,
+}
+
+fn default_java_vendor() -> String {
+    "temurin".to_string()
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -90,6 +229,9 @@ pub struct BinarySources {
     pub prefer_github: bool,
     pub include_prerelease: bool,
     pub asset_preference: Vec<String>,
+    /// GitHub API token; GITHUB_TOKEN / GH_TOKEN take precedence
+    #[serde(default)]
+    pub github_token: Option<String>,
 }
 
 impl Default for Config {
@@ -107,7 +249,7 @@ impl Default for Config {
                 parallel_downloads: 4,
                 parallel_operations: 2,
                 color_output: "auto".to_string(),
-                emoji_enabled: true,
+                emoji_enabled: EmojiSetting::Auto,
                 progress_style: "bar".to_string(),
                 verbosity: "normal".to_string(),
                 pager: "auto".to_string(),
@@ -117,11 +259,12 @@ impl Default for Config {
                 keep_downloads: false,
                 use_cache: true,
                 auto_fix: true,
+                package_manager: None,
             },
             paths: Paths {
-                cache_dir: "~/.cache/pkmgr".to_string(),
-                data_dir: "~/.local/share/pkmgr".to_string(),
-                config_dir: "~/.config/pkmgr".to_string(),
+                cache_dir: String::new(),
+                data_dir: String::new(),
+                config_dir: String::new(),
                 install_dir: "~/.local".to_string(),
                 iso_dir: "~/Downloads/ISOs".to_string(),
                 temp_dir: "/tmp/pkmgr".to_string(),
@@ -132,7 +275,13 @@ impl Default for Config {
                 retry_delay: 5,
                 bandwidth_limit: 0,
                 proxy: String::new(),
+                no_proxy: String::new(),
+                ca_cert: String::new(),
                 parallel_downloads: 4,
+                segmented_downloads: false,
+                download_timeout: default_download_timeout(),
+                offline: false,
+                no_network_checks: false,
             },
             security: Security {
                 verify_signatures: true,
@@ -140,7 +289,9 @@ impl Default for Config {
                 allow_untrusted: false,
                 keyserver: "hkps://keys.openpgp.org".to_string(),
                 key_refresh_days: 30,
+                min_trust_level: default_min_trust_level(),
             },
+            privilege: Privilege::default(),
             repositories: HashMap::new(),
             aliases: {
                 let mut aliases = HashMap::new();
@@ -163,6 +314,8 @@ impl Default for Config {
                 rust: "1.75".to_string(),
                 java: "11".to_string(),
                 dotnet: "8.0".to_string(),
+                java_vendor: default_java_vendor(),
+                auto_install: false,
             },
             binary_sources: BinarySources {
                 prefer_github: true,
@@ -172,6 +325,7 @@ impl Default for Config {
                     "appimage".to_string(),
                     "archive".to_string(),
                 ],
+                github_token: None,
             },
         }
     }
@@ -179,16 +333,15 @@ impl Default for Config {
 
 impl Config {
     pub async fn load() -> Result<Self> {
-        let config_dir = Self::get_config_dir()?;
-        let config_file = config_dir.join("config.toml");
+        let config_file = Self::config_file()?;
 
         if config_file.exists() {
             let content = fs::read_to_string(&config_file)
                 .await
-                .context("Failed to read config file")?;
+                .with_context(|| format!("Failed to read config file {}", config_file.display()))?;
 
             let config: Config = toml::from_str(&content)
-                .context("Failed to parse config file")?;
+                .with_context(|| format!("Failed to parse config file {}", config_file.display()))?;
 
             Ok(config)
         } else {
@@ -203,7 +356,7 @@ impl Config {
         let config_dir = Self::get_config_dir()?;
         fs::create_dir_all(&config_dir).await?;
 
-        let config_file = config_dir.join("config.toml");
+        let config_file = Self::config_file()?;
         let content = toml::to_string_pretty(self)
             .context("Failed to serialize config")?;
 
@@ -211,19 +364,31 @@ impl Config {
         Ok(())
     }
 
+    /// --config, $PKMGR_CONFIG, or ~/.config/pkmgr/config.toml
+    pub fn config_file() -> Result<PathBuf> {
+        match config_path_override() {
+            Some(path) => Ok(path),
+            None => Ok(Self::get_config_dir()?.join("config.toml")),
+        }
+    }
+
+    /// Directory of the config file, which also holds pkmgr's other config
     pub fn get_config_dir() -> Result<PathBuf> {
-        let home_dir = dirs::home_dir().context("Failed to get home directory")?;
-        Ok(home_dir.join(".config").join("pkmgr"))
+        Ok(super::paths::Paths::resolve()?.config_dir)
     }
 
     pub fn get_cache_dir(&self) -> Result<PathBuf> {
-        let path = shellexpand::tilde(&self.paths.cache_dir).to_string();
-        Ok(PathBuf::from(path))
+        match configured_dir(&self.paths.cache_dir, "~/.cache/pkmgr") {
+            Some(path) => Ok(path),
+            None => Ok(super::paths::Paths::resolve()?.cache_dir),
+        }
     }
 
     pub fn get_data_dir(&self) -> Result<PathBuf> {
-        let path = shellexpand::tilde(&self.paths.data_dir).to_string();
-        Ok(PathBuf::from(path))
+        match configured_dir(&self.paths.data_dir, "~/.local/share/pkmgr") {
+            Some(path) => Ok(path),
+            None => Ok(super::paths::Paths::resolve()?.data_dir),
+        }
     }
 
     pub fn get_install_dir(&self) -> Result<PathBuf> {
@@ -239,4 +404,31 @@ impl Config {
     pub fn resolve_alias(&self, command: &str) -> String {
         self.aliases.get(command).cloned().unwrap_or_else(|| command.to_string())
     }
-}
\ No newline at end of file
+}
+
+/// A directory the user chose, expanded; None when unset or still the
+/// `legacy` value older releases wrote into every config file
+fn configured_dir(value: &str, legacy: &str) -> Option<PathBuf> {
+    (!value.is_empty() && value != legacy).then(|| PathBuf::from(shellexpand::tilde(value).to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_directories_are_resolved_not_saved() {
+        let saved = toml::to_string_pretty(&Config::default()).unwrap();
+        assert!(!saved.contains("cache_dir") && !saved.contains("data_dir"), "{}", saved);
+
+        let mut config: Config = toml::from_str(&saved).unwrap();
+        let layout = crate::core::paths::Paths::resolve().unwrap();
+        assert_eq!(config.get_data_dir().unwrap(), layout.data_dir);
+
+        config.paths.cache_dir = "~/.cache/pkmgr".to_string();
+        assert_eq!(config.get_cache_dir().unwrap(), layout.cache_dir);
+
+        config.paths.cache_dir = "/srv/pkmgr-cache".to_string();
+        assert_eq!(config.get_cache_dir().unwrap(), PathBuf::from("/srv/pkmgr-cache"));
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+/// How a command finished, as scripts see it. Everything a command reports
+/// maps to an exit code here and only here
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    /// `check` found pending updates
+    UpdatesAvailable,
+    /// `doctor` found errors but nothing critical
+    HealthErrors,
+    /// `doctor` found critical problems
+    HealthCritical,
+}
+
+pub const SUCCESS: u8 = 0;
+pub const GENERAL_ERROR: u8 = 1;
+pub const USAGE_ERROR: u8 = 2;
+pub const OPERATION_CANCELLED: u8 = 13;
+pub const LOCK_HELD: u8 = 14;
+pub const HEALTH_ERRORS: u8 = 16;
+pub const HEALTH_CRITICAL: u8 = 17;
+/// Same code `dnf check-update` uses
+pub const UPDATES_AVAILABLE: u8 = 100;
+
+impl Outcome {
+    pub fn code(self) -> u8 {
+        match self {
+            Outcome::Success => SUCCESS,
+            Outcome::UpdatesAvailable => UPDATES_AVAILABLE,
+            Outcome::HealthErrors => HEALTH_ERRORS,
+            Outcome::HealthCritical => HEALTH_CRITICAL,
+        }
+    }
+}
+
+/// A command was invoked with arguments that make no sense together, caught
+/// after clap parsing
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+/// A confirmation was declined, by the user or for them in a
+/// non-interactive session without `--yes`
+#[derive(Debug)]
+pub struct Cancelled(pub String);
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Another pkmgr run holds the operation lock
+#[derive(Debug)]
+pub struct LockHeld(pub String);
+
+impl fmt::Display for LockHeld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for LockHeld {}
+
+/// Exit code for a finished run
+pub fn exit_code(result: &anyhow::Result<Outcome>) -> u8 {
+    match result {
+        Ok(outcome) => outcome.code(),
+        Err(e) if e.chain().any(|cause| cause.is::<UsageError>() || cause.is::<clap::Error>()) => USAGE_ERROR,
+        Err(e) if e.chain().any(|cause| cause.is::<Cancelled>()) => OPERATION_CANCELLED,
+        Err(e) if e.chain().any(|cause| cause.is::<LockHeld>()) => LOCK_HELD,
+        Err(_) => GENERAL_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn usage_errors_exit_2_even_with_context() {
+        let usage: anyhow::Result<Outcome> = Err(UsageError("Branch name required".to_string()))
+            .context("update-self failed");
+        assert_eq!(exit_code(&usage), USAGE_ERROR);
+        assert_eq!(exit_code(&Err(anyhow::anyhow!("boom"))), GENERAL_ERROR);
+        assert_eq!(exit_code(&Ok(Outcome::UpdatesAvailable)), 100);
+    }
+
+    #[test]
+    fn declined_and_locked_runs_have_their_own_codes() {
+        let cancelled: anyhow::Result<Outcome> = Err(Cancelled("Autoremove cancelled".to_string())).context("autoremove failed");
+        assert_eq!(exit_code(&cancelled), OPERATION_CANCELLED);
+        let locked: anyhow::Result<Outcome> = Err(LockHeld("Another pkmgr operation is in progress".to_string()).into());
+        assert_eq!(exit_code(&locked), LOCK_HELD);
+    }
+}
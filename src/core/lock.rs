@@ -0,0 +1,117 @@
+use anyhow::{Context, Result};
+use fs2::FileExt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+use super::paths::Paths;
+use crate::core::exit::LockHeld;
+
+/// Advisory lock held for the duration of a mutating pkmgr operation, so two
+/// runs never drive the native package managers at once. The OS drops it
+/// when the process exits, however it exits
+pub struct OperationLock {
+    file: File,
+}
+
+impl OperationLock {
+    /// Take the lock in the state directory for `operation`. `force` first
+    /// deletes the lock file, but only once the pkmgr recorded in it has
+    /// exited; a running holder is never broken
+    pub fn acquire(operation: &str, force: bool) -> Result<Self> {
+        let path = Paths::resolve()?.state_dir.join("pkmgr.lock");
+        Self::acquire_at(&path, operation, force)
+    }
+
+    pub fn acquire_at(path: &Path, operation: &str, force: bool) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        if force {
+            if let Some(pid) = recorded_pid(path).filter(|pid| process_alive(*pid)) {
+                anyhow::bail!(
+                    "pkmgr pid {} still holds the lock and is running; stop it instead of forcing the lock",
+                    pid
+                );
+            }
+            match std::fs::remove_file(path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e).with_context(|| format!("Failed to remove lock file {}", path.display())),
+            }
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+
+        if file.try_lock_exclusive().is_err() {
+            let mut holder = String::new();
+            let _ = file.read_to_string(&mut holder);
+            let mut lines = holder.lines();
+            let pid = lines.next().filter(|pid| !pid.is_empty()).unwrap_or("unknown");
+            let held_for = lines.next().map(|op| format!(", {}", op)).unwrap_or_default();
+            return Err(LockHeld(format!(
+                "Another pkmgr operation is in progress (pid {}{}); if it is stuck, re-run with --force-unlock",
+                pid,
+                held_for
+            )).into());
+        }
+
+        file.set_len(0)?;
+        file.rewind()?;
+        writeln!(file, "{}\n{}", std::process::id(), operation)?;
+        file.flush()?;
+
+        Ok(Self { file })
+    }
+}
+
+/// PID on the first line of the lock file, if any
+fn recorded_pid(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.lines().next()?.trim().parse().ok()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else { return false };
+    // Signal 0 only checks; EPERM means it exists under another user
+    unsafe { libc::kill(pid, 0) == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM) }
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+impl Drop for OperationLock {
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_holder_is_refused_even_when_forced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("pkmgr.lock");
+
+        let first = OperationLock::acquire_at(&path, "install", false).unwrap();
+        let err = OperationLock::acquire_at(&path, "remove", false).err().unwrap().to_string();
+        assert!(err.contains(&format!("pid {}, install", std::process::id())), "{}", err);
+
+        let forced = OperationLock::acquire_at(&path, "remove", true).err().unwrap().to_string();
+        assert!(forced.contains("still holds the lock"), "{}", forced);
+        drop(first);
+        assert!(OperationLock::acquire_at(&path, "update", false).is_ok());
+    }
+}
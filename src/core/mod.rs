@@ -1,10 +1,14 @@
 pub mod config;
 pub mod detector;
+pub mod exit;
+pub mod lock;
 pub mod platform;
 pub mod transaction;
 pub mod normalize;
 pub mod privilege;
 pub mod traits;
 pub mod normalizer;
+pub mod paths;
+pub mod sbom;
 
 pub use traits::*;
\ No newline at end of file
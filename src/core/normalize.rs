@@ -53,7 +53,7 @@ impl PackageNormalizer {
             if !name.contains("python3") {
                 variations.push(name.replace("python", "python3"));
             }
-            if self.package_manager == "apt" || self.package_manager == "dnf" {
+            if matches!(self.package_manager.as_str(), "apt" | "dnf" | "yum") {
                 // Add versioned variants
                 variations.push(format!("{}-3", name));
                 variations.push(format!("python3-{}", name.strip_prefix("python-").unwrap_or(name)));
@@ -109,7 +109,7 @@ impl PackageNormalizer {
             apt: Some(vec!["code".to_string()]), // with repo
             dnf: Some(vec!["code".to_string()]), // with repo
             pacman: Some(vec!["visual-studio-code-bin".to_string()]), // AUR
-            brew: Some(vec!["visual-studio-code".to_string()]),
+            brew: Some(vec!["homebrew/cask/visual-studio-code".to_string()]), // cask
             winget: Some(vec!["Microsoft.VisualStudioCode".to_string()]),
             choco: Some(vec!["vscode".to_string()]),
             scoop: Some(vec!["vscode".to_string()]),
@@ -123,7 +123,7 @@ impl PackageNormalizer {
             apt: Some(vec!["code".to_string()]),
             dnf: Some(vec!["code".to_string()]),
             pacman: Some(vec!["visual-studio-code-bin".to_string()]),
-            brew: Some(vec!["visual-studio-code".to_string()]),
+            brew: Some(vec!["homebrew/cask/visual-studio-code".to_string()]), // cask
             winget: Some(vec!["Microsoft.VisualStudioCode".to_string()]),
             choco: Some(vec!["vscode".to_string()]),
             scoop: Some(vec!["vscode".to_string()]),
@@ -137,7 +137,7 @@ impl PackageNormalizer {
             apt: Some(vec!["google-chrome-stable".to_string()]), // with repo
             dnf: Some(vec!["google-chrome-stable".to_string()]), // with repo
             pacman: Some(vec!["google-chrome".to_string()]), // AUR
-            brew: Some(vec!["google-chrome".to_string()]),
+            brew: Some(vec!["homebrew/cask/google-chrome".to_string()]), // cask
             winget: Some(vec!["Google.Chrome".to_string()]),
             choco: Some(vec!["googlechrome".to_string()]),
             scoop: Some(vec!["googlechrome".to_string()]),
@@ -202,6 +202,48 @@ impl PackageNormalizer {
             pkgin: Some(vec!["redis".to_string()]),
         });
 
+        // curl
+        self.add_mapping("curl", DistributionMappings {
+            apt: Some(vec!["curl".to_string()]),
+            dnf: Some(vec!["curl".to_string()]),
+            pacman: Some(vec!["curl".to_string()]),
+            brew: Some(vec!["curl".to_string()]),
+            winget: Some(vec!["cURL.cURL".to_string()]),
+            choco: Some(vec!["curl".to_string()]),
+            scoop: Some(vec!["curl".to_string()]),
+            pkg: Some(vec!["curl".to_string()]),
+            pkg_add: Some(vec!["curl".to_string()]),
+            pkgin: Some(vec!["curl".to_string()]),
+        });
+
+        // GnuPG
+        self.add_mapping("gpg", DistributionMappings {
+            apt: Some(vec!["gnupg".to_string()]),
+            dnf: Some(vec!["gnupg2".to_string()]),
+            pacman: Some(vec!["gnupg".to_string()]),
+            brew: Some(vec!["gnupg".to_string()]),
+            winget: Some(vec!["GnuPG.GnuPG".to_string()]),
+            choco: Some(vec!["gnupg".to_string()]),
+            scoop: Some(vec!["gpg".to_string()]),
+            pkg: Some(vec!["gnupg".to_string()]),
+            pkg_add: Some(vec!["gnupg".to_string()]),
+            pkgin: Some(vec!["gnupg".to_string()]),
+        });
+
+        // CA certificates; Windows and OpenBSD ship their own store
+        self.add_mapping("ca-certificates", DistributionMappings {
+            apt: Some(vec!["ca-certificates".to_string()]),
+            dnf: Some(vec!["ca-certificates".to_string()]),
+            pacman: Some(vec!["ca-certificates".to_string()]),
+            brew: Some(vec!["ca-certificates".to_string()]),
+            winget: Some(Vec::new()),
+            choco: Some(Vec::new()),
+            scoop: Some(Vec::new()),
+            pkg: Some(vec!["ca_root_nss".to_string()]),
+            pkg_add: Some(Vec::new()),
+            pkgin: Some(vec!["mozilla-rootcerts".to_string()]),
+        });
+
         // Nginx
         self.add_mapping("nginx", DistributionMappings {
             apt: Some(vec!["nginx".to_string()]),
@@ -454,6 +496,17 @@ mod tests {
         assert!(result.contains(&"podman".to_string()));
     }
 
+    #[test]
+    fn test_gui_apps_map_to_casks_on_homebrew() {
+        let normalizer = PackageNormalizer::new();
+
+        let result = normalizer.normalize("vscode", &PackageManager::Homebrew).unwrap();
+        assert_eq!(result, vec!["homebrew/cask/visual-studio-code".to_string()]);
+
+        let result = normalizer.normalize("chrome", &PackageManager::Homebrew).unwrap();
+        assert_eq!(result, vec!["homebrew/cask/google-chrome".to_string()]);
+    }
+
     #[test]
     fn test_validate_package_name() {
         let normalizer = PackageNormalizer::new();
@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Where pkmgr keeps its files. `$PKMGR_HOME` relocates everything under one
+/// root; otherwise the XDG base directories apply, each with a pkmgr/ subdir
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    /// config.toml, cache.toml, repos.toml and profiles
+    pub config_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    /// Installed language versions and other managed data
+    pub data_dir: PathBuf,
+    /// Logs and last-error records
+    pub state_dir: PathBuf,
+}
+
+impl Paths {
+    /// Layout for this process; --config or $PKMGR_CONFIG moves the config
+    /// directory to the config file's parent
+    pub fn resolve() -> Result<Self> {
+        let mut paths = Self::from_env(|key| std::env::var_os(key), dirs::home_dir())?;
+        if let Some(config_file) = super::config::config_path_override() {
+            paths.config_dir = config_file.parent()
+                .map(|dir| dir.to_path_buf())
+                .with_context(|| format!("Config path {} has no parent directory", config_file.display()))?;
+        } else if std::env::var_os("PKMGR_HOME").is_none_or(|root| root.is_empty()) {
+            if let Some(legacy) = dirs::config_dir().map(|dir| dir.join("pkmgr")) {
+                paths.config_dir = with_legacy_config(paths.config_dir, legacy);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Layout from `var` lookups and the home directory. Empty values are
+    /// unset, and relative XDG values are ignored as the spec requires
+    pub fn from_env(var: impl Fn(&str) -> Option<OsString>, home: Option<PathBuf>) -> Result<Self> {
+        let var = |key: &str| var(key).filter(|value| !value.is_empty()).map(PathBuf::from);
+
+        if let Some(root) = var("PKMGR_HOME") {
+            let root = std::path::absolute(&root).unwrap_or(root);
+            return Ok(Self {
+                config_dir: root.join("config"),
+                cache_dir: root.join("cache"),
+                data_dir: root.join("data"),
+                state_dir: root.join("state"),
+            });
+        }
+
+        let home = home.context("Failed to get home directory (set PKMGR_HOME to choose pkmgr's directory)")?;
+        let xdg = |key: &str, fallback: &str| {
+            var(key)
+                .filter(|dir| dir.is_absolute())
+                .unwrap_or_else(|| home.join(fallback))
+                .join("pkmgr")
+        };
+
+        Ok(Self {
+            config_dir: xdg("XDG_CONFIG_HOME", ".config"),
+            cache_dir: xdg("XDG_CACHE_HOME", ".cache"),
+            data_dir: xdg("XDG_DATA_HOME", ".local/share"),
+            state_dir: xdg("XDG_STATE_HOME", ".local/state"),
+        })
+    }
+
+    /// Root of pkmgr-managed versions of `language`
+    pub fn language_dir(&self, language: &str) -> PathBuf {
+        self.data_dir.join("languages").join(language)
+    }
+}
+
+/// Older releases kept the config in the platform config directory
+/// (`~/Library/Application Support/pkmgr` on macOS, `%APPDATA%\pkmgr` on
+/// Windows). Keep using it until the XDG directory exists
+fn with_legacy_config(config_dir: PathBuf, legacy: PathBuf) -> PathBuf {
+    if !config_dir.exists() && legacy.is_dir() {
+        legacy
+    } else {
+        config_dir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn paths(vars: &[(&str, &str)]) -> Paths {
+        let vars: HashMap<String, OsString> = vars.iter()
+            .map(|(key, value)| (key.to_string(), OsString::from(value)))
+            .collect();
+        Paths::from_env(|key| vars.get(key).cloned(), Some(PathBuf::from("/home/user"))).unwrap()
+    }
+
+    #[test]
+    fn pkmgr_home_relocates_every_directory() {
+        let xdg = [
+            ("XDG_CONFIG_HOME", "/xdg/config"),
+            ("XDG_CACHE_HOME", "/xdg/cache"),
+            ("XDG_DATA_HOME", "relative/data"),
+        ];
+        let defaults = paths(&xdg);
+        assert_eq!(defaults.config_dir, PathBuf::from("/xdg/config/pkmgr"));
+        assert_eq!(defaults.cache_dir, PathBuf::from("/xdg/cache/pkmgr"));
+        assert_eq!(defaults.data_dir, PathBuf::from("/home/user/.local/share/pkmgr"));
+        assert_eq!(defaults.state_dir, PathBuf::from("/home/user/.local/state/pkmgr"));
+
+        let relocated = paths(&[xdg.as_slice(), &[("PKMGR_HOME", "/opt/pkmgr")]].concat());
+        for dir in [&relocated.config_dir, &relocated.cache_dir, &relocated.data_dir, &relocated.state_dir] {
+            assert!(dir.starts_with("/opt/pkmgr"), "{} is outside PKMGR_HOME", dir.display());
+        }
+        assert_eq!(relocated.language_dir("node"), PathBuf::from("/opt/pkmgr/data/languages/node"));
+    }
+
+    #[test]
+    fn legacy_config_dir_is_used_until_the_new_one_exists() {
+        let home = tempfile::tempdir().unwrap();
+        let config_dir = home.path().join(".config/pkmgr");
+        let legacy = home.path().join("Library/Application Support/pkmgr");
+        assert_eq!(with_legacy_config(config_dir.clone(), legacy.clone()), config_dir);
+
+        std::fs::create_dir_all(&legacy).unwrap();
+        assert_eq!(with_legacy_config(config_dir.clone(), legacy.clone()), legacy);
+
+        std::fs::create_dir_all(&config_dir).unwrap();
+        assert_eq!(with_legacy_config(config_dir.clone(), legacy), config_dir);
+    }
+}
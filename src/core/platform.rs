@@ -1,5 +1,6 @@
 use std::env;
 use anyhow::Result;
+use once_cell::sync::OnceCell;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Platform {
@@ -42,6 +43,67 @@ pub enum PackageManager {
     Scoop,
 }
 
+/// Manager chosen with --manager or defaults.package_manager, set once at startup
+static MANAGER_OVERRIDE: OnceCell<PackageManager> = OnceCell::new();
+
+/// Make `manager` the primary package manager for this run, refusing one
+/// that is not installed
+pub fn set_manager_override(manager: PackageManager) -> Result<()> {
+    if !PlatformInfo::command_exists(manager.command()) {
+        anyhow::bail!("Package manager '{}' was requested but '{}' is not installed", manager, manager.command());
+    }
+    let _ = MANAGER_OVERRIDE.set(manager);
+    Ok(())
+}
+
+impl PackageManager {
+    /// Parse a manager name as shown by pkmgr (`apt`, `brew`, `choco`, ...)
+    /// or its long form (`homebrew`, `chocolatey`, ...)
+    pub fn from_name(name: &str) -> Result<Self> {
+        Ok(match name.to_lowercase().as_str() {
+            "apt" | "apt-get" => PackageManager::Apt,
+            "dnf" => PackageManager::Dnf,
+            "yum" => PackageManager::Yum,
+            "pacman" => PackageManager::Pacman,
+            "zypper" => PackageManager::Zypper,
+            "apk" => PackageManager::Apk,
+            "emerge" | "portage" => PackageManager::Emerge,
+            "xbps" => PackageManager::Xbps,
+            "pkg" => PackageManager::Pkg,
+            "pkg_add" => PackageManager::PkgAdd,
+            "pkgin" => PackageManager::Pkgin,
+            "brew" | "homebrew" => PackageManager::Homebrew,
+            "port" | "macports" => PackageManager::MacPorts,
+            "winget" => PackageManager::Winget,
+            "choco" | "chocolatey" => PackageManager::Chocolatey,
+            "scoop" => PackageManager::Scoop,
+            _ => anyhow::bail!("Unknown package manager '{}'", name),
+        })
+    }
+
+    /// Executable whose presence means the manager is installed
+    pub fn command(&self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt",
+            PackageManager::Dnf => "dnf",
+            PackageManager::Yum => "yum",
+            PackageManager::Pacman => "pacman",
+            PackageManager::Zypper => "zypper",
+            PackageManager::Apk => "apk",
+            PackageManager::Emerge => "emerge",
+            PackageManager::Xbps => "xbps-install",
+            PackageManager::Pkg => "pkg",
+            PackageManager::PkgAdd => "pkg_add",
+            PackageManager::Pkgin => "pkgin",
+            PackageManager::Homebrew => "brew",
+            PackageManager::MacPorts => "port",
+            PackageManager::Winget => "winget",
+            PackageManager::Chocolatey => "choco",
+            PackageManager::Scoop => "scoop",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PlatformInfo {
     pub platform: Platform,
@@ -120,7 +182,17 @@ impl PlatformInfo {
         }
     }
 
+    /// Installed managers in order of preference; an override moves to the front
     fn detect_package_managers(platform: &Platform) -> Vec<PackageManager> {
+        let mut managers = Self::detect_native_managers(platform);
+        if let Some(chosen) = MANAGER_OVERRIDE.get() {
+            managers.retain(|manager| manager != chosen);
+            managers.insert(0, chosen.clone());
+        }
+        managers
+    }
+
+    fn detect_native_managers(platform: &Platform) -> Vec<PackageManager> {
         match platform {
             Platform::Linux => Self::detect_linux_package_managers(),
             Platform::MacOs => Self::detect_macos_package_managers(),
@@ -1,12 +1,289 @@
 use anyhow::{Context, Result, bail};
-use std::process::Command;
+use once_cell::sync::OnceCell;
+use std::process::{Command, Stdio};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::os::unix::fs::PermissionsExt;
 
+use crate::core::config::Config;
+use crate::core::exit::UsageError;
+use crate::core::paths::Paths;
 use crate::core::platform::{Platform, PlatformInfo};
 use crate::ui::output::Output;
-use crate::ui::prompt::Prompt;
+use crate::ui::prompt::{self, Prompt};
+use crate::utils::logging::LoggedCommand;
+
+/// How privileged commands are run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escalation {
+    Root,   // Already root, run directly
+    Sudo,
+    Doas,
+    Pkexec,
+}
+
+impl Escalation {
+    /// Escalation tools in auto-detection order
+    pub const TOOLS: [Escalation; 3] = [Escalation::Sudo, Escalation::Doas, Escalation::Pkexec];
+
+    /// Parse `privilege.escalation`; "auto" means detect
+    pub fn from_config(value: &str) -> Result<Option<Self>> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "auto" => Ok(None),
+            "sudo" => Ok(Some(Escalation::Sudo)),
+            "doas" => Ok(Some(Escalation::Doas)),
+            "pkexec" => Ok(Some(Escalation::Pkexec)),
+            other => bail!("Unknown privilege.escalation '{}' (expected auto, sudo, doas or pkexec)", other),
+        }
+    }
+
+    pub fn program(&self) -> Option<&'static str> {
+        match self {
+            Escalation::Root => None,
+            Escalation::Sudo => Some("sudo"),
+            Escalation::Doas => Some("doas"),
+            Escalation::Pkexec => Some("pkexec"),
+        }
+    }
+
+    fn is_installed(&self) -> bool {
+        self.program().is_none_or(|program| which::which(program).is_ok())
+    }
+
+    /// Whether the tool can run a command without prompting
+    pub fn is_passwordless(&self) -> bool {
+        match self {
+            Escalation::Root => true,
+            Escalation::Sudo | Escalation::Doas => Command::new(self.program().unwrap_or_default())
+                .args(["-n", "true"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false),
+            // pkexec has no non-interactive probe
+            Escalation::Pkexec => false,
+        }
+    }
+}
+
+/// Configured `privilege.escalation`, set once at startup
+static ESCALATION: OnceCell<Option<Escalation>> = OnceCell::new();
+
+pub fn set_escalation(preference: Option<Escalation>) {
+    let _ = ESCALATION.set(preference);
+}
+
+pub fn is_root() -> bool {
+    #[cfg(unix)]
+    {
+        unsafe { libc::geteuid() == 0 }
+    }
+
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// Pick the escalation strategy: root first, then the configured tool, then
+/// the first of sudo, doas and pkexec that is installed
+pub fn escalation() -> Result<Escalation> {
+    if is_root() {
+        return Ok(Escalation::Root);
+    }
+
+    if let Some(preferred) = ESCALATION.get().copied().flatten() {
+        if !preferred.is_installed() {
+            bail!(
+                "privilege.escalation is set to '{}' but it is not installed",
+                preferred.program().unwrap_or_default()
+            );
+        }
+        return Ok(preferred);
+    }
+
+    Escalation::TOOLS.into_iter()
+        .find(Escalation::is_installed)
+        .context("Root privileges are required but none of sudo, doas or pkexec is available; re-run pkmgr as root")
+}
+
+/// Build a command that runs `program` with root privileges. sudo and doas
+/// run non-interactively so a missing password fails instead of hanging
+pub fn privileged_command(program: &str) -> Result<Command> {
+    let escalation = escalation()?;
+    Ok(match escalation.program() {
+        Some(tool) => {
+            let mut command = Command::new(tool);
+            if matches!(escalation, Escalation::Sudo | Escalation::Doas) {
+                command.arg("-n");
+            }
+            command.arg(program);
+            command
+        }
+        None => Command::new(program),
+    })
+}
+
+/// Run `program` with root privileges and return its stdout
+pub fn run_privileged(program: &str, args: &[&str]) -> Result<String> {
+    let output = privileged_command(program)?
+        .args(args)
+        .logged_output()
+        .with_context(|| format!("Failed to execute {}", program))?;
+
+    if !output.status.success() {
+        bail!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Where `--user` / `--global` send an install, given who is actually
+/// running pkmgr
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstallTarget {
+    /// Neither flag; the configured paths apply
+    Configured,
+    /// User space; `owner` is the sudo caller when running as root, so
+    /// files land in their home instead of root's
+    User { owner: Option<TargetUser> },
+    /// /usr/local; `escalate` when the current user is not root
+    System { escalate: bool },
+}
+
+/// Resolve `--user` and `--global` against the current uid
+pub fn install_target(user: bool, global: bool) -> Result<InstallTarget> {
+    resolve_install_target(user, global, is_root(), TargetUser::from_sudo)
+}
+
+fn resolve_install_target(
+    user: bool,
+    global: bool,
+    root: bool,
+    sudo_user: impl FnOnce() -> Option<TargetUser>,
+) -> Result<InstallTarget> {
+    match (user, global) {
+        (true, true) => Err(UsageError("--user and --global cannot be used together".to_string()).into()),
+        (true, false) => Ok(InstallTarget::User { owner: if root { sudo_user() } else { None } }),
+        (false, true) if root => Ok(InstallTarget::System { escalate: false }),
+        (false, true) => {
+            escalation().context("--global installs into /usr/local and needs root")?;
+            Ok(InstallTarget::System { escalate: true })
+        }
+        (false, false) => Ok(InstallTarget::Configured),
+    }
+}
+
+/// `config` pointed at the system-wide prefix: installs under /usr/local and
+/// their records under /usr/local/share/pkmgr, where every user can read them
+pub fn system_config(config: &Config) -> Config {
+    let mut config = config.clone();
+    config.paths.install_dir = InstallLocation::System.base_dir().display().to_string();
+    config.paths.data_dir = InstallLocation::System.share_dir().display().to_string();
+    config
+}
+
+/// The account that invoked `sudo pkmgr`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetUser {
+    pub name: String,
+    pub home: PathBuf,
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl TargetUser {
+    /// SUDO_USER looked up in the password database; None when unset or root
+    pub fn from_sudo() -> Option<Self> {
+        let name = std::env::var("SUDO_USER").ok().filter(|name| !name.is_empty() && name != "root")?;
+        Self::lookup(&name)
+    }
+
+    /// `config` with `~` in its paths meaning this user's home instead of
+    /// root's, and installs going to their ~/.local
+    pub fn config(&self, config: &Config) -> Config {
+        let home = self.home.display().to_string();
+        let expand = |path: &str| shellexpand::tilde_with_context(path, || Some(home.clone())).to_string();
+        // Unset directories follow this user's default layout, not root's
+        let layout = Paths::from_env(|_| None, Some(self.home.clone())).ok();
+        let owned = |value: &str, pick: fn(&Paths) -> &PathBuf| match (value.is_empty(), &layout) {
+            (true, Some(layout)) => pick(layout).display().to_string(),
+            _ => expand(value),
+        };
+
+        let mut config = config.clone();
+        config.paths.install_dir = expand("~/.local");
+        config.paths.data_dir = owned(&config.paths.data_dir, |layout| &layout.data_dir);
+        config.paths.cache_dir = owned(&config.paths.cache_dir, |layout| &layout.cache_dir);
+        config
+    }
+
+    #[cfg(unix)]
+    fn lookup(name: &str) -> Option<Self> {
+        let c_name = std::ffi::CString::new(name).ok()?;
+        // getpwnam returns a pointer into static storage; copy out before
+        // anything else can call it
+        unsafe {
+            let entry = libc::getpwnam(c_name.as_ptr());
+            if entry.is_null() {
+                return None;
+            }
+            let home = std::ffi::CStr::from_ptr((*entry).pw_dir).to_string_lossy().into_owned();
+            Some(Self { name: name.to_string(), home: PathBuf::from(home), uid: (*entry).pw_uid, gid: (*entry).pw_gid })
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn lookup(_name: &str) -> Option<Self> {
+        None
+    }
+
+    /// Give `path`, everything below it and its parents up to the home
+    /// directory to this user, undoing root ownership from the install
+    #[cfg(unix)]
+    pub fn adopt(&self, path: &Path) -> Result<()> {
+        if !path.starts_with(&self.home) || !path.exists() {
+            return Ok(());
+        }
+        let chown = |path: &Path| std::os::unix::fs::lchown(path, Some(self.uid), Some(self.gid))
+            .with_context(|| format!("Failed to give {} to {}", path.display(), self.name));
+
+        for entry in walkdir::WalkDir::new(path) {
+            chown(entry?.path())?;
+        }
+        for parent in path.ancestors().skip(1).take_while(|parent| *parent != self.home) {
+            chown(parent)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    pub fn adopt(&self, _path: &Path) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Run this pkmgr invocation again through the escalation tool, which may
+/// prompt for a password
+pub fn rerun_privileged() -> Result<()> {
+    let escalation = escalation()?;
+    let Some(tool) = escalation.program() else {
+        bail!("Already running as root");
+    };
+    let exe = std::env::current_exe().context("Failed to locate the pkmgr executable")?;
+    let status = Command::new(tool)
+        .arg(exe)
+        .args(std::env::args_os().skip(1))
+        .logged_status()
+        .with_context(|| format!("Failed to execute {}", tool))?;
+
+    if !status.success() {
+        bail!("pkmgr under {} failed ({})", tool, status);
+    }
+    Ok(())
+}
 
 /// Privilege escalation detection and management
 pub struct PrivilegeManager {
@@ -31,7 +308,7 @@ impl PrivilegeManager {
     pub fn is_root(&self) -> bool {
         #[cfg(unix)]
         {
-            unsafe { libc::geteuid() == 0 }
+            is_root()
         }
 
         #[cfg(windows)]
@@ -59,22 +336,20 @@ impl PrivilegeManager {
         }
     }
 
-    /// Check sudo access on Unix systems
+    /// Check escalation access on Unix systems
     fn check_sudo_access(&self) -> PrivilegeStatus {
-        // First check if sudo exists
-        if which::which("sudo").is_err() {
-            return PrivilegeStatus::None;
-        }
+        let escalation = match escalation() {
+            Ok(escalation) => escalation,
+            Err(_) => return PrivilegeStatus::None,
+        };
 
-        // Test passwordless sudo
-        let test = Command::new("sudo")
-            .args(&["-n", "true"])
-            .output();
+        if escalation.is_passwordless() {
+            return PrivilegeStatus::Passwordless;
+        }
 
-        if let Ok(output) = test {
-            if output.status.success() {
-                return PrivilegeStatus::Passwordless;
-            }
+        // pkexec authenticates through polkit rather than group membership
+        if escalation == Escalation::Pkexec {
+            return PrivilegeStatus::Available;
         }
 
         // Check if user is in sudo/wheel/admin group
@@ -90,7 +365,7 @@ impl PrivilegeManager {
         #[cfg(unix)]
         {
             let groups_output = Command::new("groups")
-                .output();
+                .logged_output();
 
             if let Ok(output) = groups_output {
                 let groups = String::from_utf8_lossy(&output.stdout);
@@ -110,7 +385,7 @@ impl PrivilegeManager {
         // This is a simplified check
         Command::new("net")
             .args(&["session"])
-            .output()
+            .logged_output()
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
@@ -141,10 +416,8 @@ impl PrivilegeManager {
                 Ok(command.to_vec())
             }
             PrivilegeStatus::Passwordless => {
-                // Can use sudo without password
-                let mut escalated = vec!["sudo".to_string()];
-                escalated.extend_from_slice(command);
-                Ok(escalated)
+                // Can escalate without password
+                self.prefix_escalation(command)
             }
             PrivilegeStatus::Available => {
                 // Need password for sudo
@@ -165,21 +438,26 @@ impl PrivilegeManager {
         }
 
         // Check if TTY is available
-        if !atty::is(atty::Stream::Stdin) {
+        if !prompt::is_interactive() {
             bail!("No TTY available for password prompt. Cannot escalate privileges.");
         }
 
         self.output.warn(&format!("⚠️  {} requires administrator privileges", operation));
 
-        if self.prompt.confirm("Proceed with sudo (will prompt for password)?")? {
-            let mut escalated = vec!["sudo".to_string()];
-            escalated.extend_from_slice(command);
-            Ok(escalated)
+        let tool = escalation()?.program().unwrap_or("sudo");
+        if self.prompt.confirm(&format!("Proceed with {} (will prompt for password)?", tool))? {
+            self.prefix_escalation(command)
         } else {
             bail!("Operation cancelled - requires privileges");
         }
     }
 
+    fn prefix_escalation(&self, command: &[String]) -> Result<Vec<String>> {
+        let mut escalated: Vec<String> = escalation()?.program().map(str::to_string).into_iter().collect();
+        escalated.extend_from_slice(command);
+        Ok(escalated)
+    }
+
     /// Handle case where no privileges are available
     fn handle_no_privileges(&self, operation: &str) -> Result<Vec<String>> {
         self.output.error(&format!("❌ {} requires administrator privileges", operation));
@@ -277,7 +555,7 @@ impl PrivilegeManager {
         let validation = Command::new("visudo")
             .args(&["-c", "-f"])
             .arg(temp_file.path())
-            .output()
+            .logged_output()
             .context("Failed to run visudo")?;
 
         if !validation.status.success() {
@@ -366,13 +644,13 @@ impl PrivilegeManager {
                 self.output.success("✅ Running as root");
             }
             PrivilegeStatus::Passwordless => {
-                self.output.success("✅ Passwordless sudo available");
+                self.output.success(&format!("✅ Passwordless {} available", self.escalation_name()));
             }
             PrivilegeStatus::Available => {
-                self.output.info("ℹ️  Sudo available (password required)");
+                self.output.info(&format!("ℹ️  {} available (password required)", self.escalation_name()));
             }
             PrivilegeStatus::None => {
-                self.output.warn("⚠️  No sudo privileges");
+                self.output.warn("⚠️  No sudo, doas or pkexec privileges");
             }
         }
 
@@ -386,6 +664,10 @@ impl PrivilegeManager {
         Ok(())
     }
 
+    fn escalation_name(&self) -> &'static str {
+        escalation().ok().and_then(|e| e.program()).unwrap_or("sudo")
+    }
+
     /// Determine installation location based on privileges
     pub fn determine_install_location(&self, prefer_user: bool) -> InstallLocation {
         if prefer_user {
@@ -396,7 +678,7 @@ impl PrivilegeManager {
             PrivilegeStatus::Root | PrivilegeStatus::Passwordless => InstallLocation::System,
             PrivilegeStatus::Available => {
                 // Ask user if they want system or user install
-                if !self.is_ci_environment() && atty::is(atty::Stream::Stdin) {
+                if !self.is_ci_environment() && prompt::is_interactive() {
                     if let Ok(use_system) = self.prompt.confirm("Install system-wide (requires sudo)?") {
                         if use_system {
                             InstallLocation::System
@@ -465,10 +747,44 @@ impl InstallLocation {
         match self {
             InstallLocation::System => PathBuf::from("/etc/pkmgr"),
             InstallLocation::User => {
-                dirs::config_dir()
-                    .map(|c| c.join("pkmgr"))
-                    .unwrap_or_else(|| PathBuf::from("~/.config/pkmgr"))
+                crate::core::config::Config::get_config_dir()
+                    .unwrap_or_else(|_| PathBuf::from("~/.config/pkmgr"))
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_and_global_conflict_is_a_usage_error() {
+        let conflict = install_target(true, true).unwrap_err();
+        assert!(conflict.is::<UsageError>());
+        assert_eq!(install_target(false, false).unwrap(), InstallTarget::Configured);
+    }
+
+    #[test]
+    fn root_installs_follow_sudo_user_or_system_prefix() {
+        let alice = TargetUser { name: "alice".to_string(), home: PathBuf::from("/home/alice"), uid: 1000, gid: 1000 };
+
+        let target = resolve_install_target(true, false, true, || Some(alice.clone())).unwrap();
+        assert_eq!(target, InstallTarget::User { owner: Some(alice.clone()) });
+        let target = resolve_install_target(true, false, false, || Some(alice.clone())).unwrap();
+        assert_eq!(target, InstallTarget::User { owner: None });
+
+        let mut config = Config::default();
+        config.paths.data_dir = "~/.local/share/pkmgr".to_string();
+        let owned = alice.config(&config);
+        assert_eq!(owned.get_install_dir().unwrap(), PathBuf::from("/home/alice/.local"));
+        assert_eq!(owned.get_data_dir().unwrap(), PathBuf::from("/home/alice/.local/share/pkmgr"));
+        assert_eq!(alice.config(&Config::default()).get_cache_dir().unwrap(), PathBuf::from("/home/alice/.cache/pkmgr"));
+
+        let target = resolve_install_target(false, true, true, || Some(alice.clone())).unwrap();
+        assert_eq!(target, InstallTarget::System { escalate: false });
+        let system = system_config(&config);
+        assert_eq!(system.get_install_dir().unwrap(), PathBuf::from("/usr/local"));
+        assert_eq!(system.get_data_dir().unwrap(), PathBuf::from("/usr/local/share/pkmgr"));
+    }
+}
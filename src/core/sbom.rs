@@ -0,0 +1,177 @@
+use serde::Serialize;
+
+/// CycloneDX spec version of the documents pkmgr writes
+const SPEC_VERSION: &str = "1.5";
+
+/// Where an inventoried component came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentSource<'a> {
+    /// A system package manager, by pkmgr name (apt, dnf, pacman, ...)
+    System { manager: &'a str, distro: Option<&'a str> },
+    /// A GitHub release binary, by owner/repo
+    Binary { repository: &'a str },
+    /// A pkmgr-managed language version
+    Language,
+}
+
+/// One inventory entry
+#[derive(Debug, Clone, Serialize)]
+pub struct Component {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    name: String,
+    version: String,
+    purl: String,
+    properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Property {
+    name: &'static str,
+    value: String,
+}
+
+impl Component {
+    pub fn new(name: &str, version: &str, source: ComponentSource) -> Self {
+        let (kind, purl, origin) = match source {
+            ComponentSource::System { manager, distro } => {
+                ("library", system_purl(manager, distro, name, version), manager.to_string())
+            }
+            ComponentSource::Binary { repository } => {
+                let purl = match repository.split_once('/') {
+                    Some((owner, repo)) => format!("pkg:github/{}/{}@{}", encode(owner), encode(repo), encode(version)),
+                    None => format!("pkg:generic/{}@{}", encode(name), encode(version)),
+                };
+                ("application", purl, "binary".to_string())
+            }
+            ComponentSource::Language => {
+                ("framework", format!("pkg:generic/{}@{}", encode(name), encode(version)), "language".to_string())
+            }
+        };
+
+        Self {
+            kind,
+            bom_ref: purl.clone(),
+            name: name.to_string(),
+            version: version.to_string(),
+            purl,
+            properties: vec![Property { name: "pkmgr:source", value: origin }],
+        }
+    }
+
+    pub fn purl(&self) -> &str {
+        &self.purl
+    }
+}
+
+/// Package URL for a system package; managers without a registered purl
+/// type are namespaced under `generic`
+fn system_purl(manager: &str, distro: Option<&str>, name: &str, version: &str) -> String {
+    let (purl_type, default_namespace) = match manager {
+        "apt" => ("deb", "debian"),
+        "dnf" => ("rpm", "fedora"),
+        "yum" => ("rpm", "centos"),
+        "pacman" => ("alpm", "arch"),
+        "apk" => ("apk", "alpine"),
+        _ => return format!("pkg:generic/{}/{}@{}", encode(manager), encode(name), encode(version)),
+    };
+    format!("pkg:{}/{}/{}@{}", purl_type, encode(distro.unwrap_or(default_namespace)), encode(name), encode(version))
+}
+
+/// Percent-encode everything outside the purl unreserved set, so epochs
+/// (`1:2.3`) and build metadata (`+b1`) stay valid
+fn encode(segment: &str) -> String {
+    segment.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom<'a> {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    serial_number: String,
+    version: u32,
+    metadata: Metadata,
+    components: &'a [Component],
+}
+
+#[derive(Serialize)]
+struct Metadata {
+    timestamp: String,
+    tools: Tools,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    component: Option<Host>,
+}
+
+#[derive(Serialize)]
+struct Tools {
+    components: Vec<Host>,
+}
+
+#[derive(Serialize)]
+struct Host {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+}
+
+/// A CycloneDX JSON document listing `components`, describing the host OS
+/// when known
+pub fn cyclonedx(components: &[Component], os: Option<(&str, Option<&str>)>) -> serde_json::Result<String> {
+    let bom = Bom {
+        bom_format: "CycloneDX",
+        spec_version: SPEC_VERSION,
+        serial_number: format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+        version: 1,
+        metadata: Metadata {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            tools: Tools {
+                components: vec![Host {
+                    kind: "application",
+                    name: "pkmgr".to_string(),
+                    version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                }],
+            },
+            component: os.map(|(name, version)| Host {
+                kind: "operating-system",
+                name: name.to_string(),
+                version: version.map(String::from),
+            }),
+        },
+        components,
+    };
+    serde_json::to_string_pretty(&bom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_purls_and_a_cyclonedx_document() {
+        let deb = Component::new("libc6", "2.36-9+deb12u4", ComponentSource::System { manager: "apt", distro: Some("debian") });
+        assert_eq!(deb.purl, "pkg:deb/debian/libc6@2.36-9%2Bdeb12u4");
+
+        let rpm = Component::new("bash", "1:5.2.26", ComponentSource::System { manager: "dnf", distro: None });
+        assert_eq!(rpm.purl, "pkg:rpm/fedora/bash@1%3A5.2.26");
+
+        let binary = Component::new("rg", "14.1.0", ComponentSource::Binary { repository: "BurntSushi/ripgrep" });
+        assert_eq!(binary.purl, "pkg:github/BurntSushi/ripgrep@14.1.0");
+
+        let doc: serde_json::Value = serde_json::from_str(&cyclonedx(&[deb, rpm, binary], Some(("debian", Some("12")))).unwrap()).unwrap();
+        assert_eq!(doc["bomFormat"], "CycloneDX");
+        assert_eq!(doc["specVersion"], SPEC_VERSION);
+        assert_eq!(doc["components"][2]["type"], "application");
+        assert_eq!(doc["components"][0]["bom-ref"], "pkg:deb/debian/libc6@2.36-9%2Bdeb12u4");
+        assert_eq!(doc["metadata"]["component"]["type"], "operating-system");
+    }
+}
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 /// Package information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageInfo {
     pub name: String,
     pub version: String,
@@ -11,15 +13,89 @@ pub struct PackageInfo {
     pub size: Option<u64>,
     pub installed: bool,
     pub source: String,
+    pub homepage: Option<String>,
+    pub license: Option<String>,
+    /// Names of the packages this one depends on; empty where the backend
+    /// does not report them
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Kind of package where a backend has several, e.g. Homebrew
+    /// "formula" or "cask"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
 }
 
 /// Search result structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
     pub packages: Vec<PackageInfo>,
     pub total_count: usize,
 }
 
+/// A newer version of an installed package available from a repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageUpdate {
+    pub name: String,
+    pub current: String,
+    pub candidate: String,
+    /// Repository, suite or tap the candidate comes from; empty if unknown
+    pub repo: String,
+}
+
+/// A mutating operation, for `simulate`
+#[derive(Debug, Clone, Copy)]
+pub enum Operation<'a> {
+    Install(&'a [String]),
+    Remove(&'a [String]),
+    Purge(&'a [String]),
+    /// None upgrades everything
+    Upgrade(Option<&'a [String]>),
+    UpgradeExcluding(&'a [String]),
+}
+
+/// What an operation would do to one package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Install,
+    Upgrade,
+    Downgrade,
+    Reinstall,
+    Remove,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ChangeKind::Install => "install",
+            ChangeKind::Upgrade => "upgrade",
+            ChangeKind::Downgrade => "downgrade",
+            ChangeKind::Reinstall => "reinstall",
+            ChangeKind::Remove => "remove",
+        })
+    }
+}
+
+/// One package change in a simulated transaction, dependencies included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedChange {
+    pub kind: ChangeKind,
+    pub name: String,
+    pub current: Option<String>,
+    pub version: Option<String>,
+}
+
+impl PlannedChange {
+    pub fn new(kind: ChangeKind, name: impl Into<String>) -> Self {
+        Self { kind, name: name.into(), current: None, version: None }
+    }
+
+    pub fn with_versions(mut self, current: Option<&str>, version: Option<&str>) -> Self {
+        self.current = current.map(String::from);
+        self.version = version.map(String::from);
+        self
+    }
+}
+
 /// Installation result
 #[derive(Debug, Clone)]
 pub struct InstallResult {
@@ -43,23 +119,76 @@ pub trait PackageManager: Send + Sync {
     /// Install packages
     async fn install(&self, packages: &[String]) -> Result<InstallResult>;
 
-    /// Remove packages
+    /// Remove packages, keeping their configuration files where the backend
+    /// distinguishes
     async fn remove(&self, packages: &[String]) -> Result<InstallResult>;
 
+    /// Remove packages together with their configuration files; the same as
+    /// remove for backends that do not keep configuration
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult>;
+
     /// Update package lists
     async fn update(&self) -> Result<()>;
 
     /// Upgrade packages
     async fn upgrade(&self, packages: Option<&[String]>) -> Result<InstallResult>;
 
+    /// Upgrade everything except the named packages, using the backend's
+    /// own exclude mechanism where it has one
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult>;
+
     /// List installed packages
     async fn list_installed(&self) -> Result<Vec<PackageInfo>>;
 
     /// Get package info
     async fn info(&self, package: &str) -> Result<Option<PackageInfo>>;
 
+    /// Names of the packages `package` directly depends on; errors when the
+    /// package is unknown or the backend cannot list dependencies
+    async fn dependencies(&self, package: &str) -> Result<Vec<String>>;
+
+    /// The package's changelog, newest entry first; None where the backend
+    /// does not ship changelogs
+    async fn changelog(&self, package: &str) -> Result<Option<String>>;
+
     /// Check if packages are installed
     async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>>;
+
+    /// Files installed by a package; empty if the package is not installed
+    async fn owned_files(&self, package: &str) -> Result<Vec<PathBuf>>;
+
+    /// Installed package that owns a file, if any
+    async fn owner_of_file(&self, path: &Path) -> Result<Option<String>>;
+
+    /// Packages in the repositories that would provide a file
+    async fn provides_file(&self, path: &str) -> Result<Vec<String>>;
+
+    /// Installed packages with a pending security update; errors where the
+    /// backend has no security metadata
+    async fn security_updates(&self) -> Result<Vec<String>>;
+
+    /// Installed packages with a newer version available, from the last
+    /// refreshed metadata
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>>;
+
+    /// Every change `operation` would make, from the backend's own
+    /// simulation without touching the system; errors where the backend
+    /// has no dry-run mode
+    async fn simulate(&self, operation: Operation<'_>) -> Result<Vec<PlannedChange>>;
+
+    /// Dependencies no installed package needs any more; errors where the
+    /// backend does not track why a package was installed
+    async fn orphans(&self) -> Result<Vec<String>>;
+
+    /// Remove exactly `orphans`, the list `orphans()` reported and the user
+    /// confirmed, so nothing that became orphaned since is removed unseen
+    async fn remove_orphans(&self, orphans: &[String]) -> Result<InstallResult>;
+
+    /// Bytes `clean_package_cache` would free
+    async fn package_cache_size(&self) -> Result<u64>;
+
+    /// Delete downloaded package files the backend keeps after installing
+    async fn clean_package_cache(&self) -> Result<()>;
 }
 
 /// Trait for language version managers
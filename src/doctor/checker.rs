@@ -1,18 +1,261 @@
 use anyhow::{Context, Result};
-use std::process::Command;
+use futures_util::stream::{self, StreamExt};
+use std::process::Stdio;
 use std::path::Path;
 use std::fs;
+use std::time::Duration;
 
 use crate::doctor::{CheckCategory, Finding, HealthReport, Severity, SystemInfo};
 use crate::core::platform::{Platform, PlatformInfo, Architecture};
 use crate::ui::output::Output;
 use crate::cache::manager::CacheManager;
 use crate::repos::manager::RepositoryManager;
+use crate::core::privilege::{self, Escalation};
+use crate::utils::download::{self, GitHubClient};
+use crate::core::config::Config;
+use crate::commands::binary;
+use crate::managers::PackageManagerFactory;
+
+/// Categories run by a default or --full check, in order of importance
+pub const DEFAULT_CATEGORIES: &[CheckCategory] = &[
+    CheckCategory::System,
+    CheckCategory::Packages,
+    CheckCategory::Storage,
+    CheckCategory::Network,
+    CheckCategory::Security,
+    CheckCategory::Repository,
+    CheckCategory::Languages,
+    CheckCategory::Binary,
+    CheckCategory::Cache,
+    CheckCategory::Configuration,
+    CheckCategory::Shell,
+];
+
+/// Categories checked at the same time
+const MAX_PARALLEL_CHECKS: usize = 4;
+
+/// How long one category may run before it is reported as timed out
+const CHECK_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Resolved to check DNS
+const DNS_PROBE_HOST: &str = "github.com:443";
+
+/// Connected to check internet connectivity without relying on DNS
+const TCP_PROBE_ADDR: &str = "8.8.8.8:443";
+
+/// GitHub release lookups in flight at once for the binary update count
+const MAX_PARALLEL_RELEASE_CHECKS: usize = 8;
+
+/// How long each network probe may take
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Whether DNS_PROBE_HOST resolves, without shelling out to nslookup
+pub async fn probe_dns() -> bool {
+    match tokio::time::timeout(PROBE_TIMEOUT, tokio::net::lookup_host(DNS_PROBE_HOST)).await {
+        Ok(Ok(mut addrs)) => addrs.next().is_some(),
+        _ => false,
+    }
+}
+
+/// Whether a TCP connection to TCP_PROBE_ADDR opens, without shelling out to ping
+pub async fn probe_tcp() -> bool {
+    matches!(
+        tokio::time::timeout(PROBE_TIMEOUT, tokio::net::TcpStream::connect(TCP_PROBE_ADDR)).await,
+        Ok(Ok(_))
+    )
+}
+
+/// Fetched through the proxy to check it forwards HTTPS traffic
+const PROXY_PROBE_URL: &str = "https://github.com";
+
+/// Whether PROXY_PROBE_URL is reachable with the client every download uses,
+/// so the configured proxy and its credentials are what gets tested
+async fn probe_proxy(proxy: &str) -> Finding {
+    let shown = download::redact_proxy(proxy);
+    let response = match download::http_client() {
+        Ok(client) => tokio::time::timeout(PROBE_TIMEOUT, client.head(PROXY_PROBE_URL).send()).await,
+        Err(e) => {
+            return Finding::new("Network", "Proxy", Severity::Error, format!("Proxy {} is not usable", shown))
+                .with_details(format!("{:#}", e))
+                .with_fix("Fix [network] proxy in the config", false);
+        }
+    };
+
+    match response {
+        Ok(Ok(response)) if response.status() == reqwest::StatusCode::PROXY_AUTHENTICATION_REQUIRED => {
+            Finding::new("Network", "Proxy", Severity::Error, format!("Proxy {} rejected the credentials", shown))
+                .with_fix("Set user:password in [network] proxy or HTTPS_PROXY", false)
+        }
+        Ok(Ok(_)) => Finding::new("Network", "Proxy", Severity::Ok, format!("{} reachable through proxy {}", PROXY_PROBE_URL, shown)),
+        Ok(Err(e)) => Finding::new("Network", "Proxy", Severity::Error, format!("Cannot reach {} through proxy {}", PROXY_PROBE_URL, shown))
+            .with_details(e.to_string())
+            .with_fix("Check the proxy address, NO_PROXY and that the proxy allows HTTPS", false),
+        Err(_) => Finding::new("Network", "Proxy", Severity::Warning, format!("Proxy {} did not answer within {}s", shown, PROBE_TIMEOUT.as_secs()))
+            .with_details("Downloads through the proxy may time out"),
+    }
+}
+
+/// Run a check's command so that it dies with the check: when a category
+/// times out its task is aborted, dropping this future and killing the child
+async fn command_output(program: &str, args: &[&str]) -> Option<std::process::Output> {
+    tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .ok()
+}
+
+/// Stdout of a pin-listing command, or None if it is missing or fails
+async fn held_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = command_output(program, args).await?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Non-empty lines, e.g. held packages from `apt-mark showhold` or
+/// problems from `dnf check`
+fn output_lines(output: &str) -> Vec<String> {
+    output.lines().map(str::trim).filter(|line| !line.is_empty()).map(String::from).collect()
+}
+
+/// Stdout of an audit command whatever its exit status, since the checkers
+/// exit non-zero when they find problems; None if it cannot run
+async fn audit_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = command_output(program, args).await?;
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Affected packages from `dpkg --audit`, which indents them under a
+/// description of each problem
+fn parse_dpkg_audit(output: &str) -> Vec<String> {
+    let packages: Vec<String> = output.lines()
+        .filter(|line| line.starts_with(' '))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(String::from)
+        .collect();
+    if packages.is_empty() { output_lines(output) } else { packages }
+}
+
+/// Packages with missing files from `pacman -Qk` lines such as
+/// `bash: 1052 total files, 2 missing files`
+fn parse_pacman_check(output: &str) -> Vec<String> {
+    output.lines()
+        .filter_map(|line| {
+            let (package, counts) = line.split_once(": ")?;
+            let missing = counts.split(", ").nth(1)?.strip_suffix(" missing files")?;
+            (missing != "0").then(|| format!("{} ({} missing files)", package, missing))
+        })
+        .collect()
+}
+
+/// First few problems, for finding details
+fn sample(problems: &[String]) -> String {
+    const SHOWN: usize = 5;
+    let mut details = problems.iter().take(SHOWN).cloned().collect::<Vec<_>>().join("; ");
+    if problems.len() > SHOWN {
+        details.push_str(&format!("; and {} more", problems.len() - SHOWN));
+    }
+    details
+}
+
+/// Package names from `dnf versionlock list`: NEVRA patterns such as
+/// `bash-0:5.1.8-6.el9.*` from dnf 4, or `Package name: bash` from dnf 5
+fn parse_versionlock(output: &str) -> Vec<String> {
+    let mut names: Vec<String> = output.lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            if let Some(name) = line.strip_prefix("Package name:") {
+                return Some(name.trim().to_string());
+            }
+            // Skip headers, comments, dnf 5 detail lines and `!` excludes
+            if line.is_empty() || line.contains(' ') || line.starts_with(['#', '!']) {
+                return None;
+            }
+            // The name ends at the `-<epoch>:` or `-<version>` that follows it
+            let end = line.match_indices('-')
+                .map(|(i, _)| i)
+                .find(|&i| line[i + 1..].starts_with(|c: char| c.is_ascii_digit()))?;
+            Some(line[..end].to_string())
+        })
+        .collect();
+    names.dedup();
+    names
+}
+
+/// Packages listed by `IgnorePkg` lines in pacman.conf
+fn parse_ignore_pkg(conf: &str) -> Vec<String> {
+    conf.lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "IgnorePkg").then_some(value)
+        })
+        .flat_map(str::split_whitespace)
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
 
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    true
+}
+
+/// Whether pkmgr has a backend for the detected platform's primary manager;
+/// without one no package command can work
+fn manager_support(platform: &PlatformInfo) -> Finding {
+    let detected = match (&platform.distribution, &platform.version) {
+        (Some(distribution), Some(version)) => format!("{} {}", distribution, version),
+        (Some(distribution), None) => distribution.clone(),
+        (None, _) => platform.os(),
+    };
+
+    match platform.primary_package_manager() {
+        None => Finding::new(
+            "Packages",
+            "Supported Manager",
+            Severity::Critical,
+            format!("No supported package manager found on {}", detected),
+        ).with_details("pkmgr drives apt, dnf, yum, pacman, Homebrew, winget, Chocolatey or Scoop; none is on PATH")
+         .with_fix("Install one of them, or use 'pkmgr binary' and the language commands only", false),
+        Some(manager) => match PackageManagerFactory::create(platform) {
+            Ok(_) => Finding::new(
+                "Packages",
+                "Supported Manager",
+                Severity::Ok,
+                format!("{} is managed with {}", detected, manager),
+            ),
+            Err(e) => Finding::new(
+                "Packages",
+                "Supported Manager",
+                Severity::Critical,
+                format!("{} uses {}, which pkmgr cannot drive", detected, manager),
+            ).with_details(e.to_string())
+             .with_fix("Use 'pkmgr binary' and the language commands, or open an issue for this distribution", false),
+        },
+    }
+}
+
+/// Finding for a check that needs network access under --no-network
+fn skipped_offline(category: &str, name: &str) -> Finding {
+    Finding::new(category, name, Severity::Skipped, format!("{} skipped (offline)", name))
+}
+
+#[derive(Clone)]
 pub struct HealthChecker {
     platform: PlatformInfo,
     output: Output,
     auto_fix: bool,
+    github_token: Option<String>,
+    no_network: bool,
+    show_progress: bool,
+    config: Config,
 }
 
 impl HealthChecker {
@@ -22,71 +265,148 @@ impl HealthChecker {
             platform,
             output,
             auto_fix,
+            github_token: None,
+            no_network: false,
+            show_progress: true,
+            config: Config::default(),
         })
     }
 
+    /// Report GitHub API rate-limit headroom using this token
+    pub fn with_github_token(mut self, token: Option<String>) -> Self {
+        self.github_token = token;
+        self
+    }
+
+    /// Mark checks that need the network as skipped instead of running them
+    pub fn with_no_network(mut self, no_network: bool) -> Self {
+        self.no_network = no_network;
+        self
+    }
+
+    /// Where installed binaries and their state file live
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Print "Checking ..." lines as each category starts
+    pub fn with_progress(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress;
+        self
+    }
+
+    fn progress(&self, message: &str) {
+        if self.show_progress {
+            self.output.progress(message);
+        }
+    }
+
     /// Run all health checks
     pub async fn check_all(&self) -> Result<HealthReport> {
         self.output.section("🏥 Running System Health Checks");
-
-        let system_info = SystemInfo::gather()?;
-        let mut report = HealthReport::new(system_info);
-
-        // Run checks in order of importance
-        self.check_system(&mut report).await?;
-        self.check_packages(&mut report).await?;
-        self.check_storage(&mut report).await?;
-        self.check_network(&mut report).await?;
-        self.check_security(&mut report).await?;
-        self.check_repositories(&mut report).await?;
-        self.check_languages(&mut report).await?;
-        self.check_cache(&mut report).await?;
-        self.check_configuration(&mut report).await?;
-        self.check_shell(&mut report).await?;
-
-        // Generate recommendations
-        report.generate_recommendations();
-
-        Ok(report)
+        self.check_categories(DEFAULT_CATEGORIES).await
     }
 
     /// Run package-specific health checks
     pub async fn check_packages_only(&self) -> Result<HealthReport> {
-        let system_info = SystemInfo::gather()?;
-        let mut report = HealthReport::new(system_info);
-
-        self.check_packages(&mut report).await?;
-        self.check_repositories(&mut report).await?;
-
-        report.generate_recommendations();
-        Ok(report)
+        self.check_categories(&[CheckCategory::Packages, CheckCategory::Repository]).await
     }
 
     /// Run USB-specific health checks
     pub async fn check_usb_only(&self) -> Result<HealthReport> {
-        let system_info = SystemInfo::gather()?;
-        let mut report = HealthReport::new(system_info);
+        self.check_categories(&[CheckCategory::USB]).await
+    }
 
-        self.check_usb_devices(&mut report).await?;
+    /// Run security-specific health checks
+    pub async fn check_security_only(&self) -> Result<HealthReport> {
+        self.check_categories(&[CheckCategory::Security]).await
+    }
+
+    /// Run the checks registered for each requested category. Categories run
+    /// concurrently, but findings are added in the requested order
+    pub async fn check_categories(&self, categories: &[CheckCategory]) -> Result<HealthReport> {
+        let system_info = SystemInfo::gather()?;
+        let mut report = HealthReport::new(system_info.clone());
+
+        // buffered() yields results in input order however they finish
+        let results: Vec<_> = stream::iter(categories.iter().cloned())
+            .map(|category| self.check_isolated(category, system_info.clone()))
+            .buffered(MAX_PARALLEL_CHECKS)
+            .collect()
+            .await;
+
+        for (category, findings) in categories.iter().zip(results) {
+            match findings? {
+                Some(findings) => findings.into_iter().for_each(|finding| report.add_finding(finding)),
+                None => self.output.info(&format!("{} No checks registered for {}",
+                    category.icon(self.output.emoji_enabled),
+                    category.display_name()
+                )),
+            }
+        }
 
         report.generate_recommendations();
         Ok(report)
     }
 
-    /// Run security-specific health checks
-    pub async fn check_security_only(&self) -> Result<HealthReport> {
+    /// Run the checks for one category into a fresh report
+    pub async fn recheck(&self, category: &CheckCategory) -> Result<HealthReport> {
         let system_info = SystemInfo::gather()?;
-        let mut report = HealthReport::new(system_info);
+        let mut report = HealthReport::new(system_info.clone());
+        for finding in self.check_isolated(category.clone(), system_info).await?.unwrap_or_default() {
+            report.add_finding(finding);
+        }
+        Ok(report)
+    }
 
-        self.check_security(&mut report).await?;
+    /// One category's findings from its own task, or None if it has no
+    /// checks; a category exceeding CHECK_TIMEOUT is abandoned with a warning
+    async fn check_isolated(&self, category: CheckCategory, system_info: SystemInfo) -> Result<Option<Vec<Finding>>> {
+        let checker = self.clone();
+        let label = category.label();
+        let mut task = tokio::spawn(async move {
+            let mut report = HealthReport::new(system_info);
+            let registered = checker.check_category(&category, &mut report).await?;
+            Ok::<_, anyhow::Error>(registered.then_some(report.findings))
+        });
+
+        match tokio::time::timeout(CHECK_TIMEOUT, &mut task).await {
+            Ok(joined) => joined.with_context(|| format!("{} checks panicked", label))?,
+            Err(_) => {
+                task.abort();
+                Ok(Some(vec![Finding::new(
+                    label,
+                    "Check Timeout",
+                    Severity::Warning,
+                    format!("{} checks did not finish within {}s", label, CHECK_TIMEOUT.as_secs()),
+                ).with_details("Their results are missing from this report")]))
+            }
+        }
+    }
 
-        report.generate_recommendations();
-        Ok(report)
+    /// Check registry: runs the checks for a category, returning false if it has none
+    async fn check_category(&self, category: &CheckCategory, report: &mut HealthReport) -> Result<bool> {
+        match category {
+            CheckCategory::System => self.check_system(report).await?,
+            CheckCategory::Packages => self.check_packages(report).await?,
+            CheckCategory::Languages => self.check_languages(report).await?,
+            CheckCategory::Network => self.check_network(report).await?,
+            CheckCategory::Storage => self.check_storage(report).await?,
+            CheckCategory::Security => self.check_security(report).await?,
+            CheckCategory::Configuration => self.check_configuration(report).await?,
+            CheckCategory::Cache => self.check_cache(report).await?,
+            CheckCategory::Repository => self.check_repositories(report).await?,
+            CheckCategory::USB => self.check_usb_devices(report).await?,
+            CheckCategory::Shell => self.check_shell(report).await?,
+            CheckCategory::Binary => self.check_binaries(report).await?,
+        }
+        Ok(true)
     }
 
     /// Check system basics
     async fn check_system(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking system...");
+        self.progress("Checking system...");
 
         // Check OS support
         let supported_os = matches!(self.platform.platform, Platform::Linux | Platform::MacOs | Platform::Windows);
@@ -124,8 +444,18 @@ impl HealthChecker {
             ));
         }
 
+        // Note anything the environment would not tell us
+        if !report.system_info.unavailable.is_empty() {
+            report.add_finding(Finding::new(
+                "System",
+                "System Information",
+                Severity::Info,
+                format!("Could not determine: {}", report.system_info.unavailable.join(", ")),
+            ).with_details("Common in minimal containers; related checks are skipped"));
+        }
+
         // Check uptime (warn if just rebooted)
-        if report.system_info.uptime.contains("minutes") && !report.system_info.uptime.contains("hours") {
+        if report.system_info.has("uptime") && report.system_info.uptime.contains("minutes") && !report.system_info.uptime.contains("hours") {
             report.add_finding(Finding::new(
                 "System",
                 "Recent Reboot",
@@ -152,6 +482,9 @@ impl HealthChecker {
         }
 
         // Check memory
+        if !report.system_info.has("memory") {
+            return Ok(());
+        }
         let memory_gb = report.system_info.memory_total / (1024 * 1024 * 1024);
         let memory_available_gb = report.system_info.memory_available / (1024 * 1024 * 1024);
 
@@ -177,7 +510,9 @@ impl HealthChecker {
 
     /// Check package managers
     async fn check_packages(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking package management...");
+        self.progress("Checking package management...");
+
+        report.add_finding(manager_support(&self.platform));
 
         // Check for package manager
         let pm_check = match self.platform.platform {
@@ -218,7 +553,12 @@ impl HealthChecker {
 
     /// Check storage
     async fn check_storage(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking storage...");
+        self.progress("Checking storage...");
+
+        if !report.system_info.has("disk") {
+            report.add_finding(Finding::new("Storage", "Disk Space", Severity::Info, "Disk usage could not be determined"));
+            return self.check_temp_directory(report).await;
+        }
 
         // Check disk space
         let disk_available_gb = report.system_info.disk_available / (1024 * 1024 * 1024);
@@ -263,14 +603,38 @@ impl HealthChecker {
 
     /// Check network connectivity
     async fn check_network(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking network...");
+        self.progress("Checking network...");
 
-        // Check DNS resolution
-        let dns_check = Command::new("nslookup")
-            .args(&["github.com"])
-            .output();
+        if self.no_network {
+            let mut skipped = vec!["DNS Resolution", "Internet Connectivity"];
+            if self.github_token.is_some() {
+                skipped.push("GitHub API Rate Limit");
+            }
+            for name in skipped {
+                report.add_finding(skipped_offline("Network", name));
+            }
+        } else {
+            self.check_online(report).await;
+        }
+
+        if let Some(proxy) = download::proxy_settings().effective() {
+            if self.no_network {
+                report.add_finding(skipped_offline("Network", "Proxy"));
+            } else {
+                report.add_finding(probe_proxy(&proxy).await);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// DNS, connectivity and GitHub rate-limit checks
+    async fn check_online(&self, report: &mut HealthReport) {
+        // DNS and connectivity probes run in-process and together, so they
+        // work without nslookup or ping
+        let (dns_ok, tcp_ok) = tokio::join!(probe_dns(), probe_tcp());
 
-        if dns_check.is_ok() && dns_check.unwrap().status.success() {
+        if dns_ok {
             report.add_finding(Finding::new(
                 "Network",
                 "DNS Resolution",
@@ -287,11 +651,7 @@ impl HealthChecker {
         }
 
         // Check internet connectivity
-        let ping_check = Command::new("ping")
-            .args(&["-c", "1", "-W", "2", "8.8.8.8"])
-            .output();
-
-        if ping_check.is_ok() && ping_check.unwrap().status.success() {
+        if tcp_ok {
             report.add_finding(Finding::new(
                 "Network",
                 "Internet Connectivity",
@@ -307,29 +667,49 @@ impl HealthChecker {
             ).with_details("Package downloads may fail"));
         }
 
-        // Check proxy settings
-        if std::env::var("HTTP_PROXY").is_ok() || std::env::var("HTTPS_PROXY").is_ok() {
-            report.add_finding(Finding::new(
-                "Network",
-                "Proxy Configuration",
-                Severity::Info,
-                "Proxy settings detected",
-            ).with_details("Using configured proxy for network requests"));
+        // Check GitHub API rate limit when a token is configured
+        if let Some(ref token) = self.github_token {
+            match crate::binary::github::fetch_rate_limit(token).await {
+                Ok(status) => {
+                    let severity = if status.remaining == 0 {
+                        Severity::Error
+                    } else if status.remaining * 10 < status.limit {
+                        Severity::Warning
+                    } else {
+                        Severity::Ok
+                    };
+
+                    report.add_finding(Finding::new(
+                        "Network",
+                        "GitHub API Rate Limit",
+                        severity,
+                        format!("{}/{} requests remaining", status.remaining, status.limit),
+                    ).with_details(format!(
+                        "Resets at {}",
+                        status.reset.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S")
+                    )));
+                }
+                Err(e) => {
+                    report.add_finding(Finding::new(
+                        "Network",
+                        "GitHub API Rate Limit",
+                        Severity::Warning,
+                        "Could not query GitHub rate limit",
+                    ).with_details(e.to_string())
+                     .with_fix("Check that GITHUB_TOKEN is valid", false));
+                }
+            }
         }
-
-        Ok(())
     }
 
     /// Check security settings
     async fn check_security(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking security...");
+        self.progress("Checking security...");
 
         // Check GPG
-        let gpg_check = Command::new("gpg")
-            .arg("--version")
-            .output();
+        let gpg_check = command_output("gpg", &["--version"]).await;
 
-        if gpg_check.is_ok() && gpg_check.unwrap().status.success() {
+        if gpg_check.is_some_and(|output| output.status.success()) {
             report.add_finding(Finding::new(
                 "Security",
                 "GPG",
@@ -360,7 +740,7 @@ impl HealthChecker {
 
     /// Check repositories
     async fn check_repositories(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking repositories...");
+        self.progress("Checking repositories...");
 
         let repo_manager = RepositoryManager::new(self.output.clone(), self.platform.clone());
         let repos = repo_manager.list()?;
@@ -380,6 +760,12 @@ impl HealthChecker {
                 format!("{} repositories configured", repos.len()),
             ));
 
+            // Metadata age only matters when it can be refreshed
+            if self.no_network {
+                report.add_finding(skipped_offline("Repository", "Repository Metadata"));
+                return Ok(());
+            }
+
             // Check for outdated repos
             for repo in &repos {
                 if repo.enabled && repo.metadata.last_updated.is_some() {
@@ -404,7 +790,7 @@ impl HealthChecker {
 
     /// Check language versions
     async fn check_languages(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking language versions...");
+        self.progress("Checking language versions...");
 
         let languages = vec![
             ("python", "python3", "--version"),
@@ -418,11 +804,9 @@ impl HealthChecker {
         ];
 
         for (name, cmd, arg) in languages {
-            let check = Command::new(cmd)
-                .arg(arg)
-                .output();
+            let check = command_output(cmd, &[arg]).await;
 
-            if check.is_ok() && check.unwrap().status.success() {
+            if check.is_some_and(|output| output.status.success()) {
                 report.add_finding(Finding::new(
                     "Languages",
                     &format!("{} Version", name),
@@ -437,7 +821,7 @@ impl HealthChecker {
 
     /// Check cache
     async fn check_cache(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking cache...");
+        self.progress("Checking cache...");
 
         let cache_manager = CacheManager::new(self.output.clone())?;
         let stats = cache_manager.get_stats()?;
@@ -472,12 +856,10 @@ impl HealthChecker {
 
     /// Check configuration
     async fn check_configuration(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking configuration...");
+        self.progress("Checking configuration...");
 
         // Check config file
-        let config_path = dirs::config_dir()
-            .map(|d| d.join("pkmgr").join("config.toml"))
-            .unwrap_or_default();
+        let config_path = crate::core::config::Config::config_file().unwrap_or_default();
 
         if config_path.exists() {
             report.add_finding(Finding::new(
@@ -522,7 +904,7 @@ impl HealthChecker {
 
     /// Check shell integration
     async fn check_shell(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking shell integration...");
+        self.progress("Checking shell integration...");
 
         use crate::shell::detector::ShellDetector;
         use crate::shell::ShellType;
@@ -581,12 +963,153 @@ impl HealthChecker {
             ));
         }
 
+        self.check_wrapper_path_order(report);
+
+        Ok(())
+    }
+
+    /// Language wrappers only work when their directory comes before any
+    /// other directory holding the same command
+    fn check_wrapper_path_order(&self, report: &mut HealthReport) {
+        use crate::shell::symlinks::{shadowed_wrappers, wrapper_dir};
+
+        let Some(wrapper_dir) = wrapper_dir(&self.config) else { return };
+        if !wrapper_dir.is_dir() {
+            return;
+        }
+
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let shadowed = shadowed_wrappers(&path, &wrapper_dir);
+        if shadowed.is_empty() {
+            report.add_finding(Finding::new(
+                "Shell",
+                "Wrapper PATH Order",
+                Severity::Ok,
+                format!("No command shadows the language wrappers in {}", wrapper_dir.display()),
+            ));
+            return;
+        }
+
+        for (command, binary) in shadowed {
+            let shadowing_dir = binary.parent().unwrap_or(&binary);
+            report.add_finding(Finding::new(
+                "Shell",
+                format!("Wrapper PATH Order ({})", command),
+                Severity::Warning,
+                format!("'{}' runs {} instead of pkmgr's wrapper in {}", command, binary.display(), wrapper_dir.display()),
+            ).with_fix(format!(
+                "Put {} before {} in PATH, e.g. export PATH=\"{}:$PATH\" at the end of your shell profile",
+                wrapper_dir.display(),
+                shadowing_dir.display(),
+                wrapper_dir.display()
+            ), false));
+        }
+    }
+
+    /// Binaries from the installed-binaries state file must still be
+    /// executables on disk; with network, count those with newer releases
+    async fn check_binaries(&self, report: &mut HealthReport) -> Result<()> {
+        self.progress("Checking binary tools...");
+
+        let installed = binary::load_installed_binaries(&self.config).await?;
+        if installed.is_empty() {
+            report.add_finding(Finding::new(
+                "Binary",
+                "Installed Binaries",
+                Severity::Ok,
+                "No binaries installed",
+            ));
+            return Ok(());
+        }
+
+        let mut healthy = 0;
+        for entry in &installed {
+            let path = entry.location(&self.config)?;
+            let problem = match fs::metadata(&path) {
+                Err(_) => Some("is missing"),
+                Ok(metadata) if !metadata.is_file() || !is_executable(&metadata) => Some("is not an executable file"),
+                Ok(_) => None,
+            };
+
+            match problem {
+                Some(problem) => report.add_finding(Finding::new(
+                    "Binary",
+                    format!("Binary: {}", entry.name),
+                    Severity::Error,
+                    format!("{} {}", path.display(), problem),
+                ).with_details(format!("Installed from {} {}", entry.repository, entry.version))
+                 .with_fix(format!("Run 'pkmgr binary install {}'", entry.repository), false)),
+                None => healthy += 1,
+            }
+        }
+
+        if healthy > 0 {
+            report.add_finding(Finding::new(
+                "Binary",
+                "Installed Binaries",
+                Severity::Ok,
+                format!("{} of {} binaries present", healthy, installed.len()),
+            ));
+        }
+
+        if self.no_network {
+            report.add_finding(skipped_offline("Binary", "Binary Updates"));
+            return Ok(());
+        }
+
+        let client = GitHubClient::with_token(self.github_token.clone())?;
+        let repositories: Vec<String> = installed.iter().map(|entry| entry.repository.clone()).collect();
+        let latest: Vec<Option<String>> = stream::iter(repositories)
+            .map(|repository| {
+                let client = &client;
+                async move {
+                    let (owner, repo) = repository.split_once('/')?;
+                    client.get_latest_release(owner, repo).await.ok().map(|release| release.tag_name)
+                }
+            })
+            .buffered(MAX_PARALLEL_RELEASE_CHECKS)
+            .collect()
+            .await;
+
+        let outdated: Vec<String> = installed.iter()
+            .zip(&latest)
+            .filter_map(|(entry, latest)| {
+                let latest = latest.as_deref()?;
+                binary::is_newer_release(&entry.version, latest)
+                    .then(|| format!("{} {} -> {}", entry.name, entry.version, latest))
+            })
+            .collect();
+        let unchecked = latest.iter().filter(|latest| latest.is_none()).count();
+
+        let mut details = Vec::new();
+        if !outdated.is_empty() {
+            details.push(sample(&outdated));
+        }
+        if unchecked > 0 {
+            details.push(format!("{} could not be checked on GitHub", unchecked));
+        }
+
+        let mut finding = if outdated.is_empty() {
+            Finding::new("Binary", "Binary Updates", Severity::Ok, "All checked binaries are up to date")
+        } else {
+            Finding::new(
+                "Binary",
+                "Binary Updates",
+                Severity::Info,
+                format!("{} of {} binaries have updates available", outdated.len(), installed.len()),
+            ).with_fix("Run 'pkmgr binary update'", false)
+        };
+        if !details.is_empty() {
+            finding = finding.with_details(details.join("; "));
+        }
+        report.add_finding(finding);
+
         Ok(())
     }
 
     /// Check USB devices
     async fn check_usb_devices(&self, report: &mut HealthReport) -> Result<()> {
-        self.output.progress("Checking USB devices...");
+        self.progress("Checking USB devices...");
 
         use crate::usb::device::DeviceDetector;
 
@@ -614,7 +1137,7 @@ impl HealthChecker {
                     "USB",
                     &format!("Device: {}", device.name),
                     Severity::Info,
-                    format!("{} - {} GB", device.model.as_ref().unwrap_or(&"Unknown".to_string()), size_gb),
+                    format!("{} - {} GB", device.model.as_deref().unwrap_or(&device.name), size_gb),
                 ).with_details(&format!("Path: {}, Filesystem: {:?}",
                     device.path.display(),
                     device.filesystem
@@ -680,56 +1203,77 @@ impl HealthChecker {
         Ok(())
     }
 
-    async fn check_broken_packages(&self, report: &mut HealthReport, _pm: &str) -> Result<()> {
-        // Platform-specific check for broken packages
-        if self.platform.platform == Platform::Linux {
-            if self.platform.distribution.as_ref().map_or(false, |d| d.contains("ubuntu") || d.contains("debian")) {
-                let check = Command::new("dpkg")
-                    .args(&["--audit"])
-                    .output();
+    /// Audit installed packages with the native tool. `rpm -Va` is too slow
+    /// for a health check, so rpm systems use `dnf check` / `yum check`
+    async fn check_broken_packages(&self, report: &mut HealthReport, pm: &str) -> Result<()> {
+        let (problems, manual_fix) = match pm {
+            "APT" => (audit_output("dpkg", &["--audit"]).await.map(|out| parse_dpkg_audit(&out)), None),
+            "DNF" => (
+                audit_output("dnf", &["check"]).await.map(|out| output_lines(&out)),
+                Some("Run 'sudo dnf distro-sync', or 'sudo dnf reinstall' the affected packages"),
+            ),
+            "YUM" => (
+                audit_output("yum", &["check"]).await.map(|out| output_lines(&out)),
+                Some("Run 'sudo yum distro-sync', or 'sudo yum reinstall' the affected packages"),
+            ),
+            "Pacman" => (
+                audit_output("pacman", &["-Qk"]).await.map(|out| parse_pacman_check(&out)),
+                Some("Reinstall the affected packages with 'sudo pacman -S <package>'"),
+            ),
+            _ => return Ok(()),
+        };
 
-                if let Ok(output) = check {
-                    if output.stdout.is_empty() && output.status.success() {
-                        report.add_finding(Finding::new(
-                            "Packages",
-                            "Package Integrity",
-                            Severity::Ok,
-                            "No broken packages detected",
-                        ));
-                    } else {
-                        report.add_finding(Finding::new(
-                            "Packages",
-                            "Package Integrity",
-                            Severity::Error,
-                            "Broken packages detected",
-                        ).with_fix("Run 'pkmgr fix'", true));
-                    }
-                }
-            }
+        let Some(problems) = problems else { return Ok(()) };
+        if problems.is_empty() {
+            report.add_finding(Finding::new(
+                "Packages",
+                "Package Integrity",
+                Severity::Ok,
+                "No broken packages detected",
+            ));
+            return Ok(());
         }
+
+        let finding = Finding::new(
+            "Packages",
+            "Package Integrity",
+            Severity::Error,
+            format!("Broken packages detected ({} problems)", problems.len()),
+        ).with_details(sample(&problems));
+
+        // Only the dpkg repair is automated
+        report.add_finding(match manual_fix {
+            Some(hint) => finding.with_fix(hint, false),
+            None => finding.with_fix("Run 'pkmgr fix'", true),
+        });
         Ok(())
     }
 
-    async fn check_held_packages(&self, report: &mut HealthReport, _pm: &str) -> Result<()> {
-        // Check for held/pinned packages
-        if self.platform.platform == Platform::Linux {
-            if self.platform.distribution.as_ref().map_or(false, |d| d.contains("ubuntu") || d.contains("debian")) {
-                let check = Command::new("apt-mark")
-                    .arg("showhold")
-                    .output();
+    /// Report packages pinned against updates, naming the pin mechanism
+    async fn check_held_packages(&self, report: &mut HealthReport, pm: &str) -> Result<()> {
+        let (held, source) = match pm {
+            "APT" => (held_output("apt-mark", &["showhold"]).await.map(|out| output_lines(&out)), "apt-mark hold"),
+            "DNF" => (held_output("dnf", &["versionlock", "list"]).await.map(|out| parse_versionlock(&out)), "dnf versionlock"),
+            "YUM" => (held_output("yum", &["versionlock", "list"]).await.map(|out| parse_versionlock(&out)), "yum versionlock"),
+            "Pacman" => (
+                fs::read_to_string("/etc/pacman.conf").ok().map(|conf| parse_ignore_pkg(&conf)),
+                "IgnorePkg in /etc/pacman.conf",
+            ),
+            _ => return Ok(()),
+        };
 
-                if let Ok(output) = check {
-                    if !output.stdout.is_empty() {
-                        let held_count = String::from_utf8_lossy(&output.stdout).lines().count();
-                        report.add_finding(Finding::new(
-                            "Packages",
-                            "Held Packages",
-                            Severity::Info,
-                            format!("{} packages are held", held_count),
-                        ).with_details("Held packages will not be updated"));
-                    }
-                }
-            }
+        let held = held.unwrap_or_default();
+        if !held.is_empty() {
+            report.add_finding(Finding::new(
+                "Packages",
+                "Held Packages",
+                Severity::Info,
+                format!("{} packages are held", held.len()),
+            ).with_details(format!(
+                "Pinned via {}: {}; held packages will not be updated",
+                source,
+                held.join(", ")
+            )));
         }
         Ok(())
     }
@@ -754,11 +1298,9 @@ impl HealthChecker {
     }
 
     async fn check_gpg_keys(&self, report: &mut HealthReport) -> Result<()> {
-        let check = Command::new("gpg")
-            .args(&["--list-keys", "--with-colons"])
-            .output();
+        let check = command_output("gpg", &["--list-keys", "--with-colons"]).await;
 
-        if let Ok(output) = check {
+        if let Some(output) = check {
             let output_str = String::from_utf8_lossy(&output.stdout);
             let expired_count = output_str.lines()
                 .filter(|line| line.contains(":e:"))
@@ -785,32 +1327,33 @@ impl HealthChecker {
     }
 
     async fn check_privileges(&self, report: &mut HealthReport) -> Result<()> {
-        // Check sudo access
-        let sudo_check = Command::new("sudo")
-            .args(&["-n", "true"])
-            .output();
-
-        if sudo_check.is_ok() && sudo_check.unwrap().status.success() {
-            report.add_finding(Finding::new(
-                "Security",
-                "Admin Access",
-                Severity::Ok,
-                "Passwordless sudo available",
-            ));
-        } else if which::which("sudo").is_ok() {
-            report.add_finding(Finding::new(
-                "Security",
-                "Admin Access",
-                Severity::Info,
-                "Sudo available (password required)",
-            ));
-        } else {
-            report.add_finding(Finding::new(
-                "Security",
-                "Admin Access",
-                Severity::Warning,
-                "No sudo access detected",
-            ).with_details("System-wide installations will fail"));
+        // Check the escalation strategy privileged operations will use
+        match privilege::escalation() {
+            Ok(Escalation::Root) => {
+                report.add_finding(Finding::new(
+                    "Security",
+                    "Admin Access",
+                    Severity::Ok,
+                    "Running as root",
+                ));
+            }
+            Ok(escalation) => {
+                let tool = escalation.program().unwrap_or_default();
+                let (severity, message) = if escalation.is_passwordless() {
+                    (Severity::Ok, format!("Passwordless {} available", tool))
+                } else {
+                    (Severity::Info, format!("{} available (password required)", tool))
+                };
+                report.add_finding(Finding::new("Security", "Admin Access", severity, message));
+            }
+            Err(e) => {
+                report.add_finding(Finding::new(
+                    "Security",
+                    "Admin Access",
+                    Severity::Warning,
+                    "No sudo, doas or pkexec access detected",
+                ).with_details(e.to_string()));
+            }
         }
 
         Ok(())
@@ -845,4 +1388,95 @@ impl HealthChecker {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pins_from_each_manager() {
+        let dnf4 = "Last metadata expiration check: 0:12:01 ago.\n\
+            bash-0:5.1.8-6.el9.*\n\
+            python3-libs-0:3.9.18-1.el9.*\n\
+            !kernel-0:5.14.0-1.el9.*\n";
+        assert_eq!(parse_versionlock(dnf4), vec!["bash", "python3-libs"]);
+
+        let dnf5 = "# Added by 'versionlock add' command on 2024-05-01 10:00:00\n\
+            Package name: kernel-core\n\
+            evr = 6.8.7-300.fc40\n";
+        assert_eq!(parse_versionlock(dnf5), vec!["kernel-core"]);
+
+        let pacman = "[options]\n#IgnorePkg   =\nIgnorePkg = linux linux-headers # LTS only\nIgnorePkg=nvidia\n";
+        assert_eq!(parse_ignore_pkg(pacman), vec!["linux", "linux-headers", "nvidia"]);
+    }
+
+    #[test]
+    fn summarizes_broken_packages() {
+        let qk = "bash: 1052 total files, 0 missing files\nglibc: 1812 total files, 3 missing files\n";
+        assert_eq!(parse_pacman_check(qk), vec!["glibc (3 missing files)"]);
+
+        let audit = "The following packages are only half configured:\n foo     Foo tool\n bar     Bar lib\n";
+        assert_eq!(parse_dpkg_audit(audit), vec!["foo", "bar"]);
+
+        let problems: Vec<String> = (1..=7).map(|i| format!("pkg{} has missing requires", i)).collect();
+        assert!(sample(&problems).ends_with("pkg5 has missing requires; and 2 more"));
+    }
+
+    #[test]
+    fn unmapped_managers_are_critical() {
+        use crate::core::platform::PackageManager as PlatformPackageManager;
+
+        let mut platform = PlatformInfo {
+            platform: Platform::Linux,
+            architecture: Architecture::X86_64,
+            package_managers: vec![PlatformPackageManager::Zypper],
+            distribution: Some("opensuse-tumbleweed".to_string()),
+            version: None,
+        };
+        let finding = manager_support(&platform);
+        assert_eq!(finding.severity, Severity::Critical);
+        assert!(finding.message.contains("opensuse-tumbleweed uses zypper"), "{}", finding.message);
+
+        platform.package_managers = vec![PlatformPackageManager::Apt];
+        assert_eq!(manager_support(&platform).severity, Severity::Ok);
+
+        platform.package_managers.clear();
+        assert_eq!(manager_support(&platform).severity, Severity::Critical);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn binaries_are_checked_at_their_recorded_path() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let global = dir.path().join("usr-local-bin").join("helm");
+        fs::create_dir_all(global.parent().unwrap()).unwrap();
+        fs::write(&global, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&global, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut config = Config::default();
+        config.paths.data_dir = dir.path().join("data").display().to_string();
+        config.paths.install_dir = dir.path().join("home-local").display().to_string();
+        let state = dir.path().join("data").join("binaries");
+        fs::create_dir_all(&state).unwrap();
+        fs::write(state.join("installed.toml"), format!(
+            "[helm]\nrepository = \"helm/helm\"\nversion = \"v3.14.0\"\ninstall_path = \"{}\"\n\n\
+             [gh]\nrepository = \"cli/cli\"\nversion = \"v2.40.0\"\n",
+            global.display(),
+        )).unwrap();
+
+        let checker = HealthChecker::new(Output::new("never".to_string(), false), false).unwrap()
+            .with_config(config)
+            .with_no_network(true)
+            .with_progress(false);
+        let mut report = HealthReport::new(SystemInfo::gather().unwrap());
+        tokio::runtime::Runtime::new().unwrap().block_on(checker.check_binaries(&mut report)).unwrap();
+
+        let severity = |name: &str| report.findings.iter().find(|f| f.name == name).map(|f| f.severity.clone());
+        assert_eq!(severity("Binary: gh"), Some(Severity::Error));
+        assert_eq!(severity("Binary: helm"), None);
+        assert_eq!(severity("Installed Binaries"), Some(Severity::Ok));
+    }
+}
@@ -1,24 +1,23 @@
 use anyhow::{Context, Result};
 use std::process::Command;
 
-use crate::doctor::{Finding, HealthReport, Severity};
+use crate::doctor::{Finding, HealthReport};
 use crate::ui::output::Output;
-use crate::recovery::ErrorFixer;
-use crate::core::platform::Platform;
 
 pub struct Diagnostics {
     output: Output,
-    auto_fix: bool,
-    dry_run: bool,
+    no_network: bool,
 }
 
 impl Diagnostics {
-    pub fn new(output: Output, auto_fix: bool, dry_run: bool) -> Self {
-        Self {
-            output,
-            auto_fix,
-            dry_run,
-        }
+    pub fn new(output: Output) -> Self {
+        Self { output, no_network: false }
+    }
+
+    /// Skip the network tests
+    pub fn with_no_network(mut self, no_network: bool) -> Self {
+        self.no_network = no_network;
+        self
     }
 
     /// Run diagnostic tests
@@ -29,7 +28,9 @@ impl Diagnostics {
         self.test_package_manager().await?;
 
         // Test network operations
-        self.test_network_operations().await?;
+        if !self.no_network {
+            self.test_network_operations().await?;
+        }
 
         // Test file system operations
         self.test_filesystem_operations().await?;
@@ -40,70 +41,6 @@ impl Diagnostics {
         Ok(())
     }
 
-    /// Apply automatic fixes for issues
-    pub async fn apply_fixes(&self, report: &HealthReport) -> Result<()> {
-        let fixable: Vec<_> = report.findings.iter()
-            .filter(|f| f.auto_fixable && f.severity >= Severity::Warning)
-            .collect();
-
-        if fixable.is_empty() {
-            self.output.success("✅ No auto-fixable issues found");
-            return Ok(());
-        }
-
-        self.output.section(&format!("🔧 Applying {} Automatic Fixes", fixable.len()));
-
-        let platform = Platform::detect()?;
-        let fixer = ErrorFixer::new(self.output.clone(), self.dry_run, self.auto_fix);
-
-        for finding in fixable {
-            self.output.progress(&format!("Fixing: {}", finding.message));
-
-            match finding.category.as_str() {
-                "Storage" => {
-                    if finding.name.contains("Disk Space") {
-                        self.fix_disk_space().await?;
-                    }
-                }
-                "Cache" => {
-                    if finding.name.contains("Cache Usage") {
-                        self.fix_cache_usage().await?;
-                    } else if finding.name.contains("Expired") {
-                        self.fix_expired_cache().await?;
-                    }
-                }
-                "Repository" => {
-                    if finding.message.contains("metadata") {
-                        self.fix_repository_metadata().await?;
-                    }
-                }
-                "Security" => {
-                    if finding.name.contains("GPG Keys") {
-                        self.fix_gpg_keys().await?;
-                    }
-                }
-                "Configuration" => {
-                    if finding.name.contains("PATH") {
-                        self.fix_path_configuration().await?;
-                    }
-                }
-                "Packages" => {
-                    if finding.name.contains("Broken") {
-                        self.fix_broken_packages().await?;
-                    }
-                }
-                _ => {
-                    self.output.info(&format!("⏭️  Skipping: {} (manual fix required)",
-                        finding.message));
-                }
-            }
-        }
-
-        self.output.success("✅ Automatic fixes applied");
-
-        Ok(())
-    }
-
     /// Test package manager operations
     async fn test_package_manager(&self) -> Result<()> {
         self.output.progress("Testing package manager...");
@@ -127,11 +64,7 @@ impl Diagnostics {
         self.output.progress("Testing network...");
 
         // Test DNS resolution
-        let dns_test = Command::new("nslookup")
-            .args(&["github.com"])
-            .output();
-
-        if dns_test.is_ok() && dns_test.unwrap().status.success() {
+        if super::checker::probe_dns().await {
             self.output.success("✓ DNS resolution working");
         } else {
             self.output.warn("⚠️  DNS resolution issues detected");
@@ -205,104 +138,6 @@ impl Diagnostics {
         Ok(())
     }
 
-    // Fix implementations
-
-    async fn fix_disk_space(&self) -> Result<()> {
-        if self.dry_run {
-            self.output.info("Would run: pkmgr cache clean");
-        } else {
-            // Run cache clean command
-            use crate::cache::cleaner::CacheCleaner;
-            let mut cleaner = CacheCleaner::new(self.output.clone(), false)?;
-            cleaner.clean_expired().await?;
-        }
-        Ok(())
-    }
-
-    async fn fix_cache_usage(&self) -> Result<()> {
-        if self.dry_run {
-            self.output.info("Would run: pkmgr cache clean");
-        } else {
-            use crate::cache::cleaner::CacheCleaner;
-            let mut cleaner = CacheCleaner::new(self.output.clone(), false)?;
-            cleaner.clean_all(true).await?;
-        }
-        Ok(())
-    }
-
-    async fn fix_expired_cache(&self) -> Result<()> {
-        if self.dry_run {
-            self.output.info("Would run: pkmgr cache clean --expired");
-        } else {
-            use crate::cache::cleaner::CacheCleaner;
-            let mut cleaner = CacheCleaner::new(self.output.clone(), false)?;
-            cleaner.clean_expired().await?;
-        }
-        Ok(())
-    }
-
-    async fn fix_repository_metadata(&self) -> Result<()> {
-        if self.dry_run {
-            self.output.info("Would run: pkmgr repos update");
-        } else {
-            // Update repository metadata
-            use crate::repos::manager::RepositoryManager;
-            use crate::core::platform::Platform;
-
-            let platform = Platform::detect()?;
-            let mut manager = RepositoryManager::new(self.output.clone(), platform);
-            manager.update_cache().await?;
-        }
-        Ok(())
-    }
-
-    async fn fix_gpg_keys(&self) -> Result<()> {
-        if self.dry_run {
-            self.output.info("Would refresh GPG keys");
-        } else {
-            // Refresh expired GPG keys
-            Command::new("gpg")
-                .args(&["--refresh-keys"])
-                .status()?;
-        }
-        Ok(())
-    }
-
-    async fn fix_path_configuration(&self) -> Result<()> {
-        if self.dry_run {
-            self.output.info("Would add ~/.local/bin to PATH");
-        } else {
-            self.output.info("Run: eval $(pkmgr shell add)");
-        }
-        Ok(())
-    }
-
-    async fn fix_broken_packages(&self) -> Result<()> {
-        if self.dry_run {
-            self.output.info("Would run: pkmgr fix");
-        } else {
-            // Run package fix commands based on platform
-            use crate::core::platform::Platform;
-            let platform = Platform::detect()?;
-
-            match platform.platform {
-                Platform::Linux => {
-                    if platform.distribution.as_ref().map_or(false, |d| d.contains("ubuntu") || d.contains("debian")) {
-                        Command::new("dpkg")
-                            .args(&["--configure", "-a"])
-                            .status()?;
-
-                        Command::new("apt-get")
-                            .args(&["--fix-broken", "install", "-y"])
-                            .status()?;
-                    }
-                }
-                _ => {}
-            }
-        }
-        Ok(())
-    }
-
     async fn test_package_search(&self, package: &str) -> Result<bool> {
         // Try to search for package using native package manager
         use crate::core::platform::Platform;
@@ -0,0 +1,267 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::process::Command;
+
+use crate::cache::cleaner::CacheCleaner;
+use crate::core::platform::Platform;
+use crate::core::privilege;
+use crate::doctor::checker::HealthChecker;
+use crate::doctor::{CheckCategory, Finding, HealthReport, Severity};
+use crate::repos::manager::RepositoryManager;
+use crate::shell::detector::ShellDetector;
+use crate::shell::integration::ShellIntegration;
+use crate::shell::ShellType;
+use crate::ui::output::Output;
+use crate::ui::prompt::Prompt;
+use crate::utils::logging::LoggedCommand;
+
+/// Concrete action that resolves an auto-fixable finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Remediation {
+    CleanExpiredCache,
+    EvictCache,
+    UpdateRepositories,
+    RefreshGpgKeys,
+    AddLocalBinToPath,
+    RepairPackages,
+}
+
+impl Remediation {
+    /// Map a finding to the remediation that addresses it
+    pub fn for_finding(finding: &Finding) -> Option<Self> {
+        if !finding.auto_fixable {
+            return None;
+        }
+
+        match (finding.category.as_str(), finding.name.as_str()) {
+            ("Storage", "Disk Space") => Some(Remediation::CleanExpiredCache),
+            ("Cache", "Cache Usage") => Some(Remediation::EvictCache),
+            ("Cache", "Expired Entries") => Some(Remediation::CleanExpiredCache),
+            ("Repository", name) if name.starts_with("Repository:") => Some(Remediation::UpdateRepositories),
+            ("Security", "GPG Keys") => Some(Remediation::RefreshGpgKeys),
+            ("Configuration", "PATH Configuration") => Some(Remediation::AddLocalBinToPath),
+            ("Packages", "Package Integrity") => Some(Remediation::RepairPackages),
+            _ => None,
+        }
+    }
+
+    /// What will be run, shown before asking for confirmation
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Remediation::CleanExpiredCache => "pkmgr cache clean --expired",
+            Remediation::EvictCache => "evict least-recently-used cache entries down to the cleanup threshold",
+            Remediation::UpdateRepositories => "pkmgr repos update",
+            Remediation::RefreshGpgKeys => "gpg --refresh-keys",
+            Remediation::AddLocalBinToPath => "add ~/.local/bin to PATH in your shell startup file",
+            Remediation::RepairPackages => "dpkg --configure -a && apt-get --fix-broken install",
+        }
+    }
+
+    /// Changes to shell startup files only show up in new shells
+    fn needs_new_shell(&self) -> bool {
+        matches!(self, Remediation::AddLocalBinToPath)
+    }
+}
+
+/// Result of attempting one finding
+#[derive(Debug, Clone)]
+pub enum FixOutcome {
+    Fixed,
+    StillFailing(Severity),
+    PendingRestart,
+    Declined,
+    Failed(String),
+}
+
+/// Summary of a `doctor --fix` run
+#[derive(Debug, Default)]
+pub struct FixReport {
+    pub results: Vec<(Finding, Remediation, FixOutcome)>,
+    pub manual: Vec<Finding>,
+}
+
+impl FixReport {
+    fn count(&self, pred: impl Fn(&FixOutcome) -> bool) -> usize {
+        self.results.iter().filter(|(_, _, o)| pred(o)).count()
+    }
+}
+
+pub struct DoctorFixer<'a> {
+    checker: &'a HealthChecker,
+    output: Output,
+    prompt: Prompt,
+    assume_yes: bool,
+    dry_run: bool,
+}
+
+impl<'a> DoctorFixer<'a> {
+    pub fn new(checker: &'a HealthChecker, output: Output, assume_yes: bool, dry_run: bool) -> Self {
+        let prompt = Prompt::new(output.emoji_enabled);
+        Self { checker, output, prompt, assume_yes, dry_run }
+    }
+
+    /// Run remediations for every auto-fixable finding, then re-check each
+    pub async fn fix(&self, report: &HealthReport) -> Result<FixReport> {
+        let mut fix_report = FixReport::default();
+
+        let problems: Vec<&Finding> = report.findings.iter()
+            .filter(|f| f.severity > Severity::Ok)
+            .collect();
+
+        let mut fixable = Vec::new();
+        for finding in problems {
+            match Remediation::for_finding(finding) {
+                Some(remediation) => fixable.push((finding, remediation)),
+                None if finding.fix_hint.is_some() => fix_report.manual.push(finding.clone()),
+                None => {}
+            }
+        }
+
+        if fixable.is_empty() {
+            self.output.success("✅ No auto-fixable issues found");
+        } else {
+            self.output.section(&format!("🔧 Fixing {} Issues", fixable.len()));
+        }
+
+        let mut applied: Vec<Remediation> = Vec::new();
+        for (finding, remediation) in fixable {
+            self.output.info(&format!("{} {}: {}", finding.severity.icon(self.output.emoji_enabled), finding.name, finding.message));
+
+            let outcome = if applied.contains(&remediation) {
+                // Shared remediation already ran for an earlier finding
+                self.verify(finding, remediation).await
+            } else if self.dry_run {
+                self.output.info(&format!("  🔍 Would run: {}", remediation.describe()));
+                continue;
+            } else if !self.assume_yes
+                && !self.prompt.confirm(&format!("Run: {}?", remediation.describe()))?
+            {
+                FixOutcome::Declined
+            } else {
+                applied.push(remediation);
+                match self.apply(remediation).await {
+                    Ok(()) => self.verify(finding, remediation).await,
+                    Err(e) => FixOutcome::Failed(e.to_string()),
+                }
+            };
+
+            match &outcome {
+                FixOutcome::Fixed => self.output.success("  ✅ Fixed"),
+                FixOutcome::StillFailing(severity) => {
+                    self.output.warn(&format!("  {} Still failing after fix", severity.icon(self.output.emoji_enabled)))
+                }
+                FixOutcome::PendingRestart => self.output.info("  🔄 Applied; open a new shell to pick it up"),
+                FixOutcome::Declined => self.output.info("  ⏭️  Skipped"),
+                FixOutcome::Failed(e) => self.output.error(&format!("  ❌ Fix failed: {}", e)),
+            }
+
+            fix_report.results.push((finding.clone(), remediation, outcome));
+        }
+
+        if !fix_report.manual.is_empty() {
+            self.output.section("📝 Manual Fixes");
+            for finding in &fix_report.manual {
+                if let Some(hint) = &finding.fix_hint {
+                    self.output.info(&format!("  💡 {}: {}", finding.name, hint));
+                }
+            }
+        }
+
+        self.summarize(&fix_report);
+        Ok(fix_report)
+    }
+
+    fn summarize(&self, report: &FixReport) {
+        if report.results.is_empty() {
+            return;
+        }
+
+        let fixed = report.count(|o| matches!(o, FixOutcome::Fixed | FixOutcome::PendingRestart));
+        let failing = report.count(|o| matches!(o, FixOutcome::StillFailing(_) | FixOutcome::Failed(_)));
+        let declined = report.count(|o| matches!(o, FixOutcome::Declined));
+
+        self.output.info("");
+        if failing == 0 {
+            self.output.success(&format!("✅ {} fixed, {} skipped", fixed, declined));
+        } else {
+            self.output.warn(&format!("⚠️  {} fixed, {} still failing, {} skipped", fixed, failing, declined));
+        }
+    }
+
+    /// Re-run the check that produced the finding and compare
+    async fn verify(&self, finding: &Finding, remediation: Remediation) -> FixOutcome {
+        if remediation.needs_new_shell() {
+            return FixOutcome::PendingRestart;
+        }
+
+        let category = match CheckCategory::from_finding(&finding.category) {
+            Some(category) => category,
+            None => return FixOutcome::Fixed,
+        };
+
+        match self.checker.recheck(&category).await {
+            Ok(recheck) => match recheck.findings.iter().find(|f| f.name == finding.name) {
+                Some(after) if after.severity >= finding.severity => FixOutcome::StillFailing(after.severity.clone()),
+                _ => FixOutcome::Fixed,
+            },
+            Err(e) => FixOutcome::Failed(format!("re-check failed: {}", e)),
+        }
+    }
+
+    async fn apply(&self, remediation: Remediation) -> Result<()> {
+        match remediation {
+            Remediation::CleanExpiredCache => {
+                CacheCleaner::new(self.output.clone(), false)?.clean_expired().await
+            }
+            Remediation::EvictCache => {
+                CacheCleaner::new(self.output.clone(), false)?.evict(false).await.map(|_| ())
+            }
+            Remediation::UpdateRepositories => {
+                let platform = Platform::detect()?;
+                RepositoryManager::new(self.output.clone(), platform).update_cache().await
+            }
+            Remediation::RefreshGpgKeys => run("gpg", &["--refresh-keys"]),
+            Remediation::AddLocalBinToPath => self.add_local_bin_to_path(),
+            Remediation::RepairPackages => {
+                privilege::run_privileged("dpkg", &["--configure", "-a"])?;
+                privilege::run_privileged("apt-get", &["--fix-broken", "install", "-y"]).map(|_| ())
+            }
+        }
+    }
+
+    /// Append the PATH snippet to the user's shell startup file once
+    fn add_local_bin_to_path(&self) -> Result<()> {
+        let shell = ShellDetector::detect_default_shell().unwrap_or(ShellType::Bash);
+        let rc_file = shell.config_files()
+            .into_iter()
+            .next()
+            .context("No startup file known for this shell")?;
+
+        let existing = fs::read_to_string(&rc_file).unwrap_or_default();
+        if existing.contains("# Add ~/.local/bin to PATH") {
+            return Ok(());
+        }
+
+        let script = ShellIntegration::new(shell, self.output.clone()).generate_path_script(true);
+        if let Some(parent) = std::path::Path::new(&rc_file).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&rc_file, format!("{}{}", existing, script))
+            .with_context(|| format!("Failed to update {}", rc_file))?;
+
+        self.output.info(&format!("  📝 Updated {}", rc_file));
+        Ok(())
+    }
+}
+
+fn run(program: &str, args: &[&str]) -> Result<()> {
+    let status = Command::new(program)
+        .args(args)
+        .logged_status()
+        .with_context(|| format!("Failed to run {}", program))?;
+
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
@@ -1,6 +1,8 @@
 pub mod checker;
 pub mod diagnostics;
+pub mod fixer;
 pub mod report;
+pub mod watch;
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -9,6 +11,7 @@ use std::collections::HashMap;
 /// Health check severity levels
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Severity {
+    Skipped,  // Not run, e.g. online checks with --no-network
     Ok,       // Everything is fine
     Info,     // Informational, no action needed
     Warning,  // Potential issue, should investigate
@@ -19,6 +22,7 @@ pub enum Severity {
 impl Severity {
     pub fn emoji(&self) -> &'static str {
         match self {
+            Severity::Skipped => "⏭️",
             Severity::Ok => "✅",
             Severity::Info => "ℹ️",
             Severity::Warning => "⚠️",
@@ -27,8 +31,25 @@ impl Severity {
         }
     }
 
+    pub fn ascii(&self) -> &'static str {
+        match self {
+            Severity::Skipped => "[SKIP]",
+            Severity::Ok => "[OK]",
+            Severity::Info => "[INFO]",
+            Severity::Warning => "[WARN]",
+            Severity::Error => "[ERR]",
+            Severity::Critical => "[CRIT]",
+        }
+    }
+
+    /// `emoji()` or `ascii()`, following the output's emoji setting
+    pub fn icon(&self, emoji_enabled: bool) -> &'static str {
+        if emoji_enabled { self.emoji() } else { self.ascii() }
+    }
+
     pub fn color(&self) -> &'static str {
         match self {
+            Severity::Skipped => "dimmed",
             Severity::Ok => "green",
             Severity::Info => "blue",
             Severity::Warning => "yellow",
@@ -76,7 +97,7 @@ impl Finding {
 }
 
 /// Health check category
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, clap::ValueEnum)]
 pub enum CheckCategory {
     System,
     Packages,
@@ -93,6 +114,43 @@ pub enum CheckCategory {
 }
 
 impl CheckCategory {
+    /// Category that produced a finding, from its `category` label
+    pub fn from_finding(category: &str) -> Option<Self> {
+        match category {
+            "System" => Some(CheckCategory::System),
+            "Packages" => Some(CheckCategory::Packages),
+            "Languages" => Some(CheckCategory::Languages),
+            "Network" => Some(CheckCategory::Network),
+            "Storage" => Some(CheckCategory::Storage),
+            "Security" => Some(CheckCategory::Security),
+            "Configuration" => Some(CheckCategory::Configuration),
+            "Cache" => Some(CheckCategory::Cache),
+            "Repository" => Some(CheckCategory::Repository),
+            "USB" => Some(CheckCategory::USB),
+            "Binary" => Some(CheckCategory::Binary),
+            "Shell" => Some(CheckCategory::Shell),
+            _ => None,
+        }
+    }
+
+    /// `category` label on this category's findings
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckCategory::System => "System",
+            CheckCategory::Packages => "Packages",
+            CheckCategory::Languages => "Languages",
+            CheckCategory::Network => "Network",
+            CheckCategory::Storage => "Storage",
+            CheckCategory::Security => "Security",
+            CheckCategory::Configuration => "Configuration",
+            CheckCategory::Cache => "Cache",
+            CheckCategory::Repository => "Repository",
+            CheckCategory::USB => "USB",
+            CheckCategory::Binary => "Binary",
+            CheckCategory::Shell => "Shell",
+        }
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
             CheckCategory::System => "System",
@@ -126,6 +184,28 @@ impl CheckCategory {
             CheckCategory::Shell => "🐚",
         }
     }
+
+    pub fn ascii(&self) -> &'static str {
+        match self {
+            CheckCategory::System => "[SYS]",
+            CheckCategory::Packages => "[PKG]",
+            CheckCategory::Languages => "[LANG]",
+            CheckCategory::Network => "[NET]",
+            CheckCategory::Storage => "[DISK]",
+            CheckCategory::Security => "[SEC]",
+            CheckCategory::Configuration => "[CFG]",
+            CheckCategory::Cache => "[CACHE]",
+            CheckCategory::Repository => "[REPO]",
+            CheckCategory::USB => "[USB]",
+            CheckCategory::Binary => "[BIN]",
+            CheckCategory::Shell => "[SHELL]",
+        }
+    }
+
+    /// `emoji()` or `ascii()`, following the output's emoji setting
+    pub fn icon(&self, emoji_enabled: bool) -> &'static str {
+        if emoji_enabled { self.emoji() } else { self.ascii() }
+    }
 }
 
 /// Health check result
@@ -152,6 +232,7 @@ impl HealthReport {
     pub fn add_finding(&mut self, finding: Finding) {
         // Update stats
         match finding.severity {
+            Severity::Skipped => self.stats.skipped_count += 1,
             Severity::Ok => self.stats.ok_count += 1,
             Severity::Info => self.stats.info_count += 1,
             Severity::Warning => self.stats.warning_count += 1,
@@ -213,7 +294,7 @@ impl HealthReport {
             .collect();
 
         if !storage_issues.is_empty() {
-            recommendations.push("💾 Consider cleaning cache with 'pkmgr cache clean'".to_string());
+            recommendations.push("💾 Free disk space with 'pkmgr reclaim' (preview with --dry-run)".to_string());
         }
 
         // Security recommendations
@@ -254,34 +335,50 @@ pub struct SystemInfo {
     pub disk_total: u64,
     pub disk_available: u64,
     pub pkmgr_version: String,
+    /// Fields that could not be gathered and hold placeholder values
+    #[serde(default)]
+    pub unavailable: Vec<String>,
 }
 
 impl SystemInfo {
+    /// Gather what the environment allows; anything that fails falls back to
+    /// "Unknown"/0 and is listed in `unavailable` instead of failing the run
     pub fn gather() -> Result<Self> {
         let platform = crate::core::platform::Platform::detect()?;
+        let mut unavailable = Vec::new();
 
         // Get system info
-        let hostname = hostname::get()?
-            .to_string_lossy()
-            .to_string();
+        let hostname = match hostname::get() {
+            Ok(name) => name.to_string_lossy().to_string(),
+            Err(_) => {
+                unavailable.push("hostname".to_string());
+                "Unknown".to_string()
+            }
+        };
 
         // Get uptime
-        #[cfg(unix)]
-        let uptime = {
-            let info = sys_info::loadavg()?;
-            format_uptime(sys_info::boottime()?.tv_sec as u64)
+        let uptime = match get_uptime_seconds() {
+            Ok(seconds) => format_uptime(seconds),
+            Err(_) => {
+                unavailable.push("uptime".to_string());
+                "Unknown".to_string()
+            }
         };
-        #[cfg(not(unix))]
-        let uptime = "unknown".to_string();
 
         // Get CPU count
         let cpu_count = num_cpus::get();
 
         // Get memory info
-        let (memory_total, memory_available) = get_memory_info()?;
+        let (memory_total, memory_available) = get_memory_info().unwrap_or_else(|_| {
+            unavailable.push("memory".to_string());
+            (0, 0)
+        });
 
         // Get disk info
-        let (disk_total, disk_available) = get_disk_info("/")?;
+        let (disk_total, disk_available) = get_disk_info("/").unwrap_or_else(|_| {
+            unavailable.push("disk".to_string());
+            (0, 0)
+        });
 
         Ok(Self {
             os: platform.platform.to_string(),
@@ -297,8 +394,14 @@ impl SystemInfo {
             disk_total,
             disk_available,
             pkmgr_version: env!("CARGO_PKG_VERSION").to_string(),
+            unavailable,
         })
     }
+
+    /// Whether a field was gathered rather than filled with a placeholder
+    pub fn has(&self, field: &str) -> bool {
+        !self.unavailable.iter().any(|f| f == field)
+    }
 }
 
 /// Health check statistics
@@ -310,6 +413,9 @@ pub struct HealthStats {
     pub error_count: usize,
     pub critical_count: usize,
     pub fixable_count: usize,
+    /// Not counted in total() and never affects the overall status
+    #[serde(default)]
+    pub skipped_count: usize,
 }
 
 impl HealthStats {
@@ -351,6 +457,25 @@ fn get_memory_info() -> Result<(u64, u64)> {
     }
 }
 
+/// Seconds since boot
+fn get_uptime_seconds() -> Result<u64> {
+    // sys-info reports uptime directly on Linux but the boot timestamp on macOS
+    #[cfg(target_os = "linux")]
+    {
+        Ok(sys_info::boottime()?.tv_sec.max(0) as u64)
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        let boot = sys_info::boottime()?.tv_sec as i64;
+        let now = chrono::Utc::now().timestamp();
+        Ok(now.saturating_sub(boot).max(0) as u64)
+    }
+    #[cfg(not(unix))]
+    {
+        anyhow::bail!("uptime not available on this platform")
+    }
+}
+
 /// Get disk information
 fn get_disk_info(path: &str) -> Result<(u64, u64)> {
     #[cfg(unix)]
@@ -362,4 +487,19 @@ fn get_disk_info(path: &str) -> Result<(u64, u64)> {
     {
         Ok((0, 0))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skipped_checks_do_not_degrade_overall_status() {
+        let mut report = HealthReport::new(SystemInfo::gather().unwrap());
+        report.add_finding(Finding::new("Network", "DNS Resolution", Severity::Skipped, "skipped (offline)"));
+        report.add_finding(Finding::new("Storage", "Disk Space", Severity::Ok, "plenty"));
+
+        assert_eq!(report.overall_status(), Severity::Ok);
+        assert_eq!((report.stats.total(), report.stats.skipped_count), (1, 1));
+    }
+}
@@ -39,9 +39,9 @@ impl ReportFormatter {
     /// Display report header
     fn display_header(&self, report: &HealthReport) {
         let status = report.overall_status();
-        let emoji = status.emoji();
+        let emoji = status.icon(self.output.emoji_enabled);
         let title = match status {
-            Severity::Ok => "System Health: Excellent",
+            Severity::Ok | Severity::Skipped => "System Health: Excellent",
             Severity::Info => "System Health: Good",
             Severity::Warning => "System Health: Fair",
             Severity::Error => "System Health: Poor",
@@ -58,39 +58,46 @@ impl ReportFormatter {
 
         if report.stats.ok_count > 0 {
             self.output.info(&format!("   {} {} passed",
-                Severity::Ok.emoji(),
+                Severity::Ok.icon(self.output.emoji_enabled),
                 report.stats.ok_count
             ));
         }
 
         if report.stats.info_count > 0 {
             self.output.info(&format!("   {} {} informational",
-                Severity::Info.emoji(),
+                Severity::Info.icon(self.output.emoji_enabled),
                 report.stats.info_count
             ));
         }
 
         if report.stats.warning_count > 0 {
             self.output.warn(&format!("   {} {} warnings",
-                Severity::Warning.emoji(),
+                Severity::Warning.icon(self.output.emoji_enabled),
                 report.stats.warning_count
             ));
         }
 
         if report.stats.error_count > 0 {
             self.output.error(&format!("   {} {} errors",
-                Severity::Error.emoji(),
+                Severity::Error.icon(self.output.emoji_enabled),
                 report.stats.error_count
             ));
         }
 
         if report.stats.critical_count > 0 {
             self.output.error(&format!("   {} {} critical",
-                Severity::Critical.emoji(),
+                Severity::Critical.icon(self.output.emoji_enabled),
                 report.stats.critical_count
             ));
         }
 
+        if report.stats.skipped_count > 0 {
+            self.output.info(&format!("   {} {} skipped (offline)",
+                Severity::Skipped.icon(self.output.emoji_enabled),
+                report.stats.skipped_count
+            ));
+        }
+
         if report.stats.fixable_count > 0 {
             self.output.info(&format!("   🔧 {} auto-fixable",
                 report.stats.fixable_count
@@ -170,19 +177,20 @@ impl ReportFormatter {
 
             // Get category emoji
             let emoji = match category.as_str() {
-                "System" => CheckCategory::System.emoji(),
-                "Packages" => CheckCategory::Packages.emoji(),
-                "Languages" => CheckCategory::Languages.emoji(),
-                "Network" => CheckCategory::Network.emoji(),
-                "Storage" => CheckCategory::Storage.emoji(),
-                "Security" => CheckCategory::Security.emoji(),
-                "Configuration" => CheckCategory::Configuration.emoji(),
-                "Cache" => CheckCategory::Cache.emoji(),
-                "Repository" => CheckCategory::Repository.emoji(),
-                "USB" => CheckCategory::USB.emoji(),
-                "Binary" => CheckCategory::Binary.emoji(),
-                "Shell" => CheckCategory::Shell.emoji(),
-                _ => "📋",
+                "System" => CheckCategory::System.icon(self.output.emoji_enabled),
+                "Packages" => CheckCategory::Packages.icon(self.output.emoji_enabled),
+                "Languages" => CheckCategory::Languages.icon(self.output.emoji_enabled),
+                "Network" => CheckCategory::Network.icon(self.output.emoji_enabled),
+                "Storage" => CheckCategory::Storage.icon(self.output.emoji_enabled),
+                "Security" => CheckCategory::Security.icon(self.output.emoji_enabled),
+                "Configuration" => CheckCategory::Configuration.icon(self.output.emoji_enabled),
+                "Cache" => CheckCategory::Cache.icon(self.output.emoji_enabled),
+                "Repository" => CheckCategory::Repository.icon(self.output.emoji_enabled),
+                "USB" => CheckCategory::USB.icon(self.output.emoji_enabled),
+                "Binary" => CheckCategory::Binary.icon(self.output.emoji_enabled),
+                "Shell" => CheckCategory::Shell.icon(self.output.emoji_enabled),
+                _ if self.output.emoji_enabled => "📋",
+                _ => "[-]",
             };
 
             self.output.section(&format!("{} {}", emoji, category));
@@ -195,7 +203,7 @@ impl ReportFormatter {
 
     /// Display a single finding
     fn display_finding(&self, finding: &Finding) {
-        let emoji = finding.severity.emoji();
+        let emoji = finding.severity.icon(self.output.emoji_enabled);
         let message = format!("{} {}", emoji, finding.message);
 
         match finding.severity {
@@ -204,7 +212,7 @@ impl ReportFormatter {
                     self.output.success(&message);
                 }
             }
-            Severity::Info => self.output.info(&message),
+            Severity::Info | Severity::Skipped => self.output.info(&message),
             Severity::Warning => self.output.warn(&message),
             Severity::Error => self.output.error(&message),
             Severity::Critical => self.output.error(&message),
@@ -310,6 +318,7 @@ impl ReportFormatter {
         content.push_str(&format!("  Warnings: {}\n", report.stats.warning_count));
         content.push_str(&format!("  Errors: {}\n", report.stats.error_count));
         content.push_str(&format!("  Critical: {}\n", report.stats.critical_count));
+        content.push_str(&format!("  Skipped: {}\n", report.stats.skipped_count));
         content.push_str(&format!("  Auto-fixable: {}\n\n", report.stats.fixable_count));
 
         content.push_str("FINDINGS\n");
@@ -375,6 +384,7 @@ impl ReportFormatter {
         content.push_str(&format!("| ⚠️ Warning | {} |\n", report.stats.warning_count));
         content.push_str(&format!("| ❌ Error | {} |\n", report.stats.error_count));
         content.push_str(&format!("| 🔴 Critical | {} |\n", report.stats.critical_count));
+        content.push_str(&format!("| ⏭️ Skipped | {} |\n", report.stats.skipped_count));
         content.push_str(&format!("| 🔧 Auto-fixable | {} |\n\n", report.stats.fixable_count));
 
         content.push_str("## Findings\n\n");
@@ -389,7 +399,7 @@ impl ReportFormatter {
             content.push_str(&format!("### {}\n\n", category));
 
             for finding in findings {
-                let emoji = finding.severity.emoji();
+                let emoji = finding.severity.icon(self.output.emoji_enabled);
                 content.push_str(&format!("- {} **{}**: {}\n", emoji, finding.name, finding.message));
 
                 if let Some(details) = &finding.details {
@@ -471,4 +481,51 @@ fn markdown_to_html(markdown: &str) -> String {
         .replace("\n|", "\n<tr><td>")
         .replace("|", "</td><td>")
         + "</p>"
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doctor::SystemInfo;
+
+    fn report(findings: Vec<Finding>) -> HealthReport {
+        let mut report = HealthReport::new(SystemInfo {
+            os: "Linux".to_string(),
+            distribution: "Ubuntu".to_string(),
+            version: "24.04".to_string(),
+            kernel: "6.8.0".to_string(),
+            architecture: "x86_64".to_string(),
+            hostname: "build".to_string(),
+            uptime: "1h".to_string(),
+            cpu_count: 4,
+            memory_total: 8,
+            memory_available: 4,
+            disk_total: 100,
+            disk_available: 50,
+            pkmgr_version: "1.0.0".to_string(),
+            unavailable: vec![],
+        });
+        for finding in findings {
+            report.add_finding(finding);
+        }
+        report
+    }
+
+    #[test]
+    fn problems_go_to_stderr_and_passing_checks_stay_hidden() {
+        let output = Output::capture();
+        let report = report(vec![
+            Finding::new("Storage", "Root partition", Severity::Ok, "Root partition has space"),
+            Finding::new("Packages", "Broken packages", Severity::Error, "2 broken packages")
+                .with_fix("Run pkmgr fix", true),
+        ]);
+
+        ReportFormatter::new(output.clone()).display(&report);
+
+        let stdout = output.captured_stdout();
+        assert!(stdout.contains("System Health: Poor"), "{}", stdout);
+        assert!(stdout.contains("Run pkmgr fix"), "{}", stdout);
+        assert!(!stdout.contains("Root partition has space"), "{}", stdout);
+        assert!(output.captured_stderr().contains("2 broken packages"));
+    }
+}
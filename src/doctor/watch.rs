@@ -0,0 +1,195 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::doctor::checker::HealthChecker;
+use crate::doctor::{CheckCategory, Finding, HealthReport, Severity};
+use crate::ui::output::Output;
+
+/// How `doctor --watch` prints changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum WatchFormat {
+    Text,
+    /// One JSON object per change, for log collectors
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    New,
+    Resolved,
+    Changed,
+}
+
+/// A problem that appeared, cleared or changed severity since the last run
+#[derive(Debug, Clone, Serialize)]
+pub struct Change {
+    #[serde(rename = "change")]
+    pub kind: ChangeKind,
+    /// Severity in the previous run, for resolved and changed findings
+    pub previous: Option<Severity>,
+    #[serde(flatten)]
+    pub finding: Finding,
+}
+
+#[derive(Serialize)]
+struct ChangeLine<'a> {
+    timestamp: chrono::DateTime<chrono::Utc>,
+    #[serde(flatten)]
+    change: &'a Change,
+}
+
+/// Changes between two reports, matching findings by (category, name).
+/// Passing and skipped findings only matter as the resolution of a problem
+pub fn diff(previous: &HealthReport, current: &HealthReport) -> Vec<Change> {
+    fn problems(report: &HealthReport) -> HashMap<(&str, &str), &Finding> {
+        report.findings.iter()
+            .filter(|finding| finding.severity > Severity::Ok)
+            .map(|finding| ((finding.category.as_str(), finding.name.as_str()), finding))
+            .collect()
+    }
+    let (before, after) = (problems(previous), problems(current));
+    let mut changes = Vec::new();
+
+    for finding in current.findings.iter().filter(|finding| finding.severity > Severity::Ok) {
+        match before.get(&(finding.category.as_str(), finding.name.as_str())) {
+            None => changes.push(Change { kind: ChangeKind::New, previous: None, finding: finding.clone() }),
+            Some(old) if old.severity != finding.severity => changes.push(Change {
+                kind: ChangeKind::Changed,
+                previous: Some(old.severity.clone()),
+                finding: finding.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for old in previous.findings.iter().filter(|finding| finding.severity > Severity::Ok) {
+        let key = (old.category.as_str(), old.name.as_str());
+        if after.contains_key(&key) {
+            continue;
+        }
+        // Report the passing finding when there is one, else the old problem
+        let finding = current.findings.iter()
+            .find(|finding| (finding.category.as_str(), finding.name.as_str()) == key)
+            .unwrap_or(old);
+        changes.push(Change { kind: ChangeKind::Resolved, previous: Some(old.severity.clone()), finding: finding.clone() });
+    }
+
+    changes
+}
+
+/// Re-runs health checks on an interval and prints what changed. Only the
+/// previous report is kept, so memory stays flat however long it runs
+pub struct Watcher {
+    checker: HealthChecker,
+    output: Output,
+    interval: Duration,
+    format: WatchFormat,
+}
+
+impl Watcher {
+    pub fn new(checker: HealthChecker, output: Output, interval: Duration, format: WatchFormat) -> Self {
+        Self { checker: checker.with_progress(false), output, interval, format }
+    }
+
+    /// Check `categories` until Ctrl-C; the first run reports every current
+    /// problem as new
+    pub async fn run(&self, categories: &[CheckCategory]) -> Result<()> {
+        if self.format == WatchFormat::Text {
+            self.output.info(&format!("🔭 Watching {} categories every {}s (Ctrl-C to stop)",
+                categories.len(),
+                self.interval.as_secs()
+            ));
+        }
+
+        // One listener for the whole loop, so Ctrl-C between selects is not lost
+        crate::utils::interrupt::handle_in_command();
+        let mut interrupted = std::pin::pin!(tokio::signal::ctrl_c());
+
+        let mut previous: Option<HealthReport> = None;
+        loop {
+            let report = tokio::select! {
+                report = self.checker.check_categories(categories) => report?,
+                _ = &mut interrupted => break,
+            };
+
+            let baseline = HealthReport::new(report.system_info.clone());
+            let changes = diff(previous.as_ref().unwrap_or(&baseline), &report);
+            for change in &changes {
+                self.print(change, &report)?;
+            }
+            if changes.is_empty() {
+                self.output.debug("No changes since the last run");
+            }
+            previous = Some(report);
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.interval) => {}
+                _ = &mut interrupted => break,
+            }
+        }
+
+        if self.format == WatchFormat::Text {
+            self.output.info("👋 Stopped watching");
+        }
+        Ok(())
+    }
+
+    fn print(&self, change: &Change, report: &HealthReport) -> Result<()> {
+        if self.format == WatchFormat::Json {
+            self.output.print(&serde_json::to_string(&ChangeLine { timestamp: report.timestamp, change })?);
+            return Ok(());
+        }
+
+        let finding = &change.finding;
+        let time = report.timestamp.with_timezone(&chrono::Local).format("%H:%M:%S");
+        let label = format!("[{}] {} / {}", time, finding.category, finding.name);
+        let message = match (change.kind, &change.previous) {
+            (ChangeKind::Resolved, _) => {
+                self.output.success(&format!("{}: resolved", label));
+                return Ok(());
+            }
+            (ChangeKind::Changed, Some(previous)) => {
+                format!("{}: {:?} → {:?}: {}", label, previous, finding.severity, finding.message)
+            }
+            _ => format!("{}: {}", label, finding.message),
+        };
+        match finding.severity {
+            Severity::Error | Severity::Critical => self.output.error(&message),
+            Severity::Warning => self.output.warn(&message),
+            _ => self.output.info(&message),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(findings: &[(&str, Severity)]) -> HealthReport {
+        let mut report = HealthReport::new(crate::doctor::SystemInfo::gather().unwrap());
+        for (name, severity) in findings {
+            report.add_finding(Finding::new("Storage", *name, severity.clone(), format!("{} is {:?}", name, severity)));
+        }
+        report
+    }
+
+    #[test]
+    fn diffs_consecutive_reports_by_category_and_name() {
+        let before = report(&[("Disk Space", Severity::Warning), ("Temp", Severity::Error), ("Inodes", Severity::Info)]);
+        let after = report(&[("Disk Space", Severity::Ok), ("Temp", Severity::Critical), ("Inodes", Severity::Info), ("Swap", Severity::Warning)]);
+
+        let changes: Vec<(ChangeKind, String)> = diff(&before, &after).into_iter()
+            .map(|change| (change.kind, change.finding.name))
+            .collect();
+        assert_eq!(changes, vec![
+            (ChangeKind::Changed, "Temp".to_string()),
+            (ChangeKind::New, "Swap".to_string()),
+            (ChangeKind::Resolved, "Disk Space".to_string()),
+        ]);
+        assert!(diff(&after, &after).is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::core::config::Config;
+use super::{Architecture, DistributionCategory, IsoDistribution, IsoVersion};
+
+/// Prefix on every custom entry's name, so none can shadow a built-in
+pub const CUSTOM_PREFIX: &str = "custom/";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomCatalog {
+    #[serde(default)]
+    distribution: Vec<CustomDistribution>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomDistribution {
+    name: String,
+    display_name: Option<String>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    homepage: String,
+    category: Option<DistributionCategory>,
+    #[serde(default)]
+    version: Vec<CustomVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomVersion {
+    version: String,
+    architecture: String,
+    url: String,
+    checksum_url: Option<String>,
+    signature_url: Option<String>,
+    flavor: Option<String>,
+    #[serde(default)]
+    size_mb: u64,
+    #[serde(default)]
+    current: bool,
+}
+
+/// isos.toml in the config directory
+pub fn custom_catalog_path() -> Result<PathBuf> {
+    Ok(Config::get_config_dir()?.join("isos.toml"))
+}
+
+/// Custom distributions from isos.toml, or none if the file does not exist
+pub fn load_custom_distributions() -> Result<Vec<IsoDistribution>> {
+    load_from(&custom_catalog_path()?)
+}
+
+pub fn load_from(path: &Path) -> Result<Vec<IsoDistribution>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_custom(&content).with_context(|| format!("Invalid custom ISO catalog {}", path.display()))
+}
+
+/// Validate custom entries and turn them into catalog entries named
+/// `custom/<name>`
+pub fn parse_custom(content: &str) -> Result<Vec<IsoDistribution>> {
+    let catalog: CustomCatalog = toml::from_str(content)?;
+    let mut distributions: Vec<IsoDistribution> = Vec::new();
+
+    for entry in catalog.distribution {
+        let name = entry.name.strip_prefix(CUSTOM_PREFIX).unwrap_or(&entry.name);
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+            anyhow::bail!("Distribution name '{}' may only contain letters, digits, '-', '_' and '.'", entry.name);
+        }
+        let name = format!("{}{}", CUSTOM_PREFIX, name);
+        if distributions.iter().any(|d| d.name == name) {
+            anyhow::bail!("Distribution '{}' is defined twice", name);
+        }
+        if entry.version.is_empty() {
+            anyhow::bail!("{}: at least one [[distribution.version]] is required", name);
+        }
+
+        let mut versions: Vec<IsoVersion> = Vec::new();
+        for custom in &entry.version {
+            let context = format!("{} {}", name, custom.version);
+            let arch = parse_architecture(&custom.architecture)
+                .with_context(|| context.clone())?;
+            check_url(&custom.url).with_context(|| format!("{}: url", context))?;
+            for url in [&custom.checksum_url, &custom.signature_url].into_iter().flatten() {
+                check_url(url).with_context(|| format!("{}: checksum or signature url", context))?;
+            }
+
+            let flavor = custom.flavor.clone().unwrap_or_else(|| "standard".to_string());
+            let key = format!("{}-{}", arch, flavor);
+            let version = match versions.iter().position(|v| v.version == custom.version) {
+                Some(index) => &mut versions[index],
+                None => {
+                    versions.push(IsoVersion {
+                        version: custom.version.clone(),
+                        codename: None,
+                        release_date: None,
+                        is_lts: false,
+                        is_current: custom.current,
+                        architectures: Vec::new(),
+                        flavors: Vec::new(),
+                        download_urls: HashMap::new(),
+                        checksum_urls: HashMap::new(),
+                        signature_urls: HashMap::new(),
+                        size_mb: custom.size_mb,
+                    });
+                    versions.last_mut().unwrap()
+                }
+            };
+            if version.download_urls.contains_key(&key) {
+                anyhow::bail!("{}: {} is listed twice", context, key);
+            }
+
+            if !version.architectures.iter().any(|a| a.to_string() == arch.to_string()) {
+                version.architectures.push(arch);
+            }
+            if !version.flavors.contains(&flavor) {
+                version.flavors.push(flavor);
+            }
+            version.is_current |= custom.current;
+            version.download_urls.insert(key.clone(), custom.url.clone());
+            if let Some(url) = &custom.checksum_url {
+                version.checksum_urls.insert(key.clone(), url.clone());
+            }
+            if let Some(url) = &custom.signature_url {
+                version.signature_urls.insert(key, url.clone());
+            }
+        }
+
+        // Without an explicit current version the first one listed is used
+        if !versions.iter().any(|v| v.is_current) {
+            versions[0].is_current = true;
+        }
+
+        distributions.push(IsoDistribution {
+            display_name: entry.display_name.unwrap_or_else(|| name.clone()),
+            name,
+            category: entry.category.unwrap_or(DistributionCategory::Other),
+            versions,
+            homepage: entry.homepage,
+            description: entry.description,
+        });
+    }
+
+    Ok(distributions)
+}
+
+fn parse_architecture(arch: &str) -> Result<Architecture> {
+    match arch.to_lowercase().as_str() {
+        "x86_64" | "amd64" => Ok(Architecture::X86_64),
+        "aarch64" | "arm64" => Ok(Architecture::Aarch64),
+        "armv7" | "armhf" => Ok(Architecture::Armv7),
+        "i686" | "i386" => Ok(Architecture::I686),
+        other => anyhow::bail!("Unknown architecture '{}' (expected x86_64, aarch64, armv7 or i686)", other),
+    }
+}
+
+fn check_url(url: &str) -> Result<()> {
+    let parsed = reqwest::Url::parse(url).with_context(|| format!("'{}' is not a URL", url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        anyhow::bail!("'{}' must be an http or https URL", url);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_entries_are_namespaced_and_validated() {
+        let distributions = parse_custom(r#"
+            [[distribution]]
+            name = "corp-desktop"
+            display_name = "Corp Desktop"
+            category = "Linux"
+
+            [[distribution.version]]
+            version = "2024.1"
+            architecture = "amd64"
+            url = "https://images.corp.example/desktop-2024.1.iso"
+            checksum_url = "https://images.corp.example/SHA256SUMS"
+
+            [[distribution.version]]
+            version = "2024.1"
+            architecture = "arm64"
+            url = "https://images.corp.example/desktop-2024.1-arm64.iso"
+        "#).unwrap();
+
+        let corp = &distributions[0];
+        assert_eq!(corp.name, "custom/corp-desktop");
+        assert_eq!(corp.versions.len(), 1);
+        let version = &corp.versions[0];
+        assert!(version.is_current);
+        assert_eq!(version.download_url(&Architecture::Aarch64, "standard").unwrap(), "https://images.corp.example/desktop-2024.1-arm64.iso");
+        assert!(version.checksum_urls.contains_key("x86_64-standard"));
+
+        let bad_arch = parse_custom(r#"
+            [[distribution]]
+            name = "corp"
+            [[distribution.version]]
+            version = "1"
+            architecture = "sparc"
+            url = "https://images.corp.example/corp.iso"
+        "#).unwrap_err();
+        assert!(format!("{:#}", bad_arch).contains("Unknown architecture 'sparc'"));
+
+        let bad_url = parse_custom(r#"
+            [[distribution]]
+            name = "corp"
+            [[distribution.version]]
+            version = "1"
+            architecture = "x86_64"
+            url = "ftp://images.corp.example/corp.iso"
+        "#).unwrap_err();
+        assert!(format!("{:#}", bad_url).contains("http or https"));
+    }
+}
@@ -3,7 +3,30 @@ use std::path::{Path, PathBuf};
 use crate::core::config::Config;
 use crate::ui::output::Output;
 use crate::utils::download::Downloader;
-use super::{distributions, verification, IsoDistribution, IsoVersion, DistributionCategory};
+use serde::Serialize;
+use crate::cache::format_size;
+use crate::commands::info::InfoFormat;
+use super::{distributions, mirrors, verification, IsoDistribution, IsoVersion, DistributionCategory};
+use crate::core::exit::Cancelled;
+
+/// `iso info --format json`: the catalogue entry with each version marked
+/// downloadable or not
+#[derive(Serialize)]
+struct DistributionJson<'a> {
+    name: &'a str,
+    display_name: &'a str,
+    category: &'a DistributionCategory,
+    homepage: &'a str,
+    description: &'a str,
+    versions: Vec<VersionJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct VersionJson<'a> {
+    #[serde(flatten)]
+    version: &'a IsoVersion,
+    downloadable: bool,
+}
 
 pub struct IsoManager {
     config: Config,
@@ -21,20 +44,31 @@ impl IsoManager {
         })
     }
 
-    /// List all supported distributions or specific distribution versions
-    pub async fn list(&self, distro: Option<String>) -> Result<()> {
-        let distributions = distributions::get_all_distributions();
+    /// List all supported distributions or specific distribution versions;
+    /// `downloadable_only` hides versions without download URLs
+    pub async fn list(&self, distro: Option<String>, downloadable_only: bool) -> Result<()> {
+        let mut distributions = super::catalog()?;
+        if downloadable_only {
+            for distro in &mut distributions {
+                distro.versions.retain(|v| v.has_downloads());
+            }
+        }
 
         if let Some(distro_name) = distro {
             // Show specific distribution
             if let Some(distro) = distributions.iter().find(|d| d.name == distro_name) {
-                self.display_distribution_details(distro);
+                if distro.versions.is_empty() {
+                    self.output.warn(&format!("{} has no versions with download URLs yet", distro.display_name));
+                } else {
+                    self.display_distribution_details(distro);
+                }
             } else {
                 self.output.error(&format!("Distribution '{}' not found", distro_name));
                 self.output.info("Use 'pkmgr iso list' to see all supported distributions");
             }
         } else {
             // Show all distributions
+            distributions.retain(|d| !d.versions.is_empty());
             self.display_all_distributions(&distributions);
         }
 
@@ -101,11 +135,12 @@ impl IsoManager {
         Ok(())
     }
 
-    /// Download ISO (current version if no version specified)
-    pub async fn install(&self, distro_name: String, version: Option<String>) -> Result<()> {
+    /// Download ISO (current version if no version specified), from `mirror`
+    /// when given or else the fastest known mirror
+    pub async fn install(&self, distro_name: String, version: Option<String>, mirror: Option<String>) -> Result<()> {
         self.output.print_header(&format!("💿 Downloading ISO: {}", distro_name));
 
-        let distributions = distributions::get_all_distributions();
+        let distributions = super::catalog()?;
 
         let distro = distributions.iter()
             .find(|d| d.name == distro_name)
@@ -132,10 +167,17 @@ impl IsoManager {
         let arch = iso_version.architectures.first()
             .ok_or_else(|| anyhow::anyhow!("No architectures available"))?;
 
-        let key = format!("{}-{}", arch, flavor);
-        let download_url = iso_version.download_urls.get(&key)
-            .or_else(|| iso_version.download_urls.values().next())
-            .ok_or_else(|| anyhow::anyhow!("No download URL available for this version"))?;
+        let canonical_url = iso_version.download_url(arch, flavor).ok_or_else(|| {
+            let reason = if iso_version.has_downloads() {
+                "this architecture is not published"
+            } else {
+                "this version is listed for reference only"
+            };
+            anyhow::anyhow!(
+                "No download URL available for {} {} {} ({}); see 'pkmgr iso list --downloadable-only'",
+                distro_name, iso_version.version, arch, reason
+            )
+        })?;
 
         // Determine download path based on category
         // Structure: linux/{desktop,server,security,utility,minimal,specialty}/{name}-{ver}-{arch}.iso
@@ -168,7 +210,7 @@ impl IsoManager {
 
         let iso_filename = format!(
             "{}-{}-{}.iso",
-            distro_name,
+            distro_name.replace('/', "-"),
             iso_version.version,
             arch
         );
@@ -187,21 +229,27 @@ impl IsoManager {
         self.output.info(&format!("📦 Flavor: {}", flavor));
         self.output.info(&format!("🏗️ Architecture: {}", arch));
         self.output.info(&format!("💾 Size: {} MB", iso_version.size_mb));
+        let download_url = &self.select_mirror(&distro.name, canonical_url, mirror.as_deref()).await;
         self.output.info(&format!("🌐 URL: {}", download_url));
 
         // Download the ISO
-        let downloader = Downloader::new(self.config.defaults.emoji_enabled)?;
+        let downloader = Downloader::new(self.config.defaults.emoji_enabled.enabled())?;
 
         self.output.download_start(&iso_filename, Some(iso_version.size_mb * 1_000_000));
 
         // Download with retry logic as specified
+        let segments = if self.config.network.segmented_downloads {
+            self.config.network.parallel_downloads.max(1) as usize
+        } else {
+            1
+        };
         let mut retry_count = 0;
         loop {
-            match downloader.download_file(download_url, &iso_path).await {
+            match downloader.download_segmented(download_url, &iso_path, segments).await {
                 Ok(_) => break,
                 Err(e) => {
                     retry_count += 1;
-                    if retry_count >= 3 {
+                    if retry_count >= 3 || !crate::utils::download::is_transient(&e) {
                         return Err(e);
                     }
                     self.output.warn(&format!("Download failed, retrying... (attempt {}/3)", retry_count + 1));
@@ -225,7 +273,7 @@ impl IsoManager {
                 // Handle failed verification
                 if verification::handle_failed_verification(&iso_path, &self.output, retry_count).await? {
                     // Retry download
-                    return Box::pin(self.install(distro_name, Some(iso_version.version.clone()))).await;
+                    return Box::pin(self.install(distro_name, Some(iso_version.version.clone()), mirror)).await;
                 } else {
                     return Err(anyhow::anyhow!("ISO verification failed"));
                 }
@@ -235,7 +283,7 @@ impl IsoManager {
             if !verification::handle_missing_checksums(&self.output).await? {
                 // User chose not to continue
                 tokio::fs::remove_file(&iso_path).await?;
-                return Err(anyhow::anyhow!("Download cancelled by user"));
+                return Err(Cancelled("Download cancelled by user".to_string()).into());
             }
         }
 
@@ -245,6 +293,49 @@ impl IsoManager {
         Ok(())
     }
 
+    /// URL to download from: the forced mirror, the cached choice for this
+    /// distribution, or the fastest mirror after probing. Checksums are
+    /// always fetched from the canonical host
+    async fn select_mirror(&self, distro: &str, url: &str, forced: Option<&str>) -> String {
+        if let Some(mirror) = forced {
+            self.output.info(&format!("🪞 Mirror: {}", mirror));
+            return mirrors::rebase(url, mirror);
+        }
+        let Some(list) = mirrors::mirror_list(url) else {
+            return url.to_string();
+        };
+        if crate::utils::download::is_offline() {
+            return url.to_string();
+        }
+
+        let mut cache = mirrors::MirrorCache::load().ok();
+        if let Some(mirror) = cache.as_ref().and_then(|cache| cache.get(distro)) {
+            self.output.debug(&format!("Using cached mirror {}", mirror));
+            return mirrors::rebase(url, mirror);
+        }
+
+        self.output.progress("🪞 Probing mirrors...");
+        let Ok(client) = crate::utils::download::http_client() else {
+            return url.to_string();
+        };
+        let probes = mirrors::probe_mirrors(&client, list, url).await;
+        match probes.first() {
+            Some(mirrors::Probe { mirror, bytes_per_sec: Some(rate) }) => {
+                self.output.info(&format!("🪞 Fastest mirror: {} ({}/s)", mirror, format_size(*rate as u64)));
+                if let Some(cache) = cache.as_mut() {
+                    if let Err(e) = cache.set(distro, mirror) {
+                        self.output.debug(&format!("Mirror choice not cached: {:#}", e));
+                    }
+                }
+                mirrors::rebase(url, mirror)
+            }
+            _ => {
+                self.output.warn(&format!("Mirror probing failed; downloading from {}", list.canonical));
+                url.to_string()
+            }
+        }
+    }
+
     /// Delete downloaded ISO file
     pub async fn remove(&self, iso_file: String) -> Result<()> {
         self.output.print_header(&format!("🗑️ Removing ISO: {}", iso_file));
@@ -273,7 +364,7 @@ impl IsoManager {
 
                         // Confirm deletion
                         use crate::ui::prompt::Prompt;
-                        let prompt = Prompt::new(self.config.defaults.emoji_enabled);
+                        let prompt = Prompt::new(self.config.defaults.emoji_enabled.enabled());
 
                         if prompt.confirm("Delete this ISO?")? {
                             tokio::fs::remove_file(iso_path).await?;
@@ -305,16 +396,65 @@ impl IsoManager {
         Ok(())
     }
 
-    /// Show distribution information
-    pub async fn info(&self, distro_name: String) -> Result<()> {
-        let distributions = distributions::get_all_distributions();
+    /// Show distribution information, and with `mirror_list` probe and rank
+    /// its mirrors
+    pub async fn info(&self, distro_name: String, format: InfoFormat, mirror_list: bool) -> Result<()> {
+        let distributions = super::catalog()?;
 
         let distro = distributions.iter()
             .find(|d| d.name == distro_name)
             .ok_or_else(|| anyhow::anyhow!("Distribution '{}' not found", distro_name))?;
 
+        if let InfoFormat::Json = format {
+            let json = DistributionJson {
+                name: &distro.name,
+                display_name: &distro.display_name,
+                category: &distro.category,
+                homepage: &distro.homepage,
+                description: &distro.description,
+                versions: distro.versions.iter()
+                    .map(|version| VersionJson { version, downloadable: version.has_downloads() })
+                    .collect(),
+            };
+            self.output.print(&serde_json::to_string_pretty(&json)?);
+            return Ok(());
+        }
+
         self.display_distribution_info(distro);
 
+        if mirror_list {
+            self.display_mirrors(distro).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn display_mirrors(&self, distro: &IsoDistribution) -> Result<()> {
+        self.output.print_section("Mirrors");
+
+        let url = distro.versions.iter()
+            .find(|v| v.is_current)
+            .or_else(|| distro.versions.first())
+            .and_then(|v| v.download_urls.values().next());
+        let Some((url, list)) = url.and_then(|url| mirrors::mirror_list(url).map(|list| (url, list))) else {
+            self.output.info("No mirror list for this distribution; downloads use the canonical URL");
+            return Ok(());
+        };
+
+        crate::utils::download::ensure_online("probe mirrors")?;
+        let client = crate::utils::download::http_client()?;
+        let cached = mirrors::MirrorCache::load().ok();
+        let cached = cached.as_ref().and_then(|cache| cache.get(&distro.name));
+
+        for probe in mirrors::probe_mirrors(&client, list, url).await {
+            let rate = match probe.bytes_per_sec {
+                Some(rate) => format!("{}/s", format_size(rate as u64)),
+                None => "unreachable".to_string(),
+            };
+            let marker = if cached == Some(probe.mirror.as_str()) { " (selected)" } else { "" };
+            self.output.info(&format!("  {} - {}{}", probe.mirror, rate, marker));
+        }
+
         Ok(())
     }
 
@@ -372,7 +512,7 @@ impl IsoManager {
             }
 
             use crate::ui::prompt::Prompt;
-            let prompt = Prompt::new(self.config.defaults.emoji_enabled);
+            let prompt = Prompt::new(self.config.defaults.emoji_enabled.enabled());
 
             if prompt.confirm("Delete these ISOs?")? {
                 for iso in duplicates {
@@ -482,16 +622,42 @@ impl IsoManager {
         self.output.info(&format!("🌐 Homepage: {}", distro.homepage));
         self.output.info(&format!("📖 Description: {}", distro.description));
 
-        if let Some(current) = distro.versions.iter().find(|v| v.is_current) {
-            self.output.print_section("Current Version");
-            self.output.info(&format!("📦 Version: {}", current.version));
-            if let Some(codename) = &current.codename {
-                self.output.info(&format!("🏷️ Codename: {}", codename));
+        for version in &distro.versions {
+            let mut labels = Vec::new();
+            if version.is_current {
+                labels.push("current");
+            }
+            if version.is_lts {
+                labels.push("LTS");
             }
-            if let Some(date) = &current.release_date {
+            let codename = version.codename.as_ref().map(|c| format!(" \"{}\"", c)).unwrap_or_default();
+            let labels = if labels.is_empty() { String::new() } else { format!(" ({})", labels.join(", ")) };
+            self.output.print_section(&format!("Version {}{}{}", version.version, codename, labels));
+
+            if let Some(date) = &version.release_date {
                 self.output.info(&format!("📅 Released: {}", date));
             }
-            self.output.info(&format!("💾 Size: {} MB", current.size_mb));
+            let archs: Vec<String> = version.architectures.iter().map(|a| a.to_string()).collect();
+            self.output.info(&format!("🏗️ Architectures: {}", archs.join(", ")));
+            self.output.info(&format!("📦 Flavors: {}", version.flavors.join(", ")));
+            self.output.info(&format!("💾 Size: {} MB", version.size_mb));
+
+            if !version.has_downloads() {
+                self.output.warn("No download URLs yet; this version is listed for reference only");
+                continue;
+            }
+            let mut keys: Vec<&String> = version.download_urls.keys().collect();
+            keys.sort();
+            for key in keys {
+                self.output.info(&format!("🌐 {}: {}", key, version.download_urls[key]));
+                match version.checksum_urls.get(key) {
+                    Some(url) => self.output.info(&format!("   🔐 Checksums: {}", url)),
+                    None => self.output.info("   🔐 Checksums: none published"),
+                }
+                if let Some(url) = version.signature_urls.get(key) {
+                    self.output.info(&format!("   ✍️ Signature: {}", url));
+                }
+            }
         }
 
         self.output.print_section("Download Command");
@@ -0,0 +1,204 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use futures_util::future::join_all;
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use crate::core::paths::Paths;
+
+/// Mirrors carrying the same tree as a canonical download host
+pub struct MirrorList {
+    pub canonical: &'static str,
+    pub mirrors: &'static [&'static str],
+}
+
+const MIRROR_LISTS: &[MirrorList] = &[
+    MirrorList {
+        canonical: "https://releases.ubuntu.com/",
+        mirrors: &[
+            "https://mirrors.edge.kernel.org/ubuntu-releases/",
+            "https://ftp.halifax.rwth-aachen.de/ubuntu-releases/",
+            "https://mirror.aarnet.edu.au/pub/ubuntu/releases/",
+        ],
+    },
+    MirrorList {
+        canonical: "https://cdimage.debian.org/debian-cd/",
+        mirrors: &[
+            "https://mirrors.kernel.org/debian-cd/",
+            "https://ftp.acc.umu.se/debian-cd/",
+            "https://mirror.aarnet.edu.au/pub/debian-cd/",
+        ],
+    },
+    MirrorList {
+        canonical: "https://download.fedoraproject.org/pub/fedora/linux/",
+        mirrors: &[
+            "https://mirrors.kernel.org/fedora/",
+            "https://ftp.fau.de/fedora/linux/",
+            "https://mirror.aarnet.edu.au/pub/fedora/linux/",
+        ],
+    },
+    MirrorList {
+        canonical: "https://geo.mirror.pkgbuild.com/",
+        mirrors: &[
+            "https://mirrors.kernel.org/archlinux/",
+            "https://mirror.rackspace.com/archlinux/",
+            "https://ftp.fau.de/archlinux/",
+        ],
+    },
+];
+
+/// How long a chosen mirror is reused before probing again
+const MIRROR_TTL_DAYS: i64 = 7;
+
+/// Bytes fetched from each candidate to measure throughput
+const PROBE_BYTES: u64 = 256 * 1024;
+
+/// How long each probe may take
+const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Mirror list whose canonical host serves `url`
+pub fn mirror_list(url: &str) -> Option<&'static MirrorList> {
+    MIRROR_LISTS.iter().find(|list| url.starts_with(list.canonical))
+}
+
+/// `url` served from `mirror`: the path below the canonical host is kept,
+/// or the whole URL path when the host has no known mirror list
+pub fn rebase(url: &str, mirror: &str) -> String {
+    let relative = match mirror_list(url) {
+        Some(list) => url[list.canonical.len()..].to_string(),
+        None => reqwest::Url::parse(url)
+            .map(|parsed| parsed.path().trim_start_matches('/').to_string())
+            .unwrap_or_else(|_| url.to_string()),
+    };
+    format!("{}/{}", mirror.trim_end_matches('/'), relative)
+}
+
+/// Throughput of one candidate, or None if it failed or timed out
+#[derive(Debug, Clone)]
+pub struct Probe {
+    pub mirror: String,
+    pub bytes_per_sec: Option<f64>,
+}
+
+/// Fetch the start of `url` from the canonical host and every mirror at
+/// once, fastest first
+pub async fn probe_mirrors(client: &Client, list: &MirrorList, url: &str) -> Vec<Probe> {
+    let candidates: Vec<&str> = std::iter::once(list.canonical).chain(list.mirrors.iter().copied()).collect();
+    let probes = candidates.iter().map(|mirror| {
+        let target = rebase(url, mirror);
+        async move {
+            let measured = tokio::time::timeout(PROBE_TIMEOUT, measure(client, &target)).await;
+            Probe { mirror: mirror.to_string(), bytes_per_sec: measured.ok().flatten() }
+        }
+    });
+
+    let mut results = join_all(probes).await;
+    results.sort_by(|a, b| {
+        b.bytes_per_sec.unwrap_or(0.0).total_cmp(&a.bytes_per_sec.unwrap_or(0.0))
+    });
+    results
+}
+
+/// Bytes per second for the first PROBE_BYTES of `url`
+async fn measure(client: &Client, url: &str) -> Option<f64> {
+    let started = Instant::now();
+    let response = client.get(url)
+        .header(reqwest::header::RANGE, format!("bytes=0-{}", PROBE_BYTES - 1))
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+
+    let mut received = 0u64;
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        received += chunk.ok()?.len() as u64;
+        if received >= PROBE_BYTES {
+            break;
+        }
+    }
+
+    let elapsed = started.elapsed().as_secs_f64();
+    (received > 0 && elapsed > 0.0).then(|| received as f64 / elapsed)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChosenMirror {
+    mirror: String,
+    chosen: DateTime<Utc>,
+}
+
+/// Fastest mirror per distribution, reused for MIRROR_TTL_DAYS
+pub struct MirrorCache {
+    path: PathBuf,
+    entries: HashMap<String, ChosenMirror>,
+}
+
+impl MirrorCache {
+    pub fn load() -> Result<Self> {
+        Ok(Self::load_from(&Paths::resolve()?.cache_dir.join("iso").join("mirrors.json")))
+    }
+
+    /// An unreadable cache is treated as empty; it is only a shortcut
+    pub fn load_from(path: &Path) -> Self {
+        let entries = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path: path.to_path_buf(), entries }
+    }
+
+    /// Mirror chosen for `distro` within the TTL
+    pub fn get(&self, distro: &str) -> Option<&str> {
+        self.entries.get(distro)
+            .filter(|entry| Utc::now() - entry.chosen < Duration::days(MIRROR_TTL_DAYS))
+            .map(|entry| entry.mirror.as_str())
+    }
+
+    pub fn set(&mut self, distro: &str, mirror: &str) -> Result<()> {
+        self.entries.insert(distro.to_string(), ChosenMirror { mirror: mirror.to_string(), chosen: Utc::now() });
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebase_keeps_the_path_below_the_canonical_host() {
+        let url = "https://download.fedoraproject.org/pub/fedora/linux/releases/39/Workstation/x86_64/iso/Fedora.iso";
+        assert_eq!(
+            rebase(url, "https://mirrors.kernel.org/fedora"),
+            "https://mirrors.kernel.org/fedora/releases/39/Workstation/x86_64/iso/Fedora.iso"
+        );
+        assert_eq!(
+            rebase("https://example.org/isos/os.iso", "https://mirror.example.net/"),
+            "https://mirror.example.net/isos/os.iso"
+        );
+    }
+
+    #[test]
+    fn cached_choice_expires_after_the_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mirrors.json");
+
+        let mut cache = MirrorCache::load_from(&path);
+        cache.set("fedora", "https://mirrors.kernel.org/fedora/").unwrap();
+        assert_eq!(MirrorCache::load_from(&path).get("fedora"), Some("https://mirrors.kernel.org/fedora/"));
+
+        cache.entries.get_mut("fedora").unwrap().chosen = Utc::now() - Duration::days(MIRROR_TTL_DAYS + 1);
+        assert_eq!(cache.get("fedora"), None);
+    }
+}
@@ -4,8 +4,17 @@ use serde::{Deserialize, Serialize};
 
 pub mod manager;
 pub mod distributions;
+pub mod custom;
+pub mod mirrors;
 pub mod verification;
 
+/// Built-in distributions followed by the user's own from isos.toml
+pub fn catalog() -> Result<Vec<IsoDistribution>> {
+    let mut all = distributions::get_all_distributions();
+    all.extend(custom::load_custom_distributions()?);
+    Ok(all)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IsoDistribution {
     pub name: String,
@@ -42,6 +51,27 @@ pub struct IsoVersion {
     pub size_mb: u64,
 }
 
+impl IsoVersion {
+    /// Whether this version can be downloaded; catalogue-only entries have
+    /// empty URL maps
+    pub fn has_downloads(&self) -> bool {
+        !self.download_urls.is_empty()
+    }
+
+    /// Download URL for `arch`, preferring the `{arch}-{flavor}` entry over
+    /// a bare `{arch}` or any other flavor of the same architecture
+    pub fn download_url(&self, arch: &Architecture, flavor: &str) -> Option<&String> {
+        let arch = arch.to_string();
+        let prefix = format!("{}-", arch);
+        let mut keys: Vec<&String> = self.download_urls.keys().filter(|key| key.starts_with(&prefix)).collect();
+        keys.sort();
+
+        self.download_urls.get(&format!("{}{}", prefix, flavor))
+            .or_else(|| self.download_urls.get(&arch))
+            .or_else(|| keys.first().map(|key| &self.download_urls[*key]))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Architecture {
     X86_64,
@@ -73,4 +103,18 @@ impl std::fmt::Display for DistributionCategory {
             DistributionCategory::Other => write!(f, "Other Operating Systems"),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn download_url_never_falls_back_to_another_architecture() {
+        let ubuntu = distributions::get_all_distributions().into_iter().find(|d| d.name == "ubuntu").unwrap();
+        let version = ubuntu.versions.iter().find(|v| v.has_downloads()).unwrap();
+        assert!(version.download_url(&Architecture::X86_64, "desktop").unwrap().contains("desktop"));
+        assert!(version.download_url(&Architecture::X86_64, "unknown").is_some());
+        assert!(version.download_url(&Architecture::Aarch64, "desktop").is_none());
+    }
+}
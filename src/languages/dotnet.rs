@@ -0,0 +1,181 @@
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::ui::output::Output;
+use crate::utils::logging::LoggedCommand;
+
+/// Where a .NET tool is installed
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolScope {
+    /// `dotnet tool -g`, shims in ~/.dotnet/tools
+    Global,
+    /// Pinned in a dotnet-tools.json manifest and restored per repository
+    Local(PathBuf),
+    /// `dotnet tool --tool-path`; the sudo caller's ~/.dotnet/tools, since
+    /// `--global` under sudo would install into root's home
+    Path(PathBuf),
+}
+
+impl ToolScope {
+    fn flags(&self) -> Vec<&str> {
+        match self {
+            ToolScope::Global => vec!["--global"],
+            ToolScope::Local(_) => vec!["--local"],
+            ToolScope::Path(dir) => vec!["--tool-path", dir.to_str().unwrap_or_default()],
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            ToolScope::Global => "global".to_string(),
+            ToolScope::Local(manifest) | ToolScope::Path(manifest) => manifest.display().to_string(),
+        }
+    }
+}
+
+/// The manifest `dotnet tool --local` would use: the nearest
+/// .config/dotnet-tools.json or dotnet-tools.json walking up from `start`
+pub fn find_manifest(start: &Path) -> Option<PathBuf> {
+    start.ancestors()
+        .flat_map(|dir| [dir.join(".config").join("dotnet-tools.json"), dir.join("dotnet-tools.json")])
+        .find(|candidate| candidate.is_file())
+}
+
+/// Package ids and versions from `dotnet tool list`, whose table starts
+/// with a "Package Id  Version  Commands" header and a dashed rule
+pub fn parse_tool_list(list_output: &str) -> Vec<(String, String)> {
+    list_output.lines()
+        .skip_while(|line| !line.starts_with("---"))
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?.to_string(), fields.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// .NET tool management through the dotnet CLI
+pub struct DotnetTools {
+    output: Output,
+}
+
+impl DotnetTools {
+    pub fn new(output: Output) -> Self {
+        Self { output }
+    }
+
+    /// Global unless `local`; a local install without a manifest creates one
+    /// in the current directory, while other local operations require it
+    pub fn scope(&self, local: bool, create_manifest: bool) -> Result<ToolScope> {
+        if !local {
+            return Ok(ToolScope::Global);
+        }
+
+        let cwd = std::env::current_dir()?;
+        if let Some(manifest) = find_manifest(&cwd) {
+            return Ok(ToolScope::Local(manifest));
+        }
+        if !create_manifest {
+            bail!("No dotnet-tools.json found above {}; install a tool with --local to create one", cwd.display());
+        }
+
+        run_dotnet(&["new", "tool-manifest"])?;
+        let manifest = find_manifest(&cwd).context("dotnet new tool-manifest did not create a manifest")?;
+        self.output.info(&format!("📝 Created {}", manifest.display()));
+        Ok(ToolScope::Local(manifest))
+    }
+
+    pub fn list(&self, scope: &ToolScope) -> Result<Vec<(String, String)>> {
+        Ok(parse_tool_list(&run_dotnet(&[&["tool", "list"], scope.flags().as_slice()].concat())?))
+    }
+
+    pub fn install(&self, scope: &ToolScope, package: &str, version: Option<&str>) -> Result<()> {
+        let mut args = [&["tool", "install"], scope.flags().as_slice(), &[package]].concat();
+        if let Some(version) = version {
+            args.extend(["--version", version]);
+        }
+        run_dotnet(&args)?;
+        self.output.success(&format!("Installed {} ({})", package, scope.describe()));
+        Ok(())
+    }
+
+    /// Update one tool, or every tool in the scope when `package` is None
+    pub fn update(&self, scope: &ToolScope, package: Option<&str>, version: Option<&str>) -> Result<()> {
+        let packages = match package {
+            Some(package) => vec![package.to_string()],
+            None => self.list(scope)?.into_iter().map(|(id, _)| id).collect(),
+        };
+        if packages.is_empty() {
+            self.output.info(&format!("No .NET tools installed ({})", scope.describe()));
+            return Ok(());
+        }
+
+        for package in &packages {
+            let mut args = [&["tool", "update"], scope.flags().as_slice(), &[package.as_str()]].concat();
+            if let Some(version) = version {
+                args.extend(["--version", version]);
+            }
+            run_dotnet(&args)?;
+            self.output.success(&format!("Updated {} ({})", package, scope.describe()));
+        }
+        Ok(())
+    }
+
+    pub fn remove(&self, scope: &ToolScope, package: &str) -> Result<()> {
+        run_dotnet(&[&["tool", "uninstall"], scope.flags().as_slice(), &[package]].concat())?;
+        self.output.success(&format!("Removed {} ({})", package, scope.describe()));
+        Ok(())
+    }
+}
+
+fn run_dotnet(args: &[&str]) -> Result<String> {
+    let output = Command::new("dotnet")
+        .args(args)
+        .env("DOTNET_CLI_TELEMETRY_OPTOUT", "1")
+        .env("DOTNET_NOLOGO", "1")
+        .logged_output()
+        .context("Failed to run dotnet; is the .NET SDK installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() { stdout } else { stderr };
+        bail!("dotnet {} failed: {}", args.join(" "), message.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_global_and_local_tool_tables() {
+        let global = "Package Id      Version      Commands\n\
+            -------------------------------------------\n\
+            dotnet-ef       8.0.1        dotnet-ef\n\
+            csharpier       0.27.2       dotnet-csharpier\n";
+        assert_eq!(parse_tool_list(global), vec![
+            ("dotnet-ef".to_string(), "8.0.1".to_string()),
+            ("csharpier".to_string(), "0.27.2".to_string()),
+        ]);
+
+        let local = "Package Id   Version   Commands   Manifest\n\
+            ----------------------------------------------\n\
+            cake.tool    4.0.0     dotnet-cake /src/app/.config/dotnet-tools.json\n";
+        assert_eq!(parse_tool_list(local), vec![("cake.tool".to_string(), "4.0.0".to_string())]);
+    }
+
+    #[test]
+    fn finds_nearest_manifest() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("src/app");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_manifest(&nested), None);
+
+        std::fs::create_dir_all(root.path().join(".config")).unwrap();
+        let manifest = root.path().join(".config/dotnet-tools.json");
+        std::fs::write(&manifest, "{}").unwrap();
+        assert_eq!(find_manifest(&nested), Some(manifest));
+    }
+}
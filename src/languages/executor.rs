@@ -4,6 +4,7 @@ use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::os::unix::process::CommandExt;
+use crate::languages::java;
 use crate::languages::resolver::{ResolvedVersion, VersionResolver};
 use crate::ui::output::Output;
 
@@ -12,6 +13,7 @@ pub struct LanguageExecutor {
     language: String,
     command_name: String,
     output: Output,
+    java_vendor: String,
 }
 
 impl LanguageExecutor {
@@ -20,22 +22,22 @@ impl LanguageExecutor {
             language,
             command_name,
             output,
+            java_vendor: "temurin".to_string(),
         }
     }
 
+    pub fn with_java_vendor(mut self, vendor: String) -> Self {
+        self.java_vendor = vendor;
+        self
+    }
+
     /// Execute the language command with version resolution
     pub async fn execute(&self, args: Vec<String>) -> Result<()> {
         // Check if this is a version override request
         let override_version = self.extract_version_override(&args);
 
         // Resolve version
-        let resolver = VersionResolver::new(self.language.clone(), self.output.clone());
-        let resolved = resolver.resolve_version(override_version).await?;
-
-        self.output.debug(&format!(
-            "🎯 Resolved {} version: {} ({})",
-            self.language, resolved.version, resolved.description
-        ));
+        let resolved = self.resolve(override_version).await?;
 
         // Set up environment variables
         let env_vars = self.setup_environment(&resolved)?;
@@ -46,6 +48,46 @@ impl LanguageExecutor {
         // Filter out pkmgr-specific arguments
         let filtered_args = self.filter_arguments(args);
 
+        self.exec(&executable_path, filtered_args, env_vars)
+    }
+
+    /// Run `args` (argv[0] first) with an explicit version instead of version
+    /// files; arguments pass through untouched and the version's bin/ leads
+    /// PATH so scripts and build tools it starts see the same version
+    pub async fn run(&self, version: Option<String>, args: Vec<String>) -> Result<()> {
+        let resolved = self.resolve(version).await?;
+        let mut env_vars = self.setup_environment(&resolved)?;
+
+        let mut executable_path = PathBuf::from(&self.command_name);
+        if resolved.version != "system" {
+            let bin_dir = resolved.path.join("bin");
+            let binary = bin_dir.join(self.map_command_to_binary());
+            if binary.exists() {
+                executable_path = binary;
+            }
+
+            let mut path = vec![bin_dir];
+            path.extend(env::split_paths(&env::var_os("PATH").unwrap_or_default()));
+            let path = env::join_paths(path).context("Invalid PATH")?;
+            env_vars.insert("PATH".to_string(), path.to_string_lossy().to_string());
+        }
+
+        self.exec(&executable_path, args.into_iter().skip(1).collect(), env_vars)
+    }
+
+    async fn resolve(&self, override_version: Option<String>) -> Result<ResolvedVersion> {
+        let resolver = VersionResolver::new(self.language.clone(), self.output.clone())
+            .with_java_vendor(self.java_vendor.clone());
+        let resolved = resolver.resolve_version(override_version).await?;
+
+        self.output.debug(&format!(
+            "🎯 Resolved {} version: {} ({})",
+            self.language, resolved.version, resolved.description
+        ));
+        Ok(resolved)
+    }
+
+    fn exec(&self, executable_path: &Path, filtered_args: Vec<String>, env_vars: HashMap<String, String>) -> Result<()> {
         self.output.debug(&format!(
             "🚀 Executing: {} with args: {:?}",
             executable_path.display(),
@@ -200,13 +242,20 @@ impl LanguageExecutor {
 
     /// Set up Java environment variables
     fn setup_java_env(&self, env_vars: &mut HashMap<String, String>, resolved: &ResolvedVersion) -> Result<()> {
-        if resolved.version != "system" {
-            let base_path = &resolved.path;
-            env_vars.insert("JAVA_HOME".to_string(), base_path.display().to_string());
-            env_vars.insert("JRE_HOME".to_string(),
-                format!("{}/jre", base_path.display()));
-            env_vars.insert("CLASSPATH".to_string(),
-                format!("{}/lib", base_path.display()));
+        // For the system JDK, resolved.path is the java binary on PATH
+        let java_home = if resolved.version == "system" {
+            java::home_of_binary(&resolved.path)
+        } else {
+            Some(resolved.path.clone())
+        };
+
+        if let Some(java_home) = java_home {
+            env_vars.insert("JAVA_HOME".to_string(), java_home.display().to_string());
+            // Only JDK 8 and older ship a separate jre/
+            if java_home.join("jre").is_dir() {
+                env_vars.insert("JRE_HOME".to_string(),
+                    format!("{}/jre", java_home.display()));
+            }
         }
         Ok(())
     }
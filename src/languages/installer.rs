@@ -1,27 +1,38 @@
 use anyhow::{Result, bail, Context};
 use std::path::PathBuf;
 use std::collections::HashMap;
-use reqwest;
 use tokio::fs;
 use tokio::process::Command;
 use crate::ui::output::Output;
 use crate::core::config::Config;
+use crate::languages::java::{self, JavaSpec};
 
 pub struct LanguageInstaller {
     language: String,
     output: Output,
     base_dir: PathBuf,
+    java_vendor: String,
 }
 
 impl LanguageInstaller {
     pub fn new(language: String, output: Output, config: &Config) -> Self {
-        let base_dir = PathBuf::from(&config.paths.data_dir)
+        let base_dir = config.get_data_dir()
+            .unwrap_or_else(|_| std::env::temp_dir().join("pkmgr"))
             .join("languages")
             .join(&language);
-        Self { language, output, base_dir }
+        let java_vendor = config.language_defaults.java_vendor.clone();
+        Self { language, output, base_dir, java_vendor }
     }
 
     pub async fn install_version(&self, version: &str) -> Result<PathBuf> {
+        // JDKs are installed per vendor, so a bare "21" becomes e.g. "temurin-21"
+        let version = if self.language == "java" {
+            JavaSpec::parse(version, &self.java_vendor)?.dir_name()
+        } else {
+            version.to_string()
+        };
+        let version = version.as_str();
+
         self.output.info(&format!("📥 Installing {} {}", self.language, version));
 
         // Create installation directory
@@ -36,6 +47,7 @@ impl LanguageInstaller {
             "go" => self.install_go(version, &install_path).await,
             "rust" => self.install_rust(version, &install_path).await,
             "ruby" => self.install_ruby(version, &install_path).await,
+            "java" => self.install_java(version, &install_path).await,
             _ => bail!("Unsupported language: {}", self.language),
         }?;
 
@@ -212,6 +224,51 @@ impl LanguageInstaller {
         Ok(())
     }
 
+    async fn install_java(&self, version: &str, install_path: &PathBuf) -> Result<()> {
+        if cfg!(windows) {
+            bail!("JDK installs are only supported on Linux and macOS; use winget or scoop on Windows");
+        }
+
+        let spec = JavaSpec::parse(version, &self.java_vendor)?;
+        self.output.info(&format!("☕ Installing {} JDK {}...", spec.vendor.name(), spec.version));
+
+        let download_url = spec.download_url().await?;
+        let archive_path = self.download_file(&download_url, &format!("{}.tar.gz", spec.dir_name())).await?;
+
+        // Extract next to the target so the JDK home can be moved into place
+        let staging = self.base_dir.join(format!(".{}.partial", spec.dir_name()));
+        if staging.exists() {
+            fs::remove_dir_all(&staging).await?;
+        }
+        fs::create_dir_all(&staging).await?;
+
+        self.output.info("📦 Extracting JDK...");
+        let status = Command::new("tar")
+            .args(["-xzf", &archive_path.to_string_lossy(), "-C", &staging.to_string_lossy(), "--strip-components=1"])
+            .status()
+            .await
+            .context("Failed to extract JDK archive")?;
+        if !status.success() {
+            fs::remove_dir_all(&staging).await.ok();
+            bail!("Failed to extract JDK archive: tar exited with {}", status);
+        }
+
+        let home = java::jdk_home(&staging);
+        if !home.join("bin").join("java").exists() {
+            fs::remove_dir_all(&staging).await.ok();
+            bail!("{} does not contain a JDK (no bin/java)", download_url);
+        }
+
+        fs::remove_dir_all(install_path).await?;
+        fs::rename(&home, install_path).await
+            .context("Failed to move JDK into place")?;
+        if staging.exists() {
+            fs::remove_dir_all(&staging).await?;
+        }
+
+        Ok(())
+    }
+
     async fn download_file(&self, url: &str, filename: &str) -> Result<PathBuf> {
         let temp_dir = PathBuf::from("/tmp/pkmgr");
         fs::create_dir_all(&temp_dir).await?;
@@ -220,9 +277,9 @@ impl LanguageInstaller {
 
         self.output.info(&format!("📥 Downloading from {}", url));
 
-        let client = reqwest::Client::new();
+        let client = crate::utils::download::http_client()?;
         let response = client.get(url).send().await
-            .context("Failed to download file")?;
+            .map_err(|e| crate::utils::download::request_error(e, "download file"))?;
 
         if !response.status().is_success() {
             bail!("Download failed with status: {}", response.status());
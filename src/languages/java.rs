@@ -0,0 +1,184 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// JDK distributions pkmgr can install
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JavaVendor {
+    Temurin,
+    Zulu,
+    GraalVm,
+}
+
+impl JavaVendor {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "temurin" | "adoptium" => Some(JavaVendor::Temurin),
+            "zulu" => Some(JavaVendor::Zulu),
+            "graalvm" => Some(JavaVendor::GraalVm),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            JavaVendor::Temurin => "temurin",
+            JavaVendor::Zulu => "zulu",
+            JavaVendor::GraalVm => "graalvm",
+        }
+    }
+}
+
+/// A vendor-qualified JDK version such as `temurin-21` or `zulu-17.0.9`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaSpec {
+    pub vendor: JavaVendor,
+    pub version: String,
+}
+
+impl JavaSpec {
+    /// Parse `<vendor>-<version>`; a bare version uses `default_vendor`
+    pub fn parse(spec: &str, default_vendor: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (vendor, version) = match spec.split_once('-') {
+            Some((vendor, version)) if !vendor.starts_with(|c: char| c.is_ascii_digit()) => {
+                let vendor = JavaVendor::from_name(vendor)
+                    .with_context(|| format!("Unknown Java vendor '{}' (expected temurin, zulu or graalvm)", vendor))?;
+                (vendor, version)
+            }
+            _ => {
+                let vendor = JavaVendor::from_name(default_vendor)
+                    .with_context(|| format!("Unknown default Java vendor '{}' in language_defaults.java_vendor", default_vendor))?;
+                (vendor, spec)
+            }
+        };
+
+        if !version.starts_with(|c: char| c.is_ascii_digit()) {
+            bail!("Invalid Java version '{}': expected e.g. 21, temurin-21 or zulu-17.0.9", spec);
+        }
+        Ok(Self { vendor, version: version.to_string() })
+    }
+
+    /// Feature release, e.g. 21 for 21.0.1+12
+    pub fn major(&self) -> &str {
+        self.version.split(['.', '+']).next().unwrap_or(&self.version)
+    }
+
+    /// Directory name under languages/java
+    pub fn dir_name(&self) -> String {
+        format!("{}-{}", self.vendor.name(), self.version)
+    }
+
+    /// Install directories that satisfy this spec, most specific first, so an
+    /// asdf-style `temurin-21.0.1+12.0.LTS` still resolves to `temurin-21`
+    pub fn candidates(&self) -> Vec<String> {
+        let mut names = vec![self.dir_name()];
+        if self.major() != self.version {
+            names.push(format!("{}-{}", self.vendor.name(), self.major()));
+        }
+        names
+    }
+
+    /// Archive URL for this platform, asking the vendor API where needed
+    pub async fn download_url(&self) -> Result<String> {
+        let (os, arch) = (platform_os(), platform_arch());
+        let exact = self.major() != self.version;
+        // Adoptium spells macOS "mac"
+        let adoptium_os = if os == "macos" { "mac" } else { os };
+
+        match self.vendor {
+            JavaVendor::Temurin if exact => Ok(format!(
+                "https://api.adoptium.net/v3/binary/version/jdk-{}/{}/{}/jdk/hotspot/normal/eclipse",
+                self.version.replace('+', "%2B"), adoptium_os, arch
+            )),
+            JavaVendor::Temurin => Ok(format!(
+                "https://api.adoptium.net/v3/binary/latest/{}/ga/{}/{}/jdk/hotspot/normal/eclipse",
+                self.version, adoptium_os, arch
+            )),
+            JavaVendor::Zulu => self.zulu_download_url().await,
+            JavaVendor::GraalVm => Ok(format!(
+                "https://download.oracle.com/graalvm/{}/{}/graalvm-jdk-{}_{}-{}_bin.tar.gz",
+                self.major(),
+                if exact { "archive" } else { "latest" },
+                if exact { &self.version } else { self.major() },
+                os, arch
+            )),
+        }
+    }
+
+    async fn zulu_download_url(&self) -> Result<String> {
+        let os = match platform_os() {
+            "linux" if cfg!(target_env = "musl") => "linux-musl",
+            "linux" => "linux-glibc",
+            other => other,
+        };
+        let url = format!(
+            "https://api.azul.com/metadata/v1/zulu/packages/?java_version={}&os={}&arch={}\
+             &archive_type=tar.gz&java_package_type=jdk&javafx_bundled=false\
+             &release_status=ga&availability_types=CA&latest=true&page_size=1",
+            self.version, os, platform_arch()
+        );
+
+        let client = crate::utils::download::http_client()?;
+        let response = client.get(&url).send().await
+            .map_err(|e| crate::utils::download::request_error(e, "query the Azul metadata API"))?;
+        if !response.status().is_success() {
+            bail!("Azul metadata API returned {}", response.status());
+        }
+
+        let packages: Value = response.json().await.context("Invalid response from the Azul metadata API")?;
+        packages.get(0)
+            .and_then(|package| package.get("download_url"))
+            .and_then(Value::as_str)
+            .map(String::from)
+            .with_context(|| format!("No Zulu JDK {} build for {}/{}", self.version, os, platform_arch()))
+    }
+}
+
+/// The JDK home inside an extracted archive; macOS bundles keep it under
+/// Contents/Home
+pub fn jdk_home(extracted: &Path) -> PathBuf {
+    let bundle_home = extracted.join("Contents").join("Home");
+    if bundle_home.join("bin").join("java").exists() {
+        bundle_home
+    } else {
+        extracted.to_path_buf()
+    }
+}
+
+/// JAVA_HOME for a `java` binary found on PATH, following the
+/// /usr/bin/java -> /usr/lib/jvm/<jdk>/bin/java alternatives chain
+pub fn home_of_binary(java: &Path) -> Option<PathBuf> {
+    let real = std::fs::canonicalize(java).ok()?;
+    let home = real.parent()?.parent()?;
+    home.join("bin").join("java").exists().then(|| home.to_path_buf())
+}
+
+fn platform_os() -> &'static str {
+    if cfg!(target_os = "macos") { "macos" } else { "linux" }
+}
+
+fn platform_arch() -> &'static str {
+    if cfg!(target_arch = "aarch64") { "aarch64" } else { "x64" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_vendor_qualified_and_bare_specs() {
+        let spec = JavaSpec::parse("temurin-21", "zulu").unwrap();
+        assert_eq!((spec.vendor, spec.version.as_str()), (JavaVendor::Temurin, "21"));
+
+        let spec = JavaSpec::parse("17.0.9", "zulu").unwrap();
+        assert_eq!(spec.dir_name(), "zulu-17.0.9");
+        assert_eq!(spec.candidates(), vec!["zulu-17.0.9", "zulu-17"]);
+
+        let spec = JavaSpec::parse("graalvm-21.0.1+12.0.LTS", "temurin").unwrap();
+        assert_eq!(spec.major(), "21");
+
+        assert!(JavaSpec::parse("corretto-21", "temurin").is_err());
+        assert!(JavaSpec::parse("temurin-latest", "temurin").is_err());
+    }
+}
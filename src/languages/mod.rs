@@ -1,13 +1,20 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use std::env;
 use crate::core::config::Config;
 use crate::ui::output::Output;
 
 pub mod resolver;
 pub mod installer;
+pub mod dotnet;
+pub mod java;
 mod executor;
 
 use executor::LanguageExecutor;
+use installer::LanguageInstaller;
+use resolver::VersionResolver;
+
+/// Languages whose versions pkmgr installs and resolves
+pub const LANGUAGES: &[&str] = &["python", "node", "ruby", "rust", "go", "php", "java", "dotnet"];
 
 pub async fn handle_language_command(language: &str, config: &Config, output: &Output) -> Result<()> {
     // Get the original command name from argv[0]
@@ -33,9 +40,37 @@ pub async fn handle_language_command(language: &str, config: &Config, output: &O
         language.to_string(),
         program_name,
         output.clone(),
-    );
+    ).with_java_vendor(config.language_defaults.java_vendor.clone());
 
     executor.execute(args).await?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// `pkmgr <lang> --use <version> -- <command> [args]`: run one command with an
+/// explicit version, installing it first when language_defaults.auto_install is set
+pub async fn run_with_version(language: &str, version: Option<String>, command: Vec<String>, config: &Config, output: &Output) -> Result<()> {
+    let program = command.first()
+        .with_context(|| format!("Nothing to run; use 'pkmgr {} --use <version> -- <command>'", language))?
+        .clone();
+
+    if let Some(version) = &version {
+        let resolver = VersionResolver::new(language.to_string(), output.clone())
+            .with_java_vendor(config.language_defaults.java_vendor.clone());
+        if !resolver.is_installed(version).await? {
+            if !config.language_defaults.auto_install {
+                bail!("{} {} is not installed; run 'pkmgr {} install {}' or set language_defaults.auto_install = true",
+                      language, version, language, version);
+            }
+            output.info(&format!("📥 {} {} is not installed; installing it first", language, version));
+            LanguageInstaller::new(language.to_string(), output.clone(), config)
+                .install_version(version)
+                .await?;
+        }
+    }
+
+    let executor = LanguageExecutor::new(language.to_string(), program, output.clone())
+        .with_java_vendor(config.language_defaults.java_vendor.clone());
+
+    executor.run(version, command).await
+}
@@ -4,6 +4,8 @@ use std::env;
 use std::path::{Path, PathBuf};
 use std::fs;
 use serde_json::Value;
+use crate::core::paths::Paths;
+use crate::languages::java::JavaSpec;
 use crate::ui::output::Output;
 
 /// Version resolution priority levels
@@ -31,11 +33,18 @@ pub struct ResolvedVersion {
 pub struct VersionResolver {
     language: String,
     output: Output,
+    java_vendor: String,
 }
 
 impl VersionResolver {
     pub fn new(language: String, output: Output) -> Self {
-        Self { language, output }
+        Self { language, output, java_vendor: "temurin".to_string() }
+    }
+
+    /// Vendor assumed for bare Java versions such as "21"
+    pub fn with_java_vendor(mut self, vendor: String) -> Self {
+        self.java_vendor = vendor;
+        self
     }
 
     /// Resolve version using priority order from CLAUDE.md specification
@@ -188,7 +197,11 @@ impl VersionResolver {
             if file_path.exists() {
                 let content = fs::read_to_string(file_path)
                     .context(format!("Failed to read {}", file_name))?;
-                let version = content.trim().to_string();
+                let version = if file_name == ".tool-versions" {
+                    self.tool_versions_entry(&content).unwrap_or_default()
+                } else {
+                    content.trim().to_string()
+                };
                 if !version.is_empty() {
                     return Ok(Some(version));
                 }
@@ -200,7 +213,7 @@ impl VersionResolver {
 
     /// Get version file names for the language
     fn get_version_file_names(&self) -> Vec<&str> {
-        match self.language.as_str() {
+        let mut files = match self.language.as_str() {
             "python" => vec![".python-version"],
             "node" => vec![".nvmrc", ".node-version"],
             "ruby" => vec![".ruby-version"],
@@ -210,7 +223,36 @@ impl VersionResolver {
             "java" => vec![".java-version"],
             "dotnet" => vec!["global.json"],
             _ => vec![],
+        };
+        files.push(".tool-versions");
+        files
+    }
+
+    /// First version listed for this language in an asdf/mise .tool-versions
+    fn tool_versions_entry(&self, content: &str) -> Option<String> {
+        let names: &[&str] = match self.language.as_str() {
+            "node" => &["nodejs", "node"],
+            "go" => &["golang", "go"],
+            "dotnet" => &["dotnet-core", "dotnet"],
+            language => &[language][..],
+        };
+
+        content.lines()
+            .map(|line| line.split('#').next().unwrap_or("").split_whitespace().collect::<Vec<_>>())
+            .find(|fields| fields.len() > 1 && names.contains(&fields[0]))
+            .map(|fields| fields[1].to_string())
+    }
+
+    /// Install directory names a requested version may live under; Java
+    /// versions are vendor-qualified, e.g. "21" -> "temurin-21"
+    fn install_dir_names(&self, version: &str) -> Vec<String> {
+        if self.language == "java" {
+            if let Ok(spec) = JavaSpec::parse(version, &self.java_vendor) {
+                return spec.candidates();
+            }
         }
+        // "v18", "^18" and "~18.1" select the same installs as "18" and "18.1"
+        vec![version.trim_start_matches(['v', '^', '~', '=']).to_string()]
     }
 
     /// Check project manifest files for version requirements
@@ -303,11 +345,7 @@ impl VersionResolver {
 
     /// Get user default version
     fn get_user_default(&self) -> Result<Option<String>> {
-        let home_dir = dirs::home_dir().context("Could not find home directory")?;
-        let current_file = home_dir
-            .join(".local/share/pkmgr/languages")
-            .join(&self.language)
-            .join("current");
+        let current_file = Paths::resolve()?.language_dir(&self.language).join("current");
 
         if current_file.exists() {
             let version = fs::read_to_string(current_file)?.trim().to_string();
@@ -337,30 +375,74 @@ impl VersionResolver {
 
     /// Find installed version in pkmgr-managed locations
     async fn find_installed_version(&self, version: &str) -> Result<Option<PathBuf>> {
-        // Check user installation first
-        if let Some(home_dir) = dirs::home_dir() {
-            let user_path = home_dir
-                .join(".local/share/pkmgr/languages")
-                .join(&self.language)
-                .join(version);
-
-            if self.check_version_installation(&user_path) {
-                return Ok(Some(user_path));
+        let dir_names = self.install_dir_names(version);
+        let roots = self.install_roots();
+
+        // User installation first, then system, for an exact match
+        for dir_name in &dir_names {
+            for root in &roots {
+                let path = root.join(dir_name);
+                if self.check_version_installation(&path) {
+                    return Ok(Some(path));
+                }
             }
         }
 
-        // Check system installation
-        let system_path = PathBuf::from("/usr/local/share/pkmgr/languages")
-            .join(&self.language)
-            .join(version);
-
-        if self.check_version_installation(&system_path) {
-            return Ok(Some(system_path));
+        // A partial version selects the newest matching install
+        for dir_name in &dir_names {
+            for root in &roots {
+                if let Some(path) = self.newest_matching_install(root, dir_name) {
+                    return Ok(Some(path));
+                }
+            }
         }
 
         Ok(None)
     }
 
+    /// Whether `version` resolves to a pkmgr-managed install
+    pub async fn is_installed(&self, version: &str) -> Result<bool> {
+        Ok(self.find_installed_version(version).await?.is_some())
+    }
+
+    /// Versions installed under pkmgr's language directories, by directory name
+    pub fn installed_versions(&self) -> Vec<String> {
+        let mut versions: Vec<String> = self.install_roots().iter()
+            .filter_map(|root| fs::read_dir(root).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| self.check_version_installation(&entry.path()))
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| !name.starts_with('.'))
+            .collect();
+        versions.sort_by_key(|name| version_key(name));
+        versions.dedup();
+        versions
+    }
+
+    fn install_roots(&self) -> Vec<PathBuf> {
+        let mut roots = Vec::new();
+        if let Ok(paths) = Paths::resolve() {
+            roots.push(paths.language_dir(&self.language));
+        }
+        roots.push(PathBuf::from("/usr/local/share/pkmgr/languages").join(&self.language));
+        roots
+    }
+
+    /// Newest install under `root` whose version extends `prefix`
+    /// component-wise: 18 matches 18.19.0 but not 180.1
+    fn newest_matching_install(&self, root: &Path, prefix: &str) -> Option<PathBuf> {
+        fs::read_dir(root).ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.strip_prefix(prefix).is_some_and(|rest| rest.starts_with(['.', '+', '-']))
+            })
+            .map(|entry| entry.path())
+            .filter(|path| self.check_version_installation(path))
+            .max_by_key(|path| version_key(&path.file_name().unwrap_or_default().to_string_lossy()))
+    }
+
     /// Check if a version is properly installed
     fn check_version_installation(&self, path: &Path) -> bool {
         if !path.exists() {
@@ -399,4 +481,48 @@ impl VersionResolver {
             _ => &self.language,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Numeric components for ordering install directory names
+fn version_key(name: &str) -> Vec<u64> {
+    name.split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_language_entry_from_tool_versions() {
+        let content = "nodejs 20.10.0\n# pinned JDK\njava temurin-21.0.1+12.0.LTS zulu-17 # lts\n";
+        let output = Output::new("never".to_string(), false);
+
+        let java = VersionResolver::new("java".to_string(), output.clone());
+        assert_eq!(java.tool_versions_entry(content).as_deref(), Some("temurin-21.0.1+12.0.LTS"));
+        assert_eq!(java.install_dir_names("17"), vec!["temurin-17"]);
+
+        let node = VersionResolver::new("node".to_string(), output.clone());
+        assert_eq!(node.tool_versions_entry(content).as_deref(), Some("20.10.0"));
+        assert_eq!(VersionResolver::new("ruby".to_string(), output).tool_versions_entry(content), None);
+    }
+
+    #[test]
+    fn partial_version_picks_newest_matching_install() {
+        let root = tempfile::tempdir().unwrap();
+        for version in ["18.2.0", "18.19.0", "180.1.0", "20.10.0"] {
+            let bin = root.path().join(version).join("bin");
+            std::fs::create_dir_all(&bin).unwrap();
+            std::fs::write(bin.join("node"), "").unwrap();
+        }
+
+        let resolver = VersionResolver::new("node".to_string(), Output::new("never".to_string(), false));
+        let newest = |prefix: &str| resolver.newest_matching_install(root.path(), prefix)
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string());
+        assert_eq!(newest("18").as_deref(), Some("18.19.0"));
+        assert_eq!(newest("18.2").as_deref(), Some("18.2.0"));
+        assert_eq!(newest("19"), None);
+        assert_eq!(resolver.install_dir_names("^20"), vec!["20"]);
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::env;
 use std::path::Path;
+use std::process::ExitCode;
 
 mod commands;
 mod core;
@@ -29,38 +30,19 @@ use clap::Parser;
 use crate::commands::Cli;
 use crate::core::config::Config;
 use crate::core::detector::SymlinkDetector;
+use crate::core::exit::{exit_code, Outcome};
 use crate::ui::output::Output;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging and error handling
-    std::panic::set_hook(Box::new(|panic_info| {
-        eprintln!("❌ Fatal error: {}", panic_info);
-        std::process::exit(1);
-    }));
-
-    // Handle signals for clean shutdown
-    #[cfg(unix)]
-    {
-        use tokio::signal::unix::{signal, SignalKind};
-
-        tokio::spawn(async {
-            let mut sigint = signal(SignalKind::interrupt()).expect("Failed to register SIGINT handler");
-            let mut sigterm = signal(SignalKind::terminate()).expect("Failed to register SIGTERM handler");
-
-            tokio::select! {
-                _ = sigint.recv() => {
-                    eprintln!("\n⚠️ Interrupted by user");
-                    std::process::exit(130);
-                }
-                _ = sigterm.recv() => {
-                    eprintln!("\n⚠️ Terminated");
-                    std::process::exit(143);
-                }
-            }
-        });
+async fn main() -> ExitCode {
+    let result = run().await;
+    if let Err(e) = &result {
+        eprintln!("Error: {:?}", e);
     }
+    ExitCode::from(exit_code(&result))
+}
 
+async fn run() -> Result<Outcome> {
     // Get the command name from argv[0] for symlink detection
     let program_name = env::args()
         .next()
@@ -73,27 +55,161 @@ async fn main() -> Result<()> {
         })
         .unwrap_or_else(|| "pkmgr".to_string());
 
+    // Language symlinks and the hidden completion callback don't take pkmgr
+    // flags; everything else is parsed before loading the (--config) config
+    let detector = SymlinkDetector::new();
+    let language = detector.detect_language(&program_name);
+    let args: Vec<String> = env::args().collect();
+    let completing = language.is_none() && args.get(1).map(String::as_str) == Some("__complete");
+    let cli = if language.is_none() && !completing { Some(Cli::parse()) } else { None };
+    if let Some(path) = cli.as_ref().and_then(|cli| cli.config.clone()) {
+        core::config::set_config_path(path);
+    }
+
     // Initialize configuration
-    let config = Config::load().await?;
-    let output = Output::new(config.defaults.color_output.clone(), config.defaults.emoji_enabled);
+    let mut config = Config::load().await?;
+    let output = Output::new(config.defaults.color_output.clone(), config.defaults.emoji_enabled.enabled())
+        .with_verbosity(
+            cli.as_ref().is_some_and(|cli| cli.verbose),
+            cli.as_ref().is_some_and(|cli| cli.quiet),
+        );
+    install_handlers(&output);
 
     // Check if we were called as a language command (symlink)
-    let detector = SymlinkDetector::new();
-    if let Some(language) = detector.detect_language(&program_name) {
+    if let Some(language) = language {
+        if let Some(profile) = load_profile(None, &output)? {
+            apply_profile_environment(&profile, false, &output);
+        }
         // Handle language command invocation
-        return languages::handle_language_command(language, &config, &output).await;
+        return languages::handle_language_command(language, &config, &output).await.map(|()| Outcome::Success);
     }
 
-    // Parse CLI arguments for normal pkmgr invocation
-    let cli = Cli::parse();
+    // Completion callback; skips profile setup so it prints nothing but candidates
+    let cli = match cli {
+        Some(cli) => cli,
+        None => return commands::complete::execute(args[2..].to_vec(), &output).map(|()| Outcome::Success),
+    };
+    // --profile overrides the config defaults for this invocation only
+    let profile = load_profile(cli.profile.as_deref(), &output)?;
+    if let (Some(profile), Some(_)) = (&profile, &cli.profile) {
+        profile.settings.apply_to(&mut config);
+    }
+    utils::download::set_offline(cli.offline || config.network.offline);
+    utils::download::set_timeouts(utils::download::Timeouts::from_config(&config, cli.timeout));
+    utils::download::set_proxy(utils::download::ProxySettings::from_config(&config));
+    if let Some(bundle) = utils::download::ca_bundle_path(cli.cacert.as_deref(), &config) {
+        utils::download::set_ca_bundle(bundle);
+    }
+    core::privilege::set_escalation(core::privilege::Escalation::from_config(&config.privilege.escalation)?);
+    if let Some(name) = &cli.manager {
+        let manager = core::platform::PackageManager::from_name(name)
+            .map_err(|e| core::exit::UsageError(e.to_string()))?;
+        core::platform::set_manager_override(manager)?;
+    } else if let Some(name) = &config.defaults.package_manager {
+        let applied = core::platform::PackageManager::from_name(name)
+            .and_then(core::platform::set_manager_override);
+        // config and doctor are how a bad setting gets fixed, so they only warn
+        match applied {
+            Err(e) if matches!(cli.command, Some(commands::Commands::Config(_) | commands::Commands::Doctor { .. })) => {
+                output.warn(&format!("Ignoring defaults.package_manager: {}", e));
+            }
+            Err(e) => return Err(e.context("defaults.package_manager in the config is not usable (fix it with 'pkmgr config edit')")),
+            Ok(()) => {}
+        }
+    }
+    if let Some(log_file) = &cli.log_file {
+        utils::logging::init(log_file, utils::logging::level_for(cli.verbose, cli.quiet))?;
+        let proxy_password = utils::download::proxy_settings().password();
+        for token in [binary::github::token_from_env(), config.binary_sources.github_token.clone(), proxy_password].into_iter().flatten() {
+            utils::logging::register_secret(&token);
+        }
+    }
+    if let Some(profile) = &profile {
+        apply_profile_environment(profile, cli.explain, &output);
+    }
 
     // If no command provided, show help
     if cli.command.is_none() {
         use clap::CommandFactory;
         Cli::command().print_help()?;
-        return Ok(());
+        return Ok(Outcome::Success);
+    }
+
+    // Execute the command; failures other than `fix` itself are kept for
+    // `pkmgr fix --last-error`
+    let recordable = !matches!(cli.command, Some(commands::Commands::Fix { .. }));
+    let result = commands::execute(cli, config, output).await;
+    if let Err(e) = &result {
+        log::error!("{:#}", e);
+        if recordable && exit_code(&result) == core::exit::GENERAL_ERROR {
+            let _ = commands::recovery::record_failure(&args, e);
+        }
+    }
+    result
+}
+
+/// The --profile or active profile; a broken active profile only warns,
+/// while a missing or invalid --profile stops before any work is done
+fn load_profile(name: Option<&str>, output: &Output) -> Result<Option<profile::Profile>> {
+    match profile::Profile::active(name) {
+        Ok(profile) => Ok(profile),
+        Err(e) if name.is_some() => Err(e),
+        Err(e) => {
+            output.warn(&format!("Active profile not loaded: {:#}", e));
+            Ok(None)
+        }
+    }
+}
+
+/// Export the profile's environment to every child process
+fn apply_profile_environment(profile: &profile::Profile, explain: bool, output: &Output) {
+    for (key, value) in &profile.environment {
+        if profile::is_sensitive_env(key) {
+            utils::logging::register_secret(value);
+        }
+    }
+    if explain {
+        for line in profile.display_environment() {
+            output.info(&format!("🌿 {} sets {}", profile.name, line));
+        }
     }
+    profile.apply_environment();
+}
+
+/// Panic hook and Unix signal handlers, reporting through `output` so they
+/// follow the emoji and color settings
+fn install_handlers(output: &Output) {
+    let panic_output = output.clone();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        panic_output.error(&format!("Fatal error: {}", panic_info));
+        std::process::exit(1);
+    }));
 
-    // Execute the command
-    commands::execute(cli, config, output).await
-}
\ No newline at end of file
+    // Handle signals for clean shutdown
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let output = output.clone();
+        tokio::spawn(async move {
+            let mut sigint = signal(SignalKind::interrupt()).expect("Failed to register SIGINT handler");
+            let mut sigterm = signal(SignalKind::terminate()).expect("Failed to register SIGTERM handler");
+
+            loop {
+                tokio::select! {
+                    _ = sigint.recv() => {
+                        if utils::interrupt::handled_by_command() {
+                            continue;
+                        }
+                        output.warn("Interrupted by user");
+                        std::process::exit(130);
+                    }
+                    _ = sigterm.recv() => {
+                        output.warn("Terminated");
+                        std::process::exit(143);
+                    }
+                }
+            }
+        });
+    }
+}
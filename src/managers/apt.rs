@@ -1,37 +1,23 @@
 use anyhow::{Result, Context, bail};
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use regex::Regex;
-use crate::core::{PackageManager, PackageInfo, SearchResult, InstallResult};
+use crate::core::{ChangeKind, InstallResult, Operation, PackageInfo, PackageManager, PackageUpdate, PlannedChange, SearchResult};
+use crate::core::privilege;
+use crate::utils::logging::LoggedCommand;
 
-pub struct AptManager {
-    sudo_available: bool,
-}
+pub struct AptManager;
 
 impl AptManager {
     pub fn new() -> Self {
-        Self {
-            sudo_available: Self::check_sudo_available(),
-        }
-    }
-
-    fn check_sudo_available() -> bool {
-        Command::new("sudo")
-            .args(["-n", "true"])
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .map(|status| status.success())
-            .unwrap_or(false)
+        Self
     }
 
     fn run_command(&self, cmd: &str, args: &[&str], needs_sudo: bool) -> Result<String> {
-        let mut command = if needs_sudo && self.sudo_available {
-            let mut c = Command::new("sudo");
-            c.arg(cmd);
-            c
+        let mut command = if needs_sudo {
+            privilege::privileged_command(cmd)?
         } else {
             Command::new(cmd)
         };
@@ -39,7 +25,7 @@ impl AptManager {
         command.args(args);
         command.env("DEBIAN_FRONTEND", "noninteractive");
 
-        let output = command.output()
+        let output = command.logged_output()
             .context(format!("Failed to execute {} command", cmd))?;
 
         if !output.status.success() {
@@ -50,6 +36,66 @@ impl AptManager {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
+    /// Packages from `apt list --upgradable` whose candidate comes from a
+    /// security suite, e.g. "openssl/jammy-updates,jammy-security 3.0.2 amd64 [...]"
+    fn parse_security_upgradable(&self, list_output: &str) -> Vec<String> {
+        list_output.lines()
+            .filter_map(|line| {
+                let (name, rest) = line.split_once('/')?;
+                let suites = rest.split_whitespace().next()?;
+                suites.split(',')
+                    .any(|suite| suite.ends_with("-security"))
+                    .then(|| name.to_string())
+            })
+            .collect()
+    }
+
+    /// Every line of `apt list --upgradable`, e.g.
+    /// "vim/jammy-updates 2:8.2.3995-1ubuntu2.16 amd64 [upgradable from: 2:8.2.3995-1ubuntu2.15]"
+    fn parse_upgradable(&self, list_output: &str) -> Vec<PackageUpdate> {
+        list_output.lines()
+            .filter_map(|line| {
+                let (name, rest) = line.split_once('/')?;
+                let mut fields = rest.split_whitespace();
+                let repo = fields.next()?;
+                let candidate = fields.next()?;
+                let current = rest.split_once("upgradable from: ")?.1.trim_end().trim_end_matches(']');
+                Some(PackageUpdate {
+                    name: name.to_string(),
+                    current: current.to_string(),
+                    candidate: candidate.to_string(),
+                    repo: repo.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// "Inst", "Remv" and "Purg" lines of an `apt-get -s` run, e.g.
+    /// "Inst libssl3 [3.0.2-0ubuntu1.14] (3.0.2-0ubuntu1.15 Ubuntu:22.04/jammy-updates [amd64])"
+    fn parse_simulation(&self, simulate_output: &str) -> Vec<PlannedChange> {
+        simulate_output.lines()
+            .filter_map(|line| {
+                let (action, rest) = line.split_once(' ')?;
+                let name = rest.split_whitespace().next()?;
+                let current = rest.split_once(" [")
+                    .filter(|(before, _)| !before.contains('('))
+                    .and_then(|(_, after)| after.split_once(']'))
+                    .map(|(version, _)| version);
+                let candidate = rest.split_once(" (")
+                    .and_then(|(_, after)| after.split_whitespace().next());
+
+                let kind = match (action, current, candidate) {
+                    ("Remv" | "Purg", _, _) => ChangeKind::Remove,
+                    ("Inst", None, _) => ChangeKind::Install,
+                    ("Inst", Some(current), Some(candidate)) if current == candidate => ChangeKind::Reinstall,
+                    ("Inst", Some(_), _) => ChangeKind::Upgrade,
+                    _ => return None,
+                };
+                Some(PlannedChange::new(kind, name).with_versions(current, candidate))
+            })
+            .collect()
+    }
+
     fn parse_apt_search(&self, search_output: &str) -> Vec<PackageInfo> {
         let mut packages = Vec::new();
         let mut current_package = None;
@@ -66,16 +112,11 @@ impl AptManager {
                 let package_part = &line[..slash_pos];
                 let rest = &line[slash_pos + 1..];
 
-                // Extract version if available
-                let version = if let Some(bracket_start) = rest.find('[') {
-                    if let Some(bracket_end) = rest.find(']') {
-                        rest[bracket_start + 1..bracket_end].to_string()
-                    } else {
-                        "unknown".to_string()
-                    }
-                } else {
-                    "unknown".to_string()
-                };
+                // Version follows the suites; the bracketed part is the status
+                let version = rest.split_whitespace()
+                    .nth(1)
+                    .unwrap_or("unknown")
+                    .to_string();
 
                 current_package = Some(PackageInfo {
                     name: package_part.to_string(),
@@ -84,6 +125,10 @@ impl AptManager {
                     size: None,
                     installed: rest.contains("installed"),
                     source: "apt".to_string(),
+                    homepage: None,
+                    license: None,
+                    dependencies: Vec::new(),
+                    kind: None,
                 });
             } else if line.starts_with("  ") && current_package.is_some() {
                 // Description line (indented)
@@ -100,45 +145,53 @@ impl AptManager {
         packages
     }
 
+    /// `apt show` for the candidate, or for the installed version when
+    /// that is the one shown first. apt does not report licenses
     fn parse_apt_show(&self, show_output: &str) -> Option<PackageInfo> {
-        let mut name = String::new();
-        let mut version = String::new();
-        let mut description = None;
-        let mut size = None;
-        let mut installed = false;
-
-        for line in show_output.lines() {
-            let line = line.trim();
-            if line.starts_with("Package:") {
-                name = line.split(':').nth(1)?.trim().to_string();
-            } else if line.starts_with("Version:") {
-                version = line.split(':').nth(1)?.trim().to_string();
-            } else if line.starts_with("Description:") {
-                description = Some(line.split(':').nth(1)?.trim().to_string());
-            } else if line.starts_with("Installed-Size:") {
-                if let Some(size_str) = line.split(':').nth(1) {
-                    // APT shows size in KB
-                    if let Ok(kb) = size_str.trim().parse::<u64>() {
-                        size = Some(kb * 1024);
-                    }
-                }
-            } else if line.starts_with("Status:") && line.contains("installed") {
-                installed = true;
-            }
-        }
-
-        if !name.is_empty() && !version.is_empty() {
-            Some(PackageInfo {
-                name,
-                version,
-                description,
-                size,
-                installed,
-                source: "apt".to_string(),
+        let fields = super::info_fields(show_output);
+        let field = |key: &str| super::info_field(&fields, key);
+
+        let name = field("Package")?.to_string();
+        let version = field("Version")?.to_string();
+        // "1,234 kB" from apt, plain KiB from dpkg
+        let size = field("Installed-Size").and_then(|size| {
+            let (number, unit) = size.split_once(' ').unwrap_or((size, "kB"));
+            let number: u64 = number.replace(',', "").parse().ok()?;
+            Some(match unit {
+                "MB" => number * 1024 * 1024,
+                _ => number * 1024,
             })
-        } else {
-            None
-        }
+        });
+        let installed = field("APT-Manual-Installed").is_some()
+            || field("APT-Sources").is_some_and(|sources| sources.contains("/var/lib/dpkg/status"))
+            || field("Status").is_some_and(|status| status.contains("install ok installed"));
+        let dependencies = ["Pre-Depends", "Depends"].iter()
+            .filter_map(|key| field(key))
+            .flat_map(Self::parse_depends)
+            .collect();
+
+        Some(PackageInfo {
+            name,
+            version,
+            description: field("Description").and_then(|d| d.lines().next()).map(String::from),
+            size,
+            installed,
+            source: "apt".to_string(),
+            homepage: field("Homepage").map(String::from),
+            license: None,
+            dependencies,
+            kind: None,
+        })
+    }
+
+    /// Package names of a Depends field, "libc6 (>= 2.34), default-mta | mail-transport-agent",
+    /// taking the first of each set of alternatives
+    fn parse_depends(depends: &str) -> Vec<String> {
+        depends.split(',')
+            .filter_map(|relation| relation.split('|').next())
+            .filter_map(|alternative| alternative.split_whitespace().next())
+            .map(|name| name.split(':').next().unwrap_or(name).to_string())
+            .collect()
     }
 }
 
@@ -190,29 +243,67 @@ impl PackageManager for AptManager {
         })
     }
 
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult> {
+        let mut args = vec!["purge", "-y"];
+        for package in packages {
+            args.push(package);
+        }
+
+        self.run_command("apt", &args, true)?;
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully purged {} packages", packages.len()),
+            packages_installed: packages.to_vec(),
+        })
+    }
+
     async fn update(&self) -> Result<()> {
         self.run_command("apt", &["update"], true)?;
         Ok(())
     }
 
     async fn upgrade(&self, packages: Option<&[String]>) -> Result<InstallResult> {
-        let mut args = vec!["upgrade", "-y"];
-
-        if let Some(pkgs) = packages {
-            for package in pkgs {
-                args.push(package);
+        // `apt upgrade <pkgs>` upgrades everything; only-upgrade limits the
+        // run to the named packages and never installs new ones
+        let message = match packages.filter(|p| !p.is_empty()) {
+            Some(pkgs) => {
+                let mut args = vec!["install", "--only-upgrade", "-y"];
+                args.extend(pkgs.iter().map(String::as_str));
+                self.run_command("apt-get", &args, true)?;
+                format!("Upgraded {}", pkgs.join(", "))
             }
-        }
-
-        let output = self.run_command("apt", &args, true)?;
+            None => {
+                self.run_command("apt", &["upgrade", "-y"], true)?;
+                "System upgraded successfully".to_string()
+            }
+        };
 
         Ok(InstallResult {
             success: true,
-            message: "System upgraded successfully".to_string(),
+            message,
             packages_installed: packages.map(|p| p.to_vec()).unwrap_or_default(),
         })
     }
 
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult> {
+        // Hold the excluded packages for this run only, leaving existing holds alone
+        let held = self.run_command("apt-mark", &["showhold"], false)?;
+        let to_hold: Vec<&str> = exclude.iter()
+            .map(String::as_str)
+            .filter(|name| !held.lines().any(|line| line.trim() == *name))
+            .collect();
+
+        if !to_hold.is_empty() {
+            self.run_command("apt-mark", &[&["hold"], to_hold.as_slice()].concat(), true)?;
+        }
+        let result = self.upgrade(None).await;
+        if !to_hold.is_empty() {
+            self.run_command("apt-mark", &[&["unhold"], to_hold.as_slice()].concat(), true)?;
+        }
+        result
+    }
+
     async fn list_installed(&self) -> Result<Vec<PackageInfo>> {
         let output = self.run_command("apt", &["list", "--installed"], false)?;
         let packages = self.parse_apt_search(&output);
@@ -226,6 +317,136 @@ impl PackageManager for AptManager {
         }
     }
 
+    async fn dependencies(&self, package: &str) -> Result<Vec<String>> {
+        let info = self.info(package).await?
+            .with_context(|| format!("Package '{}' not found", package))?;
+        Ok(info.dependencies)
+    }
+
+    /// `apt-get changelog`, which downloads it from the archive
+    async fn changelog(&self, package: &str) -> Result<Option<String>> {
+        let output = self.run_command("apt-get", &["changelog", package], false)?;
+        Ok(Some(output))
+    }
+
+    async fn owned_files(&self, package: &str) -> Result<Vec<PathBuf>> {
+        let status = self.run_command("dpkg-query", &["-W", "-f=${Status}", package], false)
+            .unwrap_or_default();
+        if !status.contains("install ok installed") {
+            return Ok(Vec::new());
+        }
+
+        let output = self.run_command("dpkg", &["-L", package], false)?;
+        Ok(output.lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with('/') && *line != "/.")
+            .map(PathBuf::from)
+            .filter(|path| !path.is_dir())
+            .collect())
+    }
+
+    async fn owner_of_file(&self, path: &Path) -> Result<Option<String>> {
+        // On merged-/usr systems dpkg records /bin/ls rather than /usr/bin/ls
+        let mut candidates = vec![path.to_string_lossy().to_string()];
+        if let Ok(stripped) = path.strip_prefix("/usr") {
+            candidates.push(format!("/{}", stripped.display()));
+        }
+
+        let output = match candidates.iter().find_map(|p| self.run_command("dpkg", &["-S", p], false).ok()) {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+
+        // "pkg[:arch][, pkg2]: /path"; skip diversion notices
+        Ok(output.lines()
+            .filter(|line| !line.starts_with("diversion by"))
+            .filter_map(|line| line.split_once(": ").map(|(pkgs, _)| pkgs))
+            .flat_map(|pkgs| pkgs.split(", "))
+            .map(|pkg| pkg.split(':').next().unwrap_or(pkg).to_string())
+            .next())
+    }
+
+    async fn provides_file(&self, path: &str) -> Result<Vec<String>> {
+        if which::which("apt-file").is_err() {
+            bail!("apt-file is required to search uninstalled packages; install it with 'pkmgr install apt-file' and run 'apt-file update'");
+        }
+
+        let output = match self.run_command("apt-file", &["search", "-F", path], false) {
+            Ok(output) => output,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut packages: Vec<String> = output.lines()
+            .filter_map(|line| line.split_once(": ").map(|(pkg, _)| pkg.to_string()))
+            .collect();
+        packages.dedup();
+        Ok(packages)
+    }
+
+    async fn security_updates(&self) -> Result<Vec<String>> {
+        let output = self.run_command("apt", &["list", "--upgradable"], false)?;
+        Ok(self.parse_security_upgradable(&output))
+    }
+
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>> {
+        let output = self.run_command("apt", &["list", "--upgradable"], false)?;
+        Ok(self.parse_upgradable(&output))
+    }
+
+    /// `apt-get -s`, which needs no root. Exclusions are held for real
+    /// upgrades; here the excluded packages are dropped from the plan
+    async fn simulate(&self, operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+        let (mut args, packages, exclude): (Vec<&str>, &[String], &[String]) = match operation {
+            Operation::Install(packages) => (vec!["-s", "install"], packages, &[]),
+            Operation::Remove(packages) => (vec!["-s", "remove"], packages, &[]),
+            Operation::Purge(packages) => (vec!["-s", "purge"], packages, &[]),
+            Operation::Upgrade(Some(packages)) => (vec!["-s", "install", "--only-upgrade"], packages, &[]),
+            Operation::Upgrade(None) => (vec!["-s", "upgrade"], &[], &[]),
+            Operation::UpgradeExcluding(exclude) => (vec!["-s", "upgrade"], &[], exclude),
+        };
+        args.extend(packages.iter().map(String::as_str));
+
+        let output = self.run_command("apt-get", &args, false)?;
+        let mut changes = self.parse_simulation(&output);
+        changes.retain(|change| !exclude.contains(&change.name));
+        Ok(changes)
+    }
+
+    /// What `apt-get autoremove` would remove, from its simulation's "Remv" lines
+    async fn orphans(&self) -> Result<Vec<String>> {
+        let output = self.run_command("apt-get", &["--simulate", "autoremove"], false)?;
+        Ok(output.lines()
+            .filter_map(|line| line.strip_prefix("Remv "))
+            .filter_map(|rest| rest.split_whitespace().next())
+            .map(String::from)
+            .collect())
+    }
+
+    async fn remove_orphans(&self, orphans: &[String]) -> Result<InstallResult> {
+        if !orphans.is_empty() {
+            let mut args = vec!["remove", "-y"];
+            args.extend(orphans.iter().map(String::as_str));
+            self.run_command("apt-get", &args, true)?;
+        }
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully removed {} orphaned packages", orphans.len()),
+            packages_installed: orphans.to_vec(),
+        })
+    }
+
+    async fn package_cache_size(&self) -> Result<u64> {
+        Ok(super::files_size(Path::new("/var/cache/apt/archives"), |path| {
+            path.extension().is_some_and(|ext| ext == "deb")
+        }))
+    }
+
+    async fn clean_package_cache(&self) -> Result<()> {
+        self.run_command("apt-get", &["clean"], true)?;
+        Ok(())
+    }
+
     async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>> {
         let mut result = HashMap::new();
 
@@ -236,4 +457,64 @@ impl PackageManager for AptManager {
 
         Ok(result)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn security_upgradable_only_lists_security_suites() {
+        let output = "Listing... Done\n\
+            openssl/jammy-updates,jammy-security 3.0.2-0ubuntu1.15 amd64 [upgradable from: 3.0.2-0ubuntu1.14]\n\
+            vim/jammy-updates 2:8.2.3995-1ubuntu2.16 amd64 [upgradable from: 2:8.2.3995-1ubuntu2.15]\n\
+            libssl3/stable-security 3.0.11-1~deb12u2 amd64 [upgradable from: 3.0.11-1~deb12u1]\n";
+
+        assert_eq!(AptManager::new().parse_security_upgradable(output), vec!["openssl", "libssl3"]);
+
+        let updates = AptManager::new().parse_upgradable(output);
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[1], PackageUpdate {
+            name: "vim".to_string(),
+            current: "2:8.2.3995-1ubuntu2.15".to_string(),
+            candidate: "2:8.2.3995-1ubuntu2.16".to_string(),
+            repo: "jammy-updates".to_string(),
+        });
+    }
+
+    #[test]
+    fn simulation_lines_become_planned_changes() {
+        let simulated = AptManager::new().parse_simulation("NOTE: This is only a simulation!\n\
+            Remv vim-tiny [2:8.2.3995-1ubuntu2.15]\n\
+            Inst libssl3 [3.0.2-0ubuntu1.14] (3.0.2-0ubuntu1.15 Ubuntu:22.04/jammy-updates [amd64])\n\
+            Inst htop (3.0.5-7build2 Ubuntu:22.04/jammy [amd64])\n\
+            Conf htop (3.0.5-7build2 Ubuntu:22.04/jammy [amd64])\n");
+        assert_eq!(simulated, vec![
+            PlannedChange::new(ChangeKind::Remove, "vim-tiny").with_versions(Some("2:8.2.3995-1ubuntu2.15"), None),
+            PlannedChange::new(ChangeKind::Upgrade, "libssl3").with_versions(Some("3.0.2-0ubuntu1.14"), Some("3.0.2-0ubuntu1.15")),
+            PlannedChange::new(ChangeKind::Install, "htop").with_versions(None, Some("3.0.5-7build2")),
+        ]);
+    }
+
+    #[test]
+    fn apt_show_fields_are_normalized() {
+        let output = "Package: curl\n\
+            Version: 7.81.0-1ubuntu1.15\n\
+            Priority: optional\n\
+            Installed-Size: 1,234 kB\n\
+            Depends: libc6 (>= 2.34), libcurl4 (= 7.81.0-1ubuntu1.15), zlib1g:any | zlib-ng\n\
+            Homepage: https://curl.se/\n\
+            APT-Manual-Installed: yes\n\
+            APT-Sources: http://archive.ubuntu.com/ubuntu jammy-updates/main amd64 Packages\n\
+            Description: command line tool for transferring data with URL syntax\n\
+            \x20curl is a command line tool for transferring data with URL syntax.\n";
+
+        let info = AptManager::new().parse_apt_show(output).unwrap();
+        assert_eq!(info.version, "7.81.0-1ubuntu1.15");
+        assert_eq!(info.size, Some(1234 * 1024));
+        assert!(info.installed);
+        assert_eq!(info.homepage.as_deref(), Some("https://curl.se/"));
+        assert_eq!(info.description.as_deref(), Some("command line tool for transferring data with URL syntax"));
+        assert_eq!(info.dependencies, vec!["libc6", "libcurl4", "zlib1g"]);
+    }
+}
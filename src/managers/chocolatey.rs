@@ -2,10 +2,13 @@ use anyhow::{Result, Context};
 use async_trait::async_trait;
 use regex::Regex;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
-use crate::core::{PackageManager, PackageInfo, SearchResult, InstallResult};
+use crate::core::{InstallResult, Operation, PackageInfo, PackageManager, PackageUpdate, PlannedChange, SearchResult};
 use crate::ui::output::Output;
+use crate::utils::logging::LoggedAsyncCommand;
+use super::SearchRecord;
 
 pub struct ChocolateyManager {
     output: Output,
@@ -50,7 +53,7 @@ impl ChocolateyManager {
             ])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .logged_output()
             .await
             .context("Failed to execute Chocolatey installer")?;
 
@@ -69,32 +72,14 @@ impl ChocolateyManager {
             .args(args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .logged_output()
             .await
             .context("Failed to execute chocolatey command")
     }
 
     /// Parse chocolatey search output
     fn parse_search_output(&self, output: &str) -> Vec<PackageInfo> {
-        let mut packages = Vec::new();
-
-        for line in output.lines() {
-            if let Some(captures) = Regex::new(r"^(\S+)\s+(\S+)\s+(.+)$")
-                .unwrap()
-                .captures(line) {
-
-                packages.push(PackageInfo {
-                    name: captures[1].to_string(),
-                    version: captures[2].to_string(),
-                    description: Some(captures[3].to_string()),
-                    size: None,
-                    installed: false,
-                    source: "chocolatey".to_string(),
-                });
-            }
-        }
-
-        packages
+        parse_search(output).into_iter().map(|record| record.into_package("chocolatey")).collect()
     }
 
     /// Parse chocolatey list output for installed packages
@@ -113,6 +98,10 @@ impl ChocolateyManager {
                     size: None,
                     installed: true,
                     source: "chocolatey".to_string(),
+                    homepage: None,
+                    license: None,
+                    dependencies: Vec::new(),
+                    kind: None,
                 });
             }
         }
@@ -211,6 +200,11 @@ impl PackageManager for ChocolateyManager {
         }
     }
 
+    /// Chocolatey packages do not separate configuration, so purge is an uninstall
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult> {
+        self.remove(packages).await
+    }
+
     async fn update(&self) -> Result<()> {
         if !self.ensure_available().await? {
             return Err(anyhow::anyhow!("Chocolatey is not available"));
@@ -259,6 +253,30 @@ impl PackageManager for ChocolateyManager {
         }
     }
 
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult> {
+        if !self.ensure_available().await? {
+            return Err(anyhow::anyhow!("Chocolatey is not available"));
+        }
+
+        let except = format!("--except={}", exclude.join(","));
+        let output = self.execute_choco(&["upgrade", "all", "-y", &except]).await?;
+
+        if output.status.success() {
+            Ok(InstallResult {
+                success: true,
+                message: "Packages upgraded successfully".to_string(),
+                packages_installed: vec![],
+            })
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            Ok(InstallResult {
+                success: false,
+                message: format!("Upgrade failed: {}", error),
+                packages_installed: vec![],
+            })
+        }
+    }
+
     async fn list_installed(&self) -> Result<Vec<PackageInfo>> {
         if !self.ensure_available().await? {
             return Ok(vec![]);
@@ -274,20 +292,132 @@ impl PackageManager for ChocolateyManager {
         Ok(self.parse_list_output(&stdout))
     }
 
+    /// `choco info` opens with "git 2.43.0 [Approved]", then indented
+    /// " Software Site: ..." fields. It does not say whether the package is
+    /// installed
     async fn info(&self, package: &str) -> Result<Option<PackageInfo>> {
         if !self.ensure_available().await? {
             return Ok(None);
         }
 
-        let output = self.execute_choco(&["info", package, "--limit-output"]).await?;
+        let output = self.execute_choco(&["info", package]).await?;
 
         if !output.status.success() {
             return Ok(None);
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let packages = self.parse_search_output(&stdout);
-        Ok(packages.into_iter().next())
+        let Some((name, version)) = stdout.lines().find_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next().filter(|name| name.eq_ignore_ascii_case(package))?;
+            Some((name.to_string(), parts.next()?.to_string()))
+        }) else {
+            return Ok(None);
+        };
+
+        let unindented: Vec<&str> = stdout.lines().map(str::trim_start).collect();
+        let fields = super::info_fields(&unindented.join("\n"));
+        let field = |key: &str| super::info_field(&fields, key).map(String::from);
+        let installed = self.is_installed(std::slice::from_ref(&name)).await
+            .map(|installed| installed.get(&name).copied().unwrap_or(false))
+            .unwrap_or(false);
+
+        Ok(Some(PackageInfo {
+            description: field("Summary").or_else(|| field("Description")),
+            homepage: field("Software Site"),
+            license: field("Software License"),
+            name,
+            version,
+            size: None,
+            installed,
+            source: "chocolatey".to_string(),
+            dependencies: Vec::new(),
+            kind: None,
+        }))
+    }
+
+    async fn dependencies(&self, _package: &str) -> Result<Vec<String>> {
+        anyhow::bail!("Chocolatey's info output does not list dependencies")
+    }
+
+    async fn changelog(&self, _package: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn owned_files(&self, package: &str) -> Result<Vec<PathBuf>> {
+        let installed = self.is_installed(&[package.to_string()]).await?;
+        if !installed.get(package).copied().unwrap_or(false) {
+            return Ok(Vec::new());
+        }
+
+        // Chocolatey only tracks what it unpacked into its lib directory
+        let root = std::env::var("ChocolateyInstall")
+            .unwrap_or_else(|_| r"C:\ProgramData\chocolatey".to_string());
+        let lib_dir = PathBuf::from(root).join("lib").join(package);
+
+        Ok(walkdir::WalkDir::new(lib_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .collect())
+    }
+
+    async fn owner_of_file(&self, path: &Path) -> Result<Option<String>> {
+        Ok(super::package_from_layout(path, "lib"))
+    }
+
+    async fn provides_file(&self, _path: &str) -> Result<Vec<String>> {
+        anyhow::bail!("Chocolatey has no file index for packages that are not installed")
+    }
+
+    async fn security_updates(&self) -> Result<Vec<String>> {
+        anyhow::bail!("Security-only updates are not supported by Chocolatey")
+    }
+
+    /// `choco outdated --limit-output`, "name|current|available|pinned"
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>> {
+        if !self.ensure_available().await? {
+            return Ok(vec![]);
+        }
+
+        let output = self.execute_choco(&["outdated", "--limit-output"]).await?;
+        if !output.status.success() {
+            anyhow::bail!("choco outdated failed: {}", String::from_utf8_lossy(&output.stdout));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.trim().split('|');
+                Some(PackageUpdate {
+                    name: fields.next()?.to_string(),
+                    current: fields.next()?.to_string(),
+                    candidate: fields.next()?.to_string(),
+                    repo: "chocolatey".to_string(),
+                })
+            })
+            .collect())
+    }
+
+    async fn simulate(&self, _operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+        anyhow::bail!("Chocolatey has no dry-run mode that resolves dependencies")
+    }
+
+    async fn orphans(&self) -> Result<Vec<String>> {
+        anyhow::bail!("Chocolatey does not track orphaned dependencies")
+    }
+
+    async fn remove_orphans(&self, _orphans: &[String]) -> Result<InstallResult> {
+        anyhow::bail!("Chocolatey does not track orphaned dependencies")
+    }
+
+    async fn package_cache_size(&self) -> Result<u64> {
+        anyhow::bail!("Chocolatey has no package cache to clean")
+    }
+
+    async fn clean_package_cache(&self) -> Result<()> {
+        anyhow::bail!("Chocolatey has no package cache to clean")
     }
 
     async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>> {
@@ -299,4 +429,42 @@ impl PackageManager for ChocolateyManager {
             .map(|package| (package.clone(), installed_names.contains(package)))
             .collect())
     }
-}
\ No newline at end of file
+}
+
+/// `choco search --limit-output` prints `id|version` per package; warnings
+/// and the summary line have no pipe
+fn parse_search(output: &str) -> Vec<SearchRecord> {
+    output.lines()
+        .filter_map(|line| {
+            let (id, version) = line.trim().split_once('|')?;
+            if id.is_empty() || id.contains(char::is_whitespace) {
+                return None;
+            }
+            Some(SearchRecord {
+                id: id.to_string(),
+                name: id.to_string(),
+                version: version.split('|').next().unwrap_or_default().to_string(),
+                source: "chocolatey".to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_output_search_is_pipe_separated() {
+        let output = "Chocolatey v2.2.2\ngit|2.43.0\ngit.install|2.43.0\ngitextensions|4.2.1\n3 packages found.\n";
+
+        let records = parse_search(output);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1], SearchRecord {
+            id: "git.install".to_string(),
+            name: "git.install".to_string(),
+            version: "2.43.0".to_string(),
+            source: "chocolatey".to_string(),
+        });
+    }
+}
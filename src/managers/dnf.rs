@@ -1,37 +1,23 @@
 use anyhow::{Result, Context, bail};
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use regex::Regex;
-use crate::core::{PackageManager, PackageInfo, SearchResult, InstallResult};
+use crate::core::{ChangeKind, InstallResult, Operation, PackageInfo, PackageManager, PackageUpdate, PlannedChange, SearchResult};
+use crate::core::privilege;
+use crate::utils::logging::LoggedCommand;
 
-pub struct DnfManager {
-    sudo_available: bool,
-}
+pub struct DnfManager;
 
 impl DnfManager {
     pub fn new() -> Self {
-        Self {
-            sudo_available: Self::check_sudo_available(),
-        }
-    }
-
-    fn check_sudo_available() -> bool {
-        Command::new("sudo")
-            .args(["-n", "true"])
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .map(|status| status.success())
-            .unwrap_or(false)
+        Self
     }
 
     fn run_command(&self, args: &[&str], needs_sudo: bool) -> Result<String> {
-        let mut cmd = if needs_sudo && self.sudo_available {
-            let mut c = Command::new("sudo");
-            c.arg("dnf");
-            c
+        let mut cmd = if needs_sudo {
+            privilege::privileged_command("dnf")?
         } else {
             Command::new("dnf")
         };
@@ -40,7 +26,7 @@ impl DnfManager {
         cmd.arg("-y"); // Auto-confirm
         cmd.arg("--quiet"); // Minimal output
 
-        let output = cmd.output()
+        let output = cmd.logged_output()
             .context("Failed to execute dnf command")?;
 
         if !output.status.success() {
@@ -51,42 +37,34 @@ impl DnfManager {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    fn parse_package_info(&self, info_output: &str) -> Option<PackageInfo> {
-        let mut name = String::new();
-        let mut version = String::new();
-        let mut description = None;
-        let mut size = None;
-        let mut installed = false;
-
-        for line in info_output.lines() {
-            let line = line.trim();
-            if line.starts_with("Name") {
-                name = line.split(':').nth(1)?.trim().to_string();
-            } else if line.starts_with("Version") {
-                version = line.split(':').nth(1)?.trim().to_string();
-            } else if line.starts_with("Summary") {
-                description = Some(line.split(':').nth(1)?.trim().to_string());
-            } else if line.starts_with("Size") {
-                if let Some(size_str) = line.split(':').nth(1) {
-                    size = Self::parse_size(size_str.trim());
-                }
-            } else if line.contains("installed") {
-                installed = true;
-            }
-        }
-
-        if !name.is_empty() && !version.is_empty() {
-            Some(PackageInfo {
-                name,
-                version,
-                description,
-                size,
-                installed,
-                source: "dnf".to_string(),
-            })
-        } else {
-            None
-        }
+    /// `dnf info`, which lists an installed block before an available one;
+    /// the first is used. Dependencies come from a separate repoquery
+    pub(super) fn parse_package_info(&self, info_output: &str) -> Option<PackageInfo> {
+        let fields = super::info_fields(info_output);
+        let field = |key: &str| super::info_field(&fields, key);
+
+        let name = field("Name")?.to_string();
+        let version = match (field("Epoch").filter(|epoch| *epoch != "0"), field("Version")?, field("Release")) {
+            (Some(epoch), version, Some(release)) => format!("{}:{}-{}", epoch, version, release),
+            (None, version, Some(release)) => format!("{}-{}", version, release),
+            (_, version, None) => version.to_string(),
+        };
+        let installed = info_output.lines()
+            .find(|line| !line.trim().is_empty())
+            .is_some_and(|line| line.to_lowercase().starts_with("installed packages"));
+
+        Some(PackageInfo {
+            name,
+            version,
+            description: field("Summary").map(String::from),
+            size: field("Size").or_else(|| field("Installed size")).and_then(Self::parse_size),
+            installed,
+            source: "dnf".to_string(),
+            homepage: field("URL").map(String::from),
+            license: field("License").map(String::from),
+            dependencies: Vec::new(),
+            kind: None,
+        })
     }
 
     fn parse_size(size_str: &str) -> Option<u64> {
@@ -106,7 +84,125 @@ impl DnfManager {
         Some((number * multiplier as f64) as u64)
     }
 
-    fn parse_search_results(&self, search_output: &str) -> Vec<PackageInfo> {
+    /// Package names from `dnf updateinfo list`, whose rows hold the advisory,
+    /// its type and severity, and a NEVRA such as "openssl-libs-1:3.0.9-2.fc38.x86_64"
+    pub(super) fn parse_security_updateinfo(&self, updateinfo_output: &str) -> Vec<String> {
+        const ARCHES: &[&str] = &[".x86_64", ".noarch", ".aarch64", ".i686", ".ppc64le", ".s390x", ".armv7hl"];
+
+        let mut packages: Vec<String> = updateinfo_output.lines()
+            .filter_map(|line| {
+                let nevra = line.split_whitespace()
+                    .find(|field| ARCHES.iter().any(|arch| field.ends_with(arch)))?;
+                // Name is everything before the last two dashes (version, release)
+                nevra.rsplitn(3, '-').nth(2).map(str::to_string)
+            })
+            .collect();
+        packages.sort();
+        packages.dedup();
+        packages
+    }
+
+    /// Rows of `dnf check-update`, "name.arch  version  repo", up to the
+    /// obsoletes section. Long names wrap onto their own line. The current
+    /// version is filled in separately
+    pub(super) fn parse_check_update(&self, check_output: &str) -> Vec<PackageUpdate> {
+        let mut updates = Vec::new();
+        let mut wrapped: Option<&str> = None;
+
+        for line in check_output.lines() {
+            if line.starts_with("Obsoleting Packages") {
+                break;
+            }
+            let mut fields: Vec<&str> = line.split_whitespace().collect();
+            if let Some(name) = wrapped.take() {
+                fields.insert(0, name);
+            }
+            match fields.as_slice() {
+                [name] if name.contains('.') => wrapped = Some(name),
+                [name_arch, candidate, repo] => {
+                    let Some((name, _arch)) = name_arch.rsplit_once('.') else { continue };
+                    updates.push(PackageUpdate {
+                        name: name.to_string(),
+                        current: String::new(),
+                        candidate: candidate.to_string(),
+                        repo: repo.to_string(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        updates
+    }
+
+    /// Package rows of a transaction table, " name  arch  version  repo  size",
+    /// grouped under "Installing:", "Upgrading:" (yum: "Updating:"),
+    /// "Removing dependencies:" and the like, up to the transaction summary
+    pub(super) fn parse_transaction(&self, transaction_output: &str) -> Vec<PlannedChange> {
+        let mut changes = Vec::new();
+        let mut kind = None;
+        let mut wrapped: Option<&str> = None;
+
+        for line in transaction_output.lines() {
+            if line.starts_with("Transaction Summary") {
+                break;
+            }
+            if !line.starts_with(' ') {
+                let heading = line.trim_end_matches(':');
+                kind = if heading.starts_with("Installing") {
+                    Some(ChangeKind::Install)
+                } else if heading.starts_with("Upgrading") || heading.starts_with("Updating") {
+                    Some(ChangeKind::Upgrade)
+                } else if heading.starts_with("Downgrading") {
+                    Some(ChangeKind::Downgrade)
+                } else if heading.starts_with("Reinstalling") {
+                    Some(ChangeKind::Reinstall)
+                } else if heading.starts_with("Removing") {
+                    Some(ChangeKind::Remove)
+                } else {
+                    None
+                };
+                continue;
+            }
+            let Some(kind) = kind else { continue };
+
+            let mut fields: Vec<&str> = line.split_whitespace().collect();
+            if let Some(name) = wrapped.take() {
+                fields.insert(0, name);
+            }
+            match fields.as_slice() {
+                ["replacing", ..] => {}
+                [name] => wrapped = Some(name),
+                [name, _arch, version, ..] => {
+                    changes.push(PlannedChange::new(kind, *name).with_versions(None, Some(version)));
+                }
+                _ => {}
+            }
+        }
+
+        changes
+    }
+
+    /// Installed "[epoch:]version-release" by package name
+    pub(super) fn installed_versions(&self, names: &[&str]) -> Result<HashMap<String, String>> {
+        if names.is_empty() {
+            return Ok(HashMap::new());
+        }
+        // rpm exits non-zero if any name is missing, but still prints the rest
+        let output = Command::new("rpm")
+            .args(["-q", "--queryformat", "%{NAME} %|EPOCH?{%{EPOCH}:}:{}|%{VERSION}-%{RELEASE}\\n"])
+            .args(names)
+            .logged_output()
+            .context("Failed to execute rpm command")?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, version)| (name.to_string(), version.to_string()))
+            .collect())
+    }
+
+    pub(super) fn parse_search_results(&self, search_output: &str) -> Vec<PackageInfo> {
         let mut packages = Vec::new();
         let mut current_package = None;
 
@@ -130,6 +226,10 @@ impl DnfManager {
                     size: None,
                     installed: false,
                     source: "dnf".to_string(),
+                    homepage: None,
+                    license: None,
+                    dependencies: Vec::new(),
+                    kind: None,
                 });
             }
         }
@@ -140,6 +240,19 @@ impl DnfManager {
 
         packages
     }
+
+    /// Packages providing what `package` requires, by name
+    fn requires(&self, package: &str) -> Result<Vec<String>> {
+        let requires = self.run_command(&["repoquery", "--requires", "--resolve", "--queryformat", "%{name}\\n", package], false)?;
+        let mut dependencies: Vec<String> = requires.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && *line != package)
+            .map(String::from)
+            .collect();
+        dependencies.sort();
+        dependencies.dedup();
+        Ok(dependencies)
+    }
 }
 
 #[async_trait]
@@ -190,8 +303,14 @@ impl PackageManager for DnfManager {
         })
     }
 
+    /// dnf has no keep-config mode: remove already deletes unmodified config
+    /// files and saves modified ones as .rpmsave
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult> {
+        self.remove(packages).await
+    }
+
     async fn update(&self) -> Result<()> {
-        self.run_command(&["check-update"], false)?;
+        self.run_command(&["makecache"], true)?;
         Ok(())
     }
 
@@ -213,6 +332,17 @@ impl PackageManager for DnfManager {
         })
     }
 
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult> {
+        let exclude_arg = format!("--exclude={}", exclude.join(","));
+        self.run_command(&["upgrade", &exclude_arg], true)?;
+
+        Ok(InstallResult {
+            success: true,
+            message: "System upgraded successfully".to_string(),
+            packages_installed: Vec::new(),
+        })
+    }
+
     async fn list_installed(&self) -> Result<Vec<PackageInfo>> {
         let output = self.run_command(&["list", "installed"], false)?;
         let packages = self.parse_search_results(&output);
@@ -221,11 +351,205 @@ impl PackageManager for DnfManager {
 
     async fn info(&self, package: &str) -> Result<Option<PackageInfo>> {
         match self.run_command(&["info", package], false) {
-            Ok(output) => Ok(self.parse_package_info(&output)),
+            Ok(output) => {
+                let Some(mut info) = self.parse_package_info(&output) else { return Ok(None) };
+                // Best effort; repoquery fails without metadata for the package
+                if let Ok(mut dependencies) = self.requires(package) {
+                    dependencies.retain(|dependency| *dependency != info.name);
+                    info.dependencies = dependencies;
+                }
+                Ok(Some(info))
+            }
             Err(_) => Ok(None), // Package not found
         }
     }
 
+    async fn dependencies(&self, package: &str) -> Result<Vec<String>> {
+        self.requires(package)
+    }
+
+    /// `rpm -q --changelog` for installed packages, else `dnf changelog`
+    /// from repository metadata, which needs dnf-plugins-core on dnf4
+    async fn changelog(&self, package: &str) -> Result<Option<String>> {
+        let output = Command::new("rpm")
+            .args(["-q", "--changelog", package])
+            .logged_output()
+            .context("Failed to execute rpm command")?;
+        if output.status.success() {
+            return Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()));
+        }
+
+        match self.run_command(&["changelog", package], false) {
+            Ok(changelog) if !changelog.trim().is_empty() => Ok(Some(changelog)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn owned_files(&self, package: &str) -> Result<Vec<PathBuf>> {
+        let output = Command::new("rpm")
+            .args(["-ql", package])
+            .logged_output()
+            .context("Failed to execute rpm command")?;
+
+        // rpm reports "package X is not installed" with a failing status
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with('/'))
+            .map(PathBuf::from)
+            .filter(|path| !path.is_dir())
+            .collect())
+    }
+
+    async fn owner_of_file(&self, path: &Path) -> Result<Option<String>> {
+        let output = Command::new("rpm")
+            .args(["-qf", "--qf", "%{NAME}\\n"])
+            .arg(path)
+            .logged_output()
+            .context("Failed to execute rpm command")?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .map(|name| name.trim().to_string()))
+    }
+
+    async fn provides_file(&self, path: &str) -> Result<Vec<String>> {
+        let output = match self.run_command(&["repoquery", "-q", "--qf", "%{name}", "--file", path], false) {
+            Ok(output) => output,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut packages: Vec<String> = output.lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+        packages.sort();
+        packages.dedup();
+        Ok(packages)
+    }
+
+    async fn security_updates(&self) -> Result<Vec<String>> {
+        let output = self.run_command(&["updateinfo", "list", "--security", "--updates"], false)?;
+        Ok(self.parse_security_updateinfo(&output))
+    }
+
+    /// `dnf check-update`, which exits 100 when updates are available
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>> {
+        let output = Command::new("dnf")
+            .args(["check-update", "--quiet"])
+            .logged_output()
+            .context("Failed to execute dnf command")?;
+        match output.status.code() {
+            Some(0) => return Ok(Vec::new()),
+            Some(100) => {}
+            _ => bail!("DNF command failed: {}", String::from_utf8_lossy(&output.stderr)),
+        }
+
+        let mut updates = self.parse_check_update(&String::from_utf8_lossy(&output.stdout));
+        let names: Vec<&str> = updates.iter().map(|update| update.name.as_str()).collect();
+        let installed = self.installed_versions(&names)?;
+        for update in &mut updates {
+            if let Some(version) = installed.get(&update.name) {
+                update.current = version.clone();
+            }
+        }
+        Ok(updates)
+    }
+
+    /// The command with `--assumeno`, which resolves the transaction, prints
+    /// it and exits 1 without changing anything
+    async fn simulate(&self, operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+        let exclude: String;
+        let mut args: Vec<&str> = match operation {
+            Operation::Install(packages) => std::iter::once("install").chain(packages.iter().map(String::as_str)).collect(),
+            Operation::Remove(packages) | Operation::Purge(packages) => {
+                std::iter::once("remove").chain(packages.iter().map(String::as_str)).collect()
+            }
+            Operation::Upgrade(packages) => {
+                std::iter::once("upgrade").chain(packages.unwrap_or_default().iter().map(String::as_str)).collect()
+            }
+            Operation::UpgradeExcluding(packages) => {
+                exclude = format!("--exclude={}", packages.join(","));
+                vec!["upgrade", &exclude]
+            }
+        };
+        args.push("--assumeno");
+
+        let output = Command::new("dnf")
+            .args(&args)
+            .env("LANG", "C")
+            .logged_output()
+            .context("Failed to execute dnf command")?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !stdout.contains("Transaction Summary") {
+            if output.status.success() || stdout.contains("Nothing to do") {
+                return Ok(Vec::new());
+            }
+            bail!("DNF command failed: {}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        let mut changes = self.parse_transaction(&stdout);
+        let upgraded: Vec<&str> = changes.iter()
+            .filter(|change| matches!(change.kind, ChangeKind::Upgrade | ChangeKind::Downgrade))
+            .map(|change| change.name.as_str())
+            .collect();
+        let installed = self.installed_versions(&upgraded)?;
+        for change in &mut changes {
+            if change.kind == ChangeKind::Remove {
+                change.current = change.version.take();
+            } else if let Some(version) = installed.get(&change.name) {
+                change.current = Some(version.clone());
+            }
+        }
+        Ok(changes)
+    }
+
+    async fn orphans(&self) -> Result<Vec<String>> {
+        let output = self.run_command(&["repoquery", "--unneeded", "--queryformat", "%{name}\\n"], false)?;
+        Ok(output.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    async fn remove_orphans(&self, orphans: &[String]) -> Result<InstallResult> {
+        // Without the setopt, remove would also take dependencies that were
+        // never listed to the user
+        if !orphans.is_empty() {
+            let mut args = vec!["remove", "--setopt=clean_requirements_on_remove=False"];
+            args.extend(orphans.iter().map(String::as_str));
+            self.run_command(&args, true)?;
+        }
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully removed {} orphaned packages", orphans.len()),
+            packages_installed: orphans.to_vec(),
+        })
+    }
+
+    /// Cached RPMs under dnf's (and dnf5's) cache directory
+    async fn package_cache_size(&self) -> Result<u64> {
+        let is_rpm = |path: &Path| path.extension().is_some_and(|ext| ext == "rpm");
+        Ok(super::files_size(Path::new("/var/cache/dnf"), is_rpm)
+            + super::files_size(Path::new("/var/cache/libdnf5"), is_rpm))
+    }
+
+    async fn clean_package_cache(&self) -> Result<()> {
+        self.run_command(&["clean", "packages"], true)?;
+        Ok(())
+    }
+
     async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>> {
         let mut result = HashMap::new();
 
@@ -236,4 +560,97 @@ impl PackageManager for DnfManager {
 
         Ok(result)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn security_updateinfo_extracts_package_names() {
+        let output = "FEDORA-2024-1a2b3c4d5e Moderate/Sec.  openssl-libs-1:3.0.9-2.fc38.x86_64\n\
+            FEDORA-2024-1a2b3c4d5e Moderate/Sec.  openssl-1:3.0.9-2.fc38.x86_64\n\
+            FEDORA-2024-9f8e7d6c5b security   Important python3-urllib3-1.26.18-1.fc38.noarch 2024-01-05 12:00:00\n";
+
+        assert_eq!(
+            DnfManager::new().parse_security_updateinfo(output),
+            vec!["openssl", "openssl-libs", "python3-urllib3"]
+        );
+    }
+
+    #[test]
+    fn check_update_rows_survive_wrapping_and_stop_at_obsoletes() {
+        let output = "\n\
+            kernel.x86_64                       6.6.8-200.fc39                updates\n\
+            python3-some-very-long-package-name-here.noarch\n\
+            \x20                                   1.2.3-1.fc39                  updates\n\
+            Obsoleting Packages\n\
+            grub2-tools.x86_64                  1:2.06-110.fc39               updates\n";
+
+        let updates = DnfManager::new().parse_check_update(output);
+        let rows: Vec<(&str, &str, &str)> = updates.iter()
+            .map(|u| (u.name.as_str(), u.candidate.as_str(), u.repo.as_str()))
+            .collect();
+        assert_eq!(rows, vec![
+            ("kernel", "6.6.8-200.fc39", "updates"),
+            ("python3-some-very-long-package-name-here", "1.2.3-1.fc39", "updates"),
+        ]);
+    }
+
+    #[test]
+    fn transaction_table_is_grouped_by_section() {
+        let output = "Dependencies resolved.\n\
+            ================================================================================\n\
+            \x20Package              Arch      Version              Repository          Size\n\
+            ================================================================================\n\
+            Installing:\n\
+            \x20htop                 x86_64    3.3.0-1.fc39         updates            185 k\n\
+            Upgrading:\n\
+            \x20openssl-libs         x86_64    1:3.1.1-4.fc39       updates            2.2 M\n\
+            \x20    replacing  openssl-libs.x86_64 1:3.1.1-1.fc39\n\
+            Installing dependencies:\n\
+            \x20python3-some-very-long-package-name-here\n\
+            \x20                     noarch    1.2.3-1.fc39         fedora              40 k\n\
+            \n\
+            Transaction Summary\n\
+            ================================================================================\n\
+            Install  2 Packages\n";
+
+        let changes = DnfManager::new().parse_transaction(output);
+        let rows: Vec<(ChangeKind, &str, Option<&str>)> = changes.iter()
+            .map(|c| (c.kind, c.name.as_str(), c.version.as_deref()))
+            .collect();
+        assert_eq!(rows, vec![
+            (ChangeKind::Install, "htop", Some("3.3.0-1.fc39")),
+            (ChangeKind::Upgrade, "openssl-libs", Some("1:3.1.1-4.fc39")),
+            (ChangeKind::Install, "python3-some-very-long-package-name-here", Some("1.2.3-1.fc39")),
+        ]);
+    }
+
+    #[test]
+    fn info_uses_the_installed_block() {
+        let output = "Installed Packages\n\
+            Name         : openssl-libs\n\
+            Epoch        : 1\n\
+            Version      : 3.1.1\n\
+            Release      : 4.fc39\n\
+            Size         : 5.5 M\n\
+            Repository   : @System\n\
+            Summary      : A general purpose cryptography library with TLS implementation\n\
+            URL          : http://www.openssl.org/\n\
+            License      : ASL 2.0\n\
+            Description  : OpenSSL is a toolkit for supporting cryptography.\n\
+            \x20            : The openssl-libs package contains the libraries.\n\
+            \n\
+            Available Packages\n\
+            Name         : openssl-libs\n\
+            Version      : 3.1.4\n";
+
+        let info = DnfManager::new().parse_package_info(output).unwrap();
+        assert_eq!(info.version, "1:3.1.1-4.fc39");
+        assert!(info.installed);
+        assert_eq!(info.homepage.as_deref(), Some("http://www.openssl.org/"));
+        assert_eq!(info.license.as_deref(), Some("ASL 2.0"));
+        assert_eq!(info.size, Some((5.5 * 1024.0 * 1024.0) as u64));
+    }
+}
@@ -1,9 +1,22 @@
 use anyhow::{Result, Context, bail};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use serde_json::Value;
-use crate::core::{PackageManager, PackageInfo, SearchResult, InstallResult};
+use crate::core::{ChangeKind, InstallResult, Operation, PackageInfo, PackageManager, PackageUpdate, PlannedChange, SearchResult};
+use crate::utils::logging::LoggedCommand;
+
+/// Kinds of Homebrew package, as `PackageInfo::kind`
+const FORMULA: &str = "formula";
+const CASK: &str = "cask";
+
+/// Tap prefixes that pin a name to a formula or a cask
+const CORE_TAP: &str = "homebrew/core/";
+const CASK_TAP: &str = "homebrew/cask/";
+
+/// Search hits looked up for versions and descriptions
+const SEARCH_DETAILS: usize = 50;
 
 pub struct HomebrewManager {
     sudo_available: bool,
@@ -22,7 +35,7 @@ impl HomebrewManager {
             .env("HOMEBREW_NO_AUTO_UPDATE", "1") // Disable auto-update during operations
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .logged_output()
             .context("Failed to execute brew command")?;
 
         if !output.status.success() {
@@ -33,50 +46,37 @@ impl HomebrewManager {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    fn parse_search_json(&self, json_output: &str) -> Vec<PackageInfo> {
-        let mut packages = Vec::new();
-
-        if let Ok(data) = serde_json::from_str::<Value>(json_output) {
-            if let Some(formulae) = data.get("formulae").and_then(|v| v.as_array()) {
-                for formula in formulae {
-                    if let (Some(name), Some(version), Some(desc)) = (
-                        formula.get("name").and_then(|v| v.as_str()),
-                        formula.get("version").and_then(|v| v.as_str()),
-                        formula.get("desc").and_then(|v| v.as_str()),
-                    ) {
-                        packages.push(PackageInfo {
-                            name: name.to_string(),
-                            version: version.to_string(),
-                            description: Some(desc.to_string()),
-                            size: None,
-                            installed: false,
-                            source: "homebrew".to_string(),
-                        });
-                    }
-                }
-            }
+    /// Homebrew's human sizes, e.g. "1.2GB" or "345.6KB"
+    fn parse_size(size: &str) -> Option<u64> {
+        let split = size.find(|c: char| c.is_ascii_alphabetic())?;
+        let number: f64 = size[..split].parse().ok()?;
+        let multiplier: u64 = match &size[split..] {
+            "KB" => 1024,
+            "MB" => 1024 * 1024,
+            "GB" => 1024 * 1024 * 1024,
+            "TB" => 1024 * 1024 * 1024 * 1024,
+            _ => 1,
+        };
+        Some((number * multiplier as f64) as u64)
+    }
 
-            if let Some(casks) = data.get("casks").and_then(|v| v.as_array()) {
-                for cask in casks {
-                    if let (Some(token), Some(version), Some(name)) = (
-                        cask.get("token").and_then(|v| v.as_str()),
-                        cask.get("version").and_then(|v| v.as_str()),
-                        cask.get("name").and_then(|v| v.as_array()).and_then(|arr| arr.first()).and_then(|v| v.as_str()),
-                    ) {
-                        packages.push(PackageInfo {
-                            name: token.to_string(),
-                            version: version.to_string(),
-                            description: Some(name.to_string()),
-                            size: None,
-                            installed: false,
-                            source: "homebrew".to_string(),
-                        });
-                    }
-                }
+    /// `brew search` prints formulae, then casks under "==> Casks", one
+    /// name per line when piped; without headers every hit is a formula
+    fn parse_search_sections(&self, search_output: &str) -> Vec<(String, &'static str)> {
+        let mut kind = FORMULA;
+        let mut hits = Vec::new();
+
+        for line in search_output.lines().map(str::trim) {
+            match line {
+                "==> Formulae" => kind = FORMULA,
+                "==> Casks" => kind = CASK,
+                // Hints such as "If you meant ..." are prose, not names
+                _ if line.is_empty() || line.contains(char::is_whitespace) => {}
+                name => hits.push((name.to_string(), kind)),
             }
         }
 
-        packages
+        hits
     }
 
     fn parse_list_output(&self, list_output: &str) -> Vec<PackageInfo> {
@@ -92,6 +92,10 @@ impl HomebrewManager {
                     size: None,
                     installed: true,
                     source: "homebrew".to_string(),
+                    homepage: None,
+                    license: None,
+                    dependencies: Vec::new(),
+                    kind: None,
                 });
             }
         }
@@ -99,33 +103,161 @@ impl HomebrewManager {
         packages
     }
 
-    fn parse_info_json(&self, json_output: &str) -> Option<PackageInfo> {
-        if let Ok(data) = serde_json::from_str::<Value>(json_output) {
-            if let Some(formula_array) = data.as_array() {
-                if let Some(formula) = formula_array.first() {
-                    let name = formula.get("name").and_then(|v| v.as_str())?;
-                    let version = formula.get("version").and_then(|v| v.as_str())?;
-                    let desc = formula.get("desc").and_then(|v| v.as_str()).map(|s| s.to_string());
-                    
-                    // Check if installed
-                    let installed = formula.get("installed").and_then(|v| v.as_array()).map(|arr| !arr.is_empty()).unwrap_or(false);
-
-                    return Some(PackageInfo {
-                        name: name.to_string(),
-                        version: version.to_string(),
-                        description: desc,
-                        size: None,
-                        installed,
-                        source: "homebrew".to_string(),
-                    });
+    /// Sections of an install or upgrade dry run: "==> Would install ..."
+    /// followed by space-separated names, "==> Would upgrade ..." followed by
+    /// "name current -> candidate" lines
+    fn parse_dry_run(&self, dry_run_output: &str) -> Vec<PlannedChange> {
+        let mut changes = Vec::new();
+        let mut kind = None;
+
+        for line in dry_run_output.lines() {
+            if let Some(heading) = line.strip_prefix("==> ") {
+                kind = if heading.starts_with("Would install") {
+                    Some(ChangeKind::Install)
+                } else if heading.starts_with("Would upgrade") {
+                    Some(ChangeKind::Upgrade)
+                } else {
+                    None
+                };
+                continue;
+            }
+            match (kind, line.split_whitespace().collect::<Vec<_>>().as_slice()) {
+                (Some(ChangeKind::Upgrade), [name, current, "->", candidate]) => {
+                    changes.push(PlannedChange::new(ChangeKind::Upgrade, *name).with_versions(Some(current), Some(candidate)));
+                }
+                (Some(ChangeKind::Install), names) => {
+                    changes.extend(names.iter().map(|name| PlannedChange::new(ChangeKind::Install, *name)));
                 }
+                _ => {}
+            }
+        }
+
+        changes
+    }
+
+    /// `brew outdated --json=v2`; formulae from a third-party tap carry it
+    /// in their full name, e.g. "owner/tap/formula"
+    fn parse_outdated_json(&self, json_output: &str) -> Vec<PackageUpdate> {
+        let Ok(data) = serde_json::from_str::<Value>(json_output) else {
+            return Vec::new();
+        };
+
+        let mut updates = Vec::new();
+        for (kind, default_repo) in [("formulae", "homebrew/core"), ("casks", "homebrew/cask")] {
+            for item in data.get(kind).and_then(|v| v.as_array()).into_iter().flatten() {
+                let Some(full_name) = item.get("name").and_then(|v| v.as_str()) else { continue };
+                let Some(candidate) = item.get("current_version").and_then(|v| v.as_str()) else { continue };
+                let current = match item.get("installed_versions") {
+                    Some(Value::Array(versions)) => versions.last().and_then(|v| v.as_str()).unwrap_or_default(),
+                    Some(Value::String(version)) => version.as_str(),
+                    _ => "",
+                };
+                let (repo, name) = match full_name.rsplit_once('/') {
+                    Some((tap, name)) => (tap, name),
+                    None => (default_repo, full_name),
+                };
+
+                updates.push(PackageUpdate {
+                    name: name.to_string(),
+                    current: current.to_string(),
+                    candidate: candidate.to_string(),
+                    repo: repo.to_string(),
+                });
+            }
+        }
+        updates
+    }
+
+    /// Every formula and cask in `brew info --json=v2` output, formulae first
+    fn parse_info_entries(&self, json_output: &str) -> Vec<PackageInfo> {
+        let Ok(data) = serde_json::from_str::<Value>(json_output) else { return Vec::new() };
+        let text = |entry: &Value, key: &str| entry.get(key).and_then(Value::as_str).map(String::from);
+        let names = |value: Option<&Value>| -> Vec<String> {
+            value.and_then(Value::as_array)
+                .map(|items| items.iter().filter_map(|item| item.as_str().map(String::from)).collect())
+                .unwrap_or_default()
+        };
+        let entries = |key: &str| data.get(key).and_then(Value::as_array).cloned().unwrap_or_default();
+
+        let formulae = entries("formulae").into_iter().filter_map(|formula| {
+            let installed = formula.get("installed").and_then(Value::as_array);
+            // The installed version when there is one, else the stable one
+            let version = installed.and_then(|versions| versions.last())
+                .and_then(|version| text(version, "version"))
+                .or_else(|| formula.pointer("/versions/stable").and_then(Value::as_str).map(String::from))?;
+
+            Some(PackageInfo {
+                name: text(&formula, "name")?,
+                version,
+                description: text(&formula, "desc"),
+                size: None,
+                installed: installed.is_some_and(|versions| !versions.is_empty()),
+                source: "homebrew".to_string(),
+                homepage: text(&formula, "homepage"),
+                license: text(&formula, "license"),
+                dependencies: names(formula.get("dependencies")),
+                kind: Some(FORMULA.to_string()),
+            })
+        });
+
+        let casks = entries("casks").into_iter().filter_map(|cask| {
+            let installed = text(&cask, "installed");
+            Some(PackageInfo {
+                name: text(&cask, "token")?,
+                version: installed.clone().or_else(|| text(&cask, "version"))?,
+                // The app's display name when the cask has no description
+                description: text(&cask, "desc")
+                    .or_else(|| cask.get("name").and_then(|n| n.get(0)).and_then(Value::as_str).map(String::from)),
+                size: None,
+                installed: installed.is_some(),
+                source: "homebrew".to_string(),
+                homepage: text(&cask, "homepage"),
+                license: None,
+                dependencies: names(cask.pointer("/depends_on/formula")),
+                kind: Some(CASK.to_string()),
+            })
+        });
+
+        formulae.chain(casks).collect()
+    }
+
+    /// `brew info --json=v2`, which answers with a formula or a cask
+    fn parse_info_json(&self, json_output: &str) -> Option<PackageInfo> {
+        self.parse_info_entries(json_output).into_iter().next()
+    }
+
+    /// Split names into formulae and casks. `homebrew/cask/<token>` always
+    /// names a cask; other names are casks only when no formula has the name,
+    /// as older brew releases will not find a cask without `--cask`
+    fn partition_kinds(&self, packages: &[String]) -> (Vec<String>, Vec<String>) {
+        let mut formulae = Vec::new();
+        let mut casks = Vec::new();
+
+        for package in packages {
+            if let Some(token) = package.strip_prefix(CASK_TAP) {
+                casks.push(token.to_string());
+                continue;
+            }
+            let kind = self.run_command(&["info", "--json=v2", package]).ok()
+                .and_then(|output| self.parse_info_json(&output))
+                .and_then(|info| info.kind);
+            match kind.as_deref() {
+                Some(CASK) => casks.push(package.clone()),
+                _ => formulae.push(package.trim_start_matches(CORE_TAP).to_string()),
             }
         }
 
-        None
+        (formulae, casks)
     }
 }
 
+/// A name as `brew list` prints it, without a core or cask tap prefix
+fn token(package: &str) -> &str {
+    package.strip_prefix(CASK_TAP)
+        .or_else(|| package.strip_prefix(CORE_TAP))
+        .unwrap_or(package)
+}
+
 #[async_trait]
 impl PackageManager for HomebrewManager {
     fn name(&self) -> &str {
@@ -136,22 +268,59 @@ impl PackageManager for HomebrewManager {
         which::which("brew").is_ok()
     }
 
+    /// Hits labelled formula or cask; versions and descriptions come from
+    /// one `brew info` over the first SEARCH_DETAILS hits
     async fn search(&self, query: &str) -> Result<SearchResult> {
-        let output = self.run_command(&["search", query, "--json"])?;
-        let packages = self.parse_search_json(&output);
+        let output = self.run_command(&["search", query])?;
+        let hits = self.parse_search_sections(&output);
+
+        let mut details: Vec<PackageInfo> = Vec::new();
+        if !hits.is_empty() {
+            let mut args = vec!["info", "--json=v2"];
+            args.extend(hits.iter().take(SEARCH_DETAILS).map(|(name, _)| name.as_str()));
+            if let Ok(info) = self.run_command(&args) {
+                details = self.parse_info_entries(&info);
+            }
+        }
+
+        let packages: Vec<PackageInfo> = hits.into_iter()
+            .map(|(name, kind)| {
+                let short = name.rsplit('/').next().unwrap_or(&name).to_string();
+                details.iter()
+                    .find(|info| info.name == short && info.kind.as_deref() == Some(kind))
+                    .cloned()
+                    .map(|info| PackageInfo { name: name.clone(), installed: false, ..info })
+                    .unwrap_or(PackageInfo {
+                        name,
+                        version: String::new(),
+                        description: None,
+                        size: None,
+                        installed: false,
+                        source: "homebrew".to_string(),
+                        homepage: None,
+                        license: None,
+                        dependencies: Vec::new(),
+                        kind: Some(kind.to_string()),
+                    })
+            })
+            .collect();
         let total_count = packages.len();
 
         Ok(SearchResult { packages, total_count })
     }
 
+    /// Formulae and casks install separately, casks with `--cask`
     async fn install(&self, packages: &[String]) -> Result<InstallResult> {
-        let mut args = vec!["install"];
-        for package in packages {
-            args.push(package);
+        let (formulae, casks) = self.partition_kinds(packages);
+        for (flag, names) in [("--formula", &formulae), ("--cask", &casks)] {
+            if names.is_empty() {
+                continue;
+            }
+            let mut args = vec!["install", flag];
+            args.extend(names.iter().map(String::as_str));
+            self.run_command(&args)?;
         }
 
-        let output = self.run_command(&args)?;
-
         Ok(InstallResult {
             success: true,
             message: format!("Successfully installed {} packages", packages.len()),
@@ -162,7 +331,7 @@ impl PackageManager for HomebrewManager {
     async fn remove(&self, packages: &[String]) -> Result<InstallResult> {
         let mut args = vec!["uninstall"];
         for package in packages {
-            args.push(package);
+            args.push(token(package));
         }
 
         let output = self.run_command(&args)?;
@@ -174,6 +343,12 @@ impl PackageManager for HomebrewManager {
         })
     }
 
+    /// Homebrew never removes configuration under the home directory, and
+    /// --zap only applies to casks, so purge is a plain uninstall
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult> {
+        self.remove(packages).await
+    }
+
     async fn update(&self) -> Result<()> {
         self.run_command(&["update"])?;
         Ok(())
@@ -199,25 +374,216 @@ impl PackageManager for HomebrewManager {
         })
     }
 
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult> {
+        // brew has no exclude flag; upgrade the outdated formulae by name instead
+        let outdated: Vec<String> = self.run_command(&["outdated", "--quiet"])?
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+        let (packages, _) = super::split_excluded(&outdated, exclude);
+
+        if packages.is_empty() {
+            return Ok(InstallResult {
+                success: true,
+                message: "Nothing to upgrade".to_string(),
+                packages_installed: Vec::new(),
+            });
+        }
+        self.upgrade(Some(&packages)).await
+    }
+
     async fn list_installed(&self) -> Result<Vec<PackageInfo>> {
         let output = self.run_command(&["list", "--versions"])?;
         Ok(self.parse_list_output(&output))
     }
 
     async fn info(&self, package: &str) -> Result<Option<PackageInfo>> {
-        match self.run_command(&["info", package, "--json"]) {
+        match self.run_command(&["info", "--json=v2", package]) {
             Ok(output) => Ok(self.parse_info_json(&output)),
             Err(_) => Ok(None),
         }
     }
 
+    async fn dependencies(&self, package: &str) -> Result<Vec<String>> {
+        let info = self.info(package).await?
+            .with_context(|| format!("Package '{}' not found", package))?;
+        Ok(info.dependencies)
+    }
+
+    async fn changelog(&self, _package: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn owned_files(&self, package: &str) -> Result<Vec<PathBuf>> {
+        let installed = self.is_installed(&[package.to_string()]).await?;
+        if !installed.get(package).copied().unwrap_or(false) {
+            return Ok(Vec::new());
+        }
+
+        let output = self.run_command(&["list", "--verbose", package])?;
+        Ok(output.lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with('/'))
+            .map(PathBuf::from)
+            .collect())
+    }
+
+    async fn owner_of_file(&self, path: &Path) -> Result<Option<String>> {
+        // Linked files are symlinks into <prefix>/Cellar/<formula>/<version>/...
+        Ok(super::package_from_layout(path, "Cellar")
+            .or_else(|| super::package_from_layout(path, "Caskroom")))
+    }
+
+    async fn provides_file(&self, _path: &str) -> Result<Vec<String>> {
+        bail!("Homebrew has no file index for formulae that are not installed")
+    }
+
+    async fn security_updates(&self) -> Result<Vec<String>> {
+        bail!("Security-only updates are not supported by Homebrew")
+    }
+
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>> {
+        let output = self.run_command(&["outdated", "--json=v2"])?;
+        Ok(self.parse_outdated_json(&output))
+    }
+
+    /// `brew install --dry-run` and `brew upgrade --dry-run`; uninstall has
+    /// no dry run
+    async fn simulate(&self, operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+        let outdated: Vec<String>;
+        let (command, packages) = match operation {
+            Operation::Install(packages) => ("install", packages),
+            Operation::Upgrade(packages) => ("upgrade", packages.unwrap_or_default()),
+            Operation::UpgradeExcluding(exclude) => {
+                outdated = self.upgradable().await?
+                    .into_iter()
+                    .map(|update| update.name)
+                    .filter(|name| !exclude.contains(name))
+                    .collect();
+                if outdated.is_empty() {
+                    return Ok(Vec::new());
+                }
+                ("upgrade", outdated.as_slice())
+            }
+            Operation::Remove(_) | Operation::Purge(_) => bail!("Homebrew has no dry run for uninstall"),
+        };
+
+        let mut args = vec![command, "--dry-run"];
+        args.extend(packages.iter().map(String::as_str));
+        let output = self.run_command(&args)?;
+        Ok(self.parse_dry_run(&output))
+    }
+
+    /// Formulae listed after "==> Would autoremove ..." in the dry run
+    async fn orphans(&self) -> Result<Vec<String>> {
+        let output = self.run_command(&["autoremove", "--dry-run"])?;
+        Ok(output.lines()
+            .skip_while(|line| !line.starts_with("==>"))
+            .skip(1)
+            .flat_map(str::split_whitespace)
+            .map(String::from)
+            .collect())
+    }
+
+    async fn remove_orphans(&self, orphans: &[String]) -> Result<InstallResult> {
+        if !orphans.is_empty() {
+            let mut args = vec!["uninstall"];
+            args.extend(orphans.iter().map(String::as_str));
+            self.run_command(&args)?;
+        }
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully removed {} orphaned packages", orphans.len()),
+            packages_installed: orphans.to_vec(),
+        })
+    }
+
+    /// From the dry run's "This operation would free approximately 1.2GB of disk space"
+    async fn package_cache_size(&self) -> Result<u64> {
+        let output = self.run_command(&["cleanup", "--dry-run"])?;
+        Ok(output.lines()
+            .find_map(|line| line.split("approximately ").nth(1))
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(Self::parse_size)
+            .unwrap_or(0))
+    }
+
+    async fn clean_package_cache(&self) -> Result<()> {
+        self.run_command(&["cleanup"])?;
+        Ok(())
+    }
+
     async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>> {
         let installed_packages = self.list_installed().await?;
         let installed_names: std::collections::HashSet<String> =
             installed_packages.into_iter().map(|p| p.name).collect();
 
         Ok(packages.iter()
-            .map(|package| (package.clone(), installed_names.contains(package)))
+            .map(|package| (package.clone(), installed_names.contains(token(package))))
             .collect())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outdated_json_splits_taps_from_names() {
+        let json = r#"{
+            "formulae": [
+                {"name": "git", "installed_versions": ["2.42.0"], "current_version": "2.43.0", "pinned": false},
+                {"name": "hashicorp/tap/terraform", "installed_versions": ["1.6.0"], "current_version": "1.6.6", "pinned": false}
+            ],
+            "casks": [
+                {"name": "firefox", "installed_versions": ["120.0"], "current_version": "121.0"}
+            ]
+        }"#;
+
+        let updates = HomebrewManager::new().parse_outdated_json(json);
+        let rows: Vec<(&str, &str, &str, &str)> = updates.iter()
+            .map(|u| (u.name.as_str(), u.current.as_str(), u.candidate.as_str(), u.repo.as_str()))
+            .collect();
+        assert_eq!(rows, vec![
+            ("git", "2.42.0", "2.43.0", "homebrew/core"),
+            ("terraform", "1.6.0", "1.6.6", "hashicorp/tap"),
+            ("firefox", "120.0", "121.0", "homebrew/cask"),
+        ]);
+    }
+
+    #[test]
+    fn info_json_reads_formulae_and_casks() {
+        let formula = r#"{"formulae": [{
+            "name": "wget", "desc": "Internet file retriever", "homepage": "https://www.gnu.org/software/wget/",
+            "license": "GPL-3.0-or-later", "versions": {"stable": "1.21.4"},
+            "dependencies": ["libidn2", "openssl@3"], "installed": []
+        }], "casks": []}"#;
+        let info = HomebrewManager::new().parse_info_json(formula).unwrap();
+        assert_eq!((info.name.as_str(), info.version.as_str(), info.installed), ("wget", "1.21.4", false));
+        assert_eq!(info.license.as_deref(), Some("GPL-3.0-or-later"));
+        assert_eq!(info.dependencies, vec!["libidn2", "openssl@3"]);
+
+        let cask = r#"{"formulae": [], "casks": [{
+            "token": "firefox", "desc": "Web browser", "homepage": "https://www.mozilla.org/firefox/",
+            "version": "121.0", "installed": "120.0"
+        }]}"#;
+        let info = HomebrewManager::new().parse_info_json(cask).unwrap();
+        assert_eq!((info.name.as_str(), info.version.as_str(), info.installed), ("firefox", "120.0", true));
+        assert!(info.dependencies.is_empty());
+        assert_eq!(info.kind.as_deref(), Some("cask"));
+    }
+
+    #[test]
+    fn search_sections_label_formulae_and_casks() {
+        let output = "==> Formulae\nwget\nwget2\n\n==> Casks\nwget-gui\n\nIf you meant \"wget\" precisely:\n";
+        let hits = HomebrewManager::new().parse_search_sections(output);
+        assert_eq!(hits, vec![
+            ("wget".to_string(), "formula"),
+            ("wget2".to_string(), "formula"),
+            ("wget-gui".to_string(), "cask"),
+        ]);
+        assert_eq!(token("homebrew/cask/visual-studio-code"), "visual-studio-code");
+    }
+}
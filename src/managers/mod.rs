@@ -1,17 +1,123 @@
 use anyhow::Result;
 use crate::core::{
     platform::{PlatformInfo, PackageManager as PlatformPackageManager},
-    traits::PackageManager,
+    traits::{PackageInfo, PackageManager},
 };
 
 pub mod apt;
 pub mod dnf;
+pub mod yum;
 pub mod pacman;
 pub mod homebrew;
 pub mod winget;
 pub mod chocolatey;
 pub mod scoop;
 
+/// Package name from a path laid out as `.../<component>/<name>/...`,
+/// for managers that keep each package in its own directory
+pub(crate) fn package_from_layout(path: &std::path::Path, component: &str) -> Option<String> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut parts = path.components().map(|c| c.as_os_str().to_string_lossy().to_string());
+    parts.find(|part| part.eq_ignore_ascii_case(component))?;
+    parts.next()
+}
+
+/// Total size of the files under `dir` that `include` accepts; a missing
+/// directory is empty
+pub(crate) fn files_size(dir: &std::path::Path, include: impl Fn(&std::path::Path) -> bool) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && include(e.path()))
+        .filter_map(|e| e.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// "Key: value" fields of a backend's info listing, in order. Only the first
+/// colon separates, so epochs and URLs survive; indented lines continue the
+/// previous value and are joined to it with a newline
+pub(crate) fn info_fields(output: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            // dnf continues with "             : more", apt with " more"
+            let rest = line.trim();
+            let rest = rest.strip_prefix(':').map(str::trim_start).unwrap_or(rest);
+            if let Some((_, value)) = fields.last_mut() {
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(rest);
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            fields.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    fields
+}
+
+/// First non-empty value of `key` in `info_fields` output, ignoring case;
+/// backends print "None" or "(none)" for absent values
+pub(crate) fn info_field<'a>(fields: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    fields.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.as_str())
+        .filter(|value| !value.is_empty() && !matches!(*value, "None" | "(none)"))
+}
+
+/// One search hit, normalized across backends whose search output differs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SearchRecord {
+    /// Identifier the backend installs by
+    pub id: String,
+    /// Display name; the id where the backend has no separate name
+    pub name: String,
+    pub version: String,
+    /// Where the hit comes from: winget source, Chocolatey feed or Scoop bucket
+    pub source: String,
+}
+
+impl SearchRecord {
+    /// The hit as a `manager` search result, named by its installable id
+    pub fn into_package(self, manager: &str) -> PackageInfo {
+        let description = match (self.name == self.id, self.source.is_empty()) {
+            (true, true) => None,
+            (true, false) => Some(format!("from {}", self.source)),
+            (false, true) => Some(self.name),
+            (false, false) => Some(format!("{} from {}", self.name, self.source)),
+        };
+
+        PackageInfo {
+            name: self.id,
+            version: self.version,
+            description,
+            size: None,
+            installed: false,
+            source: manager.to_string(),
+            homepage: None,
+            license: None,
+            dependencies: Vec::new(),
+            kind: None,
+        }
+    }
+}
+
+/// Split an update set into the packages to update and the ones skipped by
+/// `--exclude`; names compare case-insensitively for Windows package ids
+pub fn split_excluded(packages: &[String], exclude: &[String]) -> (Vec<String>, Vec<String>) {
+    packages.iter()
+        .cloned()
+        .partition(|package| !exclude.iter().any(|excluded| excluded.eq_ignore_ascii_case(package)))
+}
+
 pub struct PackageManagerFactory;
 
 impl PackageManagerFactory {
@@ -22,6 +128,7 @@ impl PackageManagerFactory {
         match primary_manager {
             PlatformPackageManager::Apt => Ok(Box::new(apt::AptManager::new())),
             PlatformPackageManager::Dnf => Ok(Box::new(dnf::DnfManager::new())),
+            PlatformPackageManager::Yum => Ok(Box::new(yum::YumManager::new())),
             PlatformPackageManager::Pacman => Ok(Box::new(pacman::PacmanManager::new())),
             PlatformPackageManager::Homebrew => Ok(Box::new(homebrew::HomebrewManager::new())),
             PlatformPackageManager::Winget => Ok(Box::new(winget::WingetManager::new())),
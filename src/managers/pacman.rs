@@ -1,37 +1,23 @@
 use anyhow::{Result, Context, bail};
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use regex::Regex;
-use crate::core::{PackageManager, PackageInfo, SearchResult, InstallResult};
+use crate::core::{ChangeKind, InstallResult, Operation, PackageInfo, PackageManager, PackageUpdate, PlannedChange, SearchResult};
+use crate::core::privilege;
+use crate::utils::logging::LoggedCommand;
 
-pub struct PacmanManager {
-    sudo_available: bool,
-}
+pub struct PacmanManager;
 
 impl PacmanManager {
     pub fn new() -> Self {
-        Self {
-            sudo_available: Self::check_sudo_available(),
-        }
-    }
-
-    fn check_sudo_available() -> bool {
-        Command::new("sudo")
-            .args(["-n", "true"])
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .map(|status| status.success())
-            .unwrap_or(false)
+        Self
     }
 
     fn run_command(&self, args: &[&str], needs_sudo: bool) -> Result<String> {
-        let mut cmd = if needs_sudo && self.sudo_available {
-            let mut c = Command::new("sudo");
-            c.arg("pacman");
-            c
+        let mut cmd = if needs_sudo {
+            privilege::privileged_command("pacman")?
         } else {
             Command::new("pacman")
         };
@@ -40,7 +26,7 @@ impl PacmanManager {
         cmd.arg("--noconfirm"); // Auto-confirm
         cmd.env("LANG", "C"); // English output
 
-        let output = cmd.output()
+        let output = cmd.logged_output()
             .context("Failed to execute pacman command")?;
 
         if !output.status.success() {
@@ -71,6 +57,10 @@ impl PacmanManager {
                     size: None,
                     installed: false,
                     source: "pacman".to_string(),
+                    homepage: None,
+                    license: None,
+                    dependencies: Vec::new(),
+                    kind: None,
                 });
             }
         }
@@ -78,45 +68,79 @@ impl PacmanManager {
         packages
     }
 
-    fn parse_package_info(&self, info_output: &str) -> Option<PackageInfo> {
-        let mut name = String::new();
-        let mut version = String::new();
-        let mut description = None;
-        let mut size = None;
-        let mut installed = false;
-
-        for line in info_output.lines() {
-            let line = line.trim();
-            if line.starts_with("Name") {
-                name = line.split(':').nth(1)?.trim().to_string();
-            } else if line.starts_with("Version") {
-                version = line.split(':').nth(1)?.trim().to_string();
-            } else if line.starts_with("Description") {
-                description = Some(line.split(':').nth(1)?.trim().to_string());
-            } else if line.starts_with("Installed Size") || line.starts_with("Download Size") {
-                if let Some(size_str) = line.split(':').nth(1) {
-                    size = Self::parse_size(size_str.trim());
-                }
-            }
-        }
+    /// Lines of `checkupdates` or `pacman -Qu`, "name 1.0-1 -> 1.1-1";
+    /// `repos` maps names to their sync repository from `pacman -Sl`
+    fn parse_updates(&self, updates_output: &str, repos: &HashMap<String, String>) -> Vec<PackageUpdate> {
+        updates_output.lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let [name, current, "->", candidate] = fields.as_slice() else { return None };
+                Some(PackageUpdate {
+                    name: name.to_string(),
+                    current: current.to_string(),
+                    candidate: candidate.to_string(),
+                    repo: repos.get(*name).cloned().unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
 
-        // Check if it's showing local package info (installed)
-        if info_output.contains("local/") {
-            installed = true;
-        }
+    /// Sync repository of every package, from "core linux 6.6.8.arch1-1 [installed]" lines
+    fn sync_repos(&self) -> HashMap<String, String> {
+        self.run_command(&["-Sl"], false)
+            .map(|output| {
+                output.lines()
+                    .filter_map(|line| {
+                        let mut fields = line.split_whitespace();
+                        let repo = fields.next()?;
+                        Some((fields.next()?.to_string(), repo.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 
-        if !name.is_empty() && !version.is_empty() {
-            Some(PackageInfo {
-                name,
-                version,
-                description,
-                size,
-                installed,
-                source: "pacman".to_string(),
+    /// The sync package that satisfies `dependency`, per `pacman -Sddp`
+    /// (which skips the dependency's own dependencies). Names pacman cannot
+    /// resolve, such as packages installed from outside the sync databases,
+    /// are kept as written
+    fn provider(&self, dependency: &str) -> String {
+        self.run_command(&["-Sddp", "--print-format", "%n", dependency], false)
+            .ok()
+            .and_then(|output| {
+                // Skip any provider-selection prompt; package names have no spaces
+                output.lines()
+                    .map(str::trim)
+                    .rfind(|line| !line.is_empty() && !line.contains(char::is_whitespace))
+                    .map(String::from)
             })
-        } else {
-            None
-        }
+            .unwrap_or_else(|| dependency.to_string())
+    }
+
+    /// `pacman -Qi` or `-Si`; only the caller knows which one answered
+    fn parse_package_info(&self, info_output: &str, installed: bool) -> Option<PackageInfo> {
+        let fields = super::info_fields(info_output);
+        let field = |key: &str| super::info_field(&fields, key);
+
+        // "glibc  ncurses  readline>=8.0", with "None" when empty
+        let dependencies = field("Depends On")
+            .map(|depends| depends.split_whitespace()
+                .map(|dependency| dependency.split(['<', '>', '=']).next().unwrap_or(dependency).to_string())
+                .collect())
+            .unwrap_or_default();
+
+        Some(PackageInfo {
+            name: field("Name")?.to_string(),
+            version: field("Version")?.to_string(),
+            description: field("Description").map(String::from),
+            size: field("Installed Size").or_else(|| field("Download Size")).and_then(Self::parse_size),
+            installed,
+            source: "pacman".to_string(),
+            homepage: field("URL").map(String::from),
+            license: field("Licenses").map(String::from),
+            dependencies,
+            kind: None,
+        })
     }
 
     fn parse_size(size_str: &str) -> Option<u64> {
@@ -188,6 +212,22 @@ impl PackageManager for PacmanManager {
         })
     }
 
+    /// `-Rn`: also skip the .pacsave backups `-R` keeps of modified config files
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult> {
+        let mut args = vec!["-Rn"];
+        for package in packages {
+            args.push(package);
+        }
+
+        self.run_command(&args, true)?;
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully purged {} packages", packages.len()),
+            packages_installed: packages.to_vec(),
+        })
+    }
+
     async fn update(&self) -> Result<()> {
         self.run_command(&["-Sy"], true)?;
         Ok(())
@@ -213,6 +253,17 @@ impl PackageManager for PacmanManager {
         })
     }
 
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult> {
+        let ignore = exclude.join(",");
+        self.run_command(&["-Syu", "--ignore", &ignore], true)?;
+
+        Ok(InstallResult {
+            success: true,
+            message: "System upgraded successfully".to_string(),
+            packages_installed: Vec::new(),
+        })
+    }
+
     async fn list_installed(&self) -> Result<Vec<PackageInfo>> {
         let output = self.run_command(&["-Q"], false)?;
         
@@ -227,6 +278,10 @@ impl PackageManager for PacmanManager {
                     size: None,
                     installed: true,
                     source: "pacman".to_string(),
+                    homepage: None,
+                    license: None,
+                    dependencies: Vec::new(),
+                    kind: None,
                 });
             }
         }
@@ -237,17 +292,208 @@ impl PackageManager for PacmanManager {
     async fn info(&self, package: &str) -> Result<Option<PackageInfo>> {
         // Try local package first
         match self.run_command(&["-Qi", package], false) {
-            Ok(output) => return Ok(self.parse_package_info(&output)),
+            Ok(output) => return Ok(self.parse_package_info(&output, true)),
             Err(_) => {},
         }
 
         // Try remote package
         match self.run_command(&["-Si", package], false) {
-            Ok(output) => Ok(self.parse_package_info(&output)),
+            Ok(output) => Ok(self.parse_package_info(&output, false)),
             Err(_) => Ok(None),
         }
     }
 
+    /// Virtual provides ("sh", "libcurl.so") are replaced by the package
+    /// that provides them
+    async fn dependencies(&self, package: &str) -> Result<Vec<String>> {
+        let info = self.info(package).await?
+            .with_context(|| format!("Package '{}' not found", package))?;
+        let mut dependencies: Vec<String> = Vec::new();
+        for dependency in &info.dependencies {
+            let provider = self.provider(dependency);
+            if !dependencies.contains(&provider) {
+                dependencies.push(provider);
+            }
+        }
+        Ok(dependencies)
+    }
+
+    /// Arch packages rarely ship a changelog; `pacman -Qc` shows one for
+    /// installed packages that do
+    async fn changelog(&self, package: &str) -> Result<Option<String>> {
+        Ok(self.run_command(&["-Qc", package], false)
+            .ok()
+            .filter(|changelog| !changelog.trim().is_empty()))
+    }
+
+    async fn owned_files(&self, package: &str) -> Result<Vec<PathBuf>> {
+        if self.run_command(&["-Q", package], false).is_err() {
+            return Ok(Vec::new());
+        }
+
+        // Lines are "<package> <path>"; directories end with '/'
+        let output = self.run_command(&["-Ql", package], false)?;
+        Ok(output.lines()
+            .filter_map(|line| line.split_once(' ').map(|(_, path)| path))
+            .filter(|path| !path.ends_with('/'))
+            .map(PathBuf::from)
+            .collect())
+    }
+
+    async fn owner_of_file(&self, path: &Path) -> Result<Option<String>> {
+        let path = path.to_string_lossy();
+        let output = match self.run_command(&["-Qo", "-q", &path], false) {
+            Ok(output) => output,
+            Err(_) => return Ok(None),
+        };
+
+        Ok(output.lines().next().map(|name| name.trim().to_string()))
+    }
+
+    async fn provides_file(&self, path: &str) -> Result<Vec<String>> {
+        // Needs the files database ('pacman -Fy'); lines are "repo/pkg version"
+        let output = match self.run_command(&["-F", "-q", path.trim_start_matches('/')], false) {
+            Ok(output) => output,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut packages: Vec<String> = output.lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(|pkg| pkg.rsplit('/').next().unwrap_or(pkg).to_string())
+            .collect();
+        packages.dedup();
+        Ok(packages)
+    }
+
+    async fn security_updates(&self) -> Result<Vec<String>> {
+        bail!("pacman has no security advisory metadata; review updates with arch-audit instead")
+    }
+
+    /// `checkupdates` from pacman-contrib, which checks against a fresh copy
+    /// of the sync databases, else `pacman -Qu`. They exit 2 and 1 when
+    /// nothing is upgradable
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>> {
+        let (program, args, none_code): (&str, &[&str], i32) = if which::which("checkupdates").is_ok() {
+            ("checkupdates", &[], 2)
+        } else {
+            ("pacman", &["-Qu"], 1)
+        };
+        let output = Command::new(program)
+            .args(args)
+            .env("LANG", "C")
+            .logged_output()
+            .with_context(|| format!("Failed to execute {} command", program))?;
+
+        if !output.status.success() {
+            if output.status.code() == Some(none_code) && output.stdout.is_empty() {
+                return Ok(Vec::new());
+            }
+            bail!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr));
+        }
+
+        Ok(self.parse_updates(&String::from_utf8_lossy(&output.stdout), &self.sync_repos()))
+    }
+
+    /// The transaction with `--print`, which lists the targets as
+    /// "name version" and exits without changing anything. Installs of
+    /// packages already present are told apart with `pacman -Q`
+    async fn simulate(&self, operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+        let (mut args, packages, removing): (Vec<&str>, &[String], bool) = match operation {
+            Operation::Install(packages) | Operation::Upgrade(Some(packages)) => (vec!["-S"], packages, false),
+            Operation::Remove(packages) => (vec!["-R"], packages, true),
+            Operation::Purge(packages) => (vec!["-Rn"], packages, true),
+            Operation::Upgrade(None) => (vec!["-Su"], &[], false),
+            Operation::UpgradeExcluding(packages) => {
+                let mut args = vec!["-Su"];
+                for package in packages {
+                    args.extend(["--ignore", package.as_str()]);
+                }
+                (args, &[], false)
+            }
+        };
+        args.extend(["--print", "--print-format", "%n %v"]);
+        args.extend(packages.iter().map(String::as_str));
+        let output = self.run_command(&args, false)?;
+
+        let installed: HashMap<String, String> = self.run_command(&["-Q"], false)?
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, version)| (name.to_string(), version.to_string()))
+            .collect();
+
+        Ok(output.lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, version)| {
+                let current = installed.get(name).map(String::as_str);
+                let kind = match current {
+                    _ if removing => ChangeKind::Remove,
+                    None => ChangeKind::Install,
+                    Some(current) if current == version => ChangeKind::Reinstall,
+                    Some(_) => ChangeKind::Upgrade,
+                };
+                if removing {
+                    PlannedChange::new(kind, name).with_versions(Some(version), None)
+                } else {
+                    PlannedChange::new(kind, name).with_versions(current, Some(version))
+                }
+            })
+            .collect())
+    }
+
+    /// `pacman -Qtdq`, which exits 1 when there are no orphans
+    async fn orphans(&self) -> Result<Vec<String>> {
+        let output = Command::new("pacman")
+            .args(["-Qtdq"])
+            .env("LANG", "C")
+            .logged_output()
+            .context("Failed to execute pacman command")?;
+
+        if !output.status.success() && !output.stderr.is_empty() {
+            bail!("Pacman command failed: {}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    /// `pacman -Rns $(pacman -Qtdq)`
+    async fn remove_orphans(&self, orphans: &[String]) -> Result<InstallResult> {
+        if !orphans.is_empty() {
+            let mut args = vec!["-Rns"];
+            args.extend(orphans.iter().map(String::as_str));
+            self.run_command(&args, true)?;
+        }
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully removed {} orphaned packages", orphans.len()),
+            packages_installed: orphans.to_vec(),
+        })
+    }
+
+    /// Cached packages `-Sc` would drop: those not matching an installed
+    /// name and version, e.g. "bash-5.2.026-2-x86_64.pkg.tar.zst"
+    async fn package_cache_size(&self) -> Result<u64> {
+        let installed: Vec<String> = self.list_installed().await?
+            .into_iter()
+            .map(|package| format!("{}-{}-", package.name, package.version))
+            .collect();
+
+        Ok(super::files_size(Path::new("/var/cache/pacman/pkg"), |path| {
+            let file = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+            file.contains(".pkg.tar") && !installed.iter().any(|prefix| file.starts_with(prefix.as_str()))
+        }))
+    }
+
+    async fn clean_package_cache(&self) -> Result<()> {
+        self.run_command(&["-Sc"], true)?;
+        Ok(())
+    }
+
     async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>> {
         let mut result = HashMap::new();
 
@@ -2,10 +2,13 @@ use anyhow::{Result, Context};
 use async_trait::async_trait;
 use regex::Regex;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
-use crate::core::{PackageManager, PackageInfo, SearchResult, InstallResult};
+use crate::core::{InstallResult, Operation, PackageInfo, PackageManager, PackageUpdate, PlannedChange, SearchResult};
 use crate::ui::output::Output;
+use crate::utils::logging::LoggedAsyncCommand;
+use super::SearchRecord;
 
 pub struct ScoopManager {
     output: Output,
@@ -49,7 +52,7 @@ impl ScoopManager {
             ])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .logged_output()
             .await
             .context("Failed to execute Scoop installer")?;
 
@@ -68,33 +71,14 @@ impl ScoopManager {
             .args(args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .logged_output()
             .await
             .context("Failed to execute scoop command")
     }
 
     /// Parse scoop search output
     fn parse_search_output(&self, output: &str) -> Vec<PackageInfo> {
-        let mut packages = Vec::new();
-
-        for line in output.lines() {
-            // Scoop search output format: "bucket/name (version): description"
-            if let Some(captures) = Regex::new(r"^(\S+)\s+\(([^)]+)\):\s*(.*)$")
-                .unwrap()
-                .captures(line) {
-
-                packages.push(PackageInfo {
-                    name: captures[1].to_string(),
-                    version: captures[2].to_string(),
-                    description: Some(captures[3].to_string()),
-                    size: None,
-                    installed: false,
-                    source: "scoop".to_string(),
-                });
-            }
-        }
-
-        packages
+        parse_search(output).into_iter().map(|record| record.into_package("scoop")).collect()
     }
 
     /// Parse scoop list output for installed packages
@@ -119,12 +103,55 @@ impl ScoopManager {
                     size: None,
                     installed: true,
                     source: "scoop".to_string(),
+                    homepage: None,
+                    license: None,
+                    dependencies: Vec::new(),
+                    kind: None,
                 });
             }
         }
 
         packages
     }
+
+    /// Uninstall each package; `--purge` also deletes the app's persisted data
+    async fn uninstall(&self, packages: &[String], purge: bool) -> Result<InstallResult> {
+        if !self.ensure_available().await? {
+            return Err(anyhow::anyhow!("Scoop is not available"));
+        }
+
+        let mut success_count = 0;
+        let mut errors = Vec::new();
+
+        for package in packages {
+            let mut args = vec!["uninstall", package.as_str()];
+            if purge {
+                args.push("--purge");
+            }
+            let output = self.execute_scoop(&args).await?;
+
+            if output.status.success() {
+                success_count += 1;
+            } else {
+                let error = String::from_utf8_lossy(&output.stderr);
+                errors.push(format!("{}: {}", package, error));
+            }
+        }
+
+        let success = errors.is_empty();
+        let message = if success {
+            format!("All {} packages removed successfully", success_count)
+        } else {
+            format!("{} of {} packages removed. Errors: {}",
+                    success_count, packages.len(), errors.join("; "))
+        };
+
+        Ok(InstallResult {
+            success,
+            message,
+            packages_installed: packages.to_vec(),
+        })
+    }
 }
 
 #[async_trait]
@@ -149,7 +176,16 @@ impl PackageManager for ScoopManager {
             return Err(anyhow::anyhow!("Scoop is not available"));
         }
 
-        let output = self.execute_scoop(&["search", query]).await?;
+        // Scoop 0.3+ returns objects, which PowerShell serializes faithfully;
+        // its host output is for people
+        let script = format!("scoop search '{}' | ConvertTo-Json", query.replace('\'', "''"));
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .logged_output()
+            .await
+            .context("Failed to execute scoop search")?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -198,37 +234,11 @@ impl PackageManager for ScoopManager {
     }
 
     async fn remove(&self, packages: &[String]) -> Result<InstallResult> {
-        if !self.ensure_available().await? {
-            return Err(anyhow::anyhow!("Scoop is not available"));
-        }
-
-        let mut success_count = 0;
-        let mut errors = Vec::new();
-
-        for package in packages {
-            let output = self.execute_scoop(&["uninstall", package]).await?;
-
-            if output.status.success() {
-                success_count += 1;
-            } else {
-                let error = String::from_utf8_lossy(&output.stderr);
-                errors.push(format!("{}: {}", package, error));
-            }
-        }
-
-        let success = errors.is_empty();
-        let message = if success {
-            format!("All {} packages removed successfully", success_count)
-        } else {
-            format!("{} of {} packages removed. Errors: {}",
-                    success_count, packages.len(), errors.join("; "))
-        };
+        self.uninstall(packages, false).await
+    }
 
-        Ok(InstallResult {
-            success,
-            message,
-            packages_installed: packages.to_vec(),
-        })
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult> {
+        self.uninstall(packages, true).await
     }
 
     async fn update(&self) -> Result<()> {
@@ -279,6 +289,25 @@ impl PackageManager for ScoopManager {
         }
     }
 
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult> {
+        // Hold the excluded apps for this run only, leaving existing holds alone
+        let output = self.execute_scoop(&["list"]).await?;
+        let held = held_apps(&String::from_utf8_lossy(&output.stdout));
+        let names: Vec<&str> = exclude.iter()
+            .map(String::as_str)
+            .filter(|name| !held.iter().any(|app| app.eq_ignore_ascii_case(name)))
+            .collect();
+
+        if !names.is_empty() {
+            self.execute_scoop(&[&["hold"], names.as_slice()].concat()).await?;
+        }
+        let result = self.upgrade(None).await;
+        if !names.is_empty() {
+            self.execute_scoop(&[&["unhold"], names.as_slice()].concat()).await?;
+        }
+        result
+    }
+
     async fn list_installed(&self) -> Result<Vec<PackageInfo>> {
         if !self.ensure_available().await? {
             return Ok(vec![]);
@@ -294,6 +323,8 @@ impl PackageManager for ScoopManager {
         Ok(self.parse_list_output(&stdout))
     }
 
+    /// `scoop info`, "Key : value" lines; "Installed" is only present for
+    /// installed apps
     async fn info(&self, package: &str) -> Result<Option<PackageInfo>> {
         if !self.ensure_available().await? {
             return Ok(None);
@@ -306,30 +337,116 @@ impl PackageManager for ScoopManager {
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-
-        // Parse scoop info output for package details
-        let mut name = package.to_string();
-        let mut version = String::new();
-        let mut description = String::new();
-
-        for line in stdout.lines() {
-            if line.starts_with("Version:") {
-                version = line.split(':').nth(1).unwrap_or("").trim().to_string();
-            } else if line.starts_with("Description:") {
-                description = line.split(':').nth(1).unwrap_or("").trim().to_string();
-            }
-        }
+        let fields = super::info_fields(&stdout);
+        let field = |key: &str| super::info_field(&fields, key);
+        let Some(version) = field("Version") else {
+            return Ok(None);
+        };
 
         Ok(Some(PackageInfo {
-            name,
-            version,
-            description: Some(description),
+            name: field("Name").unwrap_or(package).to_string(),
+            version: version.to_string(),
+            description: field("Description").map(String::from),
             size: None,
-            installed: false,
+            installed: field("Installed").is_some_and(|installed| installed != "No"),
             source: "scoop".to_string(),
+            homepage: field("Website").map(String::from),
+            license: field("License").map(String::from),
+            dependencies: field("Dependencies")
+                .map(|depends| depends.split(['|', ' ', '\n']).filter(|d| !d.is_empty()).map(String::from).collect())
+                .unwrap_or_default(),
+            kind: None,
         }))
     }
 
+    async fn dependencies(&self, package: &str) -> Result<Vec<String>> {
+        let info = self.info(package).await?
+            .with_context(|| format!("Package '{}' not found", package))?;
+        Ok(info.dependencies)
+    }
+
+    async fn changelog(&self, _package: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn owned_files(&self, package: &str) -> Result<Vec<PathBuf>> {
+        if !self.ensure_available().await? {
+            return Ok(Vec::new());
+        }
+
+        // Scoop keeps each app self-contained under its prefix directory
+        let output = self.execute_scoop(&["prefix", package]).await?;
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        let prefix = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        Ok(walkdir::WalkDir::new(prefix)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .collect())
+    }
+
+    async fn owner_of_file(&self, path: &Path) -> Result<Option<String>> {
+        Ok(super::package_from_layout(path, "apps"))
+    }
+
+    async fn provides_file(&self, _path: &str) -> Result<Vec<String>> {
+        anyhow::bail!("Scoop has no file index for apps that are not installed")
+    }
+
+    async fn security_updates(&self) -> Result<Vec<String>> {
+        anyhow::bail!("Security-only updates are not supported by Scoop")
+    }
+
+    /// From `scoop status`, against the buckets as last updated
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>> {
+        if !self.ensure_available().await? {
+            return Err(anyhow::anyhow!("Scoop is not available"));
+        }
+
+        let output = self.execute_scoop(&["status", "--local"]).await?;
+        if !output.status.success() {
+            anyhow::bail!("scoop status failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(parse_status(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    async fn simulate(&self, _operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+        anyhow::bail!("Scoop has no dry-run mode")
+    }
+
+    async fn orphans(&self) -> Result<Vec<String>> {
+        anyhow::bail!("Scoop does not track orphaned dependencies")
+    }
+
+    async fn remove_orphans(&self, _orphans: &[String]) -> Result<InstallResult> {
+        anyhow::bail!("Scoop does not track orphaned dependencies")
+    }
+
+    /// Downloads under `$SCOOP\cache`, which defaults to `~\scoop\cache`
+    async fn package_cache_size(&self) -> Result<u64> {
+        let root = std::env::var_os("SCOOP")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join("scoop")));
+        Ok(root.map(|root| super::files_size(&root.join("cache"), |_| true)).unwrap_or(0))
+    }
+
+    async fn clean_package_cache(&self) -> Result<()> {
+        if !self.ensure_available().await? {
+            return Err(anyhow::anyhow!("Scoop is not available"));
+        }
+
+        let output = self.execute_scoop(&["cache", "rm", "*"]).await?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!("Failed to clear Scoop cache: {}", error));
+        }
+        Ok(())
+    }
+
     async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>> {
         let installed_packages = self.list_installed().await?;
         let installed_names: std::collections::HashSet<String> =
@@ -339,4 +456,148 @@ impl PackageManager for ScoopManager {
             .map(|package| (package.clone(), installed_names.contains(package)))
             .collect())
     }
-}
\ No newline at end of file
+}
+
+/// `scoop search` results as JSON: an array of {Name, Version, Source}
+/// objects, or a single object for one hit. Scoop before 0.3 printed text
+/// instead, `'bucket' bucket:` followed by indented `name (version)` lines
+fn parse_search(output: &str) -> Vec<SearchRecord> {
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Hit {
+        name: String,
+        #[serde(default)]
+        version: String,
+        #[serde(default)]
+        source: String,
+    }
+
+    let trimmed = output.trim();
+    if trimmed.starts_with('[') || trimmed.starts_with('{') {
+        let hits: Vec<Hit> = serde_json::from_str::<Vec<Hit>>(trimmed)
+            .or_else(|_| serde_json::from_str::<Hit>(trimmed).map(|hit| vec![hit]))
+            .unwrap_or_default();
+        return hits.into_iter()
+            .map(|hit| SearchRecord { id: hit.name.clone(), name: hit.name, version: hit.version, source: hit.source })
+            .collect();
+    }
+
+    let bucket_header = Regex::new(r"^'([^']+)' bucket").unwrap();
+    let app = Regex::new(r"^\s+(\S+)\s+\(([^)]+)\)").unwrap();
+    let mut bucket = String::new();
+    let mut records = Vec::new();
+    for line in output.lines() {
+        if let Some(captures) = bucket_header.captures(line) {
+            bucket = captures[1].to_string();
+        } else if let Some(captures) = app.captures(line) {
+            records.push(SearchRecord {
+                id: captures[1].to_string(),
+                name: captures[1].to_string(),
+                version: captures[2].to_string(),
+                source: bucket.clone(),
+            });
+        }
+    }
+    records
+}
+
+/// Outdated apps from `scoop status`: a table with "Installed Version" and
+/// "Latest Version" columns, or before Scoop 0.3 "Updates are available
+/// for:" followed by `name: current -> latest` lines
+fn parse_status(output: &str) -> Vec<PackageUpdate> {
+    let update = |name: &str, current: &str, candidate: &str| PackageUpdate {
+        name: name.to_string(),
+        current: current.to_string(),
+        candidate: candidate.to_string(),
+        repo: String::new(),
+    };
+
+    let legacy = Regex::new(r"^\s+(\S+): (\S+) -> (\S+)").unwrap();
+    let mut updates: Vec<PackageUpdate> = output.lines()
+        .filter_map(|line| legacy.captures(line))
+        .map(|captures| update(&captures[1], &captures[2], &captures[3]))
+        .collect();
+
+    let lines: Vec<&str> = output.lines().collect();
+    let Some(header) = lines.iter().position(|line| line.contains("Latest Version")) else {
+        return updates;
+    };
+    let column = |title: &str| lines[header].find(title);
+    let (Some(installed), Some(latest)) = (column("Installed Version"), column("Latest Version")) else {
+        return updates;
+    };
+    let latest_end = column("Missing Dependencies").or_else(|| column("Info"));
+
+    for line in lines.iter().skip(header + 1).filter(|line| !line.trim_start().starts_with('-')) {
+        let cell = |start: usize, end: Option<usize>| {
+            let end = end.unwrap_or(line.len()).min(line.len());
+            line.get(start.min(end)..end).unwrap_or_default().trim()
+        };
+        let name = cell(0, Some(installed));
+        let candidate = cell(latest, latest_end);
+        if !name.is_empty() && !candidate.is_empty() {
+            updates.push(update(name, cell(installed, Some(latest)), candidate));
+        }
+    }
+    updates
+}
+
+/// Apps `scoop list` marks "Held package" in its Info column
+fn held_apps(list_output: &str) -> Vec<String> {
+    list_output.lines()
+        .filter(|line| line.contains("Held package"))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_reads_json_objects_and_legacy_text() {
+        let json = r#"[
+            {"Name": "git", "Version": "2.43.0", "Source": "main", "Binaries": ""},
+            {"Name": "git-lfs", "Version": "3.4.1", "Source": "main", "Binaries": ""}
+        ]"#;
+        let records = parse_search(json);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1], SearchRecord {
+            id: "git-lfs".to_string(),
+            name: "git-lfs".to_string(),
+            version: "3.4.1".to_string(),
+            source: "main".to_string(),
+        });
+
+        let single = r#"{"Name": "7zip", "Version": "23.01", "Source": "main", "Binaries": ""}"#;
+        assert_eq!(parse_search(single)[0].id, "7zip");
+
+        let legacy = "'main' bucket:\n    git (2.43.0)\n    gitsign (0.8.0)\n\n'extras' bucket:\n    gitkraken (9.11.0)\n";
+        let records = parse_search(legacy);
+        assert_eq!(records.len(), 3);
+        assert_eq!((records[2].id.as_str(), records[2].source.as_str()), ("gitkraken", "extras"));
+    }
+
+    #[test]
+    fn held_apps_come_from_the_info_column() {
+        let list = "Installed apps:\n\nName Version Source Updated             Info\n---- ------- ------ -------             ----\n\
+            7zip 23.01   main   2024-01-01 10:00:00\n\
+            git  2.43.0  main   2024-01-01 10:00:00 Held package\n";
+        assert_eq!(held_apps(list), vec!["git"]);
+    }
+
+    #[test]
+    fn status_lists_apps_with_a_latest_version() {
+        let table = "Name Installed Version Latest Version Missing Dependencies Info\n\
+            ---- ----------------- -------------- -------------------- ----\n\
+            git  2.43.0            2.44.0\n\
+            nvm  1.1.11                                                 Held package\n";
+        let updates = parse_status(table);
+        assert_eq!(updates.len(), 1);
+        assert_eq!((updates[0].name.as_str(), updates[0].current.as_str(), updates[0].candidate.as_str()), ("git", "2.43.0", "2.44.0"));
+
+        let legacy = "Updates are available for:\n    git: 2.43.0 -> 2.44.0\n";
+        assert_eq!(parse_status(legacy)[0].candidate, "2.44.0");
+    }
+}
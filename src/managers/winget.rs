@@ -1,10 +1,13 @@
 use anyhow::{Result, Context};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
-use crate::core::{PackageManager, PackageInfo, SearchResult, InstallResult};
+use crate::core::{InstallResult, Operation, PackageInfo, PackageManager, PackageUpdate, PlannedChange, SearchResult};
 use crate::ui::output::Output;
+use crate::utils::logging::LoggedAsyncCommand;
+use super::SearchRecord;
 
 pub struct WingetManager {
     output: Output,
@@ -39,7 +42,7 @@ impl WingetManager {
             ])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .logged_output()
             .await
             .context("Failed to check App Installer status")?;
 
@@ -53,7 +56,7 @@ impl WingetManager {
                 ])
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
-                .output()
+                .logged_output()
                 .await
                 .context("Failed to install App Installer")?;
 
@@ -84,41 +87,35 @@ impl WingetManager {
             .args(args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .logged_output()
             .await
             .context("Failed to execute winget command")
     }
 
-    /// Parse winget search output
-    fn parse_search_output(&self, output: &str) -> Vec<PackageInfo> {
-        let mut packages = Vec::new();
-
-        for line in output.lines() {
-            // Skip header lines and separators
-            if line.starts_with("Name") || line.starts_with("---") || line.trim().is_empty() {
-                continue;
-            }
-
-            // Winget search output is tab-separated or space-separated
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 3 {
-                let name = parts[0].to_string();
-                let id = parts[1].to_string();
-                let version = parts[2].to_string();
-                let description = parts.get(3..).map(|p| p.join(" ")).unwrap_or_default();
-
-                packages.push(PackageInfo {
-                    name: id, // Use package ID as name for winget
-                    version,
-                    description: Some(description),
-                    size: None,
-                    installed: false,
-                    source: "winget".to_string(),
-                });
-            }
+    /// Parse winget search output, completing ids winget cut short. Rows
+    /// whose id cannot be recovered are dropped, since they cannot be installed
+    async fn parse_search_output(&self, output: &str) -> Vec<PackageInfo> {
+        let mut records = parse_search(output);
+        for record in records.iter_mut().filter(|record| is_truncated(&record.id)) {
+            let prefix = record.id.trim_end_matches(TRUNCATION_MARK).to_string();
+            let candidates = match self.execute_winget(&["search", "--id", &prefix]).await {
+                Ok(output) if output.status.success() => parse_search(&String::from_utf8_lossy(&output.stdout)),
+                _ => Vec::new(),
+            };
+            record.id = full_id(record, &candidates).unwrap_or_default();
         }
 
-        packages
+        records.into_iter()
+            .filter(|record| !record.id.is_empty())
+            .map(|record| record.into_package("winget"))
+            .collect()
+    }
+
+    /// Packages `winget upgrade --all` would upgrade
+    async fn upgrade_rows(&self) -> Result<Vec<UpgradeRow>> {
+        let output = self.execute_winget(&["upgrade", "--accept-source-agreements"]).await?;
+        // winget exits non-zero when nothing is upgradable
+        Ok(parse_upgrade(&String::from_utf8_lossy(&output.stdout)))
     }
 
     /// Parse winget list output for installed packages
@@ -144,12 +141,55 @@ impl WingetManager {
                     size: None,
                     installed: true,
                     source: "winget".to_string(),
+                    homepage: None,
+                    license: None,
+                    dependencies: Vec::new(),
+                    kind: None,
                 });
             }
         }
 
         packages
     }
+
+    /// Uninstall each package; `--purge` also deletes the package directory
+    async fn uninstall(&self, packages: &[String], purge: bool) -> Result<InstallResult> {
+        if !self.ensure_available().await? {
+            return Err(anyhow::anyhow!("Winget is not available"));
+        }
+
+        let mut success_count = 0;
+        let mut errors = Vec::new();
+
+        for package in packages {
+            let mut args = vec!["uninstall", package.as_str(), "--silent"];
+            if purge {
+                args.push("--purge");
+            }
+            let output = self.execute_winget(&args).await?;
+
+            if output.status.success() {
+                success_count += 1;
+            } else {
+                let error = String::from_utf8_lossy(&output.stderr);
+                errors.push(format!("{}: {}", package, error));
+            }
+        }
+
+        let success = errors.is_empty();
+        let message = if success {
+            format!("All {} packages removed successfully", success_count)
+        } else {
+            format!("{} of {} packages removed. Errors: {}",
+                    success_count, packages.len(), errors.join("; "))
+        };
+
+        Ok(InstallResult {
+            success,
+            message,
+            packages_installed: packages.to_vec(),
+        })
+    }
 }
 
 #[async_trait]
@@ -182,7 +222,7 @@ impl PackageManager for WingetManager {
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let packages = self.parse_search_output(&stdout);
+        let packages = self.parse_search_output(&stdout).await;
         let total_count = packages.len();
 
         Ok(SearchResult { packages, total_count })
@@ -223,37 +263,11 @@ impl PackageManager for WingetManager {
     }
 
     async fn remove(&self, packages: &[String]) -> Result<InstallResult> {
-        if !self.ensure_available().await? {
-            return Err(anyhow::anyhow!("Winget is not available"));
-        }
-
-        let mut success_count = 0;
-        let mut errors = Vec::new();
-
-        for package in packages {
-            let output = self.execute_winget(&["uninstall", package, "--silent"]).await?;
-
-            if output.status.success() {
-                success_count += 1;
-            } else {
-                let error = String::from_utf8_lossy(&output.stderr);
-                errors.push(format!("{}: {}", package, error));
-            }
-        }
-
-        let success = errors.is_empty();
-        let message = if success {
-            format!("All {} packages removed successfully", success_count)
-        } else {
-            format!("{} of {} packages removed. Errors: {}",
-                    success_count, packages.len(), errors.join("; "))
-        };
+        self.uninstall(packages, false).await
+    }
 
-        Ok(InstallResult {
-            success,
-            message,
-            packages_installed: packages.to_vec(),
-        })
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult> {
+        self.uninstall(packages, true).await
     }
 
     async fn update(&self) -> Result<()> {
@@ -305,6 +319,40 @@ impl PackageManager for WingetManager {
         }
     }
 
+    /// winget has no exclusion flag, so upgrade each listed package that is
+    /// not excluded by id or name
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult> {
+        if !self.ensure_available().await? {
+            return Err(anyhow::anyhow!("Winget is not available"));
+        }
+
+        let rows = self.upgrade_rows().await?;
+        let excluded = |row: &UpgradeRow| exclude.iter()
+            .any(|name| name.eq_ignore_ascii_case(&row.id) || name.eq_ignore_ascii_case(&row.name));
+
+        let mut upgraded = Vec::new();
+        let mut errors = Vec::new();
+        for row in rows.iter().filter(|row| !excluded(row)) {
+            let args = [&["upgrade"], row.selector().as_slice(), &["--exact", "--silent"]].concat();
+            let output = self.execute_winget(&args).await?;
+            if output.status.success() {
+                upgraded.push(row.id.clone());
+            } else {
+                errors.push(format!("{}: {}", row.id, String::from_utf8_lossy(&output.stderr).trim()));
+            }
+        }
+
+        Ok(InstallResult {
+            success: errors.is_empty(),
+            message: if errors.is_empty() {
+                format!("Upgraded {} packages", upgraded.len())
+            } else {
+                format!("Some upgrades failed: {}", errors.join("; "))
+            },
+            packages_installed: upgraded,
+        })
+    }
+
     async fn list_installed(&self) -> Result<Vec<PackageInfo>> {
         if !self.ensure_available().await? {
             return Ok(vec![]);
@@ -320,6 +368,9 @@ impl PackageManager for WingetManager {
         Ok(self.parse_list_output(&stdout))
     }
 
+    /// `winget show`, "Key: value" lines with indented lists below "Tags:",
+    /// "Installer:" and "Dependencies:". It does not say whether the
+    /// package is installed
     async fn info(&self, package: &str) -> Result<Option<PackageInfo>> {
         if !self.ensure_available().await? {
             return Ok(None);
@@ -332,30 +383,97 @@ impl PackageManager for WingetManager {
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
+        let fields = super::info_fields(&stdout);
+        let field = |key: &str| super::info_field(&fields, key);
+        let Some(version) = field("Version") else {
+            return Ok(None);
+        };
 
-        // Parse winget show output for package details
-        let mut name = package.to_string();
-        let mut version = String::new();
-        let mut description = String::new();
-
-        for line in stdout.lines() {
-            if line.starts_with("Version:") {
-                version = line.split(':').nth(1).unwrap_or("").trim().to_string();
-            } else if line.starts_with("Description:") {
-                description = line.split(':').nth(1).unwrap_or("").trim().to_string();
-            }
-        }
+        // "- Package Dependencies:" is followed by the package ids, then by
+        // the next "- <kind> Dependencies:" list if any
+        let dependencies = field("Dependencies")
+            .map(|lists| lists.lines()
+                .skip_while(|line| !line.starts_with("- Package Dependencies"))
+                .skip(1)
+                .take_while(|line| !line.starts_with("- "))
+                .map(String::from)
+                .collect())
+            .unwrap_or_default();
+        let installed = self.is_installed(&[package.to_string()]).await
+            .map(|installed| installed.get(package).copied().unwrap_or(false))
+            .unwrap_or(false);
 
         Ok(Some(PackageInfo {
-            name,
-            version,
-            description: Some(description),
+            name: package.to_string(),
+            version: version.to_string(),
+            description: field("Description").map(|d| d.replace('\n', " ")),
             size: None,
-            installed: false,
+            installed,
             source: "winget".to_string(),
+            homepage: field("Homepage").map(String::from),
+            license: field("License").map(String::from),
+            dependencies,
+            kind: None,
         }))
     }
 
+    async fn dependencies(&self, package: &str) -> Result<Vec<String>> {
+        let info = self.info(package).await?
+            .with_context(|| format!("Package '{}' not found", package))?;
+        Ok(info.dependencies)
+    }
+
+    async fn changelog(&self, _package: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn owned_files(&self, _package: &str) -> Result<Vec<PathBuf>> {
+        anyhow::bail!("winget does not record which files a package installs")
+    }
+
+    async fn owner_of_file(&self, _path: &Path) -> Result<Option<String>> {
+        anyhow::bail!("winget does not record which files a package installs")
+    }
+
+    async fn provides_file(&self, _path: &str) -> Result<Vec<String>> {
+        anyhow::bail!("winget has no file index for packages")
+    }
+
+    async fn security_updates(&self) -> Result<Vec<String>> {
+        anyhow::bail!("Security-only updates are not supported by winget")
+    }
+
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>> {
+        if !self.ensure_available().await? {
+            return Err(anyhow::anyhow!("Winget is not available"));
+        }
+
+        Ok(self.upgrade_rows().await?
+            .into_iter()
+            .map(|row| PackageUpdate { name: row.id, current: row.current, candidate: row.available, repo: row.source })
+            .collect())
+    }
+
+    async fn simulate(&self, _operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+        anyhow::bail!("winget has no dry-run mode")
+    }
+
+    async fn orphans(&self) -> Result<Vec<String>> {
+        anyhow::bail!("winget does not track orphaned dependencies")
+    }
+
+    async fn remove_orphans(&self, _orphans: &[String]) -> Result<InstallResult> {
+        anyhow::bail!("winget does not track orphaned dependencies")
+    }
+
+    async fn package_cache_size(&self) -> Result<u64> {
+        anyhow::bail!("winget has no package cache to clean")
+    }
+
+    async fn clean_package_cache(&self) -> Result<()> {
+        anyhow::bail!("winget has no package cache to clean")
+    }
+
     async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>> {
         let installed_packages = self.list_installed().await?;
         let installed_names: std::collections::HashSet<String> =
@@ -365,4 +483,173 @@ impl PackageManager for WingetManager {
             .map(|package| (package.clone(), installed_names.contains(package)))
             .collect())
     }
-}
\ No newline at end of file
+}
+
+/// Rows of a winget table, one cell per header column. Columns are fixed
+/// width and names contain spaces, so cells are cut at the header's column
+/// starts. Progress spinners before the header end in a carriage return
+fn parse_table(output: &str) -> Vec<Vec<String>> {
+    let lines: Vec<&str> = output.lines()
+        .map(|line| line.rsplit('\r').next().unwrap_or(line))
+        .collect();
+    let Some(separator) = lines.iter().position(|line| !line.is_empty() && line.trim().chars().all(|c| c == '-')) else {
+        return Vec::new();
+    };
+    let Some(header) = separator.checked_sub(1).map(|index| lines[index]) else {
+        return Vec::new();
+    };
+
+    let header: Vec<char> = header.chars().collect();
+    let starts: Vec<usize> = (0..header.len())
+        .filter(|&i| !header[i].is_whitespace() && (i == 0 || header[i - 1].is_whitespace()))
+        .collect();
+
+    lines[separator + 1..].iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            starts.iter().enumerate()
+                .map(|(column, &start)| {
+                    let end = starts.get(column + 1).copied().unwrap_or(chars.len()).min(chars.len());
+                    chars.get(start.min(end)..end).map(|cell| cell.iter().collect::<String>().trim().to_string()).unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `winget search` table: Name, Id, Version, then Match when the query hit
+/// a tag or moniker, and Source unless one source was searched
+fn parse_search(output: &str) -> Vec<SearchRecord> {
+    parse_table(output).into_iter()
+        .filter_map(|row| {
+            let cell = |index: usize| row.get(index).filter(|cell| !cell.is_empty()).cloned();
+            let id = cell(1)?;
+            let source = if row.len() >= 4 { row.last().cloned().unwrap_or_default() } else { String::new() };
+            Some(SearchRecord {
+                name: cell(0).unwrap_or_else(|| id.clone()),
+                version: cell(2).unwrap_or_default(),
+                source: if source.is_empty() { "winget".to_string() } else { source },
+                id,
+            })
+        })
+        .collect()
+}
+
+/// A row of the `winget upgrade` table
+#[derive(Debug, Clone, PartialEq)]
+struct UpgradeRow {
+    name: String,
+    id: String,
+    current: String,
+    available: String,
+    source: String,
+}
+
+impl UpgradeRow {
+    /// Arguments naming this package; a truncated id falls back to the name
+    fn selector(&self) -> [&str; 2] {
+        if is_truncated(&self.id) {
+            ["--name", &self.name]
+        } else {
+            ["--id", &self.id]
+        }
+    }
+}
+
+/// `winget upgrade` table: Name, Id, Version, Available, Source. The rows
+/// end at the "N upgrades available." summary; packages listed after it
+/// need explicit targeting and are not part of `--all`
+fn parse_upgrade(output: &str) -> Vec<UpgradeRow> {
+    parse_table(output).into_iter()
+        .map_while(|row| {
+            let cell = |index: usize| row.get(index).filter(|cell| !cell.is_empty()).cloned();
+            Some(UpgradeRow {
+                name: cell(0)?,
+                id: cell(1)?,
+                current: cell(2)?,
+                available: cell(3)?,
+                source: cell(4).unwrap_or_else(|| "winget".to_string()),
+            })
+        })
+        .collect()
+}
+
+/// What winget puts at the end of a cell too long for its column
+const TRUNCATION_MARK: char = '…';
+
+fn is_truncated(id: &str) -> bool {
+    id.ends_with(TRUNCATION_MARK)
+}
+
+/// The complete id for a truncated row among `candidates` from a search by
+/// its id prefix: same name, an id extending the prefix and itself intact
+fn full_id(truncated: &SearchRecord, candidates: &[SearchRecord]) -> Option<String> {
+    let prefix = truncated.id.trim_end_matches(TRUNCATION_MARK);
+    candidates.iter()
+        .find(|candidate| {
+            candidate.name == truncated.name
+                && candidate.id.starts_with(prefix)
+                && !is_truncated(&candidate.id)
+        })
+        .map(|candidate| candidate.id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_table_is_cut_at_header_columns() {
+        let output = concat!(
+            "\r   - \r   \\ \rName                          Id                                Version      Match       Source\n",
+            "--------------------------------------------------------------------------------------------------\n",
+            "Visual Studio Code            Microsoft.VisualStudioCode        1.85.1       Tag: vscode winget\n",
+            "Visual Studio Code - Insiders Microsoft.VisualStudioCode.Insid… 1.86.0       Tag: vscode winget\n",
+            "Visual Studio Code            XP9KHM4BK9FZ7Q                    Unknown                  msstore\n",
+        );
+
+        let records = parse_search(output);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], SearchRecord {
+            id: "Microsoft.VisualStudioCode".to_string(),
+            name: "Visual Studio Code".to_string(),
+            version: "1.85.1".to_string(),
+            source: "winget".to_string(),
+        });
+        assert_eq!(records[1].name, "Visual Studio Code - Insiders");
+        assert_eq!(records[1].id, "Microsoft.VisualStudioCode.Insid…");
+        assert!(is_truncated(&records[1].id));
+
+        let by_prefix = parse_search(concat!(
+            "Name                          Id                                   Version Source\n",
+            "-----------------------------------------------------------------------------------\n",
+            "Visual Studio Code - Insiders Microsoft.VisualStudioCode.Insiders   1.86.0  winget\n",
+        ));
+        assert_eq!(full_id(&records[1], &by_prefix).as_deref(), Some("Microsoft.VisualStudioCode.Insiders"));
+        assert_eq!(full_id(&records[1], &[]), None);
+        assert_eq!(records[2].id, "XP9KHM4BK9FZ7Q");
+        assert_eq!(records[2].source, "msstore");
+    }
+
+    #[test]
+    fn upgrade_rows_stop_at_the_summary() {
+        let output = concat!(
+            "Name               Id                      Version  Available Source\n",
+            "---------------------------------------------------------------------\n",
+            "Git                Git.Git                 2.43.0   2.44.0    winget\n",
+            "PowerToys (Preview) Microsoft.PowerToys    0.78.0   0.79.0    winget\n",
+            "2 upgrades available.\n",
+            "\n",
+            "The following packages have an upgrade available, but require explicit targeting for upgrade:\n",
+            "Name               Id                      Version  Available Source\n",
+            "---------------------------------------------------------------------\n",
+            "Discord            Discord.Discord         1.0.9030 1.0.9031  winget\n",
+        );
+
+        let rows = parse_upgrade(output);
+        assert_eq!(rows.iter().map(|row| row.id.as_str()).collect::<Vec<_>>(), vec!["Git.Git", "Microsoft.PowerToys"]);
+        assert_eq!(rows[0].available, "2.44.0");
+        assert_eq!(rows[0].selector(), ["--id", "Git.Git"]);
+    }
+}
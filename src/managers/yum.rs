@@ -0,0 +1,407 @@
+use anyhow::{Result, Context, bail};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::core::{ChangeKind, InstallResult, Operation, PackageInfo, PackageManager, PackageUpdate, PlannedChange, SearchResult};
+use crate::core::privilege;
+use crate::utils::logging::LoggedCommand;
+use super::dnf::DnfManager;
+
+/// yum 3 on EL7 and older. Its search, info, check-update and transaction
+/// output matches dnf's, so those parsers are shared; commands dnf builds in
+/// (repoquery, autoremove listing) come from yum-utils or a dry run instead
+pub struct YumManager;
+
+/// Parsers shared with the dnf backend
+const RPM_OUTPUT: DnfManager = DnfManager;
+
+impl YumManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn run_command(&self, args: &[&str], needs_sudo: bool) -> Result<String> {
+        let mut cmd = if needs_sudo {
+            privilege::privileged_command("yum")?
+        } else {
+            Command::new("yum")
+        };
+
+        cmd.args(args);
+        cmd.arg("-y"); // Auto-confirm
+        cmd.arg("--quiet"); // Minimal output
+
+        let output = cmd.logged_output()
+            .context("Failed to execute yum command")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("YUM command failed: {}", stderr);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// `repoquery` from yum-utils; yum has no built-in equivalent
+    fn repoquery(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("repoquery")
+            .arg("--quiet")
+            .args(args)
+            .logged_output()
+            .context("Failed to execute repoquery (install yum-utils)")?;
+
+        if !output.status.success() {
+            bail!("repoquery failed: {}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Packages providing what `package` requires, by name
+    fn requires(&self, package: &str) -> Result<Vec<String>> {
+        let requires = self.repoquery(&["--requires", "--resolve", "--qf", "%{name}", package])?;
+        let mut dependencies: Vec<String> = requires.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && *line != package)
+            .map(String::from)
+            .collect();
+        dependencies.sort();
+        dependencies.dedup();
+        Ok(dependencies)
+    }
+
+    /// Rows of `yum list installed`, "name.arch  version  repo" with the
+    /// same wrapping as check-update
+    fn parse_list_installed(&self, list_output: &str) -> Vec<PackageInfo> {
+        RPM_OUTPUT.parse_check_update(list_output)
+            .into_iter()
+            .map(|row| PackageInfo {
+                name: row.name,
+                version: row.candidate,
+                description: None,
+                size: None,
+                installed: true,
+                source: "yum".to_string(),
+                homepage: None,
+                license: None,
+                dependencies: Vec::new(),
+                kind: None,
+            })
+            .collect()
+    }
+
+    /// Resolve `args` with `--assumeno`, which prints the transaction and
+    /// exits 1 without changing anything
+    fn dry_run(&self, args: &[&str]) -> Result<Vec<PlannedChange>> {
+        let output = Command::new("yum")
+            .args(args)
+            .arg("--assumeno")
+            .env("LANG", "C")
+            .logged_output()
+            .context("Failed to execute yum command")?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !stdout.contains("Transaction Summary") {
+            if output.status.success() || stdout.contains("Nothing to do") || stdout.contains("No packages marked") {
+                return Ok(Vec::new());
+            }
+            bail!("YUM command failed: {}", String::from_utf8_lossy(&output.stderr));
+        }
+        Ok(RPM_OUTPUT.parse_transaction(&stdout))
+    }
+}
+
+#[async_trait]
+impl PackageManager for YumManager {
+    fn name(&self) -> &str {
+        "yum"
+    }
+
+    async fn is_available(&self) -> bool {
+        which::which("yum").is_ok()
+    }
+
+    async fn search(&self, query: &str) -> Result<SearchResult> {
+        let output = self.run_command(&["search", query], false)?;
+        let mut packages = RPM_OUTPUT.parse_search_results(&output);
+        for package in &mut packages {
+            package.source = "yum".to_string();
+        }
+        let total_count = packages.len();
+
+        Ok(SearchResult { packages, total_count })
+    }
+
+    /// yum skips names it cannot find and still exits 0 when others install,
+    /// unless told not to
+    async fn install(&self, packages: &[String]) -> Result<InstallResult> {
+        let mut args = vec!["install", "--setopt=skip_missing_names_on_install=False"];
+        args.extend(packages.iter().map(String::as_str));
+        self.run_command(&args, true)?;
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully installed {} packages", packages.len()),
+            packages_installed: packages.to_vec(),
+        })
+    }
+
+    async fn remove(&self, packages: &[String]) -> Result<InstallResult> {
+        let mut args = vec!["remove"];
+        args.extend(packages.iter().map(String::as_str));
+        self.run_command(&args, true)?;
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully removed {} packages", packages.len()),
+            packages_installed: packages.to_vec(),
+        })
+    }
+
+    /// Like dnf, remove already deletes unmodified config files and saves
+    /// modified ones as .rpmsave
+    async fn purge(&self, packages: &[String]) -> Result<InstallResult> {
+        self.remove(packages).await
+    }
+
+    async fn update(&self) -> Result<()> {
+        self.run_command(&["makecache", "fast"], true)?;
+        Ok(())
+    }
+
+    async fn upgrade(&self, packages: Option<&[String]>) -> Result<InstallResult> {
+        let mut args = vec!["update"];
+        args.extend(packages.unwrap_or_default().iter().map(String::as_str));
+        self.run_command(&args, true)?;
+
+        Ok(InstallResult {
+            success: true,
+            message: "System upgraded successfully".to_string(),
+            packages_installed: packages.map(|p| p.to_vec()).unwrap_or_default(),
+        })
+    }
+
+    /// yum takes one `--exclude` per package
+    async fn upgrade_excluding(&self, exclude: &[String]) -> Result<InstallResult> {
+        let excludes: Vec<String> = exclude.iter().map(|package| format!("--exclude={}", package)).collect();
+        let mut args = vec!["update"];
+        args.extend(excludes.iter().map(String::as_str));
+        self.run_command(&args, true)?;
+
+        Ok(InstallResult {
+            success: true,
+            message: "System upgraded successfully".to_string(),
+            packages_installed: Vec::new(),
+        })
+    }
+
+    async fn list_installed(&self) -> Result<Vec<PackageInfo>> {
+        let output = self.run_command(&["list", "installed"], false)?;
+        Ok(self.parse_list_installed(&output))
+    }
+
+    async fn info(&self, package: &str) -> Result<Option<PackageInfo>> {
+        let Ok(output) = self.run_command(&["info", package], false) else {
+            return Ok(None); // Package not found
+        };
+        let Some(mut info) = RPM_OUTPUT.parse_package_info(&output) else { return Ok(None) };
+        info.source = "yum".to_string();
+
+        // Best effort; needs yum-utils
+        if let Ok(mut dependencies) = self.requires(package) {
+            dependencies.retain(|dependency| *dependency != info.name);
+            info.dependencies = dependencies;
+        }
+        Ok(Some(info))
+    }
+
+    async fn dependencies(&self, package: &str) -> Result<Vec<String>> {
+        self.requires(package)
+    }
+
+    /// `rpm -q --changelog` for installed packages, else `yum changelog`
+    /// from repository metadata, which needs yum-plugin-changelog
+    async fn changelog(&self, package: &str) -> Result<Option<String>> {
+        let output = Command::new("rpm")
+            .args(["-q", "--changelog", package])
+            .logged_output()
+            .context("Failed to execute rpm command")?;
+        if output.status.success() {
+            return Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()));
+        }
+
+        match self.run_command(&["changelog", "all", package], false) {
+            Ok(changelog) if !changelog.trim().is_empty() => Ok(Some(changelog)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn owned_files(&self, package: &str) -> Result<Vec<PathBuf>> {
+        RPM_OUTPUT.owned_files(package).await
+    }
+
+    async fn owner_of_file(&self, path: &Path) -> Result<Option<String>> {
+        RPM_OUTPUT.owner_of_file(path).await
+    }
+
+    async fn provides_file(&self, path: &str) -> Result<Vec<String>> {
+        let Ok(output) = self.repoquery(&["--qf", "%{name}", "--whatprovides", path]) else {
+            return Ok(Vec::new());
+        };
+
+        let mut packages: Vec<String> = output.lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+        packages.sort();
+        packages.dedup();
+        Ok(packages)
+    }
+
+    /// yum's updateinfo has no `--security` switch; the advisory type is a
+    /// list filter. Rows have the same layout as dnf's. CentOS repositories
+    /// ship no updateinfo, so there this is always empty
+    async fn security_updates(&self) -> Result<Vec<String>> {
+        let output = self.run_command(&["updateinfo", "list", "security"], false)?;
+        Ok(RPM_OUTPUT.parse_security_updateinfo(&output))
+    }
+
+    /// `yum check-update`, which exits 100 when updates are available
+    async fn upgradable(&self) -> Result<Vec<PackageUpdate>> {
+        let output = Command::new("yum")
+            .args(["check-update", "--quiet"])
+            .logged_output()
+            .context("Failed to execute yum command")?;
+        match output.status.code() {
+            Some(0) => return Ok(Vec::new()),
+            Some(100) => {}
+            _ => bail!("YUM command failed: {}", String::from_utf8_lossy(&output.stderr)),
+        }
+
+        let mut updates = RPM_OUTPUT.parse_check_update(&String::from_utf8_lossy(&output.stdout));
+        let names: Vec<&str> = updates.iter().map(|update| update.name.as_str()).collect();
+        let installed = RPM_OUTPUT.installed_versions(&names)?;
+        for update in &mut updates {
+            if let Some(version) = installed.get(&update.name) {
+                update.current = version.clone();
+            }
+        }
+        Ok(updates)
+    }
+
+    async fn simulate(&self, operation: Operation<'_>) -> Result<Vec<PlannedChange>> {
+        let excludes: Vec<String>;
+        let args: Vec<&str> = match operation {
+            Operation::Install(packages) => std::iter::once("install").chain(packages.iter().map(String::as_str)).collect(),
+            Operation::Remove(packages) | Operation::Purge(packages) => {
+                std::iter::once("remove").chain(packages.iter().map(String::as_str)).collect()
+            }
+            Operation::Upgrade(packages) => {
+                std::iter::once("update").chain(packages.unwrap_or_default().iter().map(String::as_str)).collect()
+            }
+            Operation::UpgradeExcluding(packages) => {
+                excludes = packages.iter().map(|package| format!("--exclude={}", package)).collect();
+                std::iter::once("update").chain(excludes.iter().map(String::as_str)).collect()
+            }
+        };
+
+        let mut changes = self.dry_run(&args)?;
+        let upgraded: Vec<&str> = changes.iter()
+            .filter(|change| matches!(change.kind, ChangeKind::Upgrade | ChangeKind::Downgrade))
+            .map(|change| change.name.as_str())
+            .collect();
+        let installed = RPM_OUTPUT.installed_versions(&upgraded)?;
+        for change in &mut changes {
+            if change.kind == ChangeKind::Remove {
+                change.current = change.version.take();
+            } else if let Some(version) = installed.get(&change.name) {
+                change.current = Some(version.clone());
+            }
+        }
+        Ok(changes)
+    }
+
+    /// What `yum autoremove` would remove; yum has no unneeded query
+    async fn orphans(&self) -> Result<Vec<String>> {
+        Ok(self.dry_run(&["autoremove"])?
+            .into_iter()
+            .filter(|change| change.kind == ChangeKind::Remove)
+            .map(|change| change.name)
+            .collect())
+    }
+
+    async fn remove_orphans(&self, orphans: &[String]) -> Result<InstallResult> {
+        // Without the setopt, remove would also take dependencies that were
+        // never listed to the user
+        if !orphans.is_empty() {
+            let mut args = vec!["remove", "--setopt=clean_requirements_on_remove=False"];
+            args.extend(orphans.iter().map(String::as_str));
+            self.run_command(&args, true)?;
+        }
+
+        Ok(InstallResult {
+            success: true,
+            message: format!("Successfully removed {} orphaned packages", orphans.len()),
+            packages_installed: orphans.to_vec(),
+        })
+    }
+
+    /// Cached RPMs under yum's per-repository cache directories
+    async fn package_cache_size(&self) -> Result<u64> {
+        Ok(super::files_size(Path::new("/var/cache/yum"), |path| path.extension().is_some_and(|ext| ext == "rpm")))
+    }
+
+    async fn clean_package_cache(&self) -> Result<()> {
+        self.run_command(&["clean", "packages"], true)?;
+        Ok(())
+    }
+
+    /// One rpm query for every name instead of a yum run per package
+    async fn is_installed(&self, packages: &[String]) -> Result<HashMap<String, bool>> {
+        let names: Vec<&str> = packages.iter().map(String::as_str).collect();
+        let installed = RPM_OUTPUT.installed_versions(&names)?;
+
+        Ok(packages.iter()
+            .map(|package| (package.clone(), installed.contains_key(package)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn el7_listings_and_transactions_parse() {
+        let installed = "Installed Packages\n\
+            bash.x86_64                          4.2.46-35.el7_9              @updates\n\
+            python-backports-ssl_match_hostname.noarch\n\
+            \x20                                    3.5.0.1-1.el7                @base\n";
+        let rows: Vec<(String, String)> = YumManager::new().parse_list_installed(installed)
+            .into_iter()
+            .map(|package| (package.name, package.version))
+            .collect();
+        assert_eq!(rows, vec![
+            ("bash".to_string(), "4.2.46-35.el7_9".to_string()),
+            ("python-backports-ssl_match_hostname".to_string(), "3.5.0.1-1.el7".to_string()),
+        ]);
+
+        let transaction = "Resolving Dependencies\n\
+            Dependencies Resolved\n\
+            \n\
+            ================================================================================\n\
+            \x20Package            Arch        Version                   Repository     Size\n\
+            ================================================================================\n\
+            Updating:\n\
+            \x20openssl-libs       x86_64      1:1.0.2k-26.el7_9         updates       1.2 M\n\
+            Updating for dependencies:\n\
+            \x20openssl            x86_64      1:1.0.2k-26.el7_9         updates       494 k\n\
+            \n\
+            Transaction Summary\n\
+            ================================================================================\n\
+            Upgrade  1 Package (+1 Dependent package)\n";
+        let changes = RPM_OUTPUT.parse_transaction(transaction);
+        let rows: Vec<(ChangeKind, &str)> = changes.iter().map(|c| (c.kind, c.name.as_str())).collect();
+        assert_eq!(rows, vec![(ChangeKind::Upgrade, "openssl-libs"), (ChangeKind::Upgrade, "openssl")]);
+    }
+}
@@ -0,0 +1,795 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::Command;
+use crate::commands::binary;
+use crate::core::config::Config;
+use crate::core::platform::PlatformInfo;
+use crate::core::PackageManager;
+use crate::managers::PackageManagerFactory;
+use crate::ui::output::Output;
+use super::Profile;
+
+/// Part of a profile that can be reconciled independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplyScope {
+    System,
+    Languages,
+    Binaries,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    Install,
+    Update,
+    Remove,
+}
+
+/// A single change needed to make the system match a profile
+#[derive(Debug, Clone)]
+pub struct PlannedAction {
+    pub scope: ApplyScope,
+    pub kind: ActionKind,
+    pub name: String,
+    /// Language ecosystem for language packages, repository for binaries
+    pub source: Option<String>,
+    pub current_version: Option<String>,
+    pub target_version: Option<String>,
+}
+
+impl PlannedAction {
+    pub fn describe(&self) -> String {
+        let label = match self.scope {
+            ApplyScope::System => self.name.clone(),
+            ApplyScope::Languages => format!("{} ({})", self.name, self.source.as_deref().unwrap_or("?")),
+            ApplyScope::Binaries => self.source.clone().unwrap_or_else(|| self.name.clone()),
+        };
+
+        match (self.kind, &self.current_version, &self.target_version) {
+            (ActionKind::Update, Some(current), Some(target)) => format!("{} {} → {}", label, current, target),
+            (ActionKind::Install, _, Some(target)) => format!("{}@{}", label, target),
+            (ActionKind::Remove, Some(current), _) => format!("{} {}", label, current),
+            _ => label,
+        }
+    }
+}
+
+/// Ordered set of actions produced by comparing a profile to the system
+#[derive(Debug, Clone, Default)]
+pub struct ActionPlan {
+    pub actions: Vec<PlannedAction>,
+}
+
+impl ActionPlan {
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    pub fn of_kind(&self, kind: ActionKind) -> Vec<&PlannedAction> {
+        self.actions.iter().filter(|a| a.kind == kind).collect()
+    }
+}
+
+/// Outcome of applying an action plan
+#[derive(Debug, Default)]
+pub struct ApplyReport {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// Packages previously installed by `sync` for a profile. Only these are
+/// candidates for pruning, so base system packages are never removed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SyncState {
+    system: Vec<String>,
+    languages: HashMap<String, Vec<String>>,
+    /// Binary repositories (`owner/repo`)
+    binaries: Vec<String>,
+}
+
+impl SyncState {
+    /// Track what `succeeded` installed and forget what it removed
+    fn record(&mut self, succeeded: &[&PlannedAction]) {
+        for action in succeeded {
+            let entries = match action.scope {
+                ApplyScope::System => &mut self.system,
+                ApplyScope::Languages => self.languages
+                    .entry(action.source.clone().unwrap_or_default())
+                    .or_default(),
+                ApplyScope::Binaries => &mut self.binaries,
+            };
+            let key = match action.scope {
+                ApplyScope::Binaries => action.source.as_ref().unwrap_or(&action.name),
+                _ => &action.name,
+            };
+
+            match action.kind {
+                ActionKind::Install if !entries.contains(key) => entries.push(key.clone()),
+                ActionKind::Remove => entries.retain(|e| e != key),
+                _ => {}
+            }
+        }
+        self.languages.retain(|_, names| !names.is_empty());
+    }
+
+    fn path(config: &Config, profile: &str) -> Result<PathBuf> {
+        Ok(config.get_data_dir()?.join("sync").join(format!("{}.toml", profile)))
+    }
+
+    fn load(config: &Config, profile: &str) -> Result<Self> {
+        let path = Self::path(config, profile)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .context("Failed to read sync state")?;
+        toml::from_str(&content).context("Failed to parse sync state")
+    }
+
+    fn save(&self, config: &Config, profile: &str) -> Result<()> {
+        let path = Self::path(config, profile)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = toml::to_string_pretty(self)?;
+        std::fs::write(&path, content).context("Failed to write sync state")
+    }
+}
+
+/// Names a JSON file of installed packages for the planner to compare
+/// against instead of the host, so plans can be tested deterministically
+pub const FAKE_INSTALLED_ENV: &str = "PKMGR_FAKE_INSTALLED";
+
+/// Installed packages the planner compares a profile against, in place of
+/// what the system reports
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InstalledState {
+    /// System package name to version
+    pub system: HashMap<String, String>,
+    /// Language to package name to version
+    pub languages: HashMap<String, HashMap<String, String>>,
+    /// Binary repository (`owner/repo`) to version
+    pub binaries: HashMap<String, String>,
+}
+
+impl InstalledState {
+    /// The state in the file named by PKMGR_FAKE_INSTALLED, if it is set
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(path) = std::env::var_os(FAKE_INSTALLED_ENV) else { return Ok(None) };
+        let path = PathBuf::from(path);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {} ({})", path.display(), FAKE_INSTALLED_ENV))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid installed state in {}", path.display()))
+            .map(Some)
+    }
+
+    fn binaries(&self) -> Vec<binary::InstalledBinary> {
+        self.binaries.iter()
+            .map(|(repository, version)| binary::InstalledBinary {
+                name: repository.rsplit('/').next().unwrap_or(repository).to_string(),
+                repository: repository.clone(),
+                version: version.clone(),
+                installed_date: String::new(),
+                path: None,
+            })
+            .collect()
+    }
+}
+
+/// Builds and executes the action plan that reconciles the system to a profile
+pub struct ProfileApplier {
+    output: Output,
+    config: Config,
+    scopes: Vec<ApplyScope>,
+    prune: bool,
+    installed: Option<InstalledState>,
+}
+
+impl ProfileApplier {
+    pub fn new(output: Output, config: Config) -> Self {
+        Self {
+            output,
+            config,
+            scopes: vec![ApplyScope::System, ApplyScope::Languages, ApplyScope::Binaries],
+            prune: false,
+            installed: None,
+        }
+    }
+
+    pub fn with_scopes(mut self, scopes: Vec<ApplyScope>) -> Self {
+        if !scopes.is_empty() {
+            self.scopes = scopes;
+        }
+        self
+    }
+
+    pub fn with_prune(mut self, prune: bool) -> Self {
+        self.prune = prune;
+        self
+    }
+
+    /// Plan against `installed` instead of the host; such a plan cannot be
+    /// applied
+    pub fn with_installed(mut self, installed: InstalledState) -> Self {
+        self.installed = Some(installed);
+        self
+    }
+
+    /// Compare the profile to installed state and list the required actions
+    pub async fn plan(&self, profile: &Profile) -> Result<ActionPlan> {
+        let state = SyncState::load(&self.config, &profile.name)?;
+        let mut plan = ActionPlan::default();
+
+        let fake = self.installed.as_ref();
+
+        if self.scopes.contains(&ApplyScope::System) && (!profile.packages.system.is_empty() || self.prune) {
+            self.plan_system(profile, &state, fake, &mut plan).await?;
+        }
+
+        if self.scopes.contains(&ApplyScope::Languages) {
+            self.plan_languages(profile, &state, fake, &mut plan);
+        }
+
+        if self.scopes.contains(&ApplyScope::Binaries) {
+            self.plan_binaries(profile, &state, fake, &mut plan).await?;
+        }
+
+        Ok(plan)
+    }
+
+    async fn plan_system(&self, profile: &Profile, state: &SyncState, fake: Option<&InstalledState>, plan: &mut ActionPlan) -> Result<()> {
+        let installed: HashMap<String, String> = match fake {
+            Some(fake) => fake.system.clone(),
+            None => {
+                self.output.progress("Reading installed system packages...");
+
+                let platform_info = PlatformInfo::detect_async().await?;
+                let package_manager = PackageManagerFactory::create(&platform_info)
+                    .context("Failed to create package manager")?;
+
+                package_manager.list_installed().await?
+                    .into_iter()
+                    .map(|p| (p.name, p.version))
+                    .collect()
+            }
+        };
+
+        for spec in &profile.packages.system {
+            match (installed.get(&spec.name), &spec.version) {
+                (None, _) => plan.actions.push(PlannedAction {
+                    scope: ApplyScope::System,
+                    kind: ActionKind::Install,
+                    name: spec.name.clone(),
+                    source: None,
+                    current_version: None,
+                    target_version: spec.version.clone(),
+                }),
+                (Some(current), Some(pinned)) if !current.starts_with(pinned.as_str()) => {
+                    plan.actions.push(PlannedAction {
+                        scope: ApplyScope::System,
+                        kind: ActionKind::Update,
+                        name: spec.name.clone(),
+                        source: None,
+                        current_version: Some(current.clone()),
+                        target_version: Some(pinned.clone()),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        if self.prune {
+            let wanted: HashSet<&str> = profile.packages.system.iter().map(|p| p.name.as_str()).collect();
+            for name in &state.system {
+                if wanted.contains(name.as_str()) {
+                    continue;
+                }
+                if let Some(current) = installed.get(name) {
+                    plan.actions.push(PlannedAction {
+                        scope: ApplyScope::System,
+                        kind: ActionKind::Remove,
+                        name: name.clone(),
+                        source: None,
+                        current_version: Some(current.clone()),
+                        target_version: None,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn plan_languages(&self, profile: &Profile, state: &SyncState, fake: Option<&InstalledState>, plan: &mut ActionPlan) {
+        let mut languages: Vec<&String> = profile.packages.languages.keys()
+            .chain(state.languages.keys())
+            .collect();
+        languages.sort();
+        languages.dedup();
+
+        for lang in languages {
+            let tool = match LanguageTool::for_language(lang) {
+                Some(tool) => tool,
+                None => {
+                    self.output.warn(&format!("Skipping {} packages: language not supported by sync", lang));
+                    continue;
+                }
+            };
+
+            let installed = match fake {
+                Some(fake) => fake.languages.get(lang).cloned().unwrap_or_default(),
+                None => tool.list_installed(),
+            };
+            let specs = profile.packages.languages.get(lang).map(|v| v.as_slice()).unwrap_or(&[]);
+
+            for spec in specs {
+                let current = installed.get(&spec.name)
+                    .or_else(|| installed.get(&spec.name.to_lowercase()));
+                let action = match (current, &spec.version) {
+                    (None, _) => Some((ActionKind::Install, None)),
+                    (Some(current), Some(pinned)) if current != pinned => {
+                        Some((ActionKind::Update, Some(current.clone())))
+                    }
+                    _ => None,
+                };
+
+                if let Some((kind, current_version)) = action {
+                    plan.actions.push(PlannedAction {
+                        scope: ApplyScope::Languages,
+                        kind,
+                        name: spec.name.clone(),
+                        source: Some(lang.clone()),
+                        current_version,
+                        target_version: spec.version.clone(),
+                    });
+                }
+            }
+
+            if self.prune {
+                let wanted: HashSet<&str> = specs.iter().map(|p| p.name.as_str()).collect();
+                for name in state.languages.get(lang).into_iter().flatten() {
+                    if wanted.contains(name.as_str()) {
+                        continue;
+                    }
+                    let current = installed.get(name)
+                        .or_else(|| installed.get(&name.to_lowercase()));
+                    if let Some(current) = current {
+                        plan.actions.push(PlannedAction {
+                            scope: ApplyScope::Languages,
+                            kind: ActionKind::Remove,
+                            name: name.clone(),
+                            source: Some(lang.clone()),
+                            current_version: Some(current.clone()),
+                            target_version: None,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    async fn plan_binaries(&self, profile: &Profile, state: &SyncState, fake: Option<&InstalledState>, plan: &mut ActionPlan) -> Result<()> {
+        let installed = match fake {
+            Some(fake) => fake.binaries(),
+            None => binary::load_installed_binaries(&self.config).await?,
+        };
+
+        for spec in &profile.packages.binaries {
+            let name = spec.repository.rsplit('/').next().unwrap_or(&spec.repository).to_string();
+            let current = installed.iter().find(|b| b.repository == spec.repository || b.name == name);
+
+            let kind = match (current, &spec.version) {
+                (None, _) => Some(ActionKind::Install),
+                (Some(current), Some(pinned))
+                    if current.version.trim_start_matches('v') != pinned.trim_start_matches('v') => {
+                    Some(ActionKind::Update)
+                }
+                _ => None,
+            };
+
+            if let Some(kind) = kind {
+                plan.actions.push(PlannedAction {
+                    scope: ApplyScope::Binaries,
+                    kind,
+                    name,
+                    source: Some(spec.repository.clone()),
+                    current_version: current.map(|c| c.version.clone()),
+                    target_version: spec.version.clone(),
+                });
+            }
+        }
+
+        if self.prune {
+            for repository in &state.binaries {
+                let wanted = profile.packages.binaries.iter()
+                    .any(|b| &b.repository == repository);
+                if wanted {
+                    continue;
+                }
+                if let Some(binary) = installed.iter().find(|b| &b.repository == repository) {
+                    plan.actions.push(PlannedAction {
+                        scope: ApplyScope::Binaries,
+                        kind: ActionKind::Remove,
+                        name: binary.name.clone(),
+                        source: Some(binary.repository.clone()),
+                        current_version: Some(binary.version.clone()),
+                        target_version: None,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Execute a plan, removals last, and record what sync now manages
+    pub async fn apply(&self, profile: &Profile, plan: &ActionPlan) -> Result<ApplyReport> {
+        if self.installed.is_some() {
+            anyhow::bail!("A plan made against {} can only be previewed; re-run with --dry-run", FAKE_INSTALLED_ENV);
+        }
+        let mut report = ApplyReport::default();
+
+        let package_manager = if plan.actions.iter().any(|a| a.scope == ApplyScope::System) {
+            let platform_info = PlatformInfo::detect_async().await?;
+            Some(PackageManagerFactory::create(&platform_info)
+                .context("Failed to create package manager")?)
+        } else {
+            None
+        };
+
+        let ordered = plan.of_kind(ActionKind::Install).into_iter()
+            .chain(plan.of_kind(ActionKind::Update))
+            .chain(plan.of_kind(ActionKind::Remove));
+
+        let mut succeeded = Vec::new();
+        for action in ordered {
+            let result = match action.scope {
+                ApplyScope::System => match package_manager {
+                    Some(ref pm) => self.apply_system(pm.as_ref(), action).await,
+                    None => continue,
+                },
+                ApplyScope::Languages => self.apply_language(action),
+                ApplyScope::Binaries => self.apply_binary(action).await,
+            };
+
+            match result {
+                Ok(()) => {
+                    report.succeeded.push(action.describe());
+                    succeeded.push(action);
+                }
+                Err(e) => {
+                    self.output.error(&format!("❌ {}: {}", action.describe(), e));
+                    report.failed.push(action.describe());
+                }
+            }
+        }
+
+        let mut state = SyncState::load(&self.config, &profile.name)?;
+        state.record(&succeeded);
+        state.save(&self.config, &profile.name)?;
+
+        Ok(report)
+    }
+
+    async fn apply_system(&self, pm: &dyn PackageManager, action: &PlannedAction) -> Result<()> {
+        let result = match action.kind {
+            ActionKind::Install | ActionKind::Update => {
+                self.output.install_start(&action.name);
+                let spec = match &action.target_version {
+                    Some(version) => pinned_spec(pm.name(), &action.name, version),
+                    None => action.name.clone(),
+                };
+                pm.install(&[spec]).await?
+            }
+            ActionKind::Remove => {
+                self.output.progress(&format!("Removing {}", action.name));
+                pm.remove(std::slice::from_ref(&action.name)).await?
+            }
+        };
+
+        if !result.success {
+            anyhow::bail!("{}", result.message);
+        }
+        self.output.success(&format!("✅ {}", action.describe()));
+        Ok(())
+    }
+
+    fn apply_language(&self, action: &PlannedAction) -> Result<()> {
+        let lang = action.source.as_deref().unwrap_or_default();
+        let tool = LanguageTool::for_language(lang)
+            .with_context(|| format!("Unsupported language: {}", lang))?;
+
+        let args = match action.kind {
+            ActionKind::Install => tool.install_args(&action.name, action.target_version.as_deref()),
+            ActionKind::Update => tool.update_args(&action.name, action.target_version.as_deref()),
+            ActionKind::Remove => tool.remove_args(&action.name)
+                .with_context(|| format!("Removing {} packages is not supported", lang))?,
+        };
+
+        self.output.progress(&format!("Running: {}", args.join(" ")));
+        let status = Command::new(&args[0])
+            .args(&args[1..])
+            .status()
+            .with_context(|| format!("Failed to run {}", args[0]))?;
+
+        if !status.success() {
+            anyhow::bail!("{} exited with {}", args[0], status);
+        }
+        self.output.success(&format!("✅ {}", action.describe()));
+        Ok(())
+    }
+
+    async fn apply_binary(&self, action: &PlannedAction) -> Result<()> {
+        match action.kind {
+            ActionKind::Install | ActionKind::Update => {
+                let repo = action.source.clone().unwrap_or_else(|| action.name.clone());
+                let repo = match &action.target_version {
+                    Some(version) => format!("{}@{}", repo, version),
+                    None => repo,
+                };
+                binary::install_binary(repo, &self.config, &self.output).await.map(drop)
+            }
+            ActionKind::Remove => {
+                binary::remove_binary(action.name.clone(), &self.config, &self.output).await
+            }
+        }
+    }
+}
+
+/// Format a version-pinned package argument for the given package manager
+fn pinned_spec(manager: &str, name: &str, version: &str) -> String {
+    match manager {
+        "apt" => format!("{}={}", name, version),
+        "dnf" | "yum" => format!("{}-{}", name, version),
+        "homebrew" => format!("{}@{}", name, version),
+        _ => name.to_string(),
+    }
+}
+
+/// Whether sync knows how to install packages for `lang`
+pub fn supports_language(lang: &str) -> bool {
+    LanguageTool::for_language(lang).is_some()
+}
+
+/// Native package tool for a language ecosystem
+enum LanguageTool {
+    Npm,
+    Pip,
+    Gem,
+    Cargo,
+    Go,
+    Dotnet,
+}
+
+impl LanguageTool {
+    fn for_language(lang: &str) -> Option<Self> {
+        match lang {
+            "node" | "nodejs" | "npm" => Some(Self::Npm),
+            "python" | "pip" => Some(Self::Pip),
+            "ruby" | "gem" => Some(Self::Gem),
+            "rust" | "cargo" => Some(Self::Cargo),
+            "go" | "golang" => Some(Self::Go),
+            // Profiles install global tools; dotnet-tools.json belongs to each repository
+            "dotnet" | "csharp" => Some(Self::Dotnet),
+            _ => None,
+        }
+    }
+
+    /// Installed packages and versions; empty when the tool is unavailable
+    fn list_installed(&self) -> HashMap<String, String> {
+        let run = |program: &str, args: &[&str]| -> Option<String> {
+            let output = Command::new(program).args(args).output().ok()?;
+            output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+        };
+
+        let mut installed = HashMap::new();
+        match self {
+            Self::Npm => {
+                if let Some(out) = run("npm", &["ls", "-g", "--depth=0", "--json"]) {
+                    let json: serde_json::Value = serde_json::from_str(&out).unwrap_or_default();
+                    if let Some(deps) = json["dependencies"].as_object() {
+                        for (name, info) in deps {
+                            let version = info["version"].as_str().unwrap_or_default();
+                            installed.insert(name.clone(), version.to_string());
+                        }
+                    }
+                }
+            }
+            Self::Pip => {
+                if let Some(out) = run("python3", &["-m", "pip", "list", "--format=json"]) {
+                    let json: serde_json::Value = serde_json::from_str(&out).unwrap_or_default();
+                    for pkg in json.as_array().into_iter().flatten() {
+                        if let (Some(name), Some(version)) = (pkg["name"].as_str(), pkg["version"].as_str()) {
+                            installed.insert(name.to_lowercase(), version.to_string());
+                        }
+                    }
+                }
+            }
+            Self::Gem => {
+                // Lines look like: "rake (13.0.6, 12.3.3)"
+                if let Some(out) = run("gem", &["list", "--local"]) {
+                    for line in out.lines() {
+                        if let Some((name, versions)) = line.split_once(" (") {
+                            let version = versions.trim_end_matches(')').split(',').next().unwrap_or_default();
+                            let version = version.trim_start_matches("default: ").trim();
+                            installed.insert(name.to_string(), version.to_string());
+                        }
+                    }
+                }
+            }
+            Self::Cargo => {
+                // Lines look like: "ripgrep v14.1.0:"
+                if let Some(out) = run("cargo", &["install", "--list"]) {
+                    for line in out.lines().filter(|l| !l.starts_with(char::is_whitespace)) {
+                        if let Some((name, version)) = line.trim_end_matches(':').split_once(' ') {
+                            let version = version.split_whitespace().next().unwrap_or_default();
+                            installed.insert(name.to_string(), version.trim_start_matches('v').to_string());
+                        }
+                    }
+                }
+            }
+            Self::Dotnet => {
+                if let Some(out) = run("dotnet", &["tool", "list", "--global"]) {
+                    for (id, version) in crate::languages::dotnet::parse_tool_list(&out) {
+                        installed.insert(id, version);
+                    }
+                }
+            }
+            // Go has no package inventory; read the module paths built into
+            // the binaries in GOBIN (or GOPATH/bin)
+            Self::Go => {
+                let bin_dir = run("go", &["env", "GOBIN", "GOPATH"]).and_then(|out| {
+                    let mut lines = out.lines().map(str::trim);
+                    let gobin = lines.next().filter(|l| !l.is_empty()).map(PathBuf::from);
+                    let gopath = lines.next().and_then(|l| std::env::split_paths(l).next());
+                    gobin.or_else(|| gopath.map(|p| p.join("bin")))
+                });
+                if let Some(dir) = bin_dir.filter(|d| d.is_dir()) {
+                    if let Some(out) = run("go", &["version", "-m", &dir.to_string_lossy()]) {
+                        installed.extend(parse_go_version_m(&out));
+                    }
+                }
+            }
+        }
+
+        installed
+    }
+
+    fn install_args(&self, name: &str, version: Option<&str>) -> Vec<String> {
+        match (self, version) {
+            (Self::Npm, Some(v)) => vec!["npm".into(), "install".into(), "-g".into(), format!("{}@{}", name, v)],
+            (Self::Npm, None) => vec!["npm".into(), "install".into(), "-g".into(), name.into()],
+            (Self::Pip, Some(v)) => vec!["python3".into(), "-m".into(), "pip".into(), "install".into(), "--user".into(), format!("{}=={}", name, v)],
+            (Self::Pip, None) => vec!["python3".into(), "-m".into(), "pip".into(), "install".into(), "--user".into(), name.into()],
+            (Self::Gem, Some(v)) => vec!["gem".into(), "install".into(), name.into(), "-v".into(), v.into()],
+            (Self::Gem, None) => vec!["gem".into(), "install".into(), name.into()],
+            (Self::Cargo, Some(v)) => vec!["cargo".into(), "install".into(), name.into(), "--version".into(), v.into()],
+            (Self::Cargo, None) => vec!["cargo".into(), "install".into(), name.into()],
+            (Self::Go, v) => vec!["go".into(), "install".into(), format!("{}@{}", name, v.unwrap_or("latest"))],
+            (Self::Dotnet, Some(v)) => vec!["dotnet".into(), "tool".into(), "install".into(), "--global".into(), name.into(), "--version".into(), v.into()],
+            (Self::Dotnet, None) => vec!["dotnet".into(), "tool".into(), "install".into(), "--global".into(), name.into()],
+        }
+    }
+
+    /// `dotnet tool install` refuses an installed tool, so moving a pinned
+    /// version goes through `dotnet tool update`
+    fn update_args(&self, name: &str, version: Option<&str>) -> Vec<String> {
+        match self {
+            Self::Dotnet => {
+                let mut args: Vec<String> = vec!["dotnet".into(), "tool".into(), "update".into(), "--global".into(), name.into()];
+                if let Some(v) = version {
+                    args.extend(["--version".into(), v.into()]);
+                }
+                args
+            }
+            _ => self.install_args(name, version),
+        }
+    }
+
+    fn remove_args(&self, name: &str) -> Option<Vec<String>> {
+        let args = match self {
+            Self::Npm => vec!["npm", "uninstall", "-g", name],
+            Self::Pip => vec!["python3", "-m", "pip", "uninstall", "-y", name],
+            Self::Gem => vec!["gem", "uninstall", "-x", "-a", name],
+            Self::Cargo => vec!["cargo", "uninstall", name],
+            Self::Dotnet => vec!["dotnet", "tool", "uninstall", "--global", name],
+            Self::Go => return None,
+        };
+        Some(args.into_iter().map(String::from).collect())
+    }
+}
+
+/// Package path to module version from `go version -m` output, whose
+/// binaries look like "\tpath\t<pkg>" followed by "\tmod\t<module>\t<version>"
+fn parse_go_version_m(output: &str) -> HashMap<String, String> {
+    let mut installed = HashMap::new();
+    let mut path = None;
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split('\t').map(str::trim).filter(|f| !f.is_empty()).collect();
+        match fields.as_slice() {
+            ["path", pkg] => path = Some(pkg.to_string()),
+            ["mod", _, version, ..] => {
+                if let Some(pkg) = path.take() {
+                    installed.insert(pkg, version.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    installed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::{BinarySpec, PackageSpec};
+
+    #[test]
+    fn plan_against_injected_installed_state() {
+        let spec = |name: &str, version: Option<&str>| PackageSpec {
+            name: name.to_string(),
+            version: version.map(String::from),
+            source: None,
+            options: HashMap::new(),
+        };
+        let mut profile = Profile::new("fake-installed-plan-test".to_string());
+        profile.packages.system = vec![spec("git", Some("2.40")), spec("curl", None), spec("vim", None)];
+        profile.packages.languages.insert("python".to_string(), vec![spec("requests", None)]);
+        profile.packages.binaries = vec![BinarySpec { repository: "helm/helm".to_string(), version: Some("v3.14.0".to_string()), asset_pattern: None }];
+
+        let installed: InstalledState = serde_json::from_str(r#"{
+            "system": {"git": "2.39.2-1", "vim": "9.0"},
+            "languages": {"python": {"requests": "2.31.0"}},
+            "binaries": {"helm/helm": "3.13.0"}
+        }"#).unwrap();
+
+        let applier = ProfileApplier::new(Output::new("never".to_string(), false), Config::default())
+            .with_installed(installed);
+        let plan = tokio::runtime::Runtime::new().unwrap().block_on(applier.plan(&profile)).unwrap();
+
+        let described: Vec<String> = plan.actions.iter().map(|a| a.describe()).collect();
+        assert_eq!(described, vec!["git 2.39.2-1 → 2.40", "curl", "helm/helm 3.13.0 → v3.14.0"]);
+        assert!(tokio::runtime::Runtime::new().unwrap().block_on(applier.apply(&profile, &plan)).is_err());
+    }
+
+    #[test]
+    fn sync_state_tracks_installs_and_removals() {
+        let action = |scope, kind, name: &str, source: Option<&str>| PlannedAction {
+            scope,
+            kind,
+            name: name.to_string(),
+            source: source.map(String::from),
+            current_version: None,
+            target_version: None,
+        };
+        let mut state = SyncState {
+            system: vec!["htop".to_string()],
+            binaries: vec!["cli/cli".to_string()],
+            ..Default::default()
+        };
+        let curl = action(ApplyScope::System, ActionKind::Install, "curl", None);
+        let requests = action(ApplyScope::Languages, ActionKind::Install, "requests", Some("python"));
+        let gh = action(ApplyScope::Binaries, ActionKind::Remove, "gh", Some("cli/cli"));
+        let helm = action(ApplyScope::Binaries, ActionKind::Install, "helm", Some("helm/helm"));
+
+        state.record(&[&curl, &requests, &gh, &helm]);
+
+        assert_eq!(state.system, vec!["htop", "curl"]);
+        assert_eq!(state.languages["python"], vec!["requests"]);
+        assert_eq!(state.binaries, vec!["helm/helm"]);
+    }
+
+    #[test]
+    fn go_binaries_are_keyed_by_package_path() {
+        let output = "/home/u/go/bin/gopls: go1.22.0\n\tpath\tgolang.org/x/tools/gopls\n\tmod\tgolang.org/x/tools/gopls\tv0.15.3\th1:abc=\n\tdep\tgolang.org/x/mod\tv0.17.0\th1:def=\n";
+        let installed = parse_go_version_m(output);
+        assert_eq!(installed.get("golang.org/x/tools/gopls").map(String::as_str), Some("v0.15.3"));
+        assert_eq!(installed.len(), 1);
+    }
+}
@@ -16,7 +16,7 @@ impl ProfileExporter {
 
     /// Export a profile to a file
     pub fn export(&self, profile_name: &str, output_path: &Path, format: ExportFormat) -> Result<()> {
-        let profile = Profile::load(profile_name)?;
+        let profile = Profile::load_unexpanded(profile_name)?;
 
         self.output.progress(&format!("Exporting profile '{}' to {}", profile_name, output_path.display()));
 
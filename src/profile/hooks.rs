@@ -0,0 +1,153 @@
+use anyhow::{bail, Result};
+use std::process::Command;
+use crate::commands::Cli;
+use crate::ui::output::Output;
+use crate::utils::logging::LoggedCommand;
+use super::{Profile, ProfileScripts};
+
+/// Point in an operation where profile scripts run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookStage {
+    PreInstall,
+    PostInstall,
+    PreUpdate,
+    PostUpdate,
+}
+
+impl HookStage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookStage::PreInstall => "pre_install",
+            HookStage::PostInstall => "post_install",
+            HookStage::PreUpdate => "pre_update",
+            HookStage::PostUpdate => "post_update",
+        }
+    }
+
+    fn scripts<'a>(&self, scripts: &'a ProfileScripts) -> &'a [String] {
+        match self {
+            HookStage::PreInstall => &scripts.pre_install,
+            HookStage::PostInstall => &scripts.post_install,
+            HookStage::PreUpdate => &scripts.pre_update,
+            HookStage::PostUpdate => &scripts.post_update,
+        }
+    }
+
+    /// A failing pre_* hook aborts the operation; post_* failures only warn
+    fn is_pre(&self) -> bool {
+        matches!(self, HookStage::PreInstall | HookStage::PreUpdate)
+    }
+}
+
+/// Runs a profile's scripts in a shell with the profile environment applied
+pub struct HookRunner {
+    profile: Option<Profile>,
+    output: Output,
+    dry_run: bool,
+}
+
+impl HookRunner {
+    pub fn new(profile: Option<Profile>, output: Output) -> Self {
+        Self { profile, output, dry_run: false }
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Hooks of the --profile or active profile; none with --no-hooks
+    pub fn from_cli(cli: &Cli, output: &Output) -> Result<Self> {
+        let profile = if cli.no_hooks {
+            None
+        } else {
+            Profile::active(cli.profile.as_deref())?
+        };
+        Ok(Self::new(profile, output.clone()).with_dry_run(cli.dry_run))
+    }
+
+    pub fn run(&self, stage: HookStage) -> Result<()> {
+        let profile = match &self.profile {
+            Some(profile) => profile,
+            None => return Ok(()),
+        };
+
+        for script in stage.scripts(&profile.scripts) {
+            if self.dry_run {
+                self.output.info(&format!("🪝 Would run {} hook: {}", stage.name(), script));
+                continue;
+            }
+            self.output.info(&format!("🪝 {} hook: {}", stage.name(), script));
+
+            let result = shell(script)
+                .envs(&profile.environment)
+                .env("PKMGR_HOOK", stage.name())
+                .env("PKMGR_PROFILE", &profile.name)
+                .logged_output();
+
+            let failure = match result {
+                Ok(out) => {
+                    if self.output.verbose {
+                        for line in String::from_utf8_lossy(&out.stdout).lines()
+                            .chain(String::from_utf8_lossy(&out.stderr).lines())
+                        {
+                            self.output.print(&format!("    {}", line));
+                        }
+                    }
+                    if out.status.success() {
+                        continue;
+                    }
+                    let stderr = String::from_utf8_lossy(&out.stderr);
+                    match stderr.lines().rev().find(|line| !line.trim().is_empty()) {
+                        Some(last) => format!("{} ({})", out.status, last.trim()),
+                        None => out.status.to_string(),
+                    }
+                }
+                Err(e) => e.to_string(),
+            };
+
+            let message = format!("{} hook '{}' failed: {}", stage.name(), script, failure);
+            if stage.is_pre() {
+                bail!("{}; aborting (use --no-hooks to skip profile hooks)", message);
+            }
+            self.output.warn(&message);
+        }
+
+        Ok(())
+    }
+}
+
+fn shell(script: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(script);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn runner(pre: &str, post: &str) -> HookRunner {
+        let mut profile = Profile::new("test".to_string());
+        profile.environment.insert("HOOK_VALUE".to_string(), "42".to_string());
+        profile.scripts.pre_update = vec![pre.to_string()];
+        profile.scripts.post_update = vec![post.to_string()];
+        HookRunner::new(Some(profile), Output::new("never".to_string(), false))
+    }
+
+    #[test]
+    fn pre_hook_failure_aborts_and_post_failure_warns() {
+        let hooks = runner("test \"$HOOK_VALUE\" = 42 && test \"$PKMGR_HOOK\" = pre_update", "exit 3");
+        assert!(hooks.run(HookStage::PreUpdate).is_ok());
+        assert!(hooks.run(HookStage::PostUpdate).is_ok());
+
+        let err = runner("echo broken >&2; exit 1", "true").run(HookStage::PreUpdate).unwrap_err();
+        assert!(err.to_string().contains("broken"));
+    }
+}
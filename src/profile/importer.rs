@@ -1,9 +1,21 @@
 use anyhow::{Context, Result, bail};
+use futures_util::StreamExt;
+use sha2::{Digest, Sha256};
 use std::path::Path;
 use std::fs;
+use crate::repos::gpg::{full_fingerprint, GpgManager};
 use crate::ui::output::Output;
+use crate::utils::download::{ensure_online, http_client, request_error};
 use super::Profile;
 
+/// Largest profile accepted from a URL
+const MAX_PROFILE_BYTES: u64 = 1024 * 1024;
+
+/// Whether `source` names a profile by URL rather than by name or path
+pub fn is_url(source: &str) -> bool {
+    ["http://", "https://", "file://"].iter().any(|scheme| source.starts_with(scheme))
+}
+
 pub struct ProfileImporter {
     output: Output,
 }
@@ -42,6 +54,107 @@ impl ProfileImporter {
         Ok(())
     }
 
+    /// Download and parse a profile from an http(s) or file URL as written,
+    /// without saving it. `checksum` is the SHA-256 of the file in hex;
+    /// `signature` is the URL of a detached GnuPG signature and the
+    /// fingerprint of the key that must have made it
+    pub async fn fetch(&self, url: &str, checksum: Option<&str>, signature: Option<(&str, &str)>) -> Result<Profile> {
+        self.output.progress(&format!("Fetching profile from {}", url));
+        let content = self.fetch_bytes(url).await?;
+
+        if let Some(expected) = checksum {
+            let expected = expected.trim().trim_start_matches("sha256:").to_lowercase();
+            let actual = hex::encode(Sha256::digest(&content));
+            if actual != expected {
+                bail!("Profile checksum mismatch: expected {}, got {}", expected, actual);
+            }
+            self.output.debug("Profile checksum verified");
+        }
+
+        if let Some((signature_url, fingerprint)) = signature {
+            let fingerprint = full_fingerprint(fingerprint)?;
+            let signature = self.fetch_bytes(signature_url).await?;
+            let dir = tempfile::tempdir().context("Failed to create a temporary directory")?;
+            let (profile_path, signature_path) = (dir.path().join("profile"), dir.path().join("profile.sig"));
+            fs::write(&profile_path, &content)?;
+            fs::write(&signature_path, &signature)?;
+            if !GpgManager::new(self.output.clone()).verify_signature_from(&profile_path, &signature_path, &fingerprint)? {
+                bail!(
+                    "Profile signature from {} is not a good signature by {}; import that key with gpg first",
+                    signature_url,
+                    fingerprint
+                );
+            }
+            self.output.debug("Profile signature verified");
+        }
+
+        let content = String::from_utf8(content).context("Profile is not UTF-8 text")?;
+        let profile = if url.ends_with(".json") {
+            self.parse_json(&content)?
+        } else {
+            self.parse_toml(&content)?
+        };
+        if profile.name.trim().is_empty() {
+            bail!("Profile from {} has no name", url);
+        }
+        if let Some(package) = profile.packages.system.iter().find(|package| package.name.trim().is_empty()) {
+            bail!("Profile from {} lists a package with no name ({:?})", url, package);
+        }
+
+        Ok(profile)
+    }
+
+    /// Body of an http(s) or file URL, refused above MAX_PROFILE_BYTES or
+    /// when served as something other than text
+    async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>> {
+        let parsed = reqwest::Url::parse(url).with_context(|| format!("Invalid URL '{}'", url))?;
+
+        match parsed.scheme() {
+            "file" => {
+                let path = parsed.to_file_path()
+                    .map_err(|_| anyhow::anyhow!("Invalid file URL '{}'", url))?;
+                let size = fs::metadata(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?
+                    .len();
+                if size > MAX_PROFILE_BYTES {
+                    bail!("{} is {} bytes; profiles are limited to {}", path.display(), size, MAX_PROFILE_BYTES);
+                }
+                fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))
+            }
+            "http" | "https" => {
+                ensure_online("download a profile")?;
+                let response = http_client()?.get(url)
+                    .send()
+                    .await
+                    .map_err(|e| request_error(e, "download profile"))?;
+                if !response.status().is_success() {
+                    bail!("Failed to download {}: HTTP {}", url, response.status());
+                }
+
+                let content_type = response.headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default();
+                check_content_type(content_type).with_context(|| format!("Refusing {}", url))?;
+                if response.content_length().is_some_and(|length| length > MAX_PROFILE_BYTES) {
+                    bail!("{} is larger than the {} byte profile limit", url, MAX_PROFILE_BYTES);
+                }
+
+                // The length header is optional, so the limit is enforced on the body too
+                let mut body = Vec::new();
+                let mut stream = response.bytes_stream();
+                while let Some(chunk) = stream.next().await {
+                    body.extend_from_slice(&chunk.map_err(|e| request_error(e, "download profile"))?);
+                    if body.len() as u64 > MAX_PROFILE_BYTES {
+                        bail!("{} is larger than the {} byte profile limit", url, MAX_PROFILE_BYTES);
+                    }
+                }
+                Ok(body)
+            }
+            other => bail!("Unsupported profile URL scheme '{}' (expected http, https or file)", other),
+        }
+    }
+
     /// Import from a local file
     fn import_from_file(&self, path: &Path) -> Result<Profile> {
         if !path.exists() {
@@ -67,11 +180,11 @@ impl ProfileImporter {
     async fn import_from_url(&self, url: &str) -> Result<Profile> {
         self.output.progress("Downloading profile...");
 
-        let client = reqwest::Client::new();
+        let client = crate::utils::download::http_client()?;
         let response = client.get(url)
             .send()
             .await
-            .context("Failed to download profile")?;
+            .map_err(|e| crate::utils::download::request_error(e, "download profile"))?;
 
         if !response.status().is_success() {
             bail!("Failed to download profile: HTTP {}", response.status());
@@ -111,4 +224,32 @@ impl ProfileImporter {
         self.output.warn("YAML import not yet implemented");
         bail!("YAML format not supported yet")
     }
-}
\ No newline at end of file
+}
+
+/// Profiles are TOML or JSON; servers label them as text, TOML, JSON or
+/// plain bytes. HTML usually means a login or error page
+fn check_content_type(content_type: &str) -> Result<()> {
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_lowercase();
+    let accepted = mime.is_empty()
+        || (mime.starts_with("text/") && mime != "text/html")
+        || matches!(mime.as_str(), "application/toml" | "application/x-toml" | "application/json" | "application/octet-stream");
+    if !accepted {
+        bail!("it is served as '{}', not a TOML or JSON profile", mime);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_text_like_content_types_are_profiles() {
+        for accepted in ["", "text/plain; charset=utf-8", "application/toml", "application/octet-stream"] {
+            assert!(check_content_type(accepted).is_ok(), "{}", accepted);
+        }
+        for refused in ["text/html; charset=utf-8", "image/png", "application/zip"] {
+            assert!(check_content_type(refused).is_err(), "{}", refused);
+        }
+    }
+}
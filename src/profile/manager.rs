@@ -4,7 +4,9 @@ use std::fs;
 use crate::ui::output::Output;
 use crate::ui::prompt::Prompt;
 use crate::core::config::Config;
-use super::{Profile, get_profile_templates};
+use super::Profile;
+use super::hooks::{HookRunner, HookStage};
+use crate::core::exit::Cancelled;
 
 pub struct ProfileManager {
     output: Output,
@@ -64,8 +66,8 @@ impl ProfileManager {
             let is_current = profile_name == current;
             let marker = if is_current { " (current)" } else { "" };
 
-            // Try to load profile to get description
-            if let Ok(profile) = Profile::load(&profile_name) {
+            // Unexpanded, so a profile using an unset variable is still listed
+            if let Ok(profile) = Profile::load_unexpanded(&profile_name) {
                 if !profile.description.is_empty() {
                     self.output.info(&format!(
                         "{}{} - {}",
@@ -172,18 +174,7 @@ impl ProfileManager {
         }
 
         let profile = if let Some(template_name) = from_template {
-            // Create from template
-            let templates = get_profile_templates();
-            let template = templates.iter()
-                .find(|(n, _)| n == &template_name)
-                .map(|(_, p)| p.clone())
-                .ok_or_else(|| anyhow::anyhow!("Template '{}' not found", template_name))?;
-
-            let mut profile = template;
-            profile.name = name.to_string();
-            profile.created = chrono::Utc::now();
-            profile.updated = chrono::Utc::now();
-            profile
+            Profile::from_template(&template_name, name)?
         } else if copy_current {
             // Copy current system state
             self.create_from_current_state(name).await?
@@ -205,6 +196,26 @@ impl ProfileManager {
         Ok(())
     }
 
+    /// Create a profile without prompting, empty or seeded from a template
+    pub fn init(&self, name: &str, template: Option<&str>) -> Result<()> {
+        if Profile::list_all()?.contains(&name.to_string()) {
+            bail!("Profile '{}' already exists", name);
+        }
+
+        let profile = match template {
+            Some(template) => Profile::from_template(template, name)?,
+            None => Profile::new(name.to_string()),
+        };
+        profile.save()?;
+
+        match template {
+            Some(template) => self.output.success(&format!("Profile '{}' created from template '{}'", name, template)),
+            None => self.output.success(&format!("Profile '{}' created", name)),
+        }
+        self.output.info(&format!("Edit it with: pkmgr profile edit {}", name));
+        Ok(())
+    }
+
     /// Create profile from current system state
     async fn create_from_current_state(&self, name: &str) -> Result<Profile> {
         self.output.progress("Capturing current system state...");
@@ -251,19 +262,7 @@ impl ProfileManager {
         let mut config = Config::load().await?;
 
         // Update config with profile settings
-        config.defaults.prefer_binary = profile.settings.prefer_binary;
-        config.defaults.allow_prerelease = profile.settings.allow_prerelease;
-        config.defaults.parallel_downloads = profile.settings.parallel_downloads;
-        config.defaults.parallel_operations = profile.settings.parallel_operations;
-        config.defaults.auto_cleanup = profile.settings.auto_cleanup;
-        config.defaults.auto_update_check = profile.settings.auto_update_check;
-        config.defaults.confirm_major_updates = profile.settings.confirm_major_updates;
-        config.defaults.keep_downloads = profile.settings.keep_downloads;
-        config.defaults.use_cache = profile.settings.use_cache;
-
-        config.security.verify_signatures = profile.settings.verify_signatures;
-        config.security.verify_checksums = profile.settings.verify_checksums;
-        config.security.allow_untrusted = profile.settings.allow_untrusted;
+        profile.settings.apply_to(&mut config);
 
         config.save().await?;
 
@@ -284,8 +283,7 @@ impl ProfileManager {
 
         // Confirm deletion
         if !self.prompt.confirm(&format!("Delete profile '{}'?", name))? {
-            self.output.info("Profile deletion cancelled");
-            return Ok(());
+            return Err(Cancelled("Profile deletion cancelled".to_string()).into());
         }
 
         Profile::delete(name)?;
@@ -361,12 +359,8 @@ impl ProfileManager {
         self.output.section(&format!("Applying profile: {}", name));
 
         // Run pre-install scripts
-        if !profile.scripts.pre_install.is_empty() {
-            self.output.progress("Running pre-install scripts...");
-            for script in &profile.scripts.pre_install {
-                self.run_script(script)?;
-            }
-        }
+        let hooks = HookRunner::new(Some(profile.clone()), self.output.clone());
+        hooks.run(HookStage::PreInstall)?;
 
         // Install repositories
         if !profile.repositories.is_empty() {
@@ -407,32 +401,10 @@ impl ProfileManager {
         }
 
         // Run post-install scripts
-        if !profile.scripts.post_install.is_empty() {
-            self.output.progress("Running post-install scripts...");
-            for script in &profile.scripts.post_install {
-                self.run_script(script)?;
-            }
-        }
+        hooks.run(HookStage::PostInstall)?;
 
         self.output.success("Profile applied successfully");
 
         Ok(())
     }
-
-    /// Run a script command
-    fn run_script(&self, script: &str) -> Result<()> {
-        self.output.info(&format!("Running: {}", script));
-
-        let status = std::process::Command::new("sh")
-            .arg("-c")
-            .arg(script)
-            .status()
-            .context("Failed to run script")?;
-
-        if !status.success() {
-            bail!("Script failed: {}", script);
-        }
-
-        Ok(())
-    }
 }
\ No newline at end of file
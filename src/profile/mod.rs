@@ -3,10 +3,25 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
+use crate::core::config::Config;
+use crate::core::paths::Paths;
 
 pub mod manager;
+pub mod applier;
 pub mod exporter;
 pub mod importer;
+pub mod hooks;
+pub mod validator;
+
+/// Variable names whose values must not be shown, e.g. GITHUB_TOKEN or DB_PASSWORD
+pub fn is_sensitive_env(key: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "TOKEN", "SECRET", "PASSWORD", "PASSWD", "API_KEY", "APIKEY",
+        "ACCESS_KEY", "PRIVATE_KEY", "CREDENTIAL", "AUTH",
+    ];
+    let key = key.to_ascii_uppercase();
+    MARKERS.iter().any(|marker| key.contains(marker))
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Profile {
@@ -47,11 +62,69 @@ pub enum InstallLocation {
     Custom(PathBuf),
 }
 
+impl ProfileSettings {
+    /// Override the matching config values with this profile's settings
+    pub fn apply_to(&self, config: &mut Config) {
+        config.defaults.install_location = match &self.install_location {
+            InstallLocation::Auto => "auto".to_string(),
+            InstallLocation::System => "system".to_string(),
+            InstallLocation::User => "user".to_string(),
+            InstallLocation::Custom(path) => {
+                config.paths.install_dir = path.display().to_string();
+                path.display().to_string()
+            }
+        };
+        config.defaults.prefer_binary = self.prefer_binary;
+        config.defaults.allow_prerelease = self.allow_prerelease;
+        config.defaults.parallel_downloads = self.parallel_downloads;
+        config.defaults.parallel_operations = self.parallel_operations;
+        config.defaults.auto_cleanup = self.auto_cleanup;
+        config.defaults.auto_update_check = self.auto_update_check;
+        config.defaults.confirm_major_updates = self.confirm_major_updates;
+        config.defaults.keep_downloads = self.keep_downloads;
+        config.defaults.use_cache = self.use_cache;
+
+        config.security.verify_signatures = self.verify_signatures;
+        config.security.verify_checksums = self.verify_checksums;
+        config.security.allow_untrusted = self.allow_untrusted;
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProfilePackages {
     pub system: Vec<PackageSpec>,
     pub languages: HashMap<String, Vec<PackageSpec>>,
     pub binaries: Vec<BinarySpec>,
+    /// How these lists combine with the parent profile's
+    #[serde(default)]
+    pub merge_strategy: MergeStrategy,
+}
+
+/// How a child profile's package lists combine with its parent's. Either
+/// way, an entry written `!name` (or `!owner/repo` for binaries) drops that
+/// entry from the parent instead of adding one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStrategy {
+    /// Parent entries plus the child's
+    #[default]
+    Union,
+    /// Only the child's entries
+    Replace,
+}
+
+/// Marks a package entry that removes the parent's entry of that name
+const REMOVAL_MARKER: char = '!';
+
+impl ProfilePackages {
+    /// Drop `!name` entries, for a profile with no parent to subtract from
+    fn drop_removal_markers(&mut self) {
+        self.system.retain(|p| !p.name.starts_with(REMOVAL_MARKER));
+        for pkgs in self.languages.values_mut() {
+            pkgs.retain(|p| !p.name.starts_with(REMOVAL_MARKER));
+        }
+        self.binaries.retain(|b| !b.repository.starts_with(REMOVAL_MARKER));
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,9 +187,17 @@ impl Profile {
 
     /// Merge with another profile (for inheritance)
     pub fn merge(&mut self, other: &Profile) {
+        if other.packages.merge_strategy == MergeStrategy::Replace {
+            self.packages.system.clear();
+            self.packages.languages.clear();
+            self.packages.binaries.clear();
+        }
+
         // Merge packages
         for pkg in &other.packages.system {
-            if !self.packages.system.iter().any(|p| p.name == pkg.name) {
+            if let Some(removed) = pkg.name.strip_prefix(REMOVAL_MARKER) {
+                self.packages.system.retain(|p| p.name != removed);
+            } else if !self.packages.system.iter().any(|p| p.name == pkg.name) {
                 self.packages.system.push(pkg.clone());
             }
         }
@@ -125,7 +206,9 @@ impl Profile {
         for (lang, pkgs) in &other.packages.languages {
             let entry = self.packages.languages.entry(lang.clone()).or_insert_with(Vec::new);
             for pkg in pkgs {
-                if !entry.iter().any(|p| p.name == pkg.name) {
+                if let Some(removed) = pkg.name.strip_prefix(REMOVAL_MARKER) {
+                    entry.retain(|p| p.name != removed);
+                } else if !entry.iter().any(|p| p.name == pkg.name) {
                     entry.push(pkg.clone());
                 }
             }
@@ -133,7 +216,9 @@ impl Profile {
 
         // Merge binaries
         for bin in &other.packages.binaries {
-            if !self.packages.binaries.iter().any(|b| b.repository == bin.repository) {
+            if let Some(removed) = bin.repository.strip_prefix(REMOVAL_MARKER) {
+                self.packages.binaries.retain(|b| b.repository != removed);
+            } else if !self.packages.binaries.iter().any(|b| b.repository == bin.repository) {
                 self.packages.binaries.push(bin.clone());
             }
         }
@@ -147,21 +232,23 @@ impl Profile {
 
         // Merge environment variables (child overrides parent)
         for (key, value) in &other.environment {
-            if !self.environment.contains_key(key) {
-                self.environment.insert(key.clone(), value.clone());
-            }
+            self.environment.insert(key.clone(), value.clone());
         }
     }
 
     /// Get the profile directory path
     pub fn profile_dir() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
-        Ok(config_dir.join("pkmgr").join("profiles"))
+        Ok(Paths::resolve()?.config_dir.join("profiles"))
     }
 
-    /// Load a profile from disk
+    /// A saved profile merged over its parent, with variables expanded
     pub fn load(name: &str) -> Result<Self> {
+        Self::load_unexpanded(name)?.expand_env()
+    }
+
+    /// A saved profile merged over its parent, with `$VAR` and `~` left as
+    /// written, for saving or sharing it
+    pub fn load_unexpanded(name: &str) -> Result<Self> {
         let profile_path = Self::profile_dir()?.join(format!("{}.toml", name));
 
         if !profile_path.exists() {
@@ -171,20 +258,96 @@ impl Profile {
         let content = fs::read_to_string(&profile_path)
             .context("Failed to read profile file")?;
 
-        let mut profile: Profile = toml::from_str(&content)
+        let profile: Profile = toml::from_str(&content)
             .context("Failed to parse profile")?;
 
-        // Handle inheritance
-        if let Some(ref parent_name) = profile.parent {
-            let parent = Self::load(parent_name)?;
-            let mut merged = parent.clone();
-            merged.merge(&profile);
-            merged.name = profile.name;
-            merged.description = profile.description;
-            profile = merged;
+        profile.with_inheritance()
+    }
+
+    /// The profile merged over its saved parent, if it names one
+    pub fn with_inheritance(self) -> Result<Self> {
+        let Some(ref parent_name) = self.parent else {
+            let mut profile = self;
+            profile.packages.drop_removal_markers();
+            return Ok(profile);
+        };
+
+        let parent = Self::load_unexpanded(parent_name)?;
+        let mut merged = parent.clone();
+        merged.merge(&self);
+        merged.name = self.name;
+        merged.description = self.description;
+        Ok(merged)
+    }
+
+    /// Expand `$VAR`, `${VAR}` and `~` from the process environment in the
+    /// custom install location and environment values. Other fields are
+    /// used literally
+    pub fn expand_env(mut self) -> Result<Self> {
+        let context = || format!("Profile '{}'", self.name);
+
+        if let InstallLocation::Custom(path) = &self.settings.install_location {
+            if let Some(literal) = path.to_str() {
+                let expanded = expand_field("settings.install_location", literal).with_context(context)?;
+                self.settings.install_location = InstallLocation::Custom(PathBuf::from(expanded));
+            }
+        }
+        for (key, value) in self.environment.iter_mut() {
+            *value = expand_field(&format!("environment.{}", key), value).with_context(context)?;
         }
 
-        Ok(profile)
+        Ok(self)
+    }
+
+    /// A saved profile, falling back to the built-in template of that name
+    pub fn resolve(name: &str) -> Result<Self> {
+        if Self::profile_dir()?.join(format!("{}.toml", name)).exists() {
+            return Self::load(name);
+        }
+        get_profile_templates().into_iter()
+            .find(|(template, _)| template == name)
+            .map(|(_, profile)| profile)
+            .with_context(|| format!("Profile '{}' not found; see 'pkmgr profile list'", name))
+    }
+
+    /// The named profile, or the one selected with `pkmgr profile use`;
+    /// None when no profile has been selected
+    pub fn active(name: Option<&str>) -> Result<Option<Self>> {
+        if let Some(name) = name {
+            return Self::resolve(name).map(Some);
+        }
+
+        let current_file = Self::profile_dir()?.join("current");
+        if !current_file.exists() {
+            return Ok(None);
+        }
+        let current = fs::read_to_string(&current_file)
+            .context("Failed to read current profile")?;
+        // `profile use default` selects the built-in defaults, which have no file
+        if !Self::profile_dir()?.join(format!("{}.toml", current.trim())).exists() {
+            return Ok(None);
+        }
+        Self::load(current.trim()).map(Some)
+    }
+
+    /// Export the environment into pkmgr's own process so every child it
+    /// spawns inherits it; profile values override inherited ones
+    pub fn apply_environment(&self) {
+        for (key, value) in &self.environment {
+            std::env::set_var(key, value);
+        }
+    }
+
+    /// Sorted KEY=value lines with sensitive values masked, for display
+    pub fn display_environment(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.environment.iter()
+            .map(|(key, value)| {
+                let value = if is_sensitive_env(key) { "***" } else { value.as_str() };
+                format!("{}={}", key, value)
+            })
+            .collect();
+        lines.sort();
+        lines
     }
 
     /// Save profile to disk
@@ -240,6 +403,24 @@ impl Profile {
         }
     }
 
+    /// A new profile named `name` seeded from a built-in template. The
+    /// packages are copied rather than inherited, so later edits to the
+    /// profile stand on their own
+    pub fn from_template(template: &str, name: &str) -> Result<Profile> {
+        let templates = get_profile_templates();
+        let Some((_, template)) = templates.iter().find(|(n, _)| n == template) else {
+            let names: Vec<&str> = templates.iter().map(|(n, _)| n.as_str()).collect();
+            bail!("Template '{}' not found (available: {})", template, names.join(", "));
+        };
+
+        let mut profile = template.clone();
+        profile.name = name.to_string();
+        profile.parent = None;
+        profile.created = chrono::Utc::now();
+        profile.updated = chrono::Utc::now();
+        Ok(profile)
+    }
+
     /// Create a copy of the profile
     pub fn copy(&self, new_name: &str) -> Result<Profile> {
         let mut new_profile = self.clone();
@@ -279,6 +460,7 @@ impl Default for ProfilePackages {
             system: Vec::new(),
             languages: HashMap::new(),
             binaries: Vec::new(),
+            merge_strategy: MergeStrategy::default(),
         }
     }
 }
@@ -294,6 +476,12 @@ impl Default for ProfileScripts {
     }
 }
 
+fn expand_field(field: &str, value: &str) -> Result<String> {
+    shellexpand::full(value)
+        .map(|expanded| expanded.into_owned())
+        .map_err(|e| anyhow::anyhow!("{}: ${} is not set", field, e.var_name))
+}
+
 /// Predefined profile templates
 pub fn get_profile_templates() -> Vec<(String, Profile)> {
     vec![
@@ -425,4 +613,88 @@ fn create_devops_profile() -> Profile {
     ];
 
     profile
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_environment_masks_sensitive_values() {
+        let mut profile = Profile::new("dev".to_string());
+        profile.environment.insert("GITHUB_TOKEN".to_string(), "ghp_abcdef123456".to_string());
+        profile.environment.insert("EDITOR".to_string(), "vim".to_string());
+        profile.environment.insert("db_password".to_string(), "hunter22".to_string());
+
+        assert_eq!(profile.display_environment(), vec!["EDITOR=vim", "GITHUB_TOKEN=***", "db_password=***"]);
+    }
+
+    #[test]
+    fn custom_location_and_environment_are_expanded() {
+        let home = std::env::var("HOME").unwrap();
+        let mut profile = Profile::new("dev".to_string());
+        profile.settings.install_location = InstallLocation::Custom(PathBuf::from("$HOME/tools"));
+        profile.environment.insert("GOPATH".to_string(), "${HOME}/go".to_string());
+        profile.environment.insert("NOTES".to_string(), "~/notes".to_string());
+
+        let profile = profile.expand_env().unwrap();
+        match &profile.settings.install_location {
+            InstallLocation::Custom(path) => assert_eq!(path, &PathBuf::from(format!("{}/tools", home))),
+            other => panic!("unexpected location {:?}", other),
+        }
+        assert_eq!(profile.environment["GOPATH"], format!("{}/go", home));
+        assert_eq!(profile.environment["NOTES"], format!("{}/notes", home));
+
+        let mut broken = Profile::new("dev".to_string());
+        broken.environment.insert("TOOLS".to_string(), "$PKMGR_TEST_UNDEFINED/bin".to_string());
+        let err = format!("{:#}", broken.expand_env().unwrap_err());
+        assert!(err.contains("environment.TOOLS: $PKMGR_TEST_UNDEFINED is not set"), "{}", err);
+    }
+
+    #[test]
+    fn children_can_subtract_from_or_replace_parent_packages() {
+        let spec = |name: &str| PackageSpec { name: name.to_string(), version: None, source: None, options: HashMap::new() };
+        let mut parent = Profile::new("base".to_string());
+        parent.packages.system = vec![spec("git"), spec("curl")];
+        parent.environment.insert("EDITOR".to_string(), "nano".to_string());
+
+        let mut child = Profile::new("dev".to_string());
+        child.packages.system = vec![spec("!git"), spec("vim")];
+        child.environment.insert("EDITOR".to_string(), "vim".to_string());
+
+        let mut merged = parent.clone();
+        merged.merge(&child);
+        let names: Vec<&str> = merged.packages.system.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["curl", "vim"]);
+        assert_eq!(merged.environment["EDITOR"], "vim");
+
+        child.packages.merge_strategy = MergeStrategy::Replace;
+        let mut replaced = parent.clone();
+        replaced.merge(&child);
+        let names: Vec<&str> = replaced.packages.system.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["vim"]);
+    }
+
+    #[test]
+    fn template_copies_are_not_inherited() {
+        let profile = Profile::from_template("development", "mine").unwrap();
+        assert_eq!(profile.name, "mine");
+        assert!(profile.parent.is_none());
+        assert!(!profile.packages.system.is_empty());
+
+        let err = Profile::from_template("nope", "mine").unwrap_err().to_string();
+        assert!(err.contains("available: development, server"), "{}", err);
+    }
+
+    #[test]
+    fn security_profile_turns_off_untrusted_sources() {
+        let mut config = Config::default();
+        config.security.allow_untrusted = true;
+
+        // What `--profile security` resolves to without a saved profile of that name
+        let (_, security) = get_profile_templates().into_iter().find(|(name, _)| name == "security").unwrap();
+        security.settings.apply_to(&mut config);
+        assert!(!config.security.allow_untrusted);
+        assert!(config.security.verify_signatures);
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::{Context, Result};
+use crate::binary::github;
+use crate::core::config::Config;
+use crate::core::normalizer::PackageNormalizer;
+use crate::core::platform::PlatformInfo;
+use crate::managers::PackageManagerFactory;
+use crate::ui::output::Output;
+use crate::utils::download::http_client;
+use super::applier::supports_language;
+use super::{BinarySpec, Profile, ProfileRepository};
+
+/// Outcome of checking one profile entry
+#[derive(Debug, Clone)]
+pub struct EntryCheck {
+    /// "system", "languages.<lang>", "binary" or "repository"
+    pub kind: String,
+    pub entry: String,
+    /// None when the entry checked out
+    pub problem: Option<String>,
+}
+
+impl EntryCheck {
+    fn new(kind: &str, entry: &str, problem: Option<String>) -> Self {
+        Self { kind: kind.to_string(), entry: entry.to_string(), problem }
+    }
+}
+
+/// Checks that a profile's entries resolve before it is applied
+pub struct ProfileValidator {
+    output: Output,
+    config: Config,
+    no_network: bool,
+}
+
+impl ProfileValidator {
+    pub fn new(output: Output, config: Config) -> Self {
+        Self { output, config, no_network: false }
+    }
+
+    /// Skip backend lookups, GitHub releases and repository URLs
+    pub fn with_no_network(mut self, no_network: bool) -> Self {
+        self.no_network = no_network;
+        self
+    }
+
+    pub async fn validate(&self, profile: &Profile) -> Result<Vec<EntryCheck>> {
+        let mut checks = self.check_system(profile).await?;
+
+        let mut languages: Vec<&String> = profile.packages.languages.keys().collect();
+        languages.sort();
+        for lang in languages {
+            let kind = format!("languages.{}", lang);
+            for spec in &profile.packages.languages[lang] {
+                let problem = if spec.name.trim().is_empty() {
+                    Some("empty package name".to_string())
+                } else if !supports_language(lang) {
+                    Some(format!("sync cannot install {} packages", lang))
+                } else {
+                    None
+                };
+                checks.push(EntryCheck::new(&kind, &spec.name, problem));
+            }
+        }
+
+        for spec in &profile.packages.binaries {
+            checks.push(EntryCheck::new("binary", &spec.repository, self.check_binary(spec).await));
+        }
+
+        for repository in &profile.repositories {
+            let entry = repository.name.as_deref().unwrap_or(&repository.url);
+            checks.push(EntryCheck::new("repository", entry, self.check_repository(repository).await));
+        }
+
+        Ok(checks)
+    }
+
+    /// Each system package must map to native names the detected backend knows
+    async fn check_system(&self, profile: &Profile) -> Result<Vec<EntryCheck>> {
+        let mut checks = Vec::new();
+        if profile.packages.system.is_empty() {
+            return Ok(checks);
+        }
+
+        let platform_info = PlatformInfo::detect_async().await?;
+        let package_manager = PackageManagerFactory::create(&platform_info)
+            .context("Failed to create package manager")?;
+        let pm_type = platform_info.primary_package_manager()
+            .context("No package manager available")?;
+        let normalizer = PackageNormalizer::new();
+
+        for spec in &profile.packages.system {
+            if spec.name.trim().is_empty() {
+                checks.push(EntryCheck::new("system", &spec.name, Some("empty package name".to_string())));
+                continue;
+            }
+            if let Err(e) = normalizer.validate_package_name(&spec.name, pm_type) {
+                checks.push(EntryCheck::new("system", &spec.name, Some(e.to_string())));
+                continue;
+            }
+
+            let native = normalizer.normalize(&spec.name, pm_type)?;
+            let mut problem = None;
+            if !self.no_network {
+                for name in &native {
+                    self.output.debug(&format!("Looking up {} with {}", name, package_manager.name()));
+                    match package_manager.info(name).await {
+                        Ok(Some(_)) => {}
+                        Ok(None) => {
+                            problem = Some(format!("{} not found by {}", name, package_manager.name()));
+                            break;
+                        }
+                        Err(e) => {
+                            problem = Some(format!("{} lookup failed: {}", name, e));
+                            break;
+                        }
+                    }
+                }
+            }
+            checks.push(EntryCheck::new("system", &spec.name, problem));
+        }
+
+        Ok(checks)
+    }
+
+    /// `owner/repo` with a release matching the pinned version, or any release
+    async fn check_binary(&self, spec: &BinarySpec) -> Option<String> {
+        if !is_github_repository(&spec.repository) {
+            return Some("repository must look like owner/repo".to_string());
+        }
+        if self.no_network {
+            return None;
+        }
+
+        let url = match &spec.version {
+            Some(version) => format!("https://api.github.com/repos/{}/releases/tags/{}", spec.repository, version),
+            None => format!("https://api.github.com/repos/{}/releases/latest", spec.repository),
+        };
+        let client = match http_client() {
+            Ok(client) => client,
+            Err(e) => return Some(e.to_string()),
+        };
+        let token = github::resolve_token(&self.config);
+        let response = match github::authorize(client.get(&url), token.as_deref()).send().await {
+            Ok(response) => response,
+            Err(e) => return Some(format!("GitHub unreachable: {}", e)),
+        };
+        if let Err(e) = github::check_rate_limit(&response) {
+            return Some(e.to_string());
+        }
+
+        match response.status() {
+            status if status.is_success() => None,
+            reqwest::StatusCode::NOT_FOUND => Some(match &spec.version {
+                Some(version) => format!("no release tagged {}", version),
+                None => "repository not found or has no releases".to_string(),
+            }),
+            status => Some(format!("GitHub returned HTTP {}", status)),
+        }
+    }
+
+    /// The repository URL and its key URL must answer without an error
+    async fn check_repository(&self, repository: &ProfileRepository) -> Option<String> {
+        for url in std::iter::once(&repository.url).chain(repository.gpg_key_url.as_ref()) {
+            let parsed = match reqwest::Url::parse(url) {
+                Ok(parsed) => parsed,
+                Err(_) => return Some(format!("'{}' is not a URL", url)),
+            };
+            if self.no_network || !matches!(parsed.scheme(), "http" | "https") {
+                continue;
+            }
+
+            let client = match http_client() {
+                Ok(client) => client,
+                Err(e) => return Some(e.to_string()),
+            };
+            match client.get(url).send().await {
+                Ok(response) if response.status().is_client_error() || response.status().is_server_error() => {
+                    return Some(format!("{} returned HTTP {}", url, response.status()));
+                }
+                Ok(_) => {}
+                Err(e) => return Some(format!("{} unreachable: {}", url, e)),
+            }
+        }
+        None
+    }
+}
+
+/// `owner/repo` as GitHub spells them
+fn is_github_repository(repository: &str) -> bool {
+    let mut parts = repository.split('/');
+    let valid = |part: Option<&str>| part.is_some_and(|part| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    });
+    valid(parts.next()) && valid(parts.next()) && parts.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_repositories_must_be_owner_slash_repo() {
+        assert!(is_github_repository("helm/helm"));
+        assert!(is_github_repository("kubernetes-sigs/kind"));
+        assert!(!is_github_repository("helm"));
+        assert!(!is_github_repository("https://github.com/helm/helm"));
+        assert!(!is_github_repository("helm/helm/releases"));
+        assert!(!is_github_repository("/helm"));
+    }
+}
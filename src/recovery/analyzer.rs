@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use crate::ui::output::Output;
 use crate::core::platform::PlatformInfo;
-use super::{analyze_error, ErrorAnalysis, ErrorSeverity, RiskLevel};
+use super::{analyze_error, ErrorAnalysis, PatternScope, ErrorSeverity, RiskLevel};
 
 pub struct ErrorAnalyzer {
     output: Output,
@@ -21,8 +21,8 @@ impl ErrorAnalyzer {
 
         self.output.progress("Analyzing error output...");
 
-        let platform_str = self.get_platform_string();
-        let analyses = analyze_error(stdout, stderr, exit_code, Some(&platform_str));
+        let scope = PatternScope::from_platform(&self.platform);
+        let analyses = analyze_error(stdout, stderr, exit_code, &scope);
 
         if !analyses.is_empty() {
             self.output.info(&format!("Found {} potential fixes", analyses.len()));
@@ -113,26 +113,4 @@ impl ErrorAnalyzer {
         }
         false
     }
-
-    fn get_platform_string(&self) -> String {
-        let dist = self.platform.distribution.as_ref()
-            .map(|s| s.to_lowercase())
-            .unwrap_or_default();
-
-        if dist.contains("ubuntu") {
-            "ubuntu".to_string()
-        } else if dist.contains("debian") {
-            "debian".to_string()
-        } else if dist.contains("fedora") {
-            "fedora".to_string()
-        } else if dist.contains("arch") {
-            "arch".to_string()
-        } else if dist.contains("centos") {
-            "centos".to_string()
-        } else if dist.contains("rhel") {
-            "rhel".to_string()
-        } else {
-            "linux".to_string()
-        }
-    }
-}
\ No newline at end of file
+}
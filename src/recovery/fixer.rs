@@ -10,6 +10,8 @@ pub struct ErrorFixer {
     prompt: Prompt,
     dry_run: bool,
     auto_fix: bool,
+    /// argv of the command that failed, re-run by fixes that retry it
+    original_command: Option<Vec<String>>,
 }
 
 impl ErrorFixer {
@@ -20,9 +22,15 @@ impl ErrorFixer {
             prompt: Prompt::new(emoji_enabled),
             dry_run,
             auto_fix,
+            original_command: None,
         }
     }
 
+    pub fn with_original_command(mut self, argv: &[String]) -> Self {
+        self.original_command = Some(argv.to_vec()).filter(|argv| !argv.is_empty());
+        self
+    }
+
     /// Apply a fix for an error
     pub async fn apply_fix(
         &self,
@@ -128,8 +136,7 @@ impl ErrorFixer {
                 }
 
                 if *retry_original {
-                    self.output.info("Retrying original command...");
-                    // This would need the original command context
+                    return self.retry_original();
                 }
                 Ok(true)
             }
@@ -170,7 +177,7 @@ impl ErrorFixer {
 
         let output = Command::new(&args[0])
             .args(&args[1..])
-            .output()
+            .logged_output()
             .context("Failed to execute command")?;
 
         Ok(output.status.success())
@@ -181,14 +188,17 @@ impl ErrorFixer {
         match name {
             "retry_with_sudo" => {
                 self.output.info("Retrying with administrator privileges...");
-                // This would need the original command context
-                Ok(true)
+                self.retry_original()
             }
 
             "clear_locks" => {
                 self.clear_package_locks().await
             }
 
+            "repair_keyring" => {
+                self.repair_keyring().await
+            }
+
             "fix_404_repos" => {
                 self.fix_404_repositories().await
             }
@@ -206,7 +216,15 @@ impl ErrorFixer {
             }
 
             "cleanup_disk_space" => {
-                self.cleanup_disk_space().await
+                Ok(self.cleanup_disk_space().await? > 0)
+            }
+
+            "free_space_and_retry" => {
+                if self.cleanup_disk_space().await? == 0 {
+                    self.output.warn("No space could be freed from caches");
+                    return Ok(false);
+                }
+                self.retry_original()
             }
 
             "retry_with_timeout" => {
@@ -242,22 +260,80 @@ impl ErrorFixer {
         }
     }
 
-    /// Clear package manager locks
+    /// Remove package manager lock files left behind by a crash. A manager
+    /// whose lock has a live owner is left alone, with the owner reported
     async fn clear_package_locks(&self) -> Result<bool> {
-        self.output.progress("Clearing package manager locks...");
+        self.output.progress("Checking package manager locks...");
+
+        let mut removed = Vec::new();
+        let mut refused = false;
+
+        for locks in PACKAGE_LOCKS {
+            let present: Vec<&Path> = locks.files.iter()
+                .map(Path::new)
+                .filter(|path| path.exists())
+                .collect();
+            if present.is_empty() {
+                continue;
+            }
+
+            let owners = match lock_owners(&present) {
+                Ok(owners) => owners,
+                Err(e) => {
+                    self.output.error(&format!("Could not tell whether the {} lock is in use: {:#}", locks.manager, e));
+                    refused = true;
+                    continue;
+                }
+            };
+            if !owners.is_empty() {
+                let owners: Vec<String> = owners.iter()
+                    .map(|(pid, name)| format!("pid {} ({})", pid, name))
+                    .collect();
+                self.output.error(&format!("{} lock is in use by {}; wait for it to finish instead of removing the lock",
+                    locks.manager,
+                    owners.join(", ")
+                ));
+                refused = true;
+                continue;
+            }
 
-        #[cfg(target_os = "linux")]
-        {
-            // APT locks
-            let _ = Command::new("rm").args(&["-f", "/var/lib/dpkg/lock-frontend"]).output();
-            let _ = Command::new("rm").args(&["-f", "/var/lib/dpkg/lock"]).output();
-            let _ = Command::new("rm").args(&["-f", "/var/cache/apt/archives/lock"]).output();
+            for path in present {
+                remove_lock_file(path)?;
+                self.output.info(&format!("Removed stale lock {}", path.display()));
+                removed.push(path);
+            }
 
-            // YUM/DNF locks
-            let _ = Command::new("rm").args(&["-f", "/var/run/yum.pid"]).output();
+            // A crash that left dpkg locks usually interrupted dpkg too
+            if locks.manager == "APT" {
+                self.output.progress("Finishing interrupted dpkg runs...");
+                let _ = crate::core::privilege::run_privileged("dpkg", &["--configure", "-a"]);
+            }
+        }
 
-            // Pacman lock
-            let _ = Command::new("rm").args(&["-f", "/var/lib/pacman/db.lck"]).output();
+        if removed.is_empty() && !refused {
+            self.output.info("No stale package manager locks found");
+        }
+
+        Ok(!removed.is_empty() && !refused)
+    }
+
+    /// Rebuild the package manager's trust store, stopping at the first
+    /// failing step
+    async fn repair_keyring(&self) -> Result<bool> {
+        let steps = super::strategies::keyring_repair_steps();
+        if steps.is_empty() {
+            self.output.warn("Keyring repair is not supported on this platform");
+            return Ok(false);
+        }
+
+        self.output.progress("Repairing package signing keyring...");
+        for step in &steps {
+            self.output.info(&format!("Executing: {}", step.join(" ")));
+            let args: Vec<&str> = step[1..].iter().map(String::as_str).collect();
+            if let Err(e) = crate::core::privilege::run_privileged(&step[0], &args) {
+                self.output.error(&format!("Keyring repair failed: {}", e));
+                return Ok(false);
+            }
         }
 
         Ok(true)
@@ -270,7 +346,7 @@ impl ErrorFixer {
         #[cfg(target_os = "linux")]
         {
             // Update repository metadata
-            let _ = Command::new("apt-get").args(&["update"]).output();
+            let _ = Command::new("apt-get").args(&["update"]).logged_output();
         }
 
         Ok(true)
@@ -311,22 +387,59 @@ impl ErrorFixer {
         Ok(false)
     }
 
-    /// Clean up disk space
-    async fn cleanup_disk_space(&self) -> Result<bool> {
+    /// Free space from pkmgr's expired and stale cache entries and the
+    /// package manager's download cache; returns the bytes freed
+    async fn cleanup_disk_space(&self) -> Result<u64> {
         self.output.progress("Cleaning up disk space...");
 
-        #[cfg(target_os = "linux")]
-        {
-            // Clean package caches
-            let _ = Command::new("apt-get").args(&["clean"]).output();
-            let _ = Command::new("dnf").args(&["clean", "all"]).output();
-            let _ = Command::new("pacman").args(&["-Scc", "--noconfirm"]).output();
-
-            // Clean temp files
-            let _ = Command::new("rm").args(&["-rf", "/tmp/*"]).output();
+        let mut cleaner = crate::cache::cleaner::CacheCleaner::new(self.output.clone(), false)?;
+        let entries = cleaner.select(true, true);
+        let pkmgr_freed = cleaner.remove_entries(&entries).await?.freed_bytes;
+
+        let platform = crate::core::platform::PlatformInfo::detect_async().await?;
+        let mut package_freed = 0;
+        if let Ok(manager) = crate::managers::PackageManagerFactory::create(&platform) {
+            if let Ok(before) = manager.package_cache_size().await {
+                match manager.clean_package_cache().await {
+                    Ok(()) => {
+                        let after = manager.package_cache_size().await.unwrap_or(0);
+                        package_freed = before.saturating_sub(after);
+                    }
+                    Err(e) => self.output.warn(&format!("Failed to clean {} cache: {}", manager.name(), e)),
+                }
+            }
         }
 
-        Ok(true)
+        self.output.info(&format!("Freed {} (pkmgr cache {}, package cache {})",
+            format_size(pkmgr_freed + package_freed),
+            format_size(pkmgr_freed),
+            format_size(package_freed)
+        ));
+
+        Ok(pkmgr_freed + package_freed)
+    }
+
+    /// Run the failed command again, once, with its original arguments. A
+    /// failed pkmgr run is re-executed as is, since pkmgr escalates each
+    /// native command itself; anything else goes through the privilege layer
+    fn retry_original(&self) -> Result<bool> {
+        let Some(argv) = &self.original_command else {
+            self.output.info("Re-run the failed command");
+            return Ok(true);
+        };
+
+        self.output.info(&format!("Retrying: {}", argv.join(" ")));
+        let exe = std::env::current_exe().ok();
+        let is_pkmgr = Path::new(&argv[0]).file_stem().is_some_and(|stem| stem == "pkmgr")
+            || exe.as_deref().is_some_and(|exe| Path::new(&argv[0]) == exe);
+        let mut command = match exe {
+            Some(exe) if is_pkmgr => Command::new(exe),
+            _ => crate::core::privilege::privileged_command(&argv[0])?,
+        };
+        let status = command.args(&argv[1..])
+            .logged_status()
+            .with_context(|| format!("Failed to execute {}", argv[0]))?;
+        Ok(status.success())
     }
 
     /// Rebuild a package
@@ -364,7 +477,7 @@ impl ErrorFixer {
                 {
                     let _ = Command::new("pacman")
                         .args(&["-Sy", "archlinux-keyring", "--noconfirm"])
-                        .output();
+                        .logged_output();
                 }
             }
             _ => {
@@ -383,7 +496,7 @@ impl ErrorFixer {
         {
             let _ = Command::new("dpkg-reconfigure")
                 .arg(service)
-                .output();
+                .logged_output();
         }
 
         Ok(true)
@@ -436,6 +549,11 @@ impl ErrorFixer {
 
             FixStrategy::BuiltIn(name) => {
                 self.output.info(&format!("  Run built-in fix: {}", name));
+                if name == "repair_keyring" {
+                    for step in super::strategies::keyring_repair_steps() {
+                        self.output.info(&format!("    {}", step.join(" ")));
+                    }
+                }
             }
 
             _ => {
@@ -445,4 +563,158 @@ impl ErrorFixer {
     }
 }
 
-use std::path::Path;
\ No newline at end of file
+/// Lock files a native package manager keeps
+struct PackageLocks {
+    manager: &'static str,
+    files: &'static [&'static str],
+}
+
+const PACKAGE_LOCKS: &[PackageLocks] = &[
+    PackageLocks {
+        manager: "APT",
+        files: &[
+            "/var/lib/dpkg/lock-frontend",
+            "/var/lib/dpkg/lock",
+            "/var/cache/apt/archives/lock",
+            "/var/lib/apt/lists/lock",
+        ],
+    },
+    PackageLocks {
+        manager: "DNF",
+        files: &[
+            "/var/run/yum.pid",
+            "/var/lib/dnf/rpmdb_lock.pid",
+            "/var/cache/dnf/metadata_lock.pid",
+            "/var/cache/dnf/download_lock.pid",
+        ],
+    },
+    PackageLocks {
+        manager: "Pacman",
+        files: &["/var/lib/pacman/db.lck"],
+    },
+];
+
+/// Live processes that hold `files`: the PID recorded in a pid file, holders
+/// of a POSIX lock on one of the files (dpkg), and processes with one of
+/// them open (pacman keeps db.lck open for the whole transaction). Which
+/// daemons happen to be running does not matter
+fn lock_owners(files: &[&Path]) -> Result<Vec<(u32, String)>> {
+    let mut pids: Vec<u32> = open_by(files)?;
+
+    for path in files {
+        if let Some(pid) = std::fs::read_to_string(path).ok().and_then(|content| content.trim().parse().ok()) {
+            pids.push(pid);
+        }
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if let (Ok(metadata), Ok(proc_locks)) = (std::fs::metadata(path), std::fs::read_to_string("/proc/locks")) {
+                pids.extend(lock_holders(&proc_locks, metadata.ino()));
+            }
+        }
+    }
+
+    let mut owners: Vec<(u32, String)> = pids.into_iter()
+        .filter(|pid| *pid != std::process::id())
+        .filter_map(|pid| process_name(pid).map(|name| (pid, name)))
+        .collect();
+    owners.sort();
+    owners.dedup();
+    Ok(owners)
+}
+
+/// PIDs with one of `files` open. Other users' descriptors are only
+/// visible to root, so without root this asks `fuser` through the
+/// privilege layer
+fn open_by(files: &[&Path]) -> Result<Vec<u32>> {
+    if crate::core::privilege::escalation()? == crate::core::privilege::Escalation::Root {
+        let Ok(entries) = std::fs::read_dir("/proc") else { return Ok(Vec::new()) };
+        let pids = entries.filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok())
+            .filter(|pid| {
+                std::fs::read_dir(format!("/proc/{}/fd", pid)).into_iter().flatten()
+                    .filter_map(|fd| std::fs::read_link(fd.ok()?.path()).ok())
+                    .any(|target| files.iter().any(|file| target == *file))
+            })
+            .collect();
+        return Ok(pids);
+    }
+
+    which::which("fuser").context("fuser (psmisc) is needed to check who holds the lock; or run as root")?;
+    let output = crate::core::privilege::privileged_command("fuser")?
+        .args(files)
+        .logged_output()
+        .context("Failed to run fuser")?;
+    // Exit 1 with no output at all means nobody has the files open; an
+    // escalation failure says why on stderr
+    if !output.status.success() && !(output.stdout.is_empty() && output.stderr.is_empty()) {
+        anyhow::bail!("fuser failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(parse_fuser(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// fuser writes the PIDs to stdout, each followed by an access letter
+/// ("  1234  5678c"); file names go to stderr
+fn parse_fuser(stdout: &str) -> Vec<u32> {
+    stdout.split_whitespace()
+        .filter_map(|token| token.trim_end_matches(|c: char| c.is_ascii_alphabetic()).parse().ok())
+        .collect()
+}
+
+/// PIDs holding a lock on inode `inode` in /proc/locks, whose lines read
+/// "1: POSIX  ADVISORY  WRITE 1234 08:02:131090 0 EOF"
+fn lock_holders(proc_locks: &str, inode: u64) -> Vec<u32> {
+    proc_locks.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // Blocked waiters are listed as "1: -> POSIX ..." and do not hold the lock
+            if fields.get(1) == Some(&"->") {
+                return None;
+            }
+            let pid = fields.get(4)?.parse().ok()?;
+            let lock_inode: u64 = fields.get(5)?.rsplit(':').next()?.parse().ok()?;
+            (lock_inode == inode).then_some(pid)
+        })
+        .collect()
+}
+
+/// Name of a live process, None once it has exited
+fn process_name(pid: u32) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        .ok()
+        .map(|name| name.trim().to_string())
+}
+
+/// Remove a lock file, escalating when it belongs to root
+fn remove_lock_file(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            crate::core::privilege::run_privileged("rm", &["-f", &path.to_string_lossy()])
+                .map(|_| ())
+        }
+        Err(e) => Err(e).with_context(|| format!("Failed to remove {}", path.display())),
+    }
+}
+
+use std::path::Path;
+use crate::utils::logging::LoggedCommand;
+use crate::cache::format_size;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_lock_holders_by_inode() {
+        let proc_locks = "\
+1: POSIX  ADVISORY  WRITE 4242 08:02:131090 0 EOF
+1: -> POSIX  ADVISORY  WRITE 5151 08:02:131090 0 EOF
+2: FLOCK  ADVISORY  WRITE 777 00:1a:99 0 EOF
+";
+        assert_eq!(lock_holders(proc_locks, 131090), vec![4242]);
+        assert_eq!(lock_holders(proc_locks, 99), vec![777]);
+        assert!(lock_holders(proc_locks, 1).is_empty());
+        assert_eq!(parse_fuser(" 4242  5151c\n"), vec![4242, 5151]);
+    }
+}
@@ -0,0 +1,87 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use super::{analyze_error, ErrorAnalysis, PatternScope};
+
+/// Captured output of a real failed package manager run, and the pattern
+/// that should rank first for it
+#[derive(Debug, Clone, Deserialize)]
+pub struct ErrorFixture {
+    pub description: String,
+    pub platform: String,
+    pub package_manager: String,
+    pub exit_code: i32,
+    pub expected: String,
+    #[serde(default)]
+    pub stdout: String,
+    #[serde(default)]
+    pub stderr: String,
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl ErrorFixture {
+    /// Analyze the captured output in the fixture's own platform scope
+    pub fn analyze(&self) -> Vec<ErrorAnalysis> {
+        let scope = PatternScope {
+            platform: Some(self.platform.clone()),
+            package_manager: Some(self.package_manager.clone()),
+        };
+        analyze_error(&self.stdout, &self.stderr, self.exit_code, &scope)
+    }
+}
+
+/// Fixtures shipped with the repository, one directory per package manager
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/recovery")
+}
+
+/// Every `*.toml` fixture under `dir`, sorted by path
+pub fn load_fixtures(dir: &Path) -> Result<Vec<ErrorFixture>> {
+    let mut fixtures = Vec::new();
+
+    for entry in walkdir::WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "toml") {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+        let mut fixture: ErrorFixture = toml::from_str(&content)
+            .with_context(|| format!("Invalid fixture {}", path.display()))?;
+        fixture.path = path.to_path_buf();
+        fixtures.push(fixture);
+    }
+
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_fixture_ranks_its_pattern_first() {
+        let fixtures = load_fixtures(&fixtures_dir()).unwrap();
+        assert!(!fixtures.is_empty(), "no fixtures under {}", fixtures_dir().display());
+
+        let known: Vec<String> = super::super::get_error_patterns().into_iter().map(|p| p.id).collect();
+        for fixture in &fixtures {
+            assert!(known.contains(&fixture.expected), "{}: unknown pattern {}", fixture.path.display(), fixture.expected);
+
+            let analyses = fixture.analyze();
+            let top = analyses.first().map(|analysis| analysis.matched_pattern.id.as_str());
+            assert_eq!(
+                top,
+                Some(fixture.expected.as_str()),
+                "{} ({}): matched {:?}",
+                fixture.path.display(),
+                fixture.description,
+                analyses.iter().map(|analysis| &analysis.matched_pattern.id).collect::<Vec<_>>()
+            );
+        }
+    }
+}
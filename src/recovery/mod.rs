@@ -2,11 +2,16 @@ use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use regex::Regex;
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+use crate::core::platform::PlatformInfo;
 
 pub mod patterns;
 pub mod analyzer;
 pub mod fixer;
 pub mod strategies;
+#[cfg(test)]
+pub mod fixtures;
 
 // Re-export main types for easier access
 pub use analyzer::ErrorAnalyzer;
@@ -61,6 +66,22 @@ pub struct PatternMatcher {
     pub capture_groups: Vec<String>,
 }
 
+/// Compiled matcher regexes keyed by source, so each is built once per run
+static COMPILED_REGEXES: Lazy<RwLock<HashMap<String, Regex>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+impl PatternMatcher {
+    /// The compiled regex, cached after first use
+    pub fn compiled(&self) -> Result<Regex> {
+        if let Some(re) = COMPILED_REGEXES.read().unwrap().get(&self.regex) {
+            return Ok(re.clone());
+        }
+        let re = Regex::new(&self.regex)
+            .with_context(|| format!("Invalid regex '{}'", self.regex))?;
+        COMPILED_REGEXES.write().unwrap().insert(self.regex.clone(), re.clone());
+        Ok(re)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MatchLocation {
     Stdout,
@@ -154,16 +175,13 @@ impl ErrorPattern {
                 MatchLocation::Combined => &format!("{}\n{}", stdout, stderr),
             };
 
-            if let Ok(re) = Regex::new(&matcher.regex) {
-                if let Some(captures) = re.captures(text) {
-                    // Extract named capture groups
-                    for (i, name) in matcher.capture_groups.iter().enumerate() {
-                        if let Some(matched) = captures.get(i + 1) {
-                            captured_data.insert(name.clone(), matched.as_str().to_string());
-                        }
-                    }
-                } else {
-                    return None; // Pattern didn't match
+            // An invalid regex can never match; compile_patterns reports it
+            let re = matcher.compiled().ok()?;
+            let captures = re.captures(text)?;
+            // Extract named capture groups
+            for (i, name) in matcher.capture_groups.iter().enumerate() {
+                if let Some(matched) = captures.get(i + 1) {
+                    captured_data.insert(name.clone(), matched.as_str().to_string());
                 }
             }
         }
@@ -228,7 +246,8 @@ impl ErrorPattern {
             ErrorCategory::Permission |
             ErrorCategory::Package |
             ErrorCategory::Repository |
-            ErrorCategory::Lock
+            ErrorCategory::Lock |
+            ErrorCategory::Keyring
         )
     }
 
@@ -238,6 +257,8 @@ impl ErrorPattern {
             FixStrategy::CleanRetry { .. } => RiskLevel::Medium,
             FixStrategy::Rebuild { .. } => RiskLevel::Low,
             FixStrategy::Command(_) => RiskLevel::Low,
+            // Rewrites which signing keys the system trusts
+            FixStrategy::BuiltIn(name) if name == "repair_keyring" => RiskLevel::Medium,
             _ => RiskLevel::Safe,
         }
     }
@@ -262,22 +283,84 @@ pub fn get_error_patterns() -> Vec<ErrorPattern> {
     patterns
 }
 
-/// Find matching error patterns
+/// Compile every built-in pattern up front, naming the first one whose
+/// regex is invalid
+pub fn compile_patterns() -> Result<()> {
+    for pattern in get_error_patterns() {
+        for matcher in &pattern.patterns {
+            matcher.compiled()
+                .with_context(|| format!("Built-in error pattern '{}' is broken", pattern.id))?;
+        }
+    }
+    Ok(())
+}
+
+/// The system a set of patterns is tried against. Patterns tied to other
+/// platforms or package managers are skipped, so Arch output rules never
+/// fire on an Ubuntu box
+#[derive(Debug, Clone, Default)]
+pub struct PatternScope {
+    pub platform: Option<String>,
+    pub package_manager: Option<String>,
+}
+
+impl PatternScope {
+    pub fn from_platform(info: &PlatformInfo) -> Self {
+        let dist = info.distribution.as_deref().unwrap_or_default().to_lowercase();
+        let platform = ["ubuntu", "debian", "fedora", "arch", "centos", "rhel"]
+            .into_iter()
+            .find(|name| dist.contains(name));
+
+        Self {
+            platform: platform.map(str::to_string),
+            package_manager: info.primary_package_manager().map(|pm| pm.to_string()),
+        }
+    }
+
+    /// Whether `pattern` applies here. Unknown parts of the scope admit
+    /// everything; cross-platform patterns (pip, npm) ignore the system
+    /// package manager
+    pub fn admits(&self, pattern: &ErrorPattern) -> bool {
+        if let Some(platform) = &self.platform {
+            if !pattern.platforms.is_empty() && !pattern.platforms.contains(platform) {
+                return false;
+            }
+        }
+        if let Some(manager) = &self.package_manager {
+            if !pattern.platforms.is_empty()
+                && !pattern.package_managers.is_empty()
+                && !pattern.package_managers.iter().any(|name| manager_family(manager).contains(&name.as_str()))
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Names a package manager's errors can appear under, including its
+/// front-ends
+fn manager_family(manager: &str) -> Vec<&str> {
+    match manager {
+        "apt" => vec!["apt", "apt-get"],
+        "pacman" => vec!["pacman", "yay", "paru"],
+        other => vec![other],
+    }
+}
+
+/// Find error patterns matching the output, trying only those in `scope`
 pub fn analyze_error(
     stdout: &str,
     stderr: &str,
     exit_code: i32,
-    platform: Option<&str>,
+    scope: &PatternScope,
 ) -> Vec<ErrorAnalysis> {
     let patterns = get_error_patterns();
     let mut analyses = Vec::new();
 
     for pattern in patterns {
-        // Filter by platform if specified
-        if let Some(platform) = platform {
-            if !pattern.platforms.is_empty() && !pattern.platforms.contains(&platform.to_string()) {
-                continue;
-            }
+        if !scope.admits(&pattern) {
+            continue;
         }
 
         if let Some(captured_data) = pattern.matches(stdout, stderr, exit_code) {
@@ -311,4 +394,102 @@ fn calculate_confidence(pattern: &ErrorPattern, _data: &HashMap<String, String>)
     };
 
     confidence.min(1.0)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(platform: &str, package_manager: &str) -> PatternScope {
+        PatternScope {
+            platform: Some(platform.to_string()),
+            package_manager: Some(package_manager.to_string()),
+        }
+    }
+
+    #[test]
+    fn signature_failures_map_to_keyring_repair() {
+        let stderr = "error: linux-firmware: signature from \"Arch Dev <dev@archlinux.org>\" is unknown trust\n\
+            error: failed to commit transaction (invalid or corrupted package (PGP signature))";
+        let analyses = analyze_error("", stderr, 1, &scope("arch", "pacman"));
+
+        for id in ["arch_keyring_outdated", "arch_invalid_signature"] {
+            let analysis = analyses.iter()
+                .find(|analysis| analysis.matched_pattern.id == id)
+                .unwrap_or_else(|| panic!("{} did not match", id));
+            let fix = &analysis.suggested_fixes[0];
+            assert!(matches!(&fix.strategy, FixStrategy::BuiltIn(name) if name == "repair_keyring"));
+            assert_eq!(fix.risk_level, RiskLevel::Medium);
+            assert!(fix.requires_sudo);
+        }
+    }
+
+    #[test]
+    fn only_archive_key_failures_reinstall_the_keyring() {
+        let keyring = |stderr: &str| analyze_error("", stderr, 100, &scope("ubuntu", "apt")).iter()
+            .any(|analysis| analysis.matched_pattern.id == "debian_keyring_outdated");
+
+        assert!(keyring("W: GPG error: http://archive.ubuntu.com/ubuntu jammy InRelease: The following signatures were invalid: \
+            EXPKEYSIG 871920D1991BC93C Ubuntu Archive Automatic Signing Key (2018) <ftpmaster@ubuntu.com>"));
+        assert!(!keyring("W: GPG error: https://download.docker.com/linux/ubuntu jammy InRelease: The following signatures were invalid: \
+            EXPKEYSIG 8D81803C0EBFCD88 Docker Release (CE deb) <docker@docker.com>"));
+    }
+
+    #[test]
+    fn disk_full_frees_space_and_retries_on_every_manager() {
+        let cases = [
+            ("debian", "apt", "E: You don't have enough free space in /var/cache/apt/archives/."),
+            ("fedora", "dnf", "Disk Requirements:\n   At least 412MB more space needed on the / filesystem."),
+            ("arch", "pacman", "error: Partition / too full: 63308 blocks needed, 4932 blocks free"),
+        ];
+        for (platform, manager, error) in cases {
+            let analyses = analyze_error(error, error, 1, &scope(platform, manager));
+            assert!(
+                analyses.iter().any(|analysis| matches!(
+                    &analysis.matched_pattern.fix_strategy,
+                    FixStrategy::BuiltIn(name) if name == "free_space_and_retry"
+                )),
+                "no disk space fix for {}",
+                platform
+            );
+        }
+    }
+
+    #[test]
+    fn apt_output_never_matches_pacman_patterns() {
+        let stderr = "E: Could not get lock /var/lib/dpkg/lock-frontend. It is held by process 4242 (apt)\n\
+            E: Unable to locate package libfoo\n\
+            E: You don't have enough free space in /var/cache/apt/archives/.\n\
+            W: GPG error: http://archive.ubuntu.com jammy InRelease: EXPKEYSIG 871920D1991BC93C\n\
+            dpkg: error processing archive /var/cache/apt/archives/libfoo_1.0_amd64.deb (--unpack):\n\
+             cannot copy extracted data for './usr/lib/libfoo.so' to '/usr/lib/libfoo.so.dpkg-new': \
+            failed to write (No space left on device)";
+        let analyses = analyze_error("", stderr, 100, &scope("ubuntu", "apt"));
+
+        assert!(analyses.iter().any(|analysis| analysis.matched_pattern.id == "debian_lock_held"));
+        for analysis in &analyses {
+            let pattern = &analysis.matched_pattern;
+            assert!(
+                !pattern.package_managers.iter().any(|name| ["pacman", "yay", "paru"].contains(&name.as_str())),
+                "{} matched apt output",
+                pattern.id
+            );
+        }
+
+        // Unscoped, Arch's disk-full rule fires on dpkg's ENOSPC line
+        let unscoped = analyze_error("", stderr, 100, &PatternScope::default());
+        assert!(unscoped.iter().any(|analysis| analysis.matched_pattern.platforms.contains(&"arch".to_string())));
+    }
+
+    #[test]
+    fn builtin_patterns_compile() {
+        compile_patterns().unwrap();
+
+        let broken = PatternMatcher {
+            regex: "unclosed (group".to_string(),
+            location: MatchLocation::Stderr,
+            capture_groups: vec![],
+        };
+        assert!(broken.compiled().is_err());
+    }
+}
@@ -68,21 +68,53 @@ pub fn get_patterns() -> Vec<ErrorPattern> {
             severity: ErrorSeverity::High,
             patterns: vec![
                 PatternMatcher {
-                    regex: r"error: .+: signature from .+ is marginal trust".to_string(),
+                    regex: r"error: .+: signature from .+ is (marginal|unknown) trust".to_string(),
                     location: MatchLocation::Stderr,
                     capture_groups: vec![],
                 },
+            ],
+            fix_strategy: FixStrategy::BuiltIn("repair_keyring".to_string()),
+            success_rate: 0.99,
+            platforms: vec!["arch".to_string()],
+            package_managers: vec!["pacman".to_string()],
+        },
+
+        // Signature check failing on a package from the repositories
+        ErrorPattern {
+            id: "arch_invalid_signature".to_string(),
+            name: "Invalid package signature".to_string(),
+            description: "Package signature cannot be verified against the local keyring".to_string(),
+            category: ErrorCategory::Keyring,
+            severity: ErrorSeverity::High,
+            patterns: vec![
                 PatternMatcher {
-                    regex: r"error: .+: signature from .+ is unknown trust".to_string(),
+                    regex: r"invalid or corrupted package \(PGP signature\)".to_string(),
                     location: MatchLocation::Stderr,
                     capture_groups: vec![],
                 },
             ],
-            fix_strategy: FixStrategy::CommandSequence(vec![
-                vec!["pacman".to_string(), "-Sy".to_string(), "archlinux-keyring".to_string(), "--noconfirm".to_string()],
-                vec!["pacman-key".to_string(), "--refresh-keys".to_string()],
-            ]),
-            success_rate: 0.99,
+            fix_strategy: FixStrategy::BuiltIn("repair_keyring".to_string()),
+            success_rate: 0.9,
+            platforms: vec!["arch".to_string()],
+            package_managers: vec!["pacman".to_string()],
+        },
+
+        // Not enough space for the transaction
+        ErrorPattern {
+            id: "arch_no_space".to_string(),
+            name: "No space left".to_string(),
+            description: "Insufficient disk space for the transaction".to_string(),
+            category: ErrorCategory::DiskSpace,
+            severity: ErrorSeverity::Critical,
+            patterns: vec![
+                PatternMatcher {
+                    regex: r"error: Partition \S+ too full|error: not enough free disk space|No space left on device".to_string(),
+                    location: MatchLocation::Stderr,
+                    capture_groups: vec![],
+                },
+            ],
+            fix_strategy: FixStrategy::BuiltIn("free_space_and_retry".to_string()),
+            success_rate: 0.8,
             platforms: vec!["arch".to_string()],
             package_managers: vec!["pacman".to_string()],
         },
@@ -106,11 +138,8 @@ pub fn get_patterns() -> Vec<ErrorPattern> {
                     capture_groups: vec![],
                 },
             ],
-            fix_strategy: FixStrategy::Command(vec![
-                "rm".to_string(),
-                "/var/lib/pacman/db.lck".to_string(),
-            ]),
-            success_rate: 1.0,
+            fix_strategy: FixStrategy::BuiltIn("clear_locks".to_string()),
+            success_rate: 0.95,
             platforms: vec!["arch".to_string()],
             package_managers: vec!["pacman".to_string()],
         },
@@ -92,17 +92,12 @@ pub fn get_patterns() -> Vec<ErrorPattern> {
             severity: ErrorSeverity::Critical,
             patterns: vec![
                 PatternMatcher {
-                    regex: r"No space left on device".to_string(),
-                    location: MatchLocation::Stderr,
-                    capture_groups: vec![],
-                },
-                PatternMatcher {
-                    regex: r"Disk quota exceeded".to_string(),
+                    regex: r"No space left on device|Disk quota exceeded".to_string(),
                     location: MatchLocation::Stderr,
                     capture_groups: vec![],
                 },
             ],
-            fix_strategy: FixStrategy::BuiltIn("cleanup_disk_space".to_string()),
+            fix_strategy: FixStrategy::BuiltIn("free_space_and_retry".to_string()),
             success_rate: 0.7,
             platforms: vec![],
             package_managers: vec![],
@@ -63,24 +63,14 @@ pub fn get_patterns() -> Vec<ErrorPattern> {
             category: ErrorCategory::Lock,
             severity: ErrorSeverity::Medium,
             patterns: vec![
+                // Matchers must all match, so alternatives share one regex
                 PatternMatcher {
-                    regex: r"Could not get lock /var/lib/dpkg/lock-frontend".to_string(),
-                    location: MatchLocation::Stderr,
-                    capture_groups: vec![],
-                },
-                PatternMatcher {
-                    regex: r"Unable to acquire the dpkg frontend lock".to_string(),
+                    regex: r"Could not get lock /var/(lib|cache)/(dpkg|apt)/\S*lock|Unable to acquire the dpkg frontend lock".to_string(),
                     location: MatchLocation::Stderr,
                     capture_groups: vec![],
                 },
             ],
-            fix_strategy: FixStrategy::CommandSequence(vec![
-                vec!["killall".to_string(), "apt".to_string(), "apt-get".to_string()],
-                vec!["rm".to_string(), "-f".to_string(), "/var/lib/dpkg/lock-frontend".to_string()],
-                vec!["rm".to_string(), "-f".to_string(), "/var/lib/dpkg/lock".to_string()],
-                vec!["rm".to_string(), "-f".to_string(), "/var/cache/apt/archives/lock".to_string()],
-                vec!["dpkg".to_string(), "--configure".to_string(), "-a".to_string()],
-            ]),
+            fix_strategy: FixStrategy::BuiltIn("clear_locks".to_string()),
             success_rate: 0.95,
             platforms: vec!["debian".to_string(), "ubuntu".to_string()],
             package_managers: vec!["apt".to_string(), "apt-get".to_string()],
@@ -113,6 +103,28 @@ pub fn get_patterns() -> Vec<ErrorPattern> {
             package_managers: vec!["apt".to_string(), "apt-get".to_string()],
         },
 
+        // Archive signing key expired or rotated
+        ErrorPattern {
+            id: "debian_keyring_outdated".to_string(),
+            name: "Archive keyring outdated".to_string(),
+            description: "Distribution archive keys are expired or missing from the keyring".to_string(),
+            category: ErrorCategory::Keyring,
+            severity: ErrorSeverity::High,
+            patterns: vec![
+                PatternMatcher {
+                    // Only the distribution's own archive; reinstalling its keyring
+                    // cannot fix a third-party repository's expired key
+                    regex: r"GPG error: https?://([a-z0-9-]+\.)*(ubuntu\.com|debian\.org)/.*(EXPKEYSIG|KEYEXPIRED|signatures were invalid)|(EXPKEYSIG|KEYEXPIRED) [0-9A-F]+ (Debian|Ubuntu) Archive".to_string(),
+                    location: MatchLocation::Combined,
+                    capture_groups: vec![],
+                },
+            ],
+            fix_strategy: FixStrategy::BuiltIn("repair_keyring".to_string()),
+            success_rate: 0.85,
+            platforms: vec!["debian".to_string(), "ubuntu".to_string()],
+            package_managers: vec!["apt".to_string(), "apt-get".to_string()],
+        },
+
         // Hash sum mismatch
         ErrorPattern {
             id: "debian_hash_mismatch".to_string(),
@@ -145,21 +157,12 @@ pub fn get_patterns() -> Vec<ErrorPattern> {
             severity: ErrorSeverity::Critical,
             patterns: vec![
                 PatternMatcher {
-                    regex: r"You don't have enough free space in /var/cache/apt/archives".to_string(),
-                    location: MatchLocation::Stderr,
-                    capture_groups: vec![],
-                },
-                PatternMatcher {
-                    regex: r"No space left on device".to_string(),
+                    regex: r"You don't have enough free space in /var/cache/apt/archives|No space left on device".to_string(),
                     location: MatchLocation::Stderr,
                     capture_groups: vec![],
                 },
             ],
-            fix_strategy: FixStrategy::CommandSequence(vec![
-                vec!["apt-get".to_string(), "clean".to_string()],
-                vec!["apt-get".to_string(), "autoclean".to_string()],
-                vec!["apt-get".to_string(), "autoremove".to_string(), "--purge".to_string()],
-            ]),
+            fix_strategy: FixStrategy::BuiltIn("free_space_and_retry".to_string()),
             success_rate: 0.8,
             platforms: vec!["debian".to_string(), "ubuntu".to_string()],
             package_managers: vec!["apt".to_string(), "apt-get".to_string()],
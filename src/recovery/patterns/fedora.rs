@@ -32,6 +32,47 @@ pub fn get_patterns() -> Vec<ErrorPattern> {
             package_managers: vec!["dnf".to_string(), "yum".to_string()],
         },
 
+        // Lock held by another or a crashed transaction
+        ErrorPattern {
+            id: "fedora_lock_held".to_string(),
+            name: "DNF lock held".to_string(),
+            description: "Another process holds the DNF/RPM lock, or a crashed one left it behind".to_string(),
+            category: ErrorCategory::Lock,
+            severity: ErrorSeverity::Medium,
+            patterns: vec![
+                // dnf prints the wait notice on stdout
+                PatternMatcher {
+                    regex: r"Waiting for process with pid \d+ to finish|Failed to obtain rpm transaction lock|Another app is currently holding the yum lock".to_string(),
+                    location: MatchLocation::Combined,
+                    capture_groups: vec![],
+                },
+            ],
+            fix_strategy: FixStrategy::BuiltIn("clear_locks".to_string()),
+            success_rate: 0.9,
+            platforms: vec!["fedora".to_string(), "rhel".to_string(), "centos".to_string()],
+            package_managers: vec!["dnf".to_string(), "yum".to_string()],
+        },
+
+        // Not enough space for the transaction
+        ErrorPattern {
+            id: "fedora_no_space".to_string(),
+            name: "No space left".to_string(),
+            description: "Insufficient disk space for the transaction".to_string(),
+            category: ErrorCategory::DiskSpace,
+            severity: ErrorSeverity::Critical,
+            patterns: vec![
+                PatternMatcher {
+                    regex: r"At least \d+\s*[KMG]?B more space needed on the \S+ filesystem|No space left on device".to_string(),
+                    location: MatchLocation::Combined,
+                    capture_groups: vec![],
+                },
+            ],
+            fix_strategy: FixStrategy::BuiltIn("free_space_and_retry".to_string()),
+            success_rate: 0.8,
+            platforms: vec!["fedora".to_string(), "rhel".to_string(), "centos".to_string()],
+            package_managers: vec!["dnf".to_string(), "yum".to_string()],
+        },
+
         // Module conflicts
         ErrorPattern {
             id: "fedora_module_conflict".to_string(),
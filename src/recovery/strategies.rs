@@ -52,6 +52,56 @@ pub struct RecoveryStats {
     pub categories: Vec<(&'static str, usize)>,
 }
 
+/// Commands for the `repair_keyring` built-in fix on this platform, run with
+/// root privileges in order. Empty when no supported package manager is found
+pub fn keyring_repair_steps() -> Vec<Vec<String>> {
+    let step = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    if which::which("pacman-key").is_ok() {
+        // Recreate the local master key, re-trust the packaged keys, pick up
+        // keys added since the keyring package was installed, then finish the
+        // upgrade so the refreshed sync databases never leave a partial one
+        return vec![
+            step(&["pacman-key", "--init"]),
+            step(&["pacman-key", "--populate"]),
+            step(&["pacman", "-Sy", "--needed", "--noconfirm", "archlinux-keyring"]),
+            step(&["pacman", "-Su", "--noconfirm"]),
+        ];
+    }
+
+    if which::which("apt-get").is_ok() {
+        let keyring = if std::path::Path::new("/usr/share/keyrings/ubuntu-archive-keyring.gpg").exists() {
+            "ubuntu-keyring"
+        } else {
+            "debian-archive-keyring"
+        };
+        return vec![
+            step(&["apt-get", "install", "--reinstall", "-y", keyring]),
+            step(&["apt-get", "update"]),
+        ];
+    }
+
+    if which::which("rpm").is_ok() {
+        // Re-import the distribution keys shipped in /etc/pki/rpm-gpg
+        let mut keys: Vec<String> = std::fs::read_dir("/etc/pki/rpm-gpg")
+            .map(|entries| {
+                entries.filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path().to_string_lossy().to_string())
+                    .filter(|path| path.contains("RPM-GPG-KEY"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !keys.is_empty() {
+            keys.sort();
+            keys.insert(0, "--import".to_string());
+            keys.insert(0, "rpm".to_string());
+            return vec![keys];
+        }
+    }
+
+    Vec::new()
+}
+
 /// Strategy recommendations based on error category
 pub fn recommend_strategy(category: &ErrorCategory, severity: &ErrorSeverity) -> Vec<String> {
     let mut recommendations = Vec::new();
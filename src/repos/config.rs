@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
+use crate::core::config::Config;
 
 /// Repository configuration stored by pkmgr
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -101,10 +102,7 @@ impl RepositoryConfig {
 
     /// Get configuration file path
     fn config_path() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
-
-        Ok(config_dir.join("pkmgr").join("repos.toml"))
+        Ok(Config::get_config_dir()?.join("repos.toml"))
     }
 
     /// Add a repository entry
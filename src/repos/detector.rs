@@ -275,6 +275,40 @@ impl RepositoryDetector {
         Some(repo)
     }
 
+    /// Match a repository URL against the known repository patterns
+    pub fn detect_known_repository(&self, url: &str) -> Option<Repository> {
+        let url_lower = url.to_lowercase();
+        let known = get_known_repositories().into_iter()
+            .find(|k| k.patterns.iter().any(|p| url_lower.contains(p)))?;
+
+        let repo_type = self.detect_os_type()
+            .map(|os| self.get_repo_type_for_os(&os))
+            .unwrap_or_else(|| RepositoryType::Custom("Unknown".to_string()));
+
+        let mut repo = Repository::new(known.name.to_string(), url.to_string(), repo_type)
+            .with_trust_level(known.trust_level.clone());
+
+        repo.metadata.vendor = Some(known.vendor.to_string());
+        repo.metadata.description = Some(known.description.to_string());
+        repo.metadata.is_official = known.trust_level == TrustLevel::Official;
+        repo.metadata.is_verified = matches!(known.trust_level, TrustLevel::Official | TrustLevel::Verified);
+
+        if let Some(fingerprint) = known.gpg_fingerprint {
+            let compact: String = fingerprint.chars().filter(|c| !c.is_whitespace()).collect();
+            repo.gpg_key = Some(super::GpgKeyInfo {
+                key_id: compact[compact.len().saturating_sub(8)..].to_string(),
+                fingerprint: compact,
+                key_server: None,
+                key_url: known.gpg_key_url.map(|s| s.to_string()),
+                trusted: false,
+                expires: None,
+                last_refreshed: None,
+            });
+        }
+
+        Some(repo)
+    }
+
     /// Detect if a URL is a mirror of a known repository
     pub fn detect_mirror(&self, url: &str) -> Option<String> {
         // Common mirror patterns
@@ -3,6 +3,7 @@ use std::process::Command;
 use std::path::{Path, PathBuf};
 use crate::ui::output::Output;
 use super::GpgKeyInfo;
+use crate::utils::logging::LoggedCommand;
 
 pub struct GpgManager {
     output: Output,
@@ -26,11 +27,11 @@ impl GpgManager {
         self.output.progress(&format!("Downloading GPG key from {}", url));
 
         // Download the key
-        let client = reqwest::Client::new();
+        let client = crate::utils::download::http_client()?;
         let response = client.get(url)
             .send()
             .await
-            .context("Failed to download GPG key")?;
+            .map_err(|e| crate::utils::download::request_error(e, "download GPG key"))?;
 
         if !response.status().is_success() {
             bail!("Failed to download GPG key: HTTP {}", response.status());
@@ -63,7 +64,7 @@ impl GpgManager {
                 let output = Command::new("apt-key")
                     .arg("add")
                     .arg(key_path)
-                    .output()
+                    .logged_output()
                     .context("Failed to import GPG key with apt-key")?;
 
                 if !output.status.success() {
@@ -79,7 +80,7 @@ impl GpgManager {
                 let output = Command::new("rpm")
                     .arg("--import")
                     .arg(key_path)
-                    .output()
+                    .logged_output()
                     .context("Failed to import GPG key with rpm")?;
 
                 if !output.status.success() {
@@ -95,7 +96,7 @@ impl GpgManager {
                 let output = Command::new("pacman-key")
                     .arg("--add")
                     .arg(key_path)
-                    .output()
+                    .logged_output()
                     .context("Failed to import GPG key with pacman-key")?;
 
                 if !output.status.success() {
@@ -107,7 +108,7 @@ impl GpgManager {
                 let _ = Command::new("pacman-key")
                     .arg("--lsign-key")
                     .arg(key_path)
-                    .output();
+                    .logged_output();
 
                 return self.get_key_fingerprint_from_file(key_path);
             }
@@ -117,7 +118,7 @@ impl GpgManager {
         let output = Command::new("gpg")
             .arg("--import")
             .arg(key_path)
-            .output()
+            .logged_output()
             .context("Failed to import GPG key with gpg")?;
 
         if !output.status.success() {
@@ -134,7 +135,7 @@ impl GpgManager {
             .arg("--with-fingerprint")
             .arg("--with-colons")
             .arg(key_path)
-            .output()
+            .logged_output()
             .context("Failed to get key fingerprint")?;
 
         let output_str = String::from_utf8_lossy(&output.stdout);
@@ -197,7 +198,7 @@ impl GpgManager {
                     .arg(keyserver)
                     .arg("--recv-keys")
                     .arg(fingerprint)
-                    .output()
+                    .logged_output()
                     .context("Failed to fetch key with apt-key")?;
 
                 if output.status.success() {
@@ -212,7 +213,7 @@ impl GpgManager {
             .arg(keyserver)
             .arg("--recv-keys")
             .arg(fingerprint)
-            .output()
+            .logged_output()
             .context("Failed to fetch key with gpg")?;
 
         if !output.status.success() {
@@ -233,7 +234,7 @@ impl GpgManager {
                 // List keys and check expiration
                 let output = Command::new("apt-key")
                     .arg("list")
-                    .output()?;
+                    .logged_output()?;
 
                 let output_str = String::from_utf8_lossy(&output.stdout);
 
@@ -257,7 +258,7 @@ impl GpgManager {
                     // Update all keys
                     let _ = Command::new("apt-key")
                         .arg("update")
-                        .output();
+                        .logged_output();
                 }
             }
         }
@@ -275,14 +276,14 @@ impl GpgManager {
                 let output = Command::new("apt-key")
                     .arg("list")
                     .arg("--with-colons")
-                    .output()?;
+                    .logged_output()?;
 
                 keys.extend(self.parse_gpg_output(&output.stdout)?);
             } else if Path::new("/usr/bin/rpm").exists() {
                 let output = Command::new("rpm")
                     .arg("-qa")
                     .arg("gpg-pubkey*")
-                    .output()?;
+                    .logged_output()?;
 
                 // Parse RPM GPG keys (simplified)
                 let output_str = String::from_utf8_lossy(&output.stdout);
@@ -307,7 +308,7 @@ impl GpgManager {
             let output = Command::new("gpg")
                 .arg("--list-keys")
                 .arg("--with-colons")
-                .output()?;
+                .logged_output()?;
 
             keys = self.parse_gpg_output(&output.stdout)?;
         }
@@ -366,12 +367,27 @@ impl GpgManager {
             .arg("--verify")
             .arg(signature_file)
             .arg(repo_file)
-            .output()
+            .logged_output()
             .context("Failed to verify signature")?;
 
         Ok(output.status.success())
     }
 
+    /// Verify a detached signature made by the key with `fingerprint` (its
+    /// primary key or a subkey). A good signature from any other key in the
+    /// keyring does not count
+    pub fn verify_signature_from(&self, file: &Path, signature_file: &Path, fingerprint: &str) -> Result<bool> {
+        let fingerprint = full_fingerprint(fingerprint)?;
+        let output = Command::new("gpg")
+            .args(["--status-fd", "1", "--verify"])
+            .arg(signature_file)
+            .arg(file)
+            .logged_output()
+            .context("Failed to verify signature")?;
+
+        Ok(output.status.success() && signed_by(&String::from_utf8_lossy(&output.stdout), &fingerprint))
+    }
+
     /// Trust a key (for package managers that require it)
     pub fn trust_key(&self, fingerprint: &str) -> Result<()> {
         #[cfg(target_os = "linux")]
@@ -381,10 +397,55 @@ impl GpgManager {
                 let _ = Command::new("pacman-key")
                     .arg("--lsign-key")
                     .arg(fingerprint)
-                    .output();
+                    .logged_output();
             }
         }
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// A full key fingerprint in gpg's form (upper-case hex, no spaces or 0x);
+/// short and long key ids are refused since they can collide
+pub fn full_fingerprint(fingerprint: &str) -> Result<String> {
+    let hex: String = fingerprint.trim().trim_start_matches("0x")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    if !matches!(hex.len(), 40 | 64) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("'{}' is not a full key fingerprint (40 or 64 hex digits)", fingerprint);
+    }
+    Ok(hex)
+}
+
+/// Whether gpg's `--status-fd` output has a VALIDSIG from `fingerprint`,
+/// which may be the signing subkey or its primary key
+fn signed_by(status: &str, fingerprint: &str) -> bool {
+    status.lines()
+        .filter_map(|line| line.strip_prefix("[GNUPG:] VALIDSIG "))
+        .any(|fields| {
+            let fields: Vec<&str> = fields.split_whitespace().collect();
+            fields.first() == Some(&fingerprint) || fields.get(9) == Some(&fingerprint)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_must_come_from_the_pinned_key() {
+        let primary = "A1B2C3D4E5F60718293A4B5C6D7E8F9012345678";
+        let status = "[GNUPG:] GOODSIG 6D7E8F9012345678 Profile Signer <signer@example.com>\n\
+            [GNUPG:] VALIDSIG 00112233445566778899AABBCCDDEEFF00112233 2026-01-01 1767225600 0 4 0 22 10 00 \
+            A1B2C3D4E5F60718293A4B5C6D7E8F9012345678\n";
+
+        assert!(signed_by(status, primary));
+        assert!(signed_by(status, "00112233445566778899AABBCCDDEEFF00112233"));
+        assert!(!signed_by(status, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"));
+
+        assert_eq!(full_fingerprint("0xa1b2 c3d4 e5f6 0718 293a  4b5c 6d7e 8f90 1234 5678").unwrap(), primary);
+        assert!(full_fingerprint("6D7E8F9012345678").is_err());
+    }
+}
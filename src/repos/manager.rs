@@ -3,15 +3,24 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::Write;
 use crate::ui::output::Output;
+use crate::ui::prompt::Prompt;
 use crate::core::platform::{PlatformInfo, PackageManager};
 use super::{Repository, RepositoryType, TrustLevel, detector::RepositoryDetector, gpg::GpgManager};
+use crate::utils::download::is_offline;
+use crate::utils::logging::LoggedCommand;
+use crate::cache::manager::CacheManager;
+use crate::core::{privilege, traits};
+use crate::managers::PackageManagerFactory;
+use crate::core::exit::Cancelled;
 
 pub struct RepositoryManager {
     output: Output,
     platform: PlatformInfo,
     detector: RepositoryDetector,
     gpg: GpgManager,
+    prompt: Prompt,
     repos_dir: PathBuf,
+    assume_yes: bool,
 }
 
 impl RepositoryManager {
@@ -21,12 +30,20 @@ impl RepositoryManager {
         Self {
             detector: RepositoryDetector::new(output.clone()),
             gpg: GpgManager::new(output.clone()),
+            prompt: Prompt::new(output.emoji_enabled),
             output,
             platform,
             repos_dir,
+            assume_yes: false,
         }
     }
 
+    /// Skip confirmation prompts (--yes)
+    pub fn with_assume_yes(mut self, assume_yes: bool) -> Self {
+        self.assume_yes = assume_yes;
+        self
+    }
+
     /// Get the appropriate repository directory for the platform
     fn get_repos_dir(platform: &PlatformInfo) -> PathBuf {
         let pm_name = platform.primary_package_manager()
@@ -107,7 +124,9 @@ impl RepositoryManager {
 
                     let name = self.guess_repo_name(&url, &suite);
 
-                    let mut repo = Repository::new(name, url, RepositoryType::Apt);
+                    let trust_level = super::classify_trust_level(&url);
+                    let mut repo = Repository::new(name, url, RepositoryType::Apt)
+                        .with_trust_level(trust_level);
                     repo.suites = vec![suite];
                     repo.components = components;
 
@@ -224,8 +243,112 @@ impl RepositoryManager {
         Ok(repos)
     }
 
+    /// Find the repository that would provide a package, if it can be determined
+    pub fn resolve_package_source(&self, package: &str) -> Option<Repository> {
+        // Packages that need a known third-party repository
+        if let Some(repo) = self.detector.detect_required_repository(package) {
+            return Some(repo);
+        }
+
+        let pm_name = self.platform.primary_package_manager()
+            .map(|pm| pm.to_string())
+            .unwrap_or_default();
+        match pm_name.as_str() {
+            "apt" => self.resolve_apt_source(package),
+            "dnf" | "yum" => self.resolve_rpm_source(&pm_name, package),
+            "pacman" => self.resolve_pacman_source(package),
+            _ => None,
+        }
+    }
+
+    /// Read the candidate origin from `apt-cache policy`
+    fn resolve_apt_source(&self, package: &str) -> Option<Repository> {
+        let output = std::process::Command::new("apt-cache")
+            .args(["policy", package])
+            .logged_output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        // The version table lists the candidate first:
+        //  *** 1.2-3 500
+        //         500 http://archive.ubuntu.com/ubuntu jammy/main amd64 Packages
+        let candidate = stdout.lines()
+            .find(|l| l.trim_start().starts_with("Candidate:"))?
+            .split_once(':')?.1.trim().to_string();
+        if candidate == "(none)" {
+            return None;
+        }
+
+        let mut in_candidate = false;
+        for line in stdout.lines() {
+            let trimmed = line.trim_start().trim_start_matches("***").trim();
+            if !line.starts_with("        ") && !trimmed.is_empty() {
+                in_candidate = trimmed.starts_with(&candidate);
+                continue;
+            }
+            if in_candidate {
+                let parts: Vec<&str> = trimmed.split_whitespace().collect();
+                if parts.len() >= 3 && parts[1].contains("://") {
+                    let url = parts[1].to_string();
+                    let suite = parts[2].split('/').next().unwrap_or_default();
+                    let name = self.guess_repo_name(&url, suite);
+                    let trust_level = super::classify_trust_level(&url);
+                    return Some(Repository::new(name, url, RepositoryType::Apt)
+                        .with_trust_level(trust_level));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Read the repository id from `dnf info` / `yum info`
+    fn resolve_rpm_source(&self, pm_name: &str, package: &str) -> Option<Repository> {
+        let output = std::process::Command::new(pm_name)
+            .args(["info", "-q", package])
+            .logged_output()
+            .ok()?;
+        let repo_id = String::from_utf8_lossy(&output.stdout).lines()
+            .find(|l| l.starts_with("Repository") || l.starts_with("From repo"))?
+            .split(':').nth(1)?.trim().to_string();
+
+        let trust_level = match repo_id.as_str() {
+            "@System" | "installed" => return None,
+            id if id.starts_with("copr:") => TrustLevel::Community,
+            "fedora" | "updates" | "updates-testing" | "baseos" | "appstream" | "extras"
+            | "crb" | "powertools" | "epel" | "rhel" => TrustLevel::Official,
+            id if id.starts_with("fedora-") || id.starts_with("updates-")
+                || id.starts_with("rhel-") || id.starts_with("epel-") => TrustLevel::Official,
+            id => super::get_known_repositories().into_iter()
+                .find(|k| id.contains(k.name))
+                .map(|k| k.trust_level)
+                .unwrap_or(TrustLevel::Unknown),
+        };
+
+        let repo_type = if pm_name == "dnf" { RepositoryType::Dnf } else { RepositoryType::Yum };
+        Some(Repository::new(repo_id, String::new(), repo_type).with_trust_level(trust_level))
+    }
+
+    /// Read the sync database name from `pacman -Si`
+    fn resolve_pacman_source(&self, package: &str) -> Option<Repository> {
+        let output = std::process::Command::new("pacman")
+            .args(["-Si", package])
+            .logged_output()
+            .ok()?;
+        let repo_name = String::from_utf8_lossy(&output.stdout).lines()
+            .find(|l| l.starts_with("Repository"))?
+            .split(':').nth(1)?.trim().to_string();
+
+        let trust_level = match repo_name.as_str() {
+            "core" | "extra" | "multilib" | "community" | "core-testing" | "extra-testing" => TrustLevel::Official,
+            _ => TrustLevel::Unknown,
+        };
+
+        Some(Repository::new(repo_name, String::new(), RepositoryType::Pacman).with_trust_level(trust_level))
+    }
+
     /// Add a repository
-    pub async fn add(&self, repo_spec: &str) -> Result<()> {
+    pub async fn add(&self, repo_spec: &str, allow_untrusted: bool) -> Result<()> {
         self.output.progress(&format!("Adding repository: {}", repo_spec));
 
         // Check if it's a known repository pattern
@@ -254,14 +377,14 @@ impl RepositoryManager {
             self.output.success(&format!("Repository {} added successfully", repo.name));
         } else if repo_spec.starts_with("http://") || repo_spec.starts_with("https://") {
             // URL provided
-            self.add_repo_from_url(repo_spec).await?;
+            self.add_repo_from_url(repo_spec, allow_untrusted).await?;
         } else if repo_spec.starts_with("ppa:") {
             // PPA repository (Ubuntu)
             self.add_ppa(repo_spec).await?;
         } else {
             // Try to interpret as a package that needs a repository
             if let Some(repo) = self.detector.detect_required_repository(repo_spec) {
-                return Box::pin(self.add(&repo.url)).await;
+                return Box::pin(self.add(&repo.url, allow_untrusted)).await;
             }
 
             bail!("Unknown repository format: {}", repo_spec);
@@ -270,12 +393,45 @@ impl RepositoryManager {
         Ok(())
     }
 
-    /// Add repository from URL
-    async fn add_repo_from_url(&self, url: &str) -> Result<()> {
-        let name = self.guess_repo_name(url, "");
-        let repo_type = self.get_repo_type();
+    /// Add repository from URL, filling in details for known repositories
+    async fn add_repo_from_url(&self, url: &str, allow_untrusted: bool) -> Result<()> {
+        let repo = if let Some(repo) = self.detector.detect_known_repository(url) {
+            let vendor = repo.metadata.vendor.clone().unwrap_or_else(|| repo.name.clone());
+            self.output.info(&format!(
+                "Recognized {} ({}, trust level: {})",
+                repo.metadata.description.as_deref().unwrap_or(&repo.name),
+                vendor,
+                repo.metadata.trust_level
+            ));
 
-        let repo = Repository::new(name.clone(), url.to_string(), repo_type);
+            if !self.assume_yes && !self.prompt.confirm(&format!("Add {} repository '{}'?", vendor, repo.name))? {
+                return Err(Cancelled("Repository not added".to_string()).into());
+            }
+
+            if let Some(key_url) = repo.gpg_key.as_ref().and_then(|k| k.key_url.as_ref()) {
+                self.gpg.import_key_from_url(key_url).await?;
+                self.output.success("GPG key imported successfully");
+            }
+
+            repo
+        } else {
+            let name = self.guess_repo_name(url, "");
+            let trust_level = super::classify_trust_level(url);
+
+            if trust_level == TrustLevel::Unknown && !allow_untrusted {
+                bail!(
+                    "{} is not a known repository (trust level: Unknown). Re-run with --allow-untrusted to add it anyway",
+                    url
+                );
+            }
+
+            if trust_level == TrustLevel::Unknown {
+                self.output.warn(&format!("Adding untrusted repository {}; packages will not be signature-verified by pkmgr", url));
+            }
+
+            Repository::new(name, url.to_string(), self.get_repo_type())
+                .with_trust_level(trust_level)
+        };
 
         // Check if it's a mirror
         if let Some(mirror_info) = self.detector.detect_mirror(url) {
@@ -285,7 +441,7 @@ impl RepositoryManager {
         self.write_repo_config(&repo)?;
         self.update_cache().await?;
 
-        self.output.success(&format!("Repository {} added", name));
+        self.output.success(&format!("Repository {} added", repo.name));
         Ok(())
     }
 
@@ -302,7 +458,7 @@ impl RepositoryManager {
             let output = std::process::Command::new("add-apt-repository")
                 .arg("-y")
                 .arg(ppa)
-                .output()
+                .logged_output()
                 .context("Failed to add PPA")?;
 
             if !output.status.success() {
@@ -377,67 +533,55 @@ impl RepositoryManager {
         }
     }
 
-    /// Update repository cache
+    /// Refresh the package index through the backend (apt update, dnf
+    /// makecache, pacman -Sy, ...)
     pub async fn update_cache(&self) -> Result<()> {
         self.output.progress("Updating repository cache");
 
-        let pm_name = self.platform.primary_package_manager()
-            .map(|pm| pm.to_string())
-            .unwrap_or_default();
-        match pm_name.as_str() {
-            "apt" => {
-                let output = std::process::Command::new("apt-get")
-                    .arg("update")
-                    .output()
-                    .context("Failed to update APT cache")?;
-
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    bail!("Failed to update cache: {}", stderr);
-                }
-            }
-            "dnf" => {
-                let output = std::process::Command::new("dnf")
-                    .arg("makecache")
-                    .output()
-                    .context("Failed to update DNF cache")?;
-
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    bail!("Failed to update cache: {}", stderr);
-                }
-            }
-            "yum" => {
-                let output = std::process::Command::new("yum")
-                    .arg("makecache")
-                    .output()
-                    .context("Failed to update YUM cache")?;
-
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    bail!("Failed to update cache: {}", stderr);
-                }
-            }
-            "pacman" => {
-                let output = std::process::Command::new("pacman")
-                    .arg("-Sy")
-                    .output()
-                    .context("Failed to update Pacman database")?;
-
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    bail!("Failed to update cache: {}", stderr);
-                }
-            }
-            _ => {
-                self.output.warn("Cache update not implemented for this package manager");
-            }
-        }
+        let package_manager = PackageManagerFactory::create(&self.platform)?;
+        self.refresh_index(package_manager.as_ref()).await?;
 
         self.output.success("Repository cache updated");
         Ok(())
     }
 
+    /// Refresh the index and record it in the cache, which drops search
+    /// results cached before the refresh
+    pub async fn refresh_index(&self, package_manager: &dyn traits::PackageManager) -> Result<()> {
+        package_manager.update().await
+            .context("Failed to update repository metadata")?;
+        CacheManager::new(self.output.clone())?.mark_index_refreshed(package_manager.name())
+    }
+
+    /// Refresh the index if pkmgr has not done so within the RepositoryIndex
+    /// TTL. Never offline, never when escalating would prompt, and never for
+    /// pacman, where a bare `pacman -Sy` leaves the system partially
+    /// upgraded; then, or when the refresh fails, warn and use the existing
+    /// index
+    pub async fn refresh_if_stale(&self, package_manager: &dyn traits::PackageManager) {
+        if is_offline() {
+            return;
+        }
+        let fresh = CacheManager::new(self.output.clone())
+            .map(|cache| cache.index_is_fresh(package_manager.name()))
+            .unwrap_or(false);
+        if fresh {
+            return;
+        }
+
+        let unattended = privilege::escalation().is_ok_and(|escalation| escalation.is_passwordless());
+        if unattended && package_manager.name() != "pacman" {
+            self.output.note("📥 Repository index is stale, refreshing...");
+            match self.refresh_index(package_manager).await {
+                Ok(()) => return,
+                Err(e) => self.output.warn(&format!("Could not refresh the repository index ({:#}); using the existing one", e)),
+            }
+        } else {
+            self.output.warn("Repository index may be out of date; results reflect the last refresh");
+        }
+        self.output.info("💡 Run 'pkmgr repos update' to refresh it");
+    }
+
     /// Write repository configuration
     fn write_repo_config(&self, repo: &Repository) -> Result<()> {
         let pm_name = self.platform.primary_package_manager()
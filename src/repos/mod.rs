@@ -161,6 +161,109 @@ impl std::fmt::Display for RepositoryType {
     }
 }
 
+impl TrustLevel {
+    /// Relative trust, higher is more trusted
+    pub fn rank(&self) -> u8 {
+        match self {
+            TrustLevel::Official => 4,
+            TrustLevel::Verified => 3,
+            TrustLevel::Corporate => 2,
+            TrustLevel::Community => 1,
+            TrustLevel::Unknown => 0,
+        }
+    }
+
+    /// Whether this level satisfies a minimum trust policy
+    pub fn meets(&self, minimum: &TrustLevel) -> bool {
+        self.rank() >= minimum.rank()
+    }
+}
+
+impl std::str::FromStr for TrustLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "official" => Ok(TrustLevel::Official),
+            "verified" => Ok(TrustLevel::Verified),
+            "corporate" => Ok(TrustLevel::Corporate),
+            "community" => Ok(TrustLevel::Community),
+            "unknown" => Ok(TrustLevel::Unknown),
+            _ => bail!("Unknown trust level: {} (expected official, verified, corporate, community, or unknown)", s),
+        }
+    }
+}
+
+/// OS vendor hosts whose repositories are treated as official
+const OFFICIAL_REPO_HOSTS: &[&str] = &[
+    "archive.ubuntu.com",
+    "security.ubuntu.com",
+    "ports.ubuntu.com",
+    "deb.debian.org",
+    "security.debian.org",
+    "download.fedoraproject.org",
+    "mirrors.fedoraproject.org",
+    "dl.fedoraproject.org",
+    "mirror.centos.org",
+    "cdn.redhat.com",
+    "archlinux.org",
+    "dl-cdn.alpinelinux.org",
+    "download.opensuse.org",
+];
+
+/// Community-run repository hosts (PPAs, COPR, AUR)
+const COMMUNITY_REPO_HOSTS: &[&str] = &[
+    "ppa.launchpad.net",
+    "ppa.launchpadcontent.net",
+    "copr.fedorainfracloud.org",
+    "download.copr.fedorainfracloud.org",
+    "aur.archlinux.org",
+];
+
+/// Top-level directories under which mirrors publish a distribution's own
+/// archive, e.g. https://mirror.example.edu/ubuntu or .../archlinux/$repo
+const DISTRO_MIRROR_PATHS: &[&str] = &[
+    "ubuntu",
+    "debian",
+    "debian-security",
+    "fedora",
+    "centos",
+    "rocky",
+    "almalinux",
+    "epel",
+    "archlinux",
+    "alpine",
+    "opensuse",
+];
+
+/// Classify a repository URL against known vendor and community hosts.
+/// Other hosts mirroring a distribution archive count as Community: the
+/// packages are still signed by the distribution's keys
+pub fn classify_trust_level(url: &str) -> TrustLevel {
+    let url = url.to_lowercase();
+
+    if let Some(known) = get_known_repositories().into_iter()
+        .find(|k| k.patterns.iter().any(|p| url.contains(p))) {
+        return known.trust_level;
+    }
+
+    if COMMUNITY_REPO_HOSTS.iter().any(|h| url.contains(h)) {
+        return TrustLevel::Community;
+    }
+
+    // Country mirrors such as us.archive.ubuntu.com match by suffix
+    if OFFICIAL_REPO_HOSTS.iter().any(|h| url.contains(h)) {
+        return TrustLevel::Official;
+    }
+
+    let path = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+    if path.split('/').skip(1).any(|segment| DISTRO_MIRROR_PATHS.contains(&segment)) {
+        return TrustLevel::Community;
+    }
+
+    TrustLevel::Unknown
+}
+
 impl std::fmt::Display for TrustLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -320,4 +423,17 @@ pub fn get_known_repositories() -> Vec<KnownRepository> {
             description: "Extra Packages for Enterprise Linux",
         },
     ]
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlisted_distro_mirrors_are_community() {
+        assert_eq!(classify_trust_level("http://us.archive.ubuntu.com/ubuntu"), TrustLevel::Official);
+        assert_eq!(classify_trust_level("http://mirror.example.edu/ubuntu"), TrustLevel::Community);
+        assert_eq!(classify_trust_level("https://mirror.example.net/archlinux/$repo/os/$arch"), TrustLevel::Community);
+        assert_eq!(classify_trust_level("https://ubuntu.example.com/packages"), TrustLevel::Unknown);
+    }
+}
@@ -1,11 +1,14 @@
 use anyhow::Result;
-use clap::Command;
+use clap::CommandFactory;
 use clap_complete::{generate, Shell as ClapShell};
+use clap_complete_nushell::Nushell;
+use crate::commands::complete::PACKAGE_COMMANDS;
 use crate::commands::Cli;
 use crate::shell::ShellType;
 use crate::ui::output::Output;
-use std::io;
 
+/// Completion scripts generated from the clap `Cli` definition, so they
+/// always match the current command tree
 pub struct CompletionGenerator {
     shell: ShellType,
     output: Output,
@@ -18,23 +21,33 @@ impl CompletionGenerator {
 
     /// Generate completion script for the shell
     pub fn generate(&self) -> Result<String> {
-        let clap_shell = self.to_clap_shell()?;
+        let mut app = Cli::command();
+        self.output.debug(&format!("📝 Generating {} completions", self.shell.display_name()));
 
-        let mut app = <Cli as clap::CommandFactory>::command();
-        let mut buf = Vec::new();
+        if self.shell == ShellType::Nushell {
+            let mut buf = Vec::new();
+            generate(Nushell, &mut app, "pkmgr", &mut buf);
+            return Ok(nushell_packages(&String::from_utf8(buf)?));
+        }
 
+        let clap_shell = self.to_clap_shell()?;
+        let mut buf = Vec::new();
         generate(clap_shell, &mut app, "pkmgr", &mut buf);
 
-        Ok(String::from_utf8(buf)?)
+        Ok(self.with_package_completion(String::from_utf8(buf)?))
     }
 
-    /// Generate custom completions with dynamic content
-    pub fn generate_custom(&self) -> String {
+    /// Hook `pkmgr __complete` into the static script so package arguments
+    /// complete from the cache; when it prints nothing the static
+    /// completion runs as before. Bash and zsh also complete the pki, pkr,
+    /// pku, pks and pkl aliases from the shell integration
+    fn with_package_completion(&self, script: String) -> String {
         match self.shell {
-            ShellType::Bash => self.bash_completions(),
-            ShellType::Zsh => self.zsh_completions(),
-            ShellType::Fish => self.fish_completions(),
-            _ => self.generate().unwrap_or_default(),
+            ShellType::Bash => script + BASH_PACKAGES + BASH_ALIASES,
+            ShellType::Zsh => script.replace(ZSH_REGISTER, &format!("{}{}", ZSH_ALIASES, ZSH_PACKAGES)),
+            ShellType::Fish => script + FISH_PACKAGES,
+            ShellType::PowerShell => script.replace(POWERSHELL_ELEMENTS, POWERSHELL_PACKAGES),
+            _ => script,
         }
     }
 
@@ -48,447 +61,168 @@ impl CompletionGenerator {
             _ => anyhow::bail!("Unsupported shell for completions: {:?}", self.shell),
         }
     }
-
-    /// Bash completions with dynamic content
-    fn bash_completions(&self) -> String {
-        r#"
-# pkmgr Bash Completions
-# Generated by pkmgr
-
-_pkmgr_complete_packages() {
-    local cur="${COMP_WORDS[COMP_CWORD]}"
-    local packages=$(pkmgr list installed 2>/dev/null | grep -E '^\s*\*' | awk '{print $2}')
-    COMPREPLY=($(compgen -W "$packages" -- "$cur"))
 }
 
-_pkmgr_complete_languages() {
-    local languages="python node go rust ruby php java dotnet"
-    COMPREPLY=($(compgen -W "$languages" -- "$cur"))
-}
-
-_pkmgr_complete_repos() {
+const BASH_PACKAGES: &str = r#"
+# Package names come from `pkmgr __complete`, which reads pkmgr's cache
+_pkmgr_packages() {
     local cur="${COMP_WORDS[COMP_CWORD]}"
-    local repos=$(pkmgr repos list 2>/dev/null | grep -E '^\s*\*' | awk '{print $2}')
-    COMPREPLY=($(compgen -W "$repos" -- "$cur"))
-}
-
-_pkmgr_complete_profiles() {
-    local cur="${COMP_WORDS[COMP_CWORD]}"
-    local profiles=$(pkmgr profile list 2>/dev/null | grep -E '^\s*\*' | awk '{print $2}')
-    COMPREPLY=($(compgen -W "$profiles" -- "$cur"))
-}
-
-_pkmgr_completions() {
-    local cur prev words cword
-    _init_completion || return
-
-    local commands="install remove update search list info where whatis fix node python go rust ruby php java dotnet binary iso usb repos profile config cache doctor bootstrap sync check shell"
-
-    # Handle command aliases
-    case "$prev" in
-        pkmgr)
-            COMPREPLY=($(compgen -W "$commands" -- "$cur"))
-            ;;
-        i|install)
-            # Complete with available packages (this would query package manager)
-            _pkmgr_complete_packages
-            ;;
-        r|rm|remove)
-            # Complete with installed packages
-            _pkmgr_complete_packages
-            ;;
-        u|up|update)
-            # Complete with installed packages or "all"
-            local packages=$(pkmgr list installed 2>/dev/null | grep -E '^\s*\*' | awk '{print $2}')
-            COMPREPLY=($(compgen -W "all $packages" -- "$cur"))
-            ;;
-        s|search)
-            # No completion for search query
-            ;;
-        repos)
-            local subcmds="list add remove update info"
-            COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-            ;;
-        profile)
-            local subcmds="list create use remove edit diff export import"
-            COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-            ;;
-        cache)
-            local subcmds="list clean info refresh"
-            COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-            ;;
-        shell)
-            local subcmds="load completions add remove env"
-            COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-            ;;
-        binary)
-            local subcmds="search install list update remove info"
-            COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-            ;;
-        iso)
-            local subcmds="list install remove info verify clean"
-            COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-            ;;
-        usb)
-            local subcmds="erase write boot"
-            COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-            ;;
-        node|python|go|rust|ruby|php|java|dotnet)
-            local subcmds="install use list remove current info search"
-            COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-            ;;
-    esac
-
-    # Handle flags
-    if [[ "$cur" == -* ]]; then
-        local flags="--help --version --force --quiet --verbose --yes --dry-run --explain --profile --arch --global --user"
-        COMPREPLY=($(compgen -W "$flags" -- "$cur"))
+    if [[ $COMP_CWORD -ge 2 && "$cur" != -* ]]; then
+        local IFS=$'\n'
+        COMPREPLY=( $(pkmgr __complete "${COMP_WORDS[@]:1:COMP_CWORD-1}" "$cur" 2>/dev/null) )
+        [[ ${#COMPREPLY[@]} -gt 0 ]] && return 0
     fi
-}
-
-complete -F _pkmgr_completions pkmgr
-
-# Aliases
-complete -F _pkmgr_completions pki
-complete -F _pkmgr_completions pkr
-complete -F _pkmgr_completions pku
-complete -F _pkmgr_completions pks
-complete -F _pkmgr_completions pkl
-"#
-        .to_string()
-    }
-
-    /// Zsh completions with dynamic content
-    fn zsh_completions(&self) -> String {
-        r#"
-#compdef pkmgr
-
-# pkmgr Zsh Completions
-# Generated by pkmgr
-
-_pkmgr() {
-    local context state line
-    typeset -A opt_args
-
-    _arguments -C \
-        '(- : *)'{-h,--help}'[Show help information]' \
-        '(- : *)'{-V,--version}'[Show version information]' \
-        '--force[Force override safety checks]' \
-        {-q,--quiet}'[Minimal output only]' \
-        {-v,--verbose}'[Detailed operation output]' \
-        {-y,--yes}'[Auto-confirm all prompts]' \
-        '--dry-run[Show what would happen without executing]' \
-        '--explain[Show underlying native commands]' \
-        '--profile[Use specific configuration profile]:profile:_pkmgr_profiles' \
-        '--arch[Specify target architecture]:arch:' \
-        '--global[Force system-wide installation]' \
-        '--user[Force user-space installation]' \
-        '1:command:_pkmgr_commands' \
-        '*::arg:->args'
-
-    case $state in
-        args)
-            case $words[1] in
-                install|i)
-                    _pkmgr_available_packages
-                    ;;
-                remove|r|rm)
-                    _pkmgr_installed_packages
-                    ;;
-                update|u|up)
-                    _alternative \
-                        'packages:installed packages:_pkmgr_installed_packages' \
-                        'all:all packages:(all)'
-                    ;;
-                node|python|go|rust|ruby|php|java|dotnet)
-                    _pkmgr_language_commands
-                    ;;
-                repos)
-                    _pkmgr_repos_commands
-                    ;;
-                profile)
-                    _pkmgr_profile_commands
-                    ;;
-                binary)
-                    _pkmgr_binary_commands
-                    ;;
-                iso)
-                    _pkmgr_iso_commands
-                    ;;
-                usb)
-                    _pkmgr_usb_commands
-                    ;;
-                cache)
-                    _pkmgr_cache_commands
-                    ;;
-                shell)
-                    _pkmgr_shell_commands
-                    ;;
-            esac
-            ;;
+    _pkmgr "$@"
+}
+complete -F _pkmgr_packages -o bashdefault -o default pkmgr
+"#;
+
+const BASH_ALIASES: &str = r#"
+# The integration aliases complete as the pkmgr command they expand to
+_pkmgr_aliases() {
+    local subcommand
+    case "$1" in
+        pki) subcommand=install ;;
+        pkr) subcommand=remove ;;
+        pku) subcommand=update ;;
+        pks) subcommand=search ;;
+        pkl) subcommand=list ;;
     esac
+    COMP_WORDS=(pkmgr "$subcommand" "${COMP_WORDS[@]:1}")
+    (( COMP_CWORD++ ))
+    _pkmgr_packages pkmgr "$2" "${COMP_WORDS[COMP_CWORD-1]}"
+}
+complete -F _pkmgr_aliases -o bashdefault -o default pki pkr pku pks pkl
+"#;
+
+/// Registration at the end of clap's zsh script, replaced by ZSH_PACKAGES
+const ZSH_REGISTER: &str = r#"if [ "$funcstack[1]" = "_pkmgr" ]; then
+    _pkmgr "$@"
+else
+    compdef _pkmgr pkmgr
+fi"#;
+
+/// Only used with `setopt complete_aliases`; otherwise zsh expands the
+/// aliases before completing
+const ZSH_ALIASES: &str = r#"# The integration aliases complete as the pkmgr command they expand to
+_pkmgr_aliases() {
+    local -A subcommands=(pki install pkr remove pku update pks search pkl list)
+    words=(pkmgr $subcommands[$words[1]] "${(@)words[2,-1]}")
+    (( CURRENT++ ))
+    _pkmgr_packages "$@"
+}
+
+compdef _pkmgr_aliases pki pkr pku pks pkl
+"#;
+
+const ZSH_PACKAGES: &str = r#"# Package names come from `pkmgr __complete`, which reads pkmgr's cache
+_pkmgr_packages() {
+    if (( CURRENT > 2 )) && [[ $PREFIX != -* ]]; then
+        local -a packages
+        packages=(${(f)"$(pkmgr __complete "${(@)words[2,CURRENT-1]}" "$PREFIX" 2>/dev/null)"})
+        if (( $#packages )); then
+            compadd -a packages
+            return
+        fi
+    fi
+    _pkmgr "$@"
 }
 
-_pkmgr_commands() {
-    local commands=(
-        'install:Install packages'
-        'i:Install packages (alias)'
-        'remove:Remove packages'
-        'r:Remove packages (alias)'
-        'rm:Remove packages (alias)'
-        'update:Update packages'
-        'u:Update packages (alias)'
-        'up:Update packages (alias)'
-        'search:Search for packages'
-        's:Search for packages (alias)'
-        'list:List packages'
-        'ls:List packages (alias)'
-        'info:Show package information'
-        'where:Show installation location'
-        'whatis:Show package description'
-        'fix:Fix broken dependencies'
-        'node:Node.js version management'
-        'python:Python version management'
-        'go:Go version management'
-        'rust:Rust version management'
-        'ruby:Ruby version management'
-        'php:PHP version management'
-        'java:Java version management'
-        'dotnet:.NET version management'
-        'binary:Binary management'
-        'iso:ISO management'
-        'usb:USB management'
-        'repos:Repository management'
-        'profile:Profile management'
-        'config:Configuration management'
-        'cache:Cache management'
-        'doctor:System health check'
-        'bootstrap:Bootstrap system'
-        'sync:Sync configuration'
-        'check:Check for updates'
-        'shell:Shell integration'
-    )
-    _describe 'command' commands
-}
-
-_pkmgr_installed_packages() {
-    local packages=($(pkmgr list installed 2>/dev/null | grep -E '^\s*\*' | awk '{print $2}'))
-    _describe 'installed packages' packages
-}
-
-_pkmgr_available_packages() {
-    # This would query the package manager for available packages
-    # For now, use common package names
-    local packages=(git vim emacs curl wget gcc make python nodejs ruby go rust docker)
-    _describe 'available packages' packages
-}
+compdef _pkmgr_packages pkmgr
+if [ "$funcstack[1]" = "_pkmgr" ]; then
+    _pkmgr_packages "$@"
+fi"#;
 
-_pkmgr_profiles() {
-    local profiles=($(pkmgr profile list 2>/dev/null | grep -E '^\s*\*' | awk '{print $2}'))
-    _describe 'profiles' profiles
-}
+const FISH_PACKAGES: &str = r#"
+# Package names come from `pkmgr __complete`, which reads pkmgr's cache
+complete -c pkmgr -n 'test (count (commandline -opc)) -ge 2' -a '(pkmgr __complete (commandline -opc)[2..-1] (commandline -ct) 2>/dev/null)'
+"#;
 
-_pkmgr_language_commands() {
-    local commands=(
-        'install:Install language version or package'
-        'use:Switch active version'
-        'list:Show installed versions'
-        'remove:Remove language version'
-        'current:Show current active version'
-        'info:Show package information'
-        'search:Search language-specific packages'
-    )
-    _describe 'language command' commands
-}
+/// Line in clap's PowerShell script that POWERSHELL_PACKAGES follows
+const POWERSHELL_ELEMENTS: &str = "    $commandElements = $commandAst.CommandElements\n";
 
-_pkmgr_repos_commands() {
-    local commands=(
-        'list:Show configured repositories'
-        'add:Add repository'
-        'remove:Remove repository'
-        'update:Refresh repository metadata'
-        'info:Show repository information'
-    )
-    _describe 'repository command' commands
-}
+const POWERSHELL_PACKAGES: &str = r#"    $commandElements = $commandAst.CommandElements
 
-_pkmgr_profile_commands() {
-    local commands=(
-        'list:Show all profiles'
-        'create:Create new profile'
-        'use:Switch to profile'
-        'remove:Delete profile'
-        'edit:Edit profile'
-        'diff:Compare profiles'
-        'export:Export profile'
-        'import:Import profile'
-    )
-    _describe 'profile command' commands
-}
+    # Package names come from `pkmgr __complete`, which reads pkmgr's cache
+    if ($commandElements.Count -gt 1 -and -not $wordToComplete.StartsWith('-')) {
+        $words = @($commandElements | Select-Object -Skip 1 | ForEach-Object { $_.ToString() })
+        if ($wordToComplete -eq '') { $words += '' }
+        $packages = @(pkmgr __complete @words 2>$null)
+        if ($packages.Count -gt 0) {
+            return $packages | ForEach-Object {
+                [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, $_)
+            }
+        }
+    }
+"#;
 
-_pkmgr_binary_commands() {
-    local commands=(
-        'search:Search for binaries'
-        'install:Install binary'
-        'list:Show installed binaries'
-        'update:Update binaries'
-        'remove:Remove binary'
-        'info:Show binary information'
-    )
-    _describe 'binary command' commands
-}
+const NUSHELL_PACKAGES: &str = r#"  # Package names come from `pkmgr __complete`, which reads pkmgr's cache
+  def "nu-complete pkmgr packages" [context: string] {
+    ^pkmgr __complete ...($context | split row -r '\s+' | skip 1) | lines
+  }
 
-_pkmgr_iso_commands() {
-    local commands=(
-        'list:Show distributions'
-        'install:Download ISO'
-        'remove:Delete ISO'
-        'info:Show distribution info'
-        'verify:Verify ISO'
-        'clean:Remove old ISOs'
-    )
-    _describe 'iso command' commands
-}
+"#;
 
-_pkmgr_usb_commands() {
-    local commands=(
-        'erase:Wipe USB device'
-        'write:Write ISO to USB'
-        'boot:Manage multi-boot USB'
-    )
-    _describe 'usb command' commands
-}
+/// Point the positional arguments of package commands at
+/// NUSHELL_PACKAGES in clap_complete_nushell's externs
+fn nushell_packages(script: &str) -> String {
+    let mut package_command = false;
+    let mut lines = Vec::new();
 
-_pkmgr_cache_commands() {
-    local commands=(
-        'list:Show cache contents'
-        'clean:Clean caches'
-        'info:Show cache usage'
-        'refresh:Refresh cached data'
-    )
-    _describe 'cache command' commands
+    for line in script.lines() {
+        if let Some(path) = line.trim().strip_prefix("export extern ") {
+            package_command = PACKAGE_COMMANDS.iter()
+                .any(|(name, _)| path == format!("\"pkmgr {}\" [", name));
+        }
+        let positional = line.starts_with("    ") && !line.trim_start().starts_with('-');
+        if package_command && positional && !line.contains('@') {
+            lines.push(line.replacen(": string", ": string@\"nu-complete pkmgr packages\"", 1));
+        } else {
+            lines.push(line.to_string());
+        }
+        if line == "module completions {" {
+            lines.push(NUSHELL_PACKAGES.trim_end().to_string());
+        }
+    }
+    lines.join("\n") + "\n"
 }
 
-_pkmgr_shell_commands() {
-    local commands=(
-        'load:Load shell integration'
-        'completions:Generate completions'
-        'add:Add to PATH'
-        'remove:Remove from PATH'
-        'env:Show environment'
-    )
-    _describe 'shell command' commands
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-# Register completion
-compdef _pkmgr pkmgr
-compdef _pkmgr pki=pkmgr
-compdef _pkmgr pkr=pkmgr
-compdef _pkmgr pku=pkmgr
-compdef _pkmgr pks=pkmgr
-compdef _pkmgr pkl=pkmgr
-"#
-        .to_string()
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
     }
 
-    /// Fish completions with dynamic content
-    fn fish_completions(&self) -> String {
-        r#"
-# pkmgr Fish Completions
-# Generated by pkmgr
-
-# Disable file completions by default
-complete -c pkmgr -f
-
-# Global flags
-complete -c pkmgr -s h -l help -d "Show help information"
-complete -c pkmgr -s V -l version -d "Show version information"
-complete -c pkmgr -l force -d "Force override safety checks"
-complete -c pkmgr -s q -l quiet -d "Minimal output only"
-complete -c pkmgr -s v -l verbose -d "Detailed operation output"
-complete -c pkmgr -s y -l yes -d "Auto-confirm all prompts"
-complete -c pkmgr -l dry-run -d "Show what would happen"
-complete -c pkmgr -l explain -d "Show underlying commands"
-complete -c pkmgr -l profile -d "Use specific profile" -xa "(pkmgr profile list 2>/dev/null | grep '^\s*\*' | awk '{print \$2}')"
-complete -c pkmgr -l arch -d "Specify architecture"
-complete -c pkmgr -l global -d "System-wide installation"
-complete -c pkmgr -l user -d "User-space installation"
-
-# Commands
-complete -c pkmgr -n __fish_use_subcommand -a install -d "Install packages"
-complete -c pkmgr -n __fish_use_subcommand -a i -d "Install (alias)"
-complete -c pkmgr -n __fish_use_subcommand -a remove -d "Remove packages"
-complete -c pkmgr -n __fish_use_subcommand -a r -d "Remove (alias)"
-complete -c pkmgr -n __fish_use_subcommand -a rm -d "Remove (alias)"
-complete -c pkmgr -n __fish_use_subcommand -a update -d "Update packages"
-complete -c pkmgr -n __fish_use_subcommand -a u -d "Update (alias)"
-complete -c pkmgr -n __fish_use_subcommand -a up -d "Update (alias)"
-complete -c pkmgr -n __fish_use_subcommand -a search -d "Search packages"
-complete -c pkmgr -n __fish_use_subcommand -a s -d "Search (alias)"
-complete -c pkmgr -n __fish_use_subcommand -a list -d "List packages"
-complete -c pkmgr -n __fish_use_subcommand -a ls -d "List (alias)"
-complete -c pkmgr -n __fish_use_subcommand -a info -d "Package information"
-complete -c pkmgr -n __fish_use_subcommand -a where -d "Installation location"
-complete -c pkmgr -n __fish_use_subcommand -a whatis -d "Package description"
-complete -c pkmgr -n __fish_use_subcommand -a fix -d "Fix broken dependencies"
-
-# Language commands
-complete -c pkmgr -n __fish_use_subcommand -a node -d "Node.js management"
-complete -c pkmgr -n __fish_use_subcommand -a python -d "Python management"
-complete -c pkmgr -n __fish_use_subcommand -a go -d "Go management"
-complete -c pkmgr -n __fish_use_subcommand -a rust -d "Rust management"
-complete -c pkmgr -n __fish_use_subcommand -a ruby -d "Ruby management"
-complete -c pkmgr -n __fish_use_subcommand -a php -d "PHP management"
-complete -c pkmgr -n __fish_use_subcommand -a java -d "Java management"
-complete -c pkmgr -n __fish_use_subcommand -a dotnet -d ".NET management"
-
-# Other commands
-complete -c pkmgr -n __fish_use_subcommand -a binary -d "Binary management"
-complete -c pkmgr -n __fish_use_subcommand -a iso -d "ISO management"
-complete -c pkmgr -n __fish_use_subcommand -a usb -d "USB management"
-complete -c pkmgr -n __fish_use_subcommand -a repos -d "Repository management"
-complete -c pkmgr -n __fish_use_subcommand -a profile -d "Profile management"
-complete -c pkmgr -n __fish_use_subcommand -a config -d "Configuration"
-complete -c pkmgr -n __fish_use_subcommand -a cache -d "Cache management"
-complete -c pkmgr -n __fish_use_subcommand -a doctor -d "System health check"
-complete -c pkmgr -n __fish_use_subcommand -a bootstrap -d "Bootstrap system"
-complete -c pkmgr -n __fish_use_subcommand -a sync -d "Sync configuration"
-complete -c pkmgr -n __fish_use_subcommand -a check -d "Check for updates"
-complete -c pkmgr -n __fish_use_subcommand -a shell -d "Shell integration"
-
-# Subcommand completions
-complete -c pkmgr -n "__fish_seen_subcommand_from install i" -xa "(pkmgr search '' 2>/dev/null | awk '{print \$1}')"
-complete -c pkmgr -n "__fish_seen_subcommand_from remove r rm" -xa "(pkmgr list installed 2>/dev/null | grep '^\s*\*' | awk '{print \$2}')"
-complete -c pkmgr -n "__fish_seen_subcommand_from update u up" -xa "all (pkmgr list installed 2>/dev/null | grep '^\s*\*' | awk '{print \$2}')"
-
-# Language subcommands
-for lang in node python go rust ruby php java dotnet
-    complete -c pkmgr -n "__fish_seen_subcommand_from $lang; and not __fish_seen_subcommand_from install use list remove current info search" -a install -d "Install version or package"
-    complete -c pkmgr -n "__fish_seen_subcommand_from $lang; and not __fish_seen_subcommand_from install use list remove current info search" -a use -d "Switch version"
-    complete -c pkmgr -n "__fish_seen_subcommand_from $lang; and not __fish_seen_subcommand_from install use list remove current info search" -a list -d "Show versions"
-    complete -c pkmgr -n "__fish_seen_subcommand_from $lang; and not __fish_seen_subcommand_from install use list remove current info search" -a remove -d "Remove version"
-    complete -c pkmgr -n "__fish_seen_subcommand_from $lang; and not __fish_seen_subcommand_from install use list remove current info search" -a current -d "Show current"
-    complete -c pkmgr -n "__fish_seen_subcommand_from $lang; and not __fish_seen_subcommand_from install use list remove current info search" -a info -d "Package info"
-    complete -c pkmgr -n "__fish_seen_subcommand_from $lang; and not __fish_seen_subcommand_from install use list remove current info search" -a search -d "Search packages"
-end
+    #[test]
+    fn every_shell_covers_the_command_tree() {
+        let output = Output::new("never".to_string(), false);
+        for shell in [ShellType::Bash, ShellType::Zsh, ShellType::Fish, ShellType::PowerShell, ShellType::Nushell] {
+            let script = CompletionGenerator::new(shell.clone(), output.clone()).generate().unwrap();
+            for command in ["install", "repos", "iso", "profile"] {
+                assert!(script.contains(command), "{:?} completions are missing {}", shell, command);
+            }
+        }
 
-# Repository subcommands
-complete -c pkmgr -n "__fish_seen_subcommand_from repos; and not __fish_seen_subcommand_from list add remove update info" -a list -d "Show repositories"
-complete -c pkmgr -n "__fish_seen_subcommand_from repos; and not __fish_seen_subcommand_from list add remove update info" -a add -d "Add repository"
-complete -c pkmgr -n "__fish_seen_subcommand_from repos; and not __fish_seen_subcommand_from list add remove update info" -a remove -d "Remove repository"
-complete -c pkmgr -n "__fish_seen_subcommand_from repos; and not __fish_seen_subcommand_from list add remove update info" -a update -d "Refresh metadata"
-complete -c pkmgr -n "__fish_seen_subcommand_from repos; and not __fish_seen_subcommand_from list add remove update info" -a info -d "Repository info"
+        let nushell = CompletionGenerator::new(ShellType::Nushell, output).generate().unwrap();
+        assert!(nushell.contains("export extern \"pkmgr dotnet tool install\" ["));
+        assert!(nushell.contains("--dry-run"));
+        assert!(nushell.contains("...packages: string@\"nu-complete pkmgr packages\""));
+    }
 
-# Shell subcommands
-complete -c pkmgr -n "__fish_seen_subcommand_from shell; and not __fish_seen_subcommand_from load completions add remove env" -a load -d "Load integration"
-complete -c pkmgr -n "__fish_seen_subcommand_from shell; and not __fish_seen_subcommand_from load completions add remove env" -a completions -d "Generate completions"
-complete -c pkmgr -n "__fish_seen_subcommand_from shell; and not __fish_seen_subcommand_from load completions add remove env" -a add -d "Add to PATH"
-complete -c pkmgr -n "__fish_seen_subcommand_from shell; and not __fish_seen_subcommand_from load completions add remove env" -a remove -d "Remove from PATH"
-complete -c pkmgr -n "__fish_seen_subcommand_from shell; and not __fish_seen_subcommand_from load completions add remove env" -a env -d "Show environment"
+    #[test]
+    fn static_scripts_call_back_for_package_names() {
+        let output = Output::new("never".to_string(), false);
+        for shell in [ShellType::Bash, ShellType::Zsh, ShellType::Fish, ShellType::PowerShell] {
+            let script = CompletionGenerator::new(shell.clone(), output.clone()).generate().unwrap();
+            assert!(script.contains("pkmgr __complete"), "{:?} completions never call __complete", shell);
+        }
 
-# Shell type completion
-complete -c pkmgr -n "__fish_seen_subcommand_from shell; and __fish_seen_subcommand_from completions" -xa "bash zsh fish powershell"
-"#
-        .to_string()
+        for shell in [ShellType::Bash, ShellType::Zsh] {
+            let script = CompletionGenerator::new(shell.clone(), output.clone()).generate().unwrap();
+            assert!(script.contains("pki pkr pku pks pkl"), "{:?} completions skip the aliases", shell);
+        }
     }
-}
\ No newline at end of file
+}
@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use std::process::Command;
 use std::env;
 use crate::shell::ShellType;
+use crate::shell::integration::{managed_block, BLOCK_START};
 
 pub struct ShellDetector;
 
@@ -139,11 +140,25 @@ impl ShellDetector {
         anyhow::bail!("User not found in /etc/passwd")
     }
 
+    /// Nushell's (major, minor) version: NU_VERSION inside a running nu,
+    /// otherwise `nu --version`
+    pub fn nushell_version() -> Option<(u32, u32)> {
+        let version = env::var("NU_VERSION").ok().or_else(|| {
+            let output = Command::new("nu").arg("--version").output().ok()?;
+            Some(String::from_utf8_lossy(&output.stdout).to_string())
+        })?;
+        let mut parts = version.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        Some((major, minor))
+    }
+
     /// Check if shell integration is already installed
     pub fn is_integration_installed(shell: &ShellType) -> bool {
         for config_file in shell.config_files() {
             if let Ok(content) = std::fs::read_to_string(&config_file) {
-                if content.contains("pkmgr shell integration") ||
+                if content.contains(BLOCK_START) ||
+                   content.contains("pkmgr shell integration") ||
                    content.contains("pkmgr Bash Integration") ||
                    content.contains("pkmgr Zsh Integration") ||
                    content.contains("pkmgr Fish Integration") ||
@@ -158,9 +173,8 @@ impl ShellDetector {
 
     /// Check if completions are installed
     pub fn are_completions_installed(shell: &ShellType) -> bool {
-        if let Some(comp_dir) = shell.completion_dir() {
-            let comp_file = std::path::PathBuf::from(comp_dir).join("pkmgr");
-            return comp_file.exists();
+        if shell.completion_file().is_some_and(|comp_file| comp_file.exists()) {
+            return true;
         }
 
         // For shells without standard completion directories, check config files
@@ -181,28 +195,27 @@ impl ShellDetector {
         false
     }
 
-    /// Suggest installation method for shell integration
+    /// Suggest installation method for shell integration, wrapped in the
+    /// markers `pkmgr shell unload` looks for
     pub fn suggest_installation(shell: &ShellType) -> String {
-        match shell {
-            ShellType::Bash => {
-                "Add to ~/.bashrc:\n  eval \"$(pkmgr shell load)\""
-            }
-            ShellType::Zsh => {
-                "Add to ~/.zshrc:\n  eval \"$(pkmgr shell load)\""
-            }
-            ShellType::Fish => {
-                "Add to ~/.config/fish/config.fish:\n  pkmgr shell load | source"
-            }
-            ShellType::PowerShell => {
-                "Add to $PROFILE:\n  Invoke-Expression (& pkmgr shell load)"
-            }
+        let (file, line) = match shell {
+            ShellType::Bash => ("~/.bashrc", "eval \"$(pkmgr shell load)\""),
+            ShellType::Zsh => ("~/.zshrc", "eval \"$(pkmgr shell load)\""),
+            ShellType::Fish => ("~/.config/fish/config.fish", "pkmgr shell load | source"),
+            ShellType::PowerShell => ("$PROFILE", "Invoke-Expression (& pkmgr shell load)"),
+            // nu cannot source a command's output, only a file that exists at parse time
             ShellType::Nushell => {
-                "Add to ~/.config/nushell/config.nu:\n  source (pkmgr shell load)"
+                return format!(
+                    "Add to ~/.config/nushell/env.nu:\n  {}\nand to ~/.config/nushell/config.nu:\n  {}",
+                    managed_block("mkdir ~/.cache/pkmgr; pkmgr shell load nushell | save -f ~/.cache/pkmgr/init.nu").trim_end().replace('\n', "\n  "),
+                    managed_block("source ~/.cache/pkmgr/init.nu").trim_end().replace('\n', "\n  ")
+                );
             }
             ShellType::Unknown => {
-                "Unable to detect shell. Specify with: pkmgr shell load <shell>"
+                return "Unable to detect shell. Specify with: pkmgr shell load <shell>".to_string();
             }
-        }
-        .to_string()
+        };
+        let block = managed_block(line);
+        format!("Add to {}:\n  {}", file, block.trim_end().replace('\n', "\n  "))
     }
 }
\ No newline at end of file
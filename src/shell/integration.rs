@@ -1,5 +1,6 @@
 use anyhow::Result;
 use crate::shell::ShellType;
+use crate::shell::detector::ShellDetector;
 use crate::ui::output::Output;
 use std::path::PathBuf;
 
@@ -13,19 +14,24 @@ impl ShellIntegration {
         Self { shell, output }
     }
 
-    /// Generate shell integration script
+    /// Generate shell integration script, inside the managed-block markers
+    /// so `shell unload` finds it when pasted into an rc file
     pub fn generate_script(&self) -> String {
-        match self.shell {
+        let script = match self.shell {
             ShellType::Bash => self.bash_script(),
             ShellType::Zsh => self.zsh_script(),
             ShellType::Fish => self.fish_script(),
             ShellType::PowerShell => self.powershell_script(),
-            ShellType::Nushell => self.nushell_script(),
-            ShellType::Unknown => "# Shell type could not be detected\n".to_string(),
-        }
+            ShellType::Nushell => nushell_compat(&self.nushell_script()),
+            ShellType::Unknown => return "# Shell type could not be detected\n".to_string(),
+        };
+        managed_block(script.trim_start())
     }
 
-    /// Generate PATH update script
+    /// One line that adds ~/.local/bin to (or removes it from) PATH, for
+    /// `eval $(pkmgr shell add)`. POSIX shells get a plain export computed
+    /// from the current PATH, since an unquoted `$(...)` is word-split and
+    /// globbed before eval sees it
     pub fn generate_path_script(&self, add: bool) -> String {
         let local_bin = dirs::home_dir()
             .map(|h| h.join(".local").join("bin"))
@@ -35,86 +41,41 @@ impl ShellIntegration {
 
         match self.shell {
             ShellType::Bash | ShellType::Zsh => {
-                if add {
-                    format!(
-                        r#"
-# Add ~/.local/bin to PATH if not already present
-if [[ ":$PATH:" != *":{}:"* ]]; then
-    export PATH="{}:$PATH"
-fi
-"#,
-                        path_str, path_str
-                    )
-                } else {
-                    format!(
-                        r#"
-# Remove ~/.local/bin from PATH
-export PATH=$(echo "$PATH" | sed 's|{}:||g' | sed 's|:{}||g')
-"#,
-                        path_str, path_str
-                    )
+                let current = std::env::var("PATH").unwrap_or_default();
+                let mut entries: Vec<&str> = current.split(':')
+                    .filter(|entry| !entry.is_empty())
+                    .collect();
+                let present = entries.contains(&path_str.as_ref());
+                match (add, present) {
+                    (true, true) | (false, false) => String::new(),
+                    (true, false) => format!("export PATH=\"{}:$PATH\"\n", shell_escape(&path_str)),
+                    (false, true) => {
+                        entries.retain(|entry| *entry != path_str);
+                        format!("export PATH=\"{}\"\n", shell_escape(&entries.join(":")))
+                    }
                 }
             }
             ShellType::Fish => {
                 if add {
-                    format!(
-                        r#"
-# Add ~/.local/bin to PATH if not already present
-if not contains {} $PATH
-    set -gx PATH {} $PATH
-end
-"#,
-                        path_str, path_str
-                    )
+                    format!("if not contains {} $PATH; set -gx PATH {} $PATH; end\n", path_str, path_str)
                 } else {
-                    format!(
-                        r#"
-# Remove ~/.local/bin from PATH
-set -e PATH[contains -i {} $PATH]
-"#,
-                        path_str
-                    )
+                    format!("set -gx PATH (string match -v -- '{}' $PATH)\n", path_str)
                 }
             }
             ShellType::PowerShell => {
                 if add {
-                    format!(
-                        r#"
-# Add ~/.local/bin to PATH if not already present
-if ($env:PATH -notlike "*{}*") {{
-    $env:PATH = "{};$env:PATH"
-}}
-"#,
-                        path_str, path_str
-                    )
+                    format!("if ($env:PATH -notlike \"*{}*\") {{ $env:PATH = \"{};$env:PATH\" }}\n", path_str, path_str)
                 } else {
-                    format!(
-                        r#"
-# Remove ~/.local/bin from PATH
-$env:PATH = $env:PATH -replace '{}[;:]?', ''
-"#,
-                        path_str
-                    )
+                    format!("$env:PATH = $env:PATH -replace '{}[;:]?', ''\n", path_str)
                 }
             }
             ShellType::Nushell => {
-                if add {
-                    format!(
-                        r#"
-# Add ~/.local/bin to PATH
-let-env PATH = ($env.PATH | prepend {})
-"#,
-                        path_str
-                    )
+                let script = if add {
+                    format!("$env.PATH = ($env.PATH | split row (char esep) | where $it != '{}' | prepend '{}')\n", path_str, path_str)
                 } else {
-                    format!(
-                        r#"
-# Remove ~/.local/bin from PATH
-let-env PATH = ($env.PATH | where $it != {})
-"#,
-                        path_str
-                    )
-                }
+                    format!("$env.PATH = ($env.PATH | split row (char esep) | where $it != '{}')\n", path_str)
+                };
+                nushell_compat(&script)
             }
             ShellType::Unknown => "# Unknown shell\n".to_string(),
         }
@@ -615,12 +576,12 @@ function php {
 
 function composer { & pkmgr php composer @args }
 
-# Helpful aliases
-Set-Alias pki 'pkmgr install'
-Set-Alias pkr 'pkmgr remove'
-Set-Alias pku 'pkmgr update'
-Set-Alias pks 'pkmgr search'
-Set-Alias pkl 'pkmgr list'
+# Helpful aliases (Set-Alias cannot bind a command with arguments)
+function pki { & pkmgr install @args }
+function pkr { & pkmgr remove @args }
+function pku { & pkmgr update @args }
+function pks { & pkmgr search @args }
+function pkl { & pkmgr list @args }
 
 Write-Host "✅ pkmgr shell integration loaded for PowerShell" -ForegroundColor Green
 "#
@@ -634,10 +595,10 @@ Write-Host "✅ pkmgr shell integration loaded for PowerShell" -ForegroundColor
 # Add this to your ~/.config/nushell/config.nu
 
 # Add ~/.local/bin to PATH
-let-env PATH = ($env.PATH | prepend $"($env.HOME)/.local/bin")
+$env.PATH = ($env.PATH | split row (char esep) | prepend $"($env.HOME)/.local/bin")
 
 # pkmgr environment variables
-let-env PKMGR_SHELL = "nushell"
+$env.PKMGR_SHELL = "nushell"
 
 # Language version detection
 def pkmgr-detect-version [lang: string] {
@@ -657,7 +618,7 @@ def python [...args] {
     if ($version | is-empty) {
         ^pkmgr python ...$args
     } else {
-        with-env [PKMGR_PYTHON_VERSION $version] { ^pkmgr python ...$args }
+        with-env {PKMGR_PYTHON_VERSION: $version} { ^pkmgr python ...$args }
     }
 }
 
@@ -671,7 +632,7 @@ def node [...args] {
     if ($version | is-empty) {
         ^pkmgr node ...$args
     } else {
-        with-env [PKMGR_NODE_VERSION $version] { ^pkmgr node ...$args }
+        with-env {PKMGR_NODE_VERSION: $version} { ^pkmgr node ...$args }
     }
 }
 
@@ -685,7 +646,7 @@ def ruby [...args] {
     if ($version | is-empty) {
         ^pkmgr ruby ...$args
     } else {
-        with-env [PKMGR_RUBY_VERSION $version] { ^pkmgr ruby ...$args }
+        with-env {PKMGR_RUBY_VERSION: $version} { ^pkmgr ruby ...$args }
     }
 }
 
@@ -698,7 +659,7 @@ def go [...args] {
     if ($version | is-empty) {
         ^pkmgr go ...$args
     } else {
-        with-env [PKMGR_GO_VERSION $version] { ^pkmgr go ...$args }
+        with-env {PKMGR_GO_VERSION: $version} { ^pkmgr go ...$args }
     }
 }
 
@@ -713,7 +674,7 @@ def java [...args] {
     if ($version | is-empty) {
         ^pkmgr java ...$args
     } else {
-        with-env [PKMGR_JAVA_VERSION $version] { ^pkmgr java ...$args }
+        with-env {PKMGR_JAVA_VERSION: $version} { ^pkmgr java ...$args }
     }
 }
 
@@ -730,7 +691,7 @@ def php [...args] {
     if ($version | is-empty) {
         ^pkmgr php ...$args
     } else {
-        with-env [PKMGR_PHP_VERSION $version] { ^pkmgr php ...$args }
+        with-env {PKMGR_PHP_VERSION: $version} { ^pkmgr php ...$args }
     }
 }
 
@@ -807,15 +768,172 @@ print "✅ pkmgr shell integration loaded for Nushell"
         }
 
         // Show completion status
-        if let Some(comp_dir) = self.shell.completion_dir() {
+        if let Some(comp_file) = self.shell.completion_file() {
             self.output.section("Shell Completions");
-            let comp_file = PathBuf::from(&comp_dir).join("pkmgr");
             if comp_file.exists() {
                 self.output.success(&format!("✅ Completions installed: {}", comp_file.display()));
             } else {
-                self.output.info(&format!("❌ No completions found in: {}", comp_dir));
-                self.output.info("   Run: pkmgr shell completions <shell>");
+                self.output.info(&format!("❌ No completions found at: {}", comp_file.display()));
+                self.output.info("   Run: pkmgr shell completions <shell> --install");
+            }
+        }
+    }
+}
+
+/// Nushell before 0.83 only knows `let-env NAME = value`; newer releases
+/// removed it in favour of `$env.NAME = value`. Unknown versions get the
+/// current syntax
+fn nushell_compat(script: &str) -> String {
+    match ShellDetector::nushell_version() {
+        Some(version) if version < (0, 83) => legacy_nushell(script),
+        _ => script.to_string(),
+    }
+}
+
+fn legacy_nushell(script: &str) -> String {
+    script.lines()
+        .map(|line| match line.strip_prefix("$env.").and_then(|rest| rest.split_once(" = ")) {
+            Some((name, value)) => format!("let-env {} = {}", name, value),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n") + "\n"
+}
+
+/// `value` safe inside double quotes in a POSIX shell
+fn shell_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// First line of a pkmgr-managed block in an rc file. Every supported shell
+/// uses `#` comments, so the same markers work everywhere
+pub const BLOCK_START: &str = "# >>> pkmgr >>>";
+/// Last line of a pkmgr-managed block
+pub const BLOCK_END: &str = "# <<< pkmgr <<<";
+
+/// `lines` wrapped in the managed-block markers, ready to append to an rc file
+pub fn managed_block(lines: &str) -> String {
+    format!("{}\n{}\n{}\n", BLOCK_START, lines.trim_end(), BLOCK_END)
+}
+
+/// Older integration scripts had no markers; pasted into an rc file they run
+/// from their "# pkmgr <Shell> Integration" header to the "loaded" message
+fn is_legacy_start(line: &str) -> bool {
+    line.starts_with("# pkmgr ") && line.ends_with(" Integration")
+}
+
+fn is_legacy_end(line: &str) -> bool {
+    line.contains("pkmgr shell integration loaded for")
+}
+
+fn is_block_end(line: &str) -> bool {
+    line == BLOCK_END
+}
+
+/// `content` without its pkmgr-managed blocks (and pasted integration
+/// scripts from before the markers), without stray one-line `pkmgr shell
+/// load`/`add` invocations, plus the lines that were removed. An
+/// unterminated block is an error rather than a truncated file
+pub fn strip_managed(content: &str) -> Result<(String, Vec<String>)> {
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+    let mut block_end: Option<fn(&str) -> bool> = None;
+
+    for (number, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        let closed_later = |is_end: fn(&str) -> bool| content.lines().skip(number + 1).any(|rest| is_end(rest.trim()));
+        if let Some(is_end) = block_end {
+            removed.push(line.to_string());
+            if is_end(trimmed) {
+                block_end = None;
             }
+        } else if trimmed == BLOCK_START {
+            removed.push(line.to_string());
+            block_end = Some(is_block_end);
+            if !closed_later(is_block_end) {
+                anyhow::bail!("pkmgr block starting on line {} has no closing '{}'", number + 1, BLOCK_END);
+            }
+        } else if is_legacy_start(trimmed) && closed_later(is_legacy_end) {
+            removed.push(line.to_string());
+            block_end = Some(is_legacy_end);
+        } else if !trimmed.starts_with('#')
+            && (trimmed.contains("pkmgr shell load") || trimmed.contains("pkmgr shell add"))
+        {
+            removed.push(line.to_string());
+        } else {
+            kept.push(line);
         }
     }
-}
\ No newline at end of file
+
+    let mut stripped = kept.join("\n");
+    if content.ends_with('\n') && !stripped.is_empty() {
+        stripped.push('\n');
+    }
+    Ok((stripped, removed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn managed_blocks_and_load_lines_are_stripped() {
+        let rc = format!(
+            "export EDITOR=vim\n{}eval \"$(pkmgr shell add)\"\n# pkmgr shell load is documented here\nalias ll='ls -l'\n",
+            managed_block("eval \"$(pkmgr shell load)\"")
+        );
+        let (stripped, removed) = strip_managed(&rc).unwrap();
+        assert_eq!(stripped, "export EDITOR=vim\n# pkmgr shell load is documented here\nalias ll='ls -l'\n");
+        assert_eq!(removed.len(), 4);
+
+        let unterminated = format!("{}\neval \"$(pkmgr shell load)\"\nalias ll='ls -l'\n", BLOCK_START);
+        assert!(strip_managed(&unterminated).is_err());
+    }
+
+    #[test]
+    fn pasted_integration_scripts_are_stripped() {
+        let bash = ShellIntegration::new(ShellType::Bash, Output::new("never".to_string(), false));
+        let rc = format!("export EDITOR=vim\n{}alias ll='ls -l'\n", bash.generate_script());
+        assert_eq!(strip_managed(&rc).unwrap().0, "export EDITOR=vim\nalias ll='ls -l'\n");
+
+        let legacy = format!("export EDITOR=vim\n{}alias ll='ls -l'\n", bash.bash_script().trim_start());
+        assert_eq!(strip_managed(&legacy).unwrap().0, "export EDITOR=vim\nalias ll='ls -l'\n");
+    }
+
+    #[test]
+    fn fish_removal_filters_the_path_list() {
+        let fish = ShellIntegration::new(ShellType::Fish, Output::new("never".to_string(), false));
+        let script = fish.generate_path_script(false);
+        let local_bin = dirs::home_dir().unwrap().join(".local").join("bin");
+        assert_eq!(script, format!("set -gx PATH (string match -v -- '{}' $PATH)\n", local_bin.display()));
+
+        // Parse-check with fish itself when it is installed
+        if let Ok(status) = std::process::Command::new("fish").args(["--no-execute", "-c", &script]).status() {
+            assert!(status.success(), "fish rejected: {}", script);
+        }
+    }
+
+    #[test]
+    fn powershell_shortcuts_are_functions() {
+        let powershell = ShellIntegration::new(ShellType::PowerShell, Output::new("never".to_string(), false));
+        let script = powershell.generate_script();
+        assert!(script.contains("function pki { & pkmgr install @args }"));
+        assert!(!script.contains("Set-Alias pk"));
+    }
+
+    #[test]
+    fn old_nushell_gets_let_env() {
+        let script = "$env.PKMGR_SHELL = \"nushell\"\ndef pip [...args] { ^pkmgr python -m pip ...$args }\n";
+        assert_eq!(
+            legacy_nushell(script),
+            "let-env PKMGR_SHELL = \"nushell\"\ndef pip [...args] { ^pkmgr python -m pip ...$args }\n"
+        );
+    }
+}
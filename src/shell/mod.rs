@@ -118,42 +118,42 @@ impl ShellType {
         }
     }
 
-    /// Get completion directory for shell
+    /// Get the per-user completion directory for shell
     pub fn completion_dir(&self) -> Option<String> {
         let home = dirs::home_dir()?;
         let home_str = home.to_string_lossy();
 
         match self {
+            // bash-completion loads these on demand
             ShellType::Bash => {
-                // Try common completion directories
-                for dir in &[
-                    "/usr/share/bash-completion/completions",
-                    "/etc/bash_completion.d",
-                    &format!("{}/.local/share/bash-completion/completions", home_str),
-                ] {
-                    if std::path::Path::new(dir).exists() {
-                        return Some(dir.to_string());
-                    }
-                }
-                None
-            }
-            ShellType::Zsh => {
-                // Zsh uses fpath
-                for dir in &[
-                    "/usr/share/zsh/site-functions",
-                    "/usr/local/share/zsh/site-functions",
-                    &format!("{}/.zsh/completions", home_str),
-                ] {
-                    if std::path::Path::new(dir).exists() {
-                        return Some(dir.to_string());
-                    }
-                }
-                None
+                let data_home = env::var("XDG_DATA_HOME")
+                    .unwrap_or_else(|_| format!("{}/.local/share", home_str));
+                Some(format!("{}/bash-completion/completions", data_home))
             }
+            // Needs to be on fpath before compinit
+            ShellType::Zsh => Some(format!("{}/.zsh/completions", home_str)),
             ShellType::Fish => Some(format!("{}/.config/fish/completions", home_str)),
-            ShellType::PowerShell => None, // PowerShell uses module system
-            ShellType::Nushell => None, // Nushell has different completion system
+            // Both are sourced from the shell profile
+            ShellType::PowerShell => Some(format!("{}/.config/powershell/completions", home_str)),
+            ShellType::Nushell => Some(format!("{}/.config/nushell/completions", home_str)),
             ShellType::Unknown => None,
         }
     }
+
+    /// Completion file name the shell expects
+    pub fn completion_file_name(&self) -> &'static str {
+        match self {
+            ShellType::Zsh => "_pkmgr",
+            ShellType::Fish => "pkmgr.fish",
+            ShellType::PowerShell => "pkmgr.ps1",
+            ShellType::Nushell => "pkmgr.nu",
+            ShellType::Bash | ShellType::Unknown => "pkmgr",
+        }
+    }
+
+    /// Where `pkmgr shell completions --install` writes completions
+    pub fn completion_file(&self) -> Option<std::path::PathBuf> {
+        self.completion_dir()
+            .map(|dir| std::path::PathBuf::from(dir).join(self.completion_file_name()))
+    }
 }
\ No newline at end of file
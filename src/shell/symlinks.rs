@@ -1,9 +1,89 @@
 use anyhow::{Context, Result, bail};
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::os::unix::fs::symlink;
+use crate::core::config::Config;
 use crate::ui::output::Output;
 
+/// Language commands pkmgr wraps by linking them to itself, with the emoji
+/// shown for each
+pub const WRAPPED_COMMANDS: &[(&str, &str)] = &[
+    // Python
+    ("python", "🐍"),
+    ("python3", "🐍"),
+    ("pip", "🐍"),
+    ("pip3", "🐍"),
+
+    // Node.js
+    ("node", "📦"),
+    ("npm", "📦"),
+    ("npx", "📦"),
+    ("yarn", "📦"),
+
+    // Ruby
+    ("ruby", "💎"),
+    ("gem", "💎"),
+    ("bundle", "💎"),
+    ("irb", "💎"),
+
+    // Rust
+    ("cargo", "🦀"),
+    ("rustc", "🦀"),
+    ("rustup", "🦀"),
+
+    // Go
+    ("go", "🐹"),
+    ("gofmt", "🐹"),
+
+    // Java
+    ("java", "☕"),
+    ("javac", "☕"),
+    ("jar", "☕"),
+
+    // .NET
+    ("dotnet", "🔷"),
+
+    // PHP
+    ("php", "🐘"),
+    ("composer", "🐘"),
+];
+
+/// `~/.local/bin`, where wrappers go unless an install directory is given
+pub fn default_wrapper_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".local/bin"))
+}
+
+/// Where wrappers are linked for this config: `bin` under the configured
+/// install directory (`~/.local/bin` by default)
+pub fn wrapper_dir(config: &Config) -> Option<PathBuf> {
+    config.get_install_dir().ok().map(|dir| dir.join("bin")).or_else(default_wrapper_dir)
+}
+
+/// Whether `path` is a symlink to a pkmgr binary, as setup_symlinks makes
+pub fn is_pkmgr_wrapper(path: &Path) -> bool {
+    path.is_symlink()
+        && path.canonicalize().is_ok_and(|target| target.file_stem() == Some(OsStr::new("pkmgr")))
+}
+
+/// Wrapped commands that `path` resolves to something other than their
+/// wrapper in `wrapper_dir`, with the binary found first. Commands whose
+/// file there is not a pkmgr wrapper are skipped
+pub fn shadowed_wrappers(path: &OsStr, wrapper_dir: &Path) -> Vec<(String, PathBuf)> {
+    let same_dir = |dir: &Path| {
+        dir == wrapper_dir || matches!((dir.canonicalize(), wrapper_dir.canonicalize()), (Ok(a), Ok(b)) if a == b)
+    };
+    let dirs: Vec<PathBuf> = std::env::split_paths(path).collect();
+
+    WRAPPED_COMMANDS.iter()
+        .filter(|(command, _)| is_pkmgr_wrapper(&wrapper_dir.join(command)))
+        .filter_map(|(command, _)| {
+            let dir = dirs.iter().find(|dir| dir.join(command).is_file())?;
+            (!same_dir(dir)).then(|| (command.to_string(), dir.join(command)))
+        })
+        .collect()
+}
+
 /// Language command symlinks manager
 pub struct SymlinkManager {
     output: Output,
@@ -25,52 +105,10 @@ impl SymlinkManager {
 
         self.output.section("🔗 Setting up language command symlinks");
 
-        // Define all the symlinks to create according to CLAUDE.md spec
-        let symlinks = vec![
-            // Python
-            ("python", "🐍"),
-            ("python3", "🐍"),
-            ("pip", "🐍"),
-            ("pip3", "🐍"),
-
-            // Node.js
-            ("node", "📦"),
-            ("npm", "📦"),
-            ("npx", "📦"),
-            ("yarn", "📦"),
-
-            // Ruby
-            ("ruby", "💎"),
-            ("gem", "💎"),
-            ("bundle", "💎"),
-            ("irb", "💎"),
-
-            // Rust
-            ("cargo", "🦀"),
-            ("rustc", "🦀"),
-            ("rustup", "🦀"),
-
-            // Go
-            ("go", "🐹"),
-            ("gofmt", "🐹"),
-
-            // Java
-            ("java", "☕"),
-            ("javac", "☕"),
-            ("jar", "☕"),
-
-            // .NET
-            ("dotnet", "🔷"),
-
-            // PHP
-            ("php", "🐘"),
-            ("composer", "🐘"),
-        ];
-
         let mut created_count = 0;
         let mut skipped_count = 0;
 
-        for (command, emoji) in symlinks {
+        for &(command, emoji) in WRAPPED_COMMANDS {
             let symlink_path = symlink_dir.join(command);
 
             // Check if symlink already exists and points to our binary
@@ -193,10 +231,9 @@ impl SymlinkManager {
         }
 
         // Use ~/.local/bin as default (per CLAUDE.md spec)
-        if let Some(home) = dirs::home_dir() {
-            Ok(home.join(".local/bin"))
-        } else {
-            bail!("Could not determine home directory")
+        match default_wrapper_dir() {
+            Some(dir) => Ok(dir),
+            None => bail!("Could not determine home directory"),
         }
     }
 
@@ -260,4 +297,38 @@ impl SymlinkManager {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrappers_behind_a_system_binary_are_shadowed() {
+        let root = tempfile::tempdir().unwrap();
+        let (system, wrappers) = (root.path().join("usr-bin"), root.path().join("local-bin"));
+        fs::create_dir_all(&system).unwrap();
+        fs::create_dir_all(&wrappers).unwrap();
+        let pkmgr = root.path().join("pkmgr");
+        fs::write(&pkmgr, "").unwrap();
+        for command in ["python", "node", "go"] {
+            fs::write(system.join(command), "").unwrap();
+            symlink(&pkmgr, wrappers.join(command)).unwrap();
+        }
+        fs::write(system.join("ruby"), "").unwrap();
+        // The user's own binary, not a wrapper
+        fs::write(system.join("java"), "").unwrap();
+        fs::write(wrappers.join("java"), "").unwrap();
+
+        let path = std::env::join_paths([&system, &wrappers]).unwrap();
+        let shadowed = shadowed_wrappers(&path, &wrappers);
+        assert_eq!(shadowed, vec![
+            ("python".to_string(), system.join("python")),
+            ("node".to_string(), system.join("node")),
+            ("go".to_string(), system.join("go")),
+        ]);
+
+        let path = std::env::join_paths([&wrappers, &system]).unwrap();
+        assert!(shadowed_wrappers(&path, &wrappers).is_empty());
+    }
+}
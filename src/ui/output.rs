@@ -1,5 +1,116 @@
 use console::{style, Color, Term};
+use std::borrow::Cow;
 use std::io::{self, Write};
+use crate::core::config::EmojiSetting;
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+/// Decide whether to color output. Precedence: an explicit "always" or
+/// "never" setting, then NO_COLOR, then whether stdout is a color-capable TTY
+pub fn use_color(setting: &str, no_color: Option<&str>, is_tty: bool, term: Option<&str>) -> bool {
+    match setting {
+        "always" => return true,
+        "never" => return false,
+        _ => {}
+    }
+
+    // https://no-color.org: any non-empty value disables color
+    if no_color.is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+
+    is_tty && term != Some("dumb")
+}
+
+fn detect_color(setting: &str, term: &Term) -> bool {
+    let enabled = use_color(
+        setting,
+        std::env::var("NO_COLOR").ok().as_deref(),
+        term.is_term(),
+        std::env::var("TERM").ok().as_deref(),
+    );
+    // Keep console's own styling in step with the decision
+    console::set_colors_enabled(enabled);
+    enabled
+}
+
+/// Decide whether to print emoji. An explicit setting wins; "auto" needs a
+/// UTF-8 locale (first set of LC_ALL, LC_CTYPE, LANG) and a terminal that
+/// is not dumb
+pub fn use_emoji(setting: EmojiSetting, locale: Option<&str>, term: Option<&str>) -> bool {
+    match setting {
+        EmojiSetting::Always => true,
+        EmojiSetting::Never => false,
+        EmojiSetting::Auto => {
+            let utf8 = locale.is_some_and(|locale| {
+                let locale = locale.to_lowercase();
+                locale.contains("utf-8") || locale.contains("utf8")
+            });
+            utf8 && term != Some("dumb")
+        }
+    }
+}
+
+pub fn detect_emoji(setting: EmojiSetting) -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty());
+    // Windows consoles set no locale variables but render emoji
+    if locale.is_none() && cfg!(windows) {
+        return setting != EmojiSetting::Never;
+    }
+    use_emoji(setting, locale.as_deref(), std::env::var("TERM").ok().as_deref())
+}
+
+/// Whether `c` is drawn as an emoji (or is a joiner/selector inside one);
+/// arrows and box drawing are left alone
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF
+        | 0x2600..=0x27BF
+        | 0x2300..=0x23FF
+        | 0x2B00..=0x2BFF
+        | 0x2139
+        | 0x200D
+        | 0xFE0F
+    )
+}
+
+/// `message` with emoji removed, along with the space that followed each
+pub fn strip_emoji(message: &str) -> String {
+    let mut plain = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_emoji(c) {
+            plain.push(c);
+            continue;
+        }
+        while chars.peek().is_some_and(|&next| is_emoji(next)) {
+            chars.next();
+        }
+        while chars.peek() == Some(&' ') {
+            chars.next();
+        }
+    }
+    plain
+}
+
+/// Where output ends up: the terminal, or buffers tests can read back
+#[derive(Clone, Default)]
+enum Sink {
+    #[default]
+    Terminal,
+    #[cfg(test)]
+    Capture(Arc<Mutex<Captured>>),
+}
+
+#[cfg(test)]
+#[derive(Default)]
+struct Captured {
+    stdout: String,
+    stderr: String,
+}
 
 #[derive(Clone)]
 pub struct Output {
@@ -7,114 +118,230 @@ pub struct Output {
     pub color_enabled: bool,
     pub emoji_enabled: bool,
     pub verbose: bool,
+    /// Only warnings, errors and requested data (tables, lists, `print`)
+    pub quiet: bool,
+    sink: Sink,
 }
 
 impl Output {
     pub fn new(color_output: String, emoji_enabled: bool) -> Self {
         let term = Term::stdout();
-        let color_enabled = match color_output.as_str() {
-            "always" => true,
-            "never" => false,
-            "auto" | _ => term.features().colors_supported(),
-        };
+        let color_enabled = detect_color(&color_output, &term);
 
         Self {
             term,
             color_enabled,
             emoji_enabled,
             verbose: false,
+            quiet: false,
+            sink: Sink::Terminal,
         }
     }
 
     pub fn with_verbose(color_output: String, emoji_enabled: bool, verbose: bool) -> Self {
         let term = Term::stdout();
-        let color_enabled = match color_output.as_str() {
-            "always" => true,
-            "never" => false,
-            "auto" | _ => term.features().colors_supported(),
-        };
+        let color_enabled = detect_color(&color_output, &term);
 
         Self {
             term,
             color_enabled,
             emoji_enabled,
             verbose,
+            quiet: false,
+            sink: Sink::Terminal,
+        }
+    }
+
+    /// Plain output (no color or emoji) collected in memory instead of
+    /// printed; read it back with `captured_stdout`/`captured_stderr`
+    #[cfg(test)]
+    pub fn capture() -> Self {
+        Self {
+            term: Term::stdout(),
+            color_enabled: false,
+            emoji_enabled: false,
+            verbose: false,
+            quiet: false,
+            sink: Sink::Capture(Arc::default()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn captured_stdout(&self) -> String {
+        match &self.sink {
+            Sink::Capture(captured) => captured.lock().unwrap().stdout.clone(),
+            Sink::Terminal => String::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn captured_stderr(&self) -> String {
+        match &self.sink {
+            Sink::Capture(captured) => captured.lock().unwrap().stderr.clone(),
+            Sink::Terminal => String::new(),
+        }
+    }
+
+    /// Write to stdout without a trailing newline
+    pub fn write(&self, text: &str) {
+        match &self.sink {
+            Sink::Terminal => {
+                print!("{}", text);
+                let _ = io::stdout().flush();
+            }
+            #[cfg(test)]
+            Sink::Capture(captured) => captured.lock().unwrap().stdout.push_str(text),
+        }
+    }
+
+    fn out(&self, line: String) {
+        match &self.sink {
+            Sink::Terminal => println!("{}", line),
+            #[cfg(test)]
+            Sink::Capture(captured) => {
+                let mut captured = captured.lock().unwrap();
+                captured.stdout.push_str(&line);
+                captured.stdout.push('\n');
+            }
         }
     }
 
+    fn err(&self, line: String) {
+        match &self.sink {
+            Sink::Terminal => eprintln!("{}", line),
+            #[cfg(test)]
+            Sink::Capture(captured) => {
+                let mut captured = captured.lock().unwrap();
+                captured.stderr.push_str(&line);
+                captured.stderr.push('\n');
+            }
+        }
+    }
+
+    /// Apply --verbose/--quiet; quiet wins when both are given
+    pub fn with_verbosity(mut self, verbose: bool, quiet: bool) -> Self {
+        self.verbose = verbose && !quiet;
+        self.quiet = quiet;
+        self
+    }
+
     pub fn success(&self, message: &str) {
+        if self.quiet {
+            return;
+        }
+        let message = self.plain(message);
         let prefix = if self.emoji_enabled { "✅" } else { "[OK]" };
         if self.color_enabled {
-            println!("{} {}", prefix, style(message).green());
+            self.out(format!("{} {}", prefix, style(message).green()));
         } else {
-            println!("{} {}", prefix, message);
+            self.out(format!("{} {}", prefix, message));
         }
     }
 
     pub fn error(&self, message: &str) {
-        let prefix = if self.emoji_enabled { "❌" } else { "[ERROR]" };
+        let message = self.plain(message);
+        let prefix = if self.emoji_enabled { "❌" } else { "[ERR]" };
         if self.color_enabled {
-            eprintln!("{} {}", prefix, style(message).red());
+            self.err(format!("{} {}", prefix, style(message).red()));
         } else {
-            eprintln!("{} {}", prefix, message);
+            self.err(format!("{} {}", prefix, message));
         }
     }
 
     pub fn warn(&self, message: &str) {
+        let message = self.plain(message);
         let prefix = if self.emoji_enabled { "⚠️" } else { "[WARN]" };
         if self.color_enabled {
-            println!("{} {}", prefix, style(message).yellow());
+            self.out(format!("{} {}", prefix, style(message).yellow()));
         } else {
-            println!("{} {}", prefix, message);
+            self.out(format!("{} {}", prefix, message));
         }
     }
 
     pub fn info(&self, message: &str) {
+        if self.quiet {
+            return;
+        }
+        let message = self.plain(message);
         let prefix = if self.emoji_enabled { "ℹ️" } else { "[INFO]" };
         if self.color_enabled {
-            println!("{} {}", prefix, style(message).blue());
+            self.out(format!("{} {}", prefix, style(message).blue()));
         } else {
-            println!("{} {}", prefix, message);
+            self.out(format!("{} {}", prefix, message));
         }
     }
 
     pub fn progress(&self, message: &str) {
+        if self.quiet {
+            return;
+        }
+        let message = self.plain(message);
         let prefix = if self.emoji_enabled { "⏳" } else { "[-]" };
         if self.color_enabled {
-            println!("{} {}", prefix, style(message).cyan());
+            self.out(format!("{} {}", prefix, style(message).cyan()));
         } else {
-            println!("{} {}", prefix, message);
+            self.out(format!("{} {}", prefix, message));
         }
     }
 
     pub fn step(&self, message: &str) {
+        if self.quiet {
+            return;
+        }
+        let message = self.plain(message);
         let prefix = if self.emoji_enabled { "🔄" } else { "[>]" };
         if self.color_enabled {
-            println!("{} {}", prefix, style(message).magenta());
+            self.out(format!("{} {}", prefix, style(message).magenta()));
         } else {
-            println!("{} {}", prefix, message);
+            self.out(format!("{} {}", prefix, message));
         }
     }
 
     pub fn print(&self, message: &str) {
-        println!("{}", message);
+        self.out(message.to_string());
+    }
+
+    /// Informational line on stderr, for commands whose stdout is eval'd
+    pub fn note(&self, message: &str) {
+        if !self.quiet {
+            self.err(self.plain(message).into_owned());
+        }
+    }
+
+    /// Message text as it should be shown: emoji are dropped when disabled
+    fn plain<'a>(&self, message: &'a str) -> Cow<'a, str> {
+        if self.emoji_enabled {
+            Cow::Borrowed(message)
+        } else {
+            Cow::Owned(strip_emoji(message))
+        }
     }
 
     pub fn print_header(&self, title: &str) {
+        if self.quiet {
+            return;
+        }
+        let title = self.plain(title);
+        let title = title.as_ref();
         if self.color_enabled {
-            println!("\n{}", style(title).bold().underlined());
+            self.out(format!("\n{}", style(title).bold().underlined()));
         } else {
-            println!("\n{}", title);
-            println!("{}", "=".repeat(title.len()));
+            self.out(format!("\n{}", title));
+            self.out("=".repeat(title.len()));
         }
     }
 
     pub fn print_section(&self, title: &str) {
+        if self.quiet {
+            return;
+        }
+        let title = self.plain(title);
+        let title = title.as_ref();
         if self.color_enabled {
-            println!("\n{}", style(title).bold());
+            self.out(format!("\n{}", style(title).bold()));
         } else {
-            println!("\n{}", title);
-            println!("{}", "-".repeat(title.len()));
+            self.out(format!("\n{}", title));
+            self.out("-".repeat(title.len()));
         }
     }
 
@@ -133,72 +360,49 @@ impl Output {
             }
         }
 
-        // Print header
-        print!("┏");
-        for (i, width) in widths.iter().enumerate() {
-            print!("{}", "━".repeat(width + 2));
-            if i < widths.len() - 1 {
-                print!("┳");
-            }
-        }
-        println!("┓");
+        let border = |left: &str, join: &str, right: &str| {
+            let segments: Vec<String> = widths.iter().map(|width| "━".repeat(width + 2)).collect();
+            format!("{}{}{}", left, segments.join(join), right)
+        };
 
-        print!("┃");
-        for (i, (header, width)) in headers.iter().zip(widths.iter()).enumerate() {
-            if self.color_enabled {
-                print!(" {} ", style(format!("{:width$}", header, width = width)).bold());
-            } else {
-                print!(" {:width$} ", header, width = width);
-            }
-            if i < headers.len() - 1 {
-                print!("┃");
-            }
-        }
-        println!("┃");
+        // Print header
+        self.out(border("┏", "┳", "┓"));
+
+        let header_cells: Vec<String> = headers.iter().zip(widths.iter())
+            .map(|(header, width)| {
+                if self.color_enabled {
+                    format!(" {} ", style(format!("{:width$}", header, width = width)).bold())
+                } else {
+                    format!(" {:width$} ", header, width = width)
+                }
+            })
+            .collect();
+        self.out(format!("┃{}┃", header_cells.join("┃")));
 
         // Print separator
-        print!("┣");
-        for (i, width) in widths.iter().enumerate() {
-            print!("{}", "━".repeat(width + 2));
-            if i < widths.len() - 1 {
-                print!("╋");
-            }
-        }
-        println!("┫");
+        self.out(border("┣", "╋", "┫"));
 
         // Print rows
         for row in rows {
-            print!("┃");
-            for (i, (cell, width)) in row.iter().zip(widths.iter()).enumerate() {
-                print!(" {:width$} ", cell, width = width);
-                if i < row.len() - 1 {
-                    print!("┃");
-                }
-            }
-            println!("┃");
+            let cells: Vec<String> = row.iter().zip(widths.iter())
+                .map(|(cell, width)| format!(" {:width$} ", cell, width = width))
+                .collect();
+            self.out(format!("┃{}┃", cells.join("┃")));
         }
 
         // Print bottom border
-        print!("┗");
-        for (i, width) in widths.iter().enumerate() {
-            print!("{}", "━".repeat(width + 2));
-            if i < widths.len() - 1 {
-                print!("┻");
-            }
-        }
-        println!("┛");
+        self.out(border("┗", "┻", "┛"));
     }
 
     pub fn print_list(&self, items: &[String]) {
         for item in items {
             let bullet = if self.emoji_enabled { "📦" } else { "•" };
-            println!("  {} {}", bullet, item);
+            self.out(format!("  {} {}", bullet, item));
         }
     }
 
     pub fn clear_line(&self) {
-        print!("\r\x1B[2K");
-        io::stdout().flush().unwrap();
+        self.write("\r\x1B[2K");
     }
 
     pub fn is_tty(&self) -> bool {
@@ -260,12 +464,78 @@ impl Output {
 
     pub fn debug(&self, message: &str) {
         if self.verbose {
+            let message = self.plain(message);
             let prefix = if self.emoji_enabled { "🐛" } else { "[DEBUG]" };
             if self.color_enabled {
-                println!("{} {}", prefix, style(message).dim());
+                self.out(format!("{} {}", prefix, style(message).dim()));
             } else {
-                println!("{} {}", prefix, message);
+                self.out(format!("{} {}", prefix, message));
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_setting_wins() {
+        assert!(use_color("always", Some("1"), false, Some("dumb")));
+        assert!(!use_color("never", None, true, Some("xterm-256color")));
+    }
+
+    #[test]
+    fn no_color_disables_auto() {
+        assert!(!use_color("auto", Some("1"), true, Some("xterm-256color")));
+        assert!(use_color("auto", Some(""), true, Some("xterm-256color")));
+    }
+
+    #[test]
+    fn auto_follows_tty() {
+        assert!(use_color("auto", None, true, Some("xterm-256color")));
+        assert!(!use_color("auto", None, false, Some("xterm-256color")));
+        assert!(!use_color("auto", None, true, Some("dumb")));
+    }
+
+    #[test]
+    fn quiet_keeps_errors_and_data() {
+        let output = Output::capture().with_verbosity(false, true);
+        output.print_header("Installed Packages");
+        output.info("Listing packages...");
+        output.success("Done");
+        output.print_table(&["Name", "Version"], &[vec!["git".to_string(), "2.43.0".to_string()]]);
+        output.error("Failed");
+
+        assert_eq!(output.captured_stdout(), "\
+┏━━━━━━┳━━━━━━━━━┓
+┃ Name ┃ Version ┃
+┣━━━━━━╋━━━━━━━━━┫
+┃ git  ┃ 2.43.0  ┃
+┗━━━━━━┻━━━━━━━━━┛
+");
+        assert_eq!(output.captured_stderr(), "[ERR] Failed\n");
+    }
+
+    #[test]
+    fn auto_emoji_needs_utf8_locale_and_capable_terminal() {
+        assert!(use_emoji(EmojiSetting::Auto, Some("en_US.UTF-8"), Some("xterm-256color")));
+        assert!(use_emoji(EmojiSetting::Auto, Some("C.utf8"), None));
+        assert!(!use_emoji(EmojiSetting::Auto, Some("C"), Some("xterm-256color")));
+        assert!(!use_emoji(EmojiSetting::Auto, None, Some("xterm-256color")));
+        assert!(!use_emoji(EmojiSetting::Auto, Some("en_US.UTF-8"), Some("dumb")));
+        assert!(use_emoji(EmojiSetting::Always, Some("C"), Some("dumb")));
+        assert!(!use_emoji(EmojiSetting::Never, Some("en_US.UTF-8"), None));
+    }
+
+    #[test]
+    fn emoji_are_stripped_from_messages_when_disabled() {
+        assert_eq!(strip_emoji("🔍 Checking for updates"), "Checking for updates");
+        assert_eq!(strip_emoji("  ⏭️  Skipped"), "  Skipped");
+        assert_eq!(strip_emoji("New version: 1.0 → 1.1"), "New version: 1.0 → 1.1");
+
+        let output = Output::capture();
+        output.info("📥 Downloading from: https://example.com");
+        assert_eq!(output.captured_stdout(), "[INFO] Downloading from: https://example.com\n");
+    }
+}
@@ -1,6 +1,16 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use dialoguer::{Confirm, Input, MultiSelect, Select};
 
+/// Whether prompts can be answered. PKMGR_NONINTERACTIVE=1 forces prompts
+/// off and PKMGR_NONINTERACTIVE=0 forces them on when TTY detection is wrong
+pub fn is_interactive() -> bool {
+    match std::env::var("PKMGR_NONINTERACTIVE").ok().as_deref().map(str::trim) {
+        Some("1") | Some("true") | Some("yes") => false,
+        Some("0") | Some("false") | Some("no") => true,
+        _ => atty::is(atty::Stream::Stdin),
+    }
+}
+
 pub struct Prompt {
     emoji_enabled: bool,
 }
@@ -10,7 +20,14 @@ impl Prompt {
         Self { emoji_enabled }
     }
 
+    /// Declines without prompting in non-interactive sessions; callers skip
+    /// the prompt entirely when --yes is given
     pub fn confirm(&self, message: &str) -> Result<bool> {
+        if !is_interactive() {
+            self.refuse(message);
+            return Ok(false);
+        }
+
         let prompt = if self.emoji_enabled {
             format!("❓ {}", message)
         } else {
@@ -23,7 +40,14 @@ impl Prompt {
             .interact()?)
     }
 
+    /// Defaults to yes at the prompt, but like `confirm` declines without
+    /// one: an unattended run must pass --yes to proceed
     pub fn confirm_default_yes(&self, message: &str) -> Result<bool> {
+        if !is_interactive() {
+            self.refuse(message);
+            return Ok(false);
+        }
+
         let prompt = if self.emoji_enabled {
             format!("❓ {}", message)
         } else {
@@ -37,6 +61,8 @@ impl Prompt {
     }
 
     pub fn input(&self, message: &str) -> Result<String> {
+        self.require_interactive(message)?;
+
         let prompt = if self.emoji_enabled {
             format!("📝 {}", message)
         } else {
@@ -49,6 +75,10 @@ impl Prompt {
     }
 
     pub fn input_with_default(&self, message: &str, default: &str) -> Result<String> {
+        if !is_interactive() {
+            return Ok(default.to_string());
+        }
+
         let prompt = if self.emoji_enabled {
             format!("📝 {}", message)
         } else {
@@ -65,6 +95,8 @@ impl Prompt {
     where
         T: ToString,
     {
+        self.require_interactive(message)?;
+
         let prompt = if self.emoji_enabled {
             format!("🎯 {}", message)
         } else {
@@ -82,6 +114,8 @@ impl Prompt {
     where
         T: ToString,
     {
+        self.require_interactive(message)?;
+
         let prompt = if self.emoji_enabled {
             format!("☑️ {}", message)
         } else {
@@ -95,6 +129,11 @@ impl Prompt {
     }
 
     pub fn destructive_confirm(&self, message: &str, confirmation_text: &str) -> Result<bool> {
+        if !is_interactive() {
+            self.refuse(message);
+            return Ok(false);
+        }
+
         let warning = if self.emoji_enabled {
             format!("⚠️ DESTRUCTIVE OPERATION")
         } else {
@@ -113,6 +152,11 @@ impl Prompt {
     }
 
     pub fn version_select(&self, versions: &[String], current: Option<&str>) -> Result<usize> {
+        // Latest is the default choice
+        if !is_interactive() {
+            return Ok(0);
+        }
+
         let title = if self.emoji_enabled {
             "🐍 Select Version"
         } else {
@@ -160,6 +204,8 @@ impl Prompt {
     }
 
     pub fn usb_device_select(&self, devices: &[(String, String, u64)]) -> Result<usize> {
+        self.require_interactive("Select USB device")?;
+
         let title = if self.emoji_enabled {
             "💾 USB Device Setup Wizard"
         } else {
@@ -199,4 +245,17 @@ impl Prompt {
             .default(0)
             .interact()?)
     }
-}
\ No newline at end of file
+
+    /// On stderr so a declined prompt never ends up in piped stdout
+    fn refuse(&self, message: &str) {
+        let prefix = if self.emoji_enabled { "⚠️" } else { "[WARN]" };
+        eprintln!("{} non-interactive session: declining '{}' without --yes", prefix, message);
+    }
+
+    fn require_interactive(&self, message: &str) -> Result<()> {
+        if !is_interactive() {
+            bail!("non-interactive session: cannot prompt for '{}'; set PKMGR_NONINTERACTIVE=0 to force prompts", message);
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use sha2::{Digest, Sha256};
+use crate::utils::logging::LoggedCommand;
+use crate::ui::output::Output;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UpdateBranch {
@@ -47,13 +51,12 @@ pub struct UpdateManager {
     current_version: String,
     repo_owner: String,
     repo_name: String,
+    output: Output,
 }
 
 impl UpdateManager {
-    pub fn new(current_version: String) -> Result<Self> {
-        let config_dir = dirs::config_dir()
-            .context("Could not determine config directory")?
-            .join("pkmgr");
+    pub fn new(current_version: String, output: Output) -> Result<Self> {
+        let config_dir = crate::core::config::Config::get_config_dir()?;
         
         fs::create_dir_all(&config_dir)?;
         
@@ -62,6 +65,7 @@ impl UpdateManager {
             current_version,
             repo_owner: "pkmgr".to_string(),
             repo_name: "pkmgr".to_string(),
+            output,
         })
     }
 
@@ -75,7 +79,25 @@ impl UpdateManager {
         UpdateBranch::from_str(&config.branch)
     }
 
+    /// Switch channels; beta and daily must have at least one published
+    /// release, so `check` has something to compare against
     pub fn set_branch(&self, branch: UpdateBranch) -> Result<()> {
+        if branch != UpdateBranch::Stable {
+            let latest = self.fetch_latest_version(branch)
+                .with_context(|| format!("Failed to look up {} releases", branch.as_str()))?;
+            if latest.is_none() {
+                anyhow::bail!(
+                    "No {} releases are published; staying on {}",
+                    branch.as_str(),
+                    self.get_branch()?.as_str()
+                );
+            }
+            self.output.warn(&format!(
+                "{} builds are pre-releases and may be unstable; switch back with 'pkmgr update-self branch stable'",
+                branch.as_str()
+            ));
+        }
+
         let config = UpdateConfig {
             branch: branch.as_str().to_string(),
         };
@@ -83,26 +105,26 @@ impl UpdateManager {
         let content = toml::to_string_pretty(&config)?;
         fs::write(&self.config_path, content)?;
 
-        println!("✅ Update branch set to: {}", branch.as_str());
+        self.output.success(&format!("Update branch set to: {}", branch.as_str()));
         Ok(())
     }
 
     pub fn check_for_updates(&self) -> Result<Option<String>> {
         let branch = self.get_branch()?;
         
-        println!("🔍 Checking for updates on {} branch...", branch.as_str());
+        self.output.progress(&format!("🔍 Checking for updates on {} branch...", branch.as_str()));
         
         let latest_version = self.fetch_latest_version(branch)?;
         
         if let Some(ref version) = latest_version {
             if version != &self.current_version {
-                println!("✨ New version available: {} → {}", self.current_version, version);
+                self.output.info(&format!("✨ New version available: {} → {}", self.current_version, version));
                 return Ok(Some(version.clone()));
             } else {
-                println!("✅ Already up to date ({})", self.current_version);
+                self.output.success(&format!("Already up to date ({})", self.current_version));
             }
         } else {
-            println!("ℹ️  No updates available");
+            self.output.info("No updates available");
         }
 
         Ok(latest_version)
@@ -124,12 +146,10 @@ impl UpdateManager {
             }
         };
 
-        let client = reqwest::blocking::ClientBuilder::new()
-            .user_agent("pkmgr")
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
+        let client = crate::utils::download::blocking_http_client(std::time::Duration::from_secs(30))?;
 
-        let response = client.get(&url).send()?;
+        let response = client.get(&url).send()
+            .map_err(|e| crate::utils::download::request_error(e, "check for updates"))?;
 
         if response.status() == 404 {
             return Ok(None);
@@ -189,20 +209,20 @@ impl UpdateManager {
     pub fn perform_update(&self) -> Result<()> {
         let branch = self.get_branch()?;
         
-        println!("⏳ Checking for updates on {} branch...", branch.as_str());
+        self.output.progress(&format!("Checking for updates on {} branch...", branch.as_str()));
         
         let latest_version = self.fetch_latest_version(branch)?;
         
         match latest_version {
             Some(version) if version != self.current_version => {
-                println!("📦 Downloading version {}...", version);
+                self.output.progress(&format!("📦 Downloading version {}...", version));
                 self.download_and_install(&version, branch)?;
             }
             Some(_) => {
-                println!("✅ Already up to date ({})", self.current_version);
+                self.output.success(&format!("Already up to date ({})", self.current_version));
             }
             None => {
-                println!("ℹ️  No updates available");
+                self.output.info("No updates available");
             }
         }
 
@@ -224,52 +244,334 @@ impl UpdateManager {
             format!("pkmgr-{}-{}", os, arch)
         };
 
-        let download_url = format!(
-            "https://github.com/{}/{}/releases/download/{}/{}",
-            self.repo_owner, self.repo_name, tag, binary_name
+        let release_url = format!(
+            "https://github.com/{}/{}/releases/download/{}",
+            self.repo_owner, self.repo_name, tag
         );
+        let download_url = format!("{}/{}", release_url, binary_name);
 
-        let client = reqwest::blocking::ClientBuilder::new()
-            .user_agent("pkmgr")
-            .timeout(std::time::Duration::from_secs(300))
-            .build()?;
+        let client = crate::utils::download::blocking_http_client(std::time::Duration::from_secs(300))?;
 
-        println!("📥 Downloading from: {}", download_url);
+        self.output.info(&format!("📥 Downloading from: {}", download_url));
         
-        let response = client.get(&download_url).send()?;
+        let response = client.get(&download_url).send()
+            .map_err(|e| crate::utils::download::request_error(e, "download the update"))?;
         response.error_for_status_ref()?;
 
         let bytes = response.bytes()?;
+
+        self.output.progress("🔍 Verifying download...");
+        let sums = fetch_asset(&client, &release_url, CHECKSUMS_ASSET)?
+            .with_context(|| format!(
+                "Release {} publishes no {}; refusing to install an unverified binary",
+                tag, CHECKSUMS_ASSET
+            ))?;
+        let sums = String::from_utf8(sums).context("Checksums file is not text")?;
+        verify_checksum(&sums, &binary_name, &bytes)?;
+        self.verify_checksums_signature(&client, &release_url, &sums)?;
+        check_binary_target(&bytes, os, arch)?;
+        self.output.success("Checksum and binary format verified");
         
         let current_exe = std::env::current_exe()?;
-        let backup_path = current_exe.with_extension("bak");
-        
-        println!("💾 Creating backup...");
-        fs::copy(&current_exe, &backup_path)?;
 
-        println!("✨ Installing new version...");
-        
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let temp_path = current_exe.with_extension("new");
-            fs::write(&temp_path, &bytes)?;
-            let mut perms = fs::metadata(&current_exe)?.permissions();
-            perms.set_mode(0o755);
-            fs::set_permissions(&temp_path, perms)?;
-            fs::rename(&temp_path, &current_exe)?;
+        self.output.progress("✨ Installing new version...");
+        swap_binary(&current_exe, &bytes, check_runs)?;
+
+        self.output.success(&format!("Update complete! Version {} installed", version));
+        self.output.info("💡 Restart pkmgr to use the new version");
+
+        Ok(())
+    }
+
+    /// Check the detached signature over the checksums file with gpgv
+    /// against the embedded release key only, never the user's keyring; a
+    /// bad signature refuses the update, while an unsigned release, a build
+    /// without an embedded key or a missing gpgv leave the checksum as the
+    /// only check, with a warning
+    fn verify_checksums_signature(
+        &self,
+        client: &reqwest::blocking::Client,
+        release_url: &str,
+        sums: &str,
+    ) -> Result<()> {
+        let Some(keyring) = RELEASE_KEYRING else {
+            self.output.warn("This build of pkmgr has no embedded release key, so the release signature cannot be checked; the download is verified by checksum only");
+            return Ok(());
+        };
+        let mut signature = None;
+        for asset in SIGNATURE_ASSETS {
+            if let Some(bytes) = fetch_asset(client, release_url, asset)? {
+                signature = Some(bytes);
+                break;
+            }
+        }
+        let Some(signature) = signature else {
+            self.output.warn(&format!("Release has no signature for {}; the download is verified by checksum only", CHECKSUMS_ASSET));
+            return Ok(());
+        };
+        if which::which("gpgv").is_err() {
+            self.output.warn("gpgv is not installed, so the release signature cannot be checked; the download is verified by checksum only (install gnupg to check it)");
+            return Ok(());
         }
 
-        #[cfg(windows)]
-        {
-            let temp_path = current_exe.with_extension("new");
-            fs::write(&temp_path, &bytes)?;
-            fs::rename(&temp_path, &current_exe)?;
+        let dir = tempfile::tempdir()?;
+        let keyring_path = dir.path().join("release.gpg");
+        let sums_path = dir.path().join(CHECKSUMS_ASSET);
+        let signature_path = dir.path().join(format!("{}.sig", CHECKSUMS_ASSET));
+        fs::write(&keyring_path, keyring)?;
+        fs::write(&sums_path, sums)?;
+        fs::write(&signature_path, signature)?;
+
+        let status = Command::new("gpgv")
+            .arg("--keyring")
+            .arg(&keyring_path)
+            .arg(&signature_path)
+            .arg(&sums_path)
+            .logged_output()
+            .context("Failed to run gpgv")?;
+        if !status.status.success() {
+            anyhow::bail!(
+                "Signature on {} did not verify; refusing to install:\n{}",
+                CHECKSUMS_ASSET,
+                String::from_utf8_lossy(&status.stderr).trim()
+            );
         }
+        self.output.success("Release signature verified");
+        Ok(())
+    }
+}
 
-        println!("✅ Update complete! Version {} installed", version);
-        println!("💡 Restart pkmgr to use the new version");
+/// Checksums published with every release (see the release workflow)
+const CHECKSUMS_ASSET: &str = "SHA256SUMS.txt";
+const SIGNATURE_ASSETS: &[&str] = &["SHA256SUMS.txt.asc", "SHA256SUMS.txt.sig"];
+
+/// Public key that signs releases, embedded by build.rs
+#[cfg(release_keyring)]
+const RELEASE_KEYRING: Option<&[u8]> = Some(include_bytes!(env!("PKMGR_RELEASE_KEYRING")));
+#[cfg(not(release_keyring))]
+const RELEASE_KEYRING: Option<&[u8]> = None;
+
+/// Body of a release asset, or None when the release does not have it
+fn fetch_asset(client: &reqwest::blocking::Client, release_url: &str, asset: &str) -> Result<Option<Vec<u8>>> {
+    let response = client.get(format!("{}/{}", release_url, asset)).send()
+        .map_err(|e| crate::utils::download::request_error(e, &format!("download {}", asset)))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    response.error_for_status_ref()?;
+    Ok(Some(response.bytes()?.to_vec()))
+}
 
-        Ok(())
+/// Compare `bytes` against the `sha256sum` line for `asset`
+fn verify_checksum(sums: &str, asset: &str, bytes: &[u8]) -> Result<()> {
+    let expected = sums.lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?, parts.next()?.trim_start_matches('*')))
+        })
+        .find(|(_, name)| *name == asset || name.rsplit('/').next() == Some(asset))
+        .map(|(hash, _)| hash.to_lowercase())
+        .with_context(|| format!("{} has no entry for {}", CHECKSUMS_ASSET, asset))?;
+
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    if actual != expected {
+        anyhow::bail!(
+            "Checksum mismatch for {}: expected {}, got {}; refusing to install",
+            asset,
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Make sure the download is an executable for this OS and architecture,
+/// by its ELF, Mach-O or PE header
+fn check_binary_target(bytes: &[u8], os: &str, arch: &str) -> Result<()> {
+    let u16_le = |at: usize| bytes.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let u32_le = |at: usize| bytes.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+
+    let (format, machine) = if bytes.starts_with(b"\x7fELF") {
+        let machine = match u16_le(18) {
+            Some(62) => "x86_64",
+            Some(183) => "aarch64",
+            Some(40) => "arm",
+            Some(3) => "x86",
+            Some(243) => "riscv64",
+            Some(21) => "powerpc64",
+            Some(22) => "s390x",
+            _ => "unknown",
+        };
+        ("linux", machine)
+    } else if bytes.starts_with(&[0xcf, 0xfa, 0xed, 0xfe]) {
+        let machine = match u32_le(4) {
+            Some(0x0100_0007) => "x86_64",
+            Some(0x0100_000c) => "aarch64",
+            _ => "unknown",
+        };
+        ("macos", machine)
+    } else if bytes.starts_with(&[0xca, 0xfe, 0xba, 0xbe]) {
+        // Universal binary: slices for every architecture
+        ("macos", arch)
+    } else if bytes.starts_with(b"MZ") {
+        let pe = u32_le(0x3c).unwrap_or(0) as usize;
+        let machine = if bytes.get(pe..pe + 4) == Some(b"PE\0\0") {
+            match u16_le(pe + 4) {
+                Some(0x8664) => "x86_64",
+                Some(0xaa64) => "aarch64",
+                Some(0x014c) => "x86",
+                _ => "unknown",
+            }
+        } else {
+            "unknown"
+        };
+        ("windows", machine)
+    } else {
+        anyhow::bail!("Downloaded file is not an executable; refusing to install");
+    };
+
+    // ELF covers every Unix-like target pkmgr ships for
+    let os_matches = format == os || (format == "linux" && os != "macos" && os != "windows");
+    if !os_matches || machine != arch {
+        anyhow::bail!(
+            "Downloaded binary is for {} {}, this system is {} {}; refusing to install",
+            format,
+            machine,
+            os,
+            arch
+        );
+    }
+    Ok(())
+}
+
+/// Replace `current` with `bytes` without ever leaving it missing or
+/// half-written: stage the new binary beside it, `check` the staged copy,
+/// keep a `.bak`, rename it into place, then `check` the installed path and
+/// restore the backup if that fails
+fn swap_binary(current: &Path, bytes: &[u8], check: impl Fn(&Path) -> Result<()>) -> Result<()> {
+    let staged = current.with_extension("new");
+    let backup = current.with_extension("bak");
+
+    fs::write(&staged, bytes)
+        .with_context(|| format!("Failed to write {}", staged.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(current)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&staged, perms)?;
+    }
+    if let Err(e) = check(&staged) {
+        let _ = fs::remove_file(&staged);
+        return Err(e);
+    }
+
+    fs::copy(current, &backup)
+        .with_context(|| format!("Failed to back up {}", current.display()))?;
+    if let Err(e) = replace_running(&staged, current) {
+        let _ = fs::remove_file(&staged);
+        return Err(e);
+    }
+
+    if let Err(e) = check(current) {
+        fs::copy(&backup, &staged)
+            .and_then(|_| fs::rename(&staged, current))
+            .with_context(|| format!(
+                "New binary failed and restoring {} also failed; copy it back by hand",
+                backup.display()
+            ))?;
+        return Err(e.context("New binary failed after install; restored the previous version"));
+    }
+    Ok(())
+}
+
+/// Rename `staged` over `current`. Unix renames over a running executable
+/// atomically; Windows cannot, so the running one is moved aside to `.old`
+/// first and moved back if the rename fails
+fn replace_running(staged: &Path, current: &Path) -> Result<()> {
+    if cfg!(windows) {
+        let old = current.with_extension("old");
+        // Left behind by the previous update once that process has exited
+        let _ = fs::remove_file(&old);
+        fs::rename(current, &old)
+            .with_context(|| format!("Failed to move {} aside", current.display()))?;
+        if let Err(e) = fs::rename(staged, current) {
+            fs::rename(&old, current)?;
+            return Err(e).context("Failed to install the new binary");
+        }
+        return Ok(());
+    }
+
+    fs::rename(staged, current)
+        .with_context(|| format!("Failed to replace {}", current.display()))
+}
+
+/// The binary at `path` runs and reports itself as pkmgr
+fn check_runs(path: &Path) -> Result<()> {
+    let output = Command::new(path)
+        .arg("--version")
+        .output()
+        .with_context(|| format!("New binary {} does not run", path.display()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !stdout.starts_with("pkmgr") {
+        anyhow::bail!("New binary failed its --version check; refusing to install");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elf(machine: u16) -> Vec<u8> {
+        let mut bytes = vec![0u8; 64];
+        bytes[..4].copy_from_slice(b"\x7fELF");
+        bytes[18..20].copy_from_slice(&machine.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn checksum_must_match_the_release_entry() {
+        let binary = b"pkmgr binary";
+        let hash = format!("{:x}", Sha256::digest(binary));
+        let sums = format!("{}  pkmgr-linux-x86_64\n{}  pkmgr-linux-aarch64\n", hash, "0".repeat(64));
+
+        assert!(verify_checksum(&sums, "pkmgr-linux-x86_64", binary).is_ok());
+        let err = verify_checksum(&sums, "pkmgr-linux-aarch64", binary).unwrap_err().to_string();
+        assert!(err.contains("Checksum mismatch"), "{}", err);
+        assert!(verify_checksum(&sums, "pkmgr-macos-aarch64", binary).is_err());
+    }
+
+    #[test]
+    fn binary_must_target_this_platform() {
+        assert!(check_binary_target(&elf(62), "linux", "x86_64").is_ok());
+        assert!(check_binary_target(&elf(183), "freebsd", "aarch64").is_ok());
+        assert!(check_binary_target(&elf(183), "linux", "x86_64").is_err());
+        assert!(check_binary_target(&elf(62), "macos", "x86_64").is_err());
+        assert!(check_binary_target(b"<html>Not Found</html>", "linux", "x86_64").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_post_swap_check_restores_the_previous_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let current = dir.path().join("pkmgr");
+        fs::write(&current, b"old").unwrap();
+
+        // Staged copy passes, the installed one fails
+        let result = swap_binary(&current, b"new", |path| {
+            if path == current.as_path() && fs::read(path).unwrap() == b"new" {
+                anyhow::bail!("does not run");
+            }
+            Ok(())
+        });
+
+        assert!(result.unwrap_err().to_string().contains("restored the previous version"));
+        assert_eq!(fs::read(&current).unwrap(), b"old");
+        assert!(!current.with_extension("new").exists());
+
+        swap_binary(&current, b"new", |_| Ok(())).unwrap();
+        assert_eq!(fs::read(&current).unwrap(), b"new");
+        assert_eq!(fs::read(current.with_extension("bak")).unwrap(), b"old");
     }
 }
@@ -2,8 +2,21 @@ use anyhow::{Context, Result, bail};
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::Write;
-use super::{MultiBootConfig, BootEntry, BootloaderType};
+use super::{MultiBootConfig, BootEntry, BootloaderType, UsbDevice};
+use crate::core::privilege;
 use crate::iso::IsoDistribution;
+use crate::utils::logging::LoggedCommand;
+
+/// Partition labels Ventoy2Disk creates: exFAT data plus the EFI partition
+const VENTOY_DATA_LABEL: &str = "Ventoy";
+const VENTOY_EFI_LABEL: &str = "VTOYEFI";
+
+/// How a device was prepared for Ventoy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VentoySetup {
+    Reused,     // Ventoy was already installed, data kept
+    Installed,  // Fresh install, device erased
+}
 
 pub struct BootloaderManager {
     bootloader_type: BootloaderType,
@@ -81,10 +94,152 @@ impl BootloaderManager {
         Ok(())
     }
 
-    fn install_ventoy(&self, _device: &Path) -> Result<()> {
-        // Ventoy requires downloading and running their installer
-        // For now, we'll just note this
-        bail!("Ventoy installation requires manual setup. Download from ventoy.net");
+    /// Check for the partition pair Ventoy2Disk leaves behind
+    pub fn is_ventoy_device(device: &UsbDevice) -> bool {
+        let has_label = |label: &str| device.partitions.iter()
+            .any(|p| p.label.as_deref() == Some(label));
+        has_label(VENTOY_DATA_LABEL) && has_label(VENTOY_EFI_LABEL)
+    }
+
+    /// Mounted root of a pkmgr multi-boot device: the Ventoy data partition,
+    /// or the partition holding the multi-boot manifest
+    pub fn multiboot_root(device: &UsbDevice) -> Result<PathBuf> {
+        if Self::is_ventoy_device(device) {
+            return Self::ventoy_root(device);
+        }
+
+        device.partitions.iter()
+            .filter_map(|p| p.mount_point.clone())
+            .find(|root| root.join(MultiBootConfig::FILE_NAME).exists())
+            .with_context(|| format!(
+                "{} is not a mounted pkmgr multi-boot device; create one with 'pkmgr usb boot create'",
+                device.path.display()
+            ))
+    }
+
+    /// Mount point of the Ventoy data partition, where ISOs are copied
+    pub fn ventoy_root(device: &UsbDevice) -> Result<PathBuf> {
+        let partition = device.partitions.iter()
+            .find(|p| p.label.as_deref() == Some(VENTOY_DATA_LABEL))
+            .with_context(|| format!("{} has no Ventoy partition", device.path.display()))?;
+
+        partition.mount_point.clone().with_context(|| format!(
+            "Ventoy partition {} is not mounted; mount it and try again",
+            partition.path.display()
+        ))
+    }
+
+    /// Reuse an existing Ventoy install, or install fresh when `force` allows
+    /// erasing the device
+    pub fn setup_ventoy(&self, device: &UsbDevice, force: bool) -> Result<VentoySetup> {
+        if Self::is_ventoy_device(device) {
+            return Ok(VentoySetup::Reused);
+        }
+
+        if !force {
+            bail!(
+                "Installing Ventoy erases all data on {} ({}); re-run with --force to continue",
+                device.path.display(),
+                device.format_size()
+            );
+        }
+
+        self.install_ventoy(&device.path)?;
+        Ok(VentoySetup::Installed)
+    }
+
+    fn install_ventoy(&self, device: &Path) -> Result<()> {
+        let installer = find_ventoy_installer().context(
+            "Ventoy2Disk.sh not found; download Ventoy from https://www.ventoy.net and put Ventoy2Disk.sh on PATH"
+        )?;
+
+        // -I installs even over an existing Ventoy; it asks twice before erasing
+        let mut command = privilege::privileged_command("sh")?;
+        command.arg("-c")
+            .arg("printf 'y\\ny\\n' | sh \"$0\" -I \"$1\"")
+            .arg(&installer)
+            .arg(device);
+        if let Some(dir) = installer.parent() {
+            // Ventoy2Disk.sh resolves its tools relative to the working directory
+            command.current_dir(dir);
+        }
+
+        let result = command.logged_output().context("Failed to run Ventoy2Disk.sh")?;
+        if !result.status.success() {
+            bail!("Ventoy installation failed: {}", String::from_utf8_lossy(&result.stderr).trim());
+        }
+
+        Ok(())
+    }
+
+    /// Copy an ISO onto the device and record it. Ventoy finds ISOs anywhere
+    /// on its data partition at boot; GRUB and Syslinux boot them from isos/
+    /// through the regenerated menu
+    pub fn add_iso(&self, usb_root: &Path, iso: &Path, config: &mut MultiBootConfig) -> Result<BootEntry> {
+        let file_name = iso.file_name()
+            .and_then(|n| n.to_str())
+            .with_context(|| format!("Invalid ISO path: {}", iso.display()))?;
+
+        let iso_size = fs::metadata(iso)
+            .with_context(|| format!("ISO not found: {}", iso.display()))?
+            .len();
+        let available = fs2::available_space(usb_root)
+            .with_context(|| format!("Failed to check free space on {}", usb_root.display()))?;
+        if iso_size > available {
+            bail!(
+                "Not enough space on {}: {} needs {} MB, {} MB free",
+                usb_root.display(), file_name, iso_size / 1_000_000, available / 1_000_000
+            );
+        }
+
+        let iso_path = match self.bootloader_type {
+            BootloaderType::Ventoy => format!("/{}", file_name),
+            BootloaderType::Grub2 | BootloaderType::Syslinux => format!("/isos/{}", file_name),
+        };
+        let destination = usb_root.join(iso_path.trim_start_matches('/'));
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(iso, &destination)
+            .with_context(|| format!("Failed to copy {} to {}", iso.display(), destination.display()))?;
+
+        let name = file_name.trim_end_matches(".iso").to_string();
+        let entry = BootEntry {
+            display_name: name.clone(),
+            iso_path,
+            category: categorize_iso(&name),
+            version: String::new(),
+            architecture: if name.contains("arm64") || name.contains("aarch64") { "aarch64" } else { "x86_64" }.to_string(),
+            boot_params: Vec::new(),
+            added: chrono::Utc::now(),
+            name,
+        };
+
+        self.add_entry(usb_root, config, entry.clone())?;
+        Ok(entry)
+    }
+
+    /// Delete an ISO and its entry. Removing the last entry leaves an empty
+    /// boot menu, so it needs `force`
+    pub fn remove_iso(&self, usb_root: &Path, config: &mut MultiBootConfig, name: &str, force: bool) -> Result<BootEntry> {
+        let entry = config.entries.iter()
+            .find(|e| e.name == name || e.iso_path.trim_start_matches('/') == name.trim_start_matches('/')
+                || e.iso_path.rsplit('/').next() == Some(name))
+            .cloned()
+            .with_context(|| format!("No ISO named '{}' on this device", name))?;
+
+        if config.entries.len() == 1 && !force {
+            bail!("'{}' is the last ISO on the device; re-run with --force to remove it", entry.name);
+        }
+
+        let file = usb_root.join(entry.iso_path.trim_start_matches('/'));
+        if file.exists() {
+            fs::remove_file(&file)
+                .with_context(|| format!("Failed to delete {}", file.display()))?;
+        }
+
+        self.remove_entry(usb_root, config, &entry.name)?;
+        Ok(entry)
     }
 
     fn generate_grub_config(&self, usb_root: &Path, entries: &[BootEntry]) -> Result<()> {
@@ -110,8 +265,8 @@ impl BootloaderManager {
         config.push_str("    set theme=/boot/grub/themes/pkmgr/theme.txt\n");
         config.push_str("fi\n\n");
 
-        // Group entries by category
-        let mut categories = std::collections::HashMap::new();
+        // Group entries by category, in a stable order
+        let mut categories = std::collections::BTreeMap::new();
         for entry in entries {
             categories.entry(entry.category.clone())
                 .or_insert_with(Vec::new)
@@ -160,63 +315,50 @@ impl BootloaderManager {
     }
 
     fn generate_grub_entry(&self, entry: &BootEntry) -> Result<String> {
-        let mut config = String::new();
+        let family = grub_family(entry);
+        let params = if entry.boot_params.is_empty() {
+            family.default_params.to_string()
+        } else {
+            entry.boot_params.join(" ")
+        };
 
-        config.push_str(&format!("    menuentry '{}' {{\n", entry.display_name));
+        let mut config = String::new();
+        config.push_str(&format!("    menuentry '{}' {{\n", entry.display_name.replace('\'', "")));
         config.push_str(&format!("        set isofile=\"{}\"\n", entry.iso_path));
         config.push_str("        loopback loop $isofile\n");
-
-        // Distribution-specific boot parameters
-        let boot_params = self.get_boot_params(&entry.name, &entry.version);
-
-        match entry.name.as_str() {
-            "ubuntu" | "debian" | "mint" => {
-                config.push_str("        linux (loop)/casper/vmlinuz boot=casper ");
-                config.push_str(&format!("iso-scan/filename=$isofile {}\n", boot_params));
-                config.push_str("        initrd (loop)/casper/initrd\n");
-            }
-
-            "fedora" | "centos" | "rocky" | "alma" => {
-                config.push_str("        linux (loop)/isolinux/vmlinuz inst.stage2=hd:LABEL=MULTIBOOT ");
-                config.push_str(&format!("iso-scan/filename=$isofile {}\n", boot_params));
-                config.push_str("        initrd (loop)/isolinux/initrd.img\n");
-            }
-
-            "arch" | "manjaro" => {
-                config.push_str("        linux (loop)/arch/boot/x86_64/vmlinuz-linux ");
-                config.push_str("archisodevice=/dev/loop0 img_dev=/dev/disk/by-label/MULTIBOOT ");
-                config.push_str(&format!("img_loop=$isofile {}\n", boot_params));
-                config.push_str("        initrd (loop)/arch/boot/x86_64/initramfs-linux.img\n");
-            }
-
-            "kali" | "parrot" => {
-                config.push_str("        linux (loop)/live/vmlinuz boot=live ");
-                config.push_str(&format!("findiso=$isofile {}\n", boot_params));
-                config.push_str("        initrd (loop)/live/initrd.img\n");
-            }
-
-            _ => {
-                // Generic fallback
-                config.push_str("        linux (loop)/vmlinuz ");
-                config.push_str(&format!("iso-scan/filename=$isofile {}\n", boot_params));
-                config.push_str("        initrd (loop)/initrd.img\n");
-            }
+        for line in family.setup {
+            config.push_str(&format!("        {}\n", line));
         }
-
+        config.push_str(&format!("        linux (loop){} {} {}\n", family.kernel, family.iso_args, params));
+        config.push_str(&format!("        initrd (loop){}\n", family.initrd));
         config.push_str("    }\n\n");
 
         Ok(config)
     }
 
-    fn get_boot_params(&self, distro: &str, _version: &str) -> String {
-        match distro {
-            "ubuntu" | "debian" | "mint" => "quiet splash",
-            "fedora" | "centos" | "rocky" | "alma" => "quiet",
-            "arch" | "manjaro" => "quiet splash",
-            "kali" | "parrot" => "quiet splash apparmor=0",
-            "tails" => "quiet splash noautologin",
-            _ => "quiet",
-        }.to_string()
+    /// Add an entry, replacing one for the same ISO, then regenerate the
+    /// boot menu and save the config
+    pub fn add_entry(&self, usb_root: &Path, config: &mut MultiBootConfig, entry: BootEntry) -> Result<()> {
+        config.entries.retain(|e| e.iso_path != entry.iso_path);
+        config.entries.push(entry);
+        self.commit_entries(usb_root, config)
+    }
+
+    /// Remove entries by name or ISO path; returns whether any matched
+    pub fn remove_entry(&self, usb_root: &Path, config: &mut MultiBootConfig, name: &str) -> Result<bool> {
+        let before = config.entries.len();
+        config.entries.retain(|e| e.name != name && e.iso_path.trim_start_matches('/') != name.trim_start_matches('/'));
+        if config.entries.len() == before {
+            return Ok(false);
+        }
+        self.commit_entries(usb_root, config)?;
+        Ok(true)
+    }
+
+    fn commit_entries(&self, usb_root: &Path, config: &mut MultiBootConfig) -> Result<()> {
+        config.updated = chrono::Utc::now();
+        self.generate_config(usb_root, &config.entries)?;
+        config.save(usb_root)
     }
 
     fn generate_syslinux_config(&self, usb_root: &Path, entries: &[BootEntry]) -> Result<()> {
@@ -282,6 +424,103 @@ impl BootloaderManager {
     }
 }
 
+/// Loopback boot stanza shared by a family of distributions
+struct GrubFamily {
+    /// Matched against the lowercased entry name
+    keywords: &'static [&'static str],
+    /// Category fallback when no keyword matches
+    categories: &'static [&'static str],
+    kernel: &'static str,
+    initrd: &'static str,
+    /// GRUB commands run after the loopback, before `linux`
+    setup: &'static [&'static str],
+    /// Kernel arguments that point the live system at the ISO
+    iso_args: &'static str,
+    /// Used when the entry has no boot_params of its own
+    default_params: &'static str,
+}
+
+const GRUB_FAMILIES: &[GrubFamily] = &[
+    // Ubuntu and derivatives: casper
+    GrubFamily {
+        keywords: &["ubuntu", "kubuntu", "xubuntu", "mint", "pop-os", "elementary", "zorin"],
+        categories: &[],
+        kernel: "/casper/vmlinuz",
+        initrd: "/casper/initrd",
+        setup: &[],
+        iso_args: "boot=casper iso-scan/filename=$isofile",
+        default_params: "quiet splash",
+    },
+    // Debian live-boot, also used by Kali, Parrot, Tails and the rescue tools
+    GrubFamily {
+        keywords: &["debian", "kali", "parrot", "tails", "gparted", "clonezilla"],
+        categories: &["Security Tools", "System Tools"],
+        kernel: "/live/vmlinuz",
+        initrd: "/live/initrd.img",
+        setup: &[],
+        iso_args: "boot=live findiso=$isofile",
+        default_params: "quiet splash",
+    },
+    // Fedora and EL live images find their root by volume label
+    GrubFamily {
+        keywords: &["fedora", "centos", "rocky", "alma", "rhel"],
+        categories: &[],
+        kernel: "/images/pxeboot/vmlinuz",
+        initrd: "/images/pxeboot/initrd.img",
+        setup: &["probe --label --set=isolabel (loop)"],
+        iso_args: "root=live:CDLABEL=$isolabel rd.live.image iso-scan/filename=$isofile",
+        default_params: "quiet",
+    },
+    // archiso
+    GrubFamily {
+        keywords: &["archlinux", "arch-", "manjaro", "endeavouros"],
+        categories: &[],
+        kernel: "/arch/boot/x86_64/vmlinuz-linux",
+        initrd: "/arch/boot/x86_64/initramfs-linux.img",
+        setup: &["probe --label --set=isolabel (loop)"],
+        iso_args: "archisolabel=$isolabel img_dev=/dev/disk/by-label/MULTIBOOT img_loop=$isofile",
+        default_params: "quiet splash",
+    },
+    GrubFamily {
+        keywords: &["opensuse"],
+        categories: &[],
+        kernel: "/boot/x86_64/loader/linux",
+        initrd: "/boot/x86_64/loader/initrd",
+        setup: &[],
+        iso_args: "isofrom_device=/dev/disk/by-label/MULTIBOOT isofrom_system=$isofile",
+        default_params: "quiet splash",
+    },
+];
+
+/// Best-effort stanza for anything unrecognized
+const GRUB_GENERIC: GrubFamily = GrubFamily {
+    keywords: &[],
+    categories: &[],
+    kernel: "/vmlinuz",
+    initrd: "/initrd.img",
+    setup: &[],
+    iso_args: "iso-scan/filename=$isofile",
+    default_params: "quiet",
+};
+
+fn grub_family(entry: &BootEntry) -> &'static GrubFamily {
+    let name = entry.name.to_lowercase();
+    GRUB_FAMILIES.iter()
+        .find(|f| f.keywords.iter().any(|k| name.contains(k)))
+        .or_else(|| GRUB_FAMILIES.iter().find(|f| f.categories.contains(&entry.category.as_str())))
+        .unwrap_or(&GRUB_GENERIC)
+}
+
+/// Ventoy2Disk.sh on PATH, or the location distro packages install it to
+fn find_ventoy_installer() -> Option<PathBuf> {
+    which::which("Ventoy2Disk.sh").ok()
+        .or_else(|| {
+            ["/opt/ventoy/Ventoy2Disk.sh", "/usr/share/ventoy/Ventoy2Disk.sh"].iter()
+                .map(PathBuf::from)
+                .find(|p| p.exists())
+        })
+}
+
 /// Determine the appropriate category for an ISO
 pub fn categorize_iso(name: &str) -> String {
     let name_lower = name.to_lowercase();
@@ -324,4 +563,62 @@ pub fn categorize_iso(name: &str) -> String {
     }
 
     "Other".to_string()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, category: &str, params: &[&str]) -> BootEntry {
+        BootEntry {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            iso_path: format!("/isos/{}.iso", name),
+            category: category.to_string(),
+            version: String::new(),
+            architecture: "x86_64".to_string(),
+            boot_params: params.iter().map(|p| p.to_string()).collect(),
+            added: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn grub_entries_follow_family_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BootloaderManager::new(BootloaderType::Grub2);
+        let mut config = MultiBootConfig::new(BootloaderType::Grub2);
+
+        manager.add_entry(dir.path(), &mut config, entry("ubuntu-24.04-desktop", "Linux Distributions", &[])).unwrap();
+        manager.add_entry(dir.path(), &mut config, entry("Fedora-Workstation-40", "Linux Distributions", &["nomodeset"])).unwrap();
+
+        let grub = fs::read_to_string(dir.path().join("boot/grub/grub.cfg")).unwrap();
+        assert!(grub.contains("linux (loop)/casper/vmlinuz boot=casper iso-scan/filename=$isofile quiet splash"));
+        assert!(grub.contains("probe --label --set=isolabel (loop)"));
+        assert!(grub.contains("rd.live.image iso-scan/filename=$isofile nomodeset"));
+
+        assert!(manager.remove_entry(dir.path(), &mut config, "ubuntu-24.04-desktop").unwrap());
+        let grub = fs::read_to_string(dir.path().join("boot/grub/grub.cfg")).unwrap();
+        assert!(!grub.contains("/casper/vmlinuz"));
+        assert!(dir.path().join(MultiBootConfig::FILE_NAME).exists());
+    }
+
+    #[test]
+    fn removing_last_iso_needs_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let iso = dir.path().join("debian-12.iso");
+        fs::write(&iso, b"iso").unwrap();
+        let usb_root = dir.path().join("usb");
+        fs::create_dir_all(&usb_root).unwrap();
+
+        let manager = BootloaderManager::new(BootloaderType::Grub2);
+        let mut config = MultiBootConfig::new(BootloaderType::Grub2);
+        let entry = manager.add_iso(&usb_root, &iso, &mut config).unwrap();
+        assert_eq!(entry.iso_path, "/isos/debian-12.iso");
+        assert!(usb_root.join("isos/debian-12.iso").exists());
+
+        assert!(manager.remove_iso(&usb_root, &mut config, "debian-12", false).is_err());
+        manager.remove_iso(&usb_root, &mut config, "debian-12.iso", true).unwrap();
+        assert!(config.entries.is_empty());
+        assert!(!usb_root.join("isos/debian-12.iso").exists());
+    }
+}
@@ -3,9 +3,90 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::fs;
 use super::{UsbDevice, UsbPartition};
+use crate::utils::logging::LoggedCommand;
 
 pub struct DeviceDetector;
 
+/// Where udev keeps per-device properties, keyed by b<major>:<minor>
+const UDEV_DATA_DIR: &str = "/run/udev/data";
+
+/// Vendor and model of a block device
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceIdentity {
+    pub vendor: Option<String>,
+    pub model: Option<String>,
+}
+
+impl DeviceIdentity {
+    /// Read from the SCSI attributes under /sys/block/<dev>/device, filling
+    /// gaps from the USB descriptor strings and then the udev database
+    pub fn read(sys_block_dev: &Path, udev_data_dir: &Path) -> Self {
+        let mut identity = Self {
+            vendor: read_attr(&sys_block_dev.join("device/vendor")),
+            model: read_attr(&sys_block_dev.join("device/model")),
+        };
+
+        if identity.vendor.is_none() || identity.model.is_none() {
+            if let Some(usb_dir) = usb_parent(sys_block_dev) {
+                identity.fill(read_attr(&usb_dir.join("manufacturer")), read_attr(&usb_dir.join("product")));
+            }
+        }
+
+        if identity.vendor.is_none() || identity.model.is_none() {
+            if let Some(props) = read_attr(&sys_block_dev.join("dev"))
+                .and_then(|dev| fs::read_to_string(udev_data_dir.join(format!("b{}", dev))).ok())
+            {
+                let prop = |key: &str| props.lines()
+                    .find_map(|line| line.strip_prefix("E:")?.strip_prefix(key)?.strip_prefix('='))
+                    .map(|value| value.replace('_', " ").trim().to_string())
+                    .filter(|value| !value.is_empty());
+                identity.fill(
+                    prop("ID_VENDOR_FROM_DATABASE").or_else(|| prop("ID_VENDOR")),
+                    prop("ID_MODEL"),
+                );
+            }
+        }
+
+        identity
+    }
+
+    fn fill(&mut self, vendor: Option<String>, model: Option<String>) {
+        if self.vendor.is_none() {
+            self.vendor = vendor;
+        }
+        if self.model.is_none() {
+            self.model = model;
+        }
+    }
+
+    /// "SanDisk Ultra", or whichever half is known, or the kernel name
+    pub fn display_name(&self, fallback: &str) -> String {
+        match (&self.vendor, &self.model) {
+            (Some(vendor), Some(model)) if model.starts_with(vendor.as_str()) => model.clone(),
+            (Some(vendor), Some(model)) => format!("{} {}", vendor, model),
+            (Some(name), None) | (None, Some(name)) => name.clone(),
+            (None, None) => fallback.to_string(),
+        }
+    }
+}
+
+/// Sysfs attributes are space padded; empty means unknown
+fn read_attr(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// The USB device directory above a block device carries a `product` file
+fn usb_parent(sys_block_dev: &Path) -> Option<PathBuf> {
+    let resolved = fs::canonicalize(sys_block_dev).unwrap_or_else(|_| sys_block_dev.to_path_buf());
+    resolved.ancestors()
+        .skip(1)
+        .take(12)
+        .find(|dir| dir.join("product").is_file())
+        .map(Path::to_path_buf)
+}
+
 impl DeviceDetector {
     pub fn new() -> Self {
         Self {}
@@ -14,7 +95,7 @@ impl DeviceDetector {
     /// List all USB devices that are safe to use
     pub fn list_usb_devices(&self) -> Result<Vec<UsbDevice>> {
         #[cfg(target_os = "linux")]
-        return self.list_usb_devices_linux();
+        return self.list_usb_devices_linux(false);
 
         #[cfg(target_os = "macos")]
         return self.list_usb_devices_macos();
@@ -27,8 +108,9 @@ impl DeviceDetector {
     }
 
     #[cfg(target_os = "linux")]
-    fn list_usb_devices_linux(&self) -> Result<Vec<UsbDevice>> {
+    fn list_usb_devices_linux(&self, include_fixed: bool) -> Result<Vec<UsbDevice>> {
         let mut devices = Vec::new();
+        let system_disks = system_disks()?;
 
         // Read block devices from /sys/block
         let block_dir = Path::new("/sys/block");
@@ -47,21 +129,22 @@ impl DeviceDetector {
 
             let device_path = entry.path();
 
-            // Check if device is removable
-            let removable_path = device_path.join("removable");
-            if let Ok(removable) = fs::read_to_string(&removable_path) {
-                if removable.trim() != "1" {
-                    continue; // Not removable, skip
-                }
-            } else {
-                continue; // Can't determine if removable, skip for safety
-            }
-
             // Get device info
             let dev_path = PathBuf::from(format!("/dev/{}", device_name));
 
-            // Check if it's actually a USB device
-            if !self.is_usb_device_linux(&device_path)? {
+            // Never offer the disk the running system lives on
+            if is_system_disk(&dev_path, &system_disks) {
+                continue;
+            }
+
+            // USB sticks often report removable=0, so the bus decides
+            let removable = fs::read_to_string(device_path.join("removable"))
+                .map(|r| r.trim() == "1")
+                .unwrap_or(false);
+            let is_usb = self.is_usb_device_linux(&device_path)?;
+            let is_removable = removable || is_usb;
+
+            if !is_removable && !include_fixed {
                 continue;
             }
 
@@ -74,18 +157,12 @@ impl DeviceDetector {
             let size_bytes = size_blocks * 512;
 
             // Skip devices that are too small or too large (likely not USB sticks)
-            if size_bytes < 1_000_000_000 || size_bytes > 2_000_000_000_000 {
+            if !include_fixed && !(1_000_000_000..=2_000_000_000_000).contains(&size_bytes) {
                 continue; // Less than 1GB or more than 2TB
             }
 
             // Get vendor and model
-            let vendor = fs::read_to_string(device_path.join("device/vendor"))
-                .ok()
-                .map(|s| s.trim().to_string());
-
-            let model = fs::read_to_string(device_path.join("device/model"))
-                .ok()
-                .map(|s| s.trim().to_string());
+            let identity = DeviceIdentity::read(&device_path, Path::new(UDEV_DATA_DIR));
 
             // Get filesystem and mount info using lsblk
             let (filesystem, is_mounted, mount_points) = self.get_device_info_lsblk(&dev_path)?;
@@ -95,16 +172,13 @@ impl DeviceDetector {
 
             let device = UsbDevice {
                 path: dev_path,
-                name: format!("{} {}",
-                    vendor.as_ref().unwrap_or(&"Unknown".to_string()),
-                    model.as_ref().unwrap_or(&device_name.to_string())
-                ),
+                name: identity.display_name(&device_name),
                 size_bytes,
                 size_display: format_size(size_bytes),
                 filesystem,
-                vendor,
-                model,
-                is_removable: true,
+                vendor: identity.vendor,
+                model: identity.model,
+                is_removable,
                 is_mounted,
                 mount_points,
                 partitions,
@@ -328,6 +402,61 @@ impl DeviceDetector {
         Ok(Vec::new())
     }
 
+    /// Like `list_usb_devices`, but also returns fixed, non-USB disks so that
+    /// `--force` can target them; system disks are still excluded
+    pub fn list_all_devices(&self) -> Result<Vec<UsbDevice>> {
+        #[cfg(target_os = "linux")]
+        return self.list_usb_devices_linux(true);
+
+        #[cfg(not(target_os = "linux"))]
+        self.list_usb_devices()
+    }
+
+    /// Mount every unmounted filesystem on the device and return all of its
+    /// mount points
+    pub fn mount_device(&self, device: &UsbDevice) -> Result<Vec<PathBuf>> {
+        let mut targets: Vec<&Path> = device.partitions.iter()
+            .filter(|p| p.filesystem.is_some() && p.mount_point.is_none())
+            .map(|p| p.path.as_path())
+            .collect();
+        // Superfloppy layout: the filesystem sits on the whole disk
+        if device.partitions.is_empty() && device.filesystem.is_some() && !device.is_mounted {
+            targets.push(device.path.as_path());
+        }
+
+        let mut mount_points = device.mount_points.clone();
+        if targets.is_empty() && mount_points.is_empty() {
+            bail!("No mountable filesystem on {}", device.path.display());
+        }
+
+        #[cfg(target_os = "linux")]
+        for target in targets {
+            mount_points.push(mount_linux(target)?);
+        }
+
+        #[cfg(target_os = "macos")]
+        if !targets.is_empty() {
+            let output = Command::new("diskutil")
+                .arg("mountDisk")
+                .arg(&device.path)
+                .logged_output()
+                .context("Failed to run diskutil")?;
+            if !output.status.success() {
+                bail!("diskutil mountDisk failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+            }
+            mount_points = mounted_at_macos(&device.path)?;
+        }
+
+        #[cfg(windows)]
+        if !targets.is_empty() {
+            bail!("Mounting is handled by Windows automatically");
+        }
+
+        mount_points.sort();
+        mount_points.dedup();
+        Ok(mount_points)
+    }
+
     /// Unmount a USB device
     pub fn unmount_device(&self, device: &UsbDevice) -> Result<()> {
         if !device.is_mounted {
@@ -337,50 +466,224 @@ impl DeviceDetector {
         #[cfg(target_os = "linux")]
         {
             for mount_point in &device.mount_points {
-                Command::new("umount")
+                let status = Command::new("umount")
                     .arg(mount_point)
-                    .status()
+                    .logged_status()
                     .context("Failed to unmount device")?;
+                if !status.success() {
+                    bail!("Failed to unmount {} (is it in use?)", mount_point.display());
+                }
             }
         }
 
         #[cfg(target_os = "macos")]
         {
-            Command::new("diskutil")
+            let status = Command::new("diskutil")
                 .arg("unmountDisk")
                 .arg(&device.path)
-                .status()
+                .logged_status()
                 .context("Failed to unmount device")?;
+            if !status.success() {
+                bail!("Failed to unmount {} (is it in use?)", device.path.display());
+            }
         }
 
         Ok(())
     }
 
-    /// Eject a USB device safely
+    /// Eject a USB device safely: refuse system disks, flush pending writes,
+    /// unmount and power the device off
     pub fn eject_device(&self, device: &UsbDevice) -> Result<()> {
-        self.unmount_device(device)?;
+        if let Some(critical) = device.mount_points.iter().find(|m| is_critical_mount(m)) {
+            bail!("{} backs {}; refusing to eject it", device.path.display(), critical.display());
+        }
+        if !super::is_device_safe(&device.path)? {
+            bail!("{} is a system disk; refusing to eject it", device.path.display());
+        }
+
+        #[cfg(unix)]
+        Command::new("sync")
+            .logged_status()
+            .context("Failed to flush pending writes")?;
 
         #[cfg(target_os = "linux")]
         {
-            Command::new("eject")
-                .arg(&device.path)
-                .status()
-                .context("Failed to eject device")?;
+            if which::which("udisksctl").is_ok() {
+                for partition in device.partitions.iter().filter(|p| p.mount_point.is_some()) {
+                    udisksctl(&["unmount", "-b"], &partition.path)?;
+                }
+                if device.partitions.is_empty() && device.is_mounted {
+                    udisksctl(&["unmount", "-b"], &device.path)?;
+                }
+                udisksctl(&["power-off", "-b"], &device.path)?;
+            } else {
+                self.unmount_device(device)?;
+                let status = Command::new("eject")
+                    .arg(&device.path)
+                    .logged_status()
+                    .context("Failed to eject device")?;
+                if !status.success() {
+                    bail!("eject {} failed", device.path.display());
+                }
+            }
         }
 
         #[cfg(target_os = "macos")]
         {
-            Command::new("diskutil")
+            let status = Command::new("diskutil")
                 .arg("eject")
                 .arg(&device.path)
-                .status()
+                .logged_status()
                 .context("Failed to eject device")?;
+            if !status.success() {
+                bail!("diskutil eject {} failed", device.path.display());
+            }
         }
 
         Ok(())
     }
 }
 
+/// Mounts whose backing device must stay attached
+const CRITICAL_MOUNTS: &[&str] = &["/", "/boot", "/boot/efi", "/usr", "/var", "/home"];
+
+fn is_critical_mount(mount_point: &Path) -> bool {
+    CRITICAL_MOUNTS.iter().any(|critical| mount_point == Path::new(critical))
+}
+
+/// Mount through udisks so the user owns the mount, falling back to a
+/// privileged mount under /media/pkmgr
+#[cfg(target_os = "linux")]
+fn mount_linux(partition: &Path) -> Result<PathBuf> {
+    if which::which("udisksctl").is_ok() {
+        let stdout = udisksctl(&["mount", "-b"], partition)?;
+        // "Mounted /dev/sdb1 at /media/user/LABEL"
+        return stdout.split_once(" at ")
+            .map(|(_, at)| PathBuf::from(at.trim().trim_end_matches('.')))
+            .with_context(|| format!("Unexpected udisksctl output: {}", stdout.trim()));
+    }
+
+    let name = partition.file_name().and_then(|n| n.to_str()).unwrap_or("usb");
+    let mount_point = PathBuf::from("/media/pkmgr").join(name);
+    let target = mount_point.to_string_lossy();
+    crate::core::privilege::run_privileged("mkdir", &["-p", &target])?;
+    crate::core::privilege::run_privileged("mount", &[&partition.to_string_lossy(), &target])?;
+    Ok(mount_point)
+}
+
+#[cfg(target_os = "linux")]
+fn udisksctl(args: &[&str], device: &Path) -> Result<String> {
+    let output = Command::new("udisksctl")
+        .args(args)
+        .arg(device)
+        .arg("--no-user-interaction")
+        .logged_output()
+        .context("Failed to run udisksctl")?;
+    if !output.status.success() {
+        bail!("udisksctl {} {} failed: {}", args[0], device.display(),
+            String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Mount points of the disk's slices, from `mount` output lines like
+/// "/dev/disk4s1 on /Volumes/USB (msdos, local, nodev)"
+#[cfg(target_os = "macos")]
+fn mounted_at_macos(device: &Path) -> Result<Vec<PathBuf>> {
+    let output = Command::new("mount").logged_output().context("Failed to run mount")?;
+    let prefix = format!("{}s", device.display());
+    Ok(String::from_utf8_lossy(&output.stdout).lines()
+        .filter(|line| line.starts_with(&prefix))
+        .filter_map(|line| line.split_once(" on ")?.1.rsplit_once(" (").map(|(at, _)| PathBuf::from(at)))
+        .collect())
+}
+
+/// Whole disks backing `/`, `/boot` and `/boot/efi`, resolved through
+/// partitions, LVM and LUKS
+#[cfg(target_os = "linux")]
+pub fn system_disks() -> Result<Vec<PathBuf>> {
+    let mut disks = Vec::new();
+
+    for mount in ["/", "/boot", "/boot/efi"] {
+        let output = Command::new("findmnt")
+            .args(["-n", "-o", "SOURCE", "--target", mount])
+            .output()
+            .context("Failed to run findmnt")?;
+        if !output.status.success() {
+            continue;
+        }
+
+        // btrfs subvolumes are reported as /dev/sda2[/@]
+        let source = String::from_utf8_lossy(&output.stdout);
+        let source = source.trim().split('[').next().unwrap_or_default();
+        if !source.starts_with("/dev/") {
+            continue; // overlay, tmpfs, network filesystems
+        }
+
+        // -s walks from the filesystem up to the physical disks
+        let output = Command::new("lsblk")
+            .args(["-l", "-n", "-s", "-p", "-o", "NAME,TYPE", source])
+            .output()
+            .context("Failed to run lsblk")?;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let mut fields = line.split_whitespace();
+            if let (Some(name), Some("disk")) = (fields.next(), fields.next()) {
+                disks.push(PathBuf::from(name));
+            }
+        }
+    }
+
+    if disks.is_empty() {
+        bail!("No disk found backing / or /boot");
+    }
+    disks.sort();
+    disks.dedup();
+    Ok(disks)
+}
+
+/// Whole disks holding the boot volume and, on APFS, its physical store
+#[cfg(target_os = "macos")]
+pub fn system_disks() -> Result<Vec<PathBuf>> {
+    let output = Command::new("diskutil")
+        .args(["info", "/"])
+        .output()
+        .context("Failed to run diskutil")?;
+    if !output.status.success() {
+        bail!("diskutil could not describe the boot volume");
+    }
+
+    let mut disks = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match key.trim() {
+            "Part of Whole" => disks.push(PathBuf::from(format!("/dev/{}", value))),
+            // "disk0s2" lives on disk0
+            "APFS Physical Store" => {
+                let whole = value.rsplit_once('s').map(|(disk, _)| disk).unwrap_or(value);
+                disks.push(PathBuf::from(format!("/dev/{}", whole)));
+            }
+            _ => {}
+        }
+    }
+
+    if disks.is_empty() {
+        bail!("No disk found backing /");
+    }
+    Ok(disks)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn system_disks() -> Result<Vec<PathBuf>> {
+    bail!("System disk detection not supported on this platform");
+}
+
+/// Compare through symlinks such as /dev/disk/by-id
+pub fn is_system_disk(device: &Path, system_disks: &[PathBuf]) -> bool {
+    let device = fs::canonicalize(device).unwrap_or_else(|_| device.to_path_buf());
+    system_disks.iter().any(|disk| fs::canonicalize(disk).unwrap_or_else(|_| disk.clone()) == device)
+}
+
 fn format_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes as f64;
@@ -396,4 +699,48 @@ fn format_size(bytes: u64) -> String {
     } else {
         format!("{:.1} {}", size, UNITS[unit_index])
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn identity_from_scsi_attributes() {
+        let root = tempfile::tempdir().unwrap();
+        let dev = root.path().join("block/sdb");
+        write(&dev.join("device/vendor"), "SanDisk \n");
+        write(&dev.join("device/model"), "Ultra           \n");
+
+        let identity = DeviceIdentity::read(&dev, &root.path().join("udev"));
+        assert_eq!(identity.vendor.as_deref(), Some("SanDisk"));
+        assert_eq!(identity.display_name("sdb"), "SanDisk Ultra");
+    }
+
+    #[test]
+    fn identity_falls_back_to_usb_descriptor_then_udev() {
+        let root = tempfile::tempdir().unwrap();
+        let usb = root.path().join("devices/usb1/1-1");
+        write(&usb.join("product"), "Flash Voyager\n");
+        let dev = usb.join("1-1:1.0/host0/block/sdc");
+        write(&dev.join("dev"), "8:32\n");
+        write(&root.path().join("udev/b8:32"), "E:ID_VENDOR=Corsair\nE:ID_MODEL=Voyager_GT\n");
+
+        let identity = DeviceIdentity::read(&dev, &root.path().join("udev"));
+        assert_eq!(identity.vendor.as_deref(), Some("Corsair"));
+        assert_eq!(identity.model.as_deref(), Some("Flash Voyager"));
+    }
+
+    #[test]
+    fn identity_missing_uses_fallback_name() {
+        let root = tempfile::tempdir().unwrap();
+        let identity = DeviceIdentity::read(&root.path().join("block/sdd"), &root.path().join("udev"));
+        assert_eq!(identity, DeviceIdentity::default());
+        assert_eq!(identity.display_name("sdd"), "sdd");
+    }
+}
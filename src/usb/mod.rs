@@ -65,6 +65,41 @@ pub struct MultiBootConfig {
     pub entries: Vec<BootEntry>,
 }
 
+impl MultiBootConfig {
+    /// File kept at the root of the multi-boot data partition
+    pub const FILE_NAME: &'static str = "pkmgr-multiboot.json";
+
+    pub fn new(bootloader: BootloaderType) -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            version: "1".to_string(),
+            created: now,
+            updated: now,
+            bootloader,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Load the config from a mounted device, or start a new one
+    pub fn load_or_new(usb_root: &Path, bootloader: BootloaderType) -> Result<Self> {
+        let path = usb_root.join(Self::FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::new(bootloader));
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, usb_root: &Path) -> Result<()> {
+        let path = usb_root.join(Self::FILE_NAME);
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BootloaderType {
     Grub2,
@@ -107,24 +142,18 @@ impl UsbDevice {
     }
 }
 
-/// Check if a device is safe to use for USB operations
+/// Check if a device is safe to use for USB operations. The disks backing
+/// `/`, `/boot` and `/boot/efi` are found at runtime and always refused, so a
+/// USB enclosure that happens to be `/dev/sda` is still usable
 pub fn is_device_safe(device_path: &Path) -> Result<bool> {
-    // Never allow operations on these devices
-    let forbidden_patterns = vec![
-        "/dev/sda",     // Usually system disk
-        "/dev/nvme",    // NVMe drives
-        "/dev/md",      // RAID arrays
-        "/dev/dm",      // Device mapper (LVM)
-        "/dev/loop",    // Loop devices
-    ];
-
+    // Virtual block devices are never USB targets
+    let virtual_prefixes = ["/dev/md", "/dev/dm-", "/dev/mapper/", "/dev/loop", "/dev/ram", "/dev/zram"];
     let path_str = device_path.to_string_lossy();
-    for pattern in forbidden_patterns {
-        if path_str.starts_with(pattern) {
-            return Ok(false);
-        }
+    if virtual_prefixes.iter().any(|prefix| path_str.starts_with(prefix)) {
+        return Ok(false);
     }
 
-    // Additional safety checks will be in device.rs
-    Ok(true)
-}
\ No newline at end of file
+    let system_disks = device::system_disks()
+        .context("Could not determine the system disk; refusing to touch any device")?;
+    Ok(!device::is_system_disk(device_path, &system_disks))
+}
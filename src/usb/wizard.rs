@@ -1,15 +1,17 @@
 use anyhow::{Context, Result, bail};
 use std::path::{Path, PathBuf};
 use crate::ui::output::Output;
-use crate::ui::prompt::Prompt;
+use crate::ui::prompt::{self, Prompt};
 use super::{UsbDevice, UsbOperation};
 use super::device::DeviceDetector;
 use super::writer::UsbWriter;
+use crate::core::exit::Cancelled;
 
 pub struct UsbWizard {
     output: Output,
     prompt: Prompt,
     detector: DeviceDetector,
+    assume_yes: bool,
 }
 
 impl UsbWizard {
@@ -19,9 +21,16 @@ impl UsbWizard {
             output,
             prompt: Prompt::new(emoji_enabled),
             detector: DeviceDetector::new(),
+            assume_yes: false,
         }
     }
 
+    /// Skip the typed wipe confirmation (--yes)
+    pub fn with_assume_yes(mut self, assume_yes: bool) -> Self {
+        self.assume_yes = assume_yes;
+        self
+    }
+
     /// Launch the interactive USB wizard
     pub async fn run(&self) -> Result<()> {
         self.output.section("USB Device Setup Wizard");
@@ -197,20 +206,10 @@ impl UsbWizard {
                     self.list_multiboot_isos(&device.path).await?;
                 }
                 "6" => {
-                    // Erase device
-                    self.output.error(&format!(
-                        "WARNING: This will PERMANENTLY ERASE all data on {}",
-                        device.path.display()
-                    ));
-
-                    let confirm = self.prompt.input("Type 'YES' in capitals to confirm: ")?;
-                    if confirm == "YES" {
-                        return Ok(Some(UsbOperation::Erase {
-                            device: device.path.clone(),
-                        }));
-                    } else {
-                        self.output.info("Erase cancelled.");
-                    }
+                    // Erase device; confirmed with the device details before running
+                    return Ok(Some(UsbOperation::Erase {
+                        device: device.path.clone(),
+                    }));
                 }
                 "b" => return Ok(None),
                 "q" => std::process::exit(0),
@@ -352,6 +351,18 @@ impl UsbWizard {
 
     /// Execute the selected operation
     async fn execute_operation(&self, operation: UsbOperation, device: &UsbDevice) -> Result<()> {
+        let wipe_action = match &operation {
+            UsbOperation::WriteSingle { .. } => Some("Writing the ISO"),
+            UsbOperation::CreateMultiBoot { .. } => Some("Creating a multi-boot USB"),
+            UsbOperation::Erase { .. } => Some("Erasing"),
+            _ => None,
+        };
+        if let Some(action) = wipe_action {
+            if !confirm_wipe(device, action, self.assume_yes, &self.output)? {
+                return Err(Cancelled("Operation cancelled; device left untouched".to_string()).into());
+            }
+        }
+
         // First unmount the device if needed
         if device.is_mounted {
             self.output.progress("Unmounting device...");
@@ -361,33 +372,14 @@ impl UsbWizard {
         let writer = UsbWriter::new(self.output.clone());
 
         match operation {
-            UsbOperation::WriteSingle { iso_path, device: device_path } => {
-                // Confirm operation
-                self.output.warn(&format!(
-                    "This will overwrite all data on {}",
-                    device_path.display()
-                ));
-
-                if !self.prompt.confirm("Continue?")? {
-                    return Ok(());
-                }
-
+            UsbOperation::WriteSingle { iso_path, device: _ } => {
                 writer.write_iso(&iso_path, device, true).await?;
 
                 self.output.info("Safely ejecting device...");
                 self.detector.eject_device(device)?;
             }
 
-            UsbOperation::CreateMultiBoot { device: device_path, initial_isos } => {
-                self.output.warn(&format!(
-                    "This will erase {} and create a multi-boot USB",
-                    device_path.display()
-                ));
-
-                if !self.prompt.confirm("Continue?")? {
-                    return Ok(());
-                }
-
+            UsbOperation::CreateMultiBoot { device: _, initial_isos } => {
                 // This would create the multi-boot structure
                 self.create_multiboot_usb(device, initial_isos).await?;
             }
@@ -450,6 +442,72 @@ impl UsbWizard {
     }
 }
 
+/// Show what is about to be destroyed and make the user type the device's
+/// size or model back, so a wrong-drive pick is caught; `--yes` skips typing
+/// and non-interactive sessions without it are an error
+pub fn confirm_wipe(device: &UsbDevice, action: &str, assume_yes: bool, output: &Output) -> Result<bool> {
+    output.section(&format!("🔥 {} will DESTROY all data on this device", action));
+    output.print(&format!("  Device:     {}", device.path.display()));
+    output.print(&format!("  Model:      {}", device_model(device).unwrap_or_else(|| "unknown".to_string())));
+    output.print(&format!("  Size:       {}", device.format_size()));
+    if device.partitions.is_empty() {
+        output.print("  Partitions: none");
+    } else {
+        output.print("  Partitions:");
+        for partition in &device.partitions {
+            let mut line = format!("    {}  {}  {}",
+                partition.path.display(),
+                format_size(partition.size_bytes),
+                partition.filesystem.as_deref().unwrap_or("unknown"));
+            if let Some(label) = &partition.label {
+                line.push_str(&format!("  \"{}\"", label));
+            }
+            if let Some(mount_point) = &partition.mount_point {
+                line.push_str(&format!("  mounted at {}", mount_point.display()));
+            }
+            output.print(&line);
+        }
+    }
+    output.print("");
+
+    if assume_yes {
+        output.warn("--yes given; skipping the typed confirmation");
+        return Ok(true);
+    }
+    if !prompt::is_interactive() {
+        return Err(Cancelled(format!("non-interactive session: refusing to wipe {} without --yes", device.path.display())).into());
+    }
+
+    let hint = match device_model(device) {
+        Some(model) => format!("Type the size ({}) or model ({}) to continue: ", device.format_size(), model),
+        None => format!("Type the size ({}) to continue: ", device.format_size()),
+    };
+    let answer = Prompt::new(output.emoji_enabled).input(&hint)?;
+    if wipe_answer_matches(device, &answer) {
+        Ok(true)
+    } else {
+        output.warn("Confirmation did not match the device size or model");
+        Ok(false)
+    }
+}
+
+fn device_model(device: &UsbDevice) -> Option<String> {
+    device.model.as_ref()
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty())
+}
+
+/// Case and whitespace are ignored so "14.9gb" matches "14.9 GB"
+fn wipe_answer_matches(device: &UsbDevice, answer: &str) -> bool {
+    let normalize = |s: &str| -> String {
+        s.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase()
+    };
+    let answer = normalize(answer);
+    !answer.is_empty()
+        && (answer == normalize(&device.format_size())
+            || device_model(device).is_some_and(|model| normalize(&model) == answer))
+}
+
 fn format_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes as f64;
@@ -465,4 +523,36 @@ fn format_size(bytes: u64) -> String {
     } else {
         format!("{:.1} {}", size, UNITS[unit_index])
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(model: Option<&str>) -> UsbDevice {
+        UsbDevice {
+            path: PathBuf::from("/dev/sdb"),
+            name: "SanDisk Ultra".to_string(),
+            size_bytes: 16_000_000_000,
+            size_display: String::new(),
+            filesystem: None,
+            vendor: Some("SanDisk".to_string()),
+            model: model.map(str::to_string),
+            is_removable: true,
+            is_mounted: false,
+            mount_points: Vec::new(),
+            partitions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn wipe_answer_accepts_size_or_model() {
+        let usb = device(Some("Ultra USB 3.0"));
+        assert!(wipe_answer_matches(&usb, "16.0 GB"));
+        assert!(wipe_answer_matches(&usb, " 16.0gb"));
+        assert!(wipe_answer_matches(&usb, "ultra usb 3.0"));
+        assert!(!wipe_answer_matches(&usb, "yes"));
+        assert!(!wipe_answer_matches(&usb, ""));
+        assert!(!wipe_answer_matches(&device(None), ""));
+    }
+}
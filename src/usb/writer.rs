@@ -7,28 +7,60 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::{Instant, Duration};
 use crate::ui::output::Output;
+use crate::ui::prompt::Prompt;
 use super::UsbDevice;
 
 pub struct UsbWriter {
     output: Output,
+    force: bool,
 }
 
 impl UsbWriter {
     pub fn new(output: Output) -> Self {
-        Self { output }
+        Self { output, force: false }
     }
 
-    /// Write an ISO to a USB device (dd-style)
-    pub async fn write_iso(&self, iso_path: &Path, device: &UsbDevice, verify: bool) -> Result<()> {
-        // Safety checks
-        if !device.is_removable {
-            bail!("Device {} is not removable. Refusing to write for safety.", device.path.display());
-        }
+    /// Allow non-removable targets after a typed confirmation
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
 
+    /// System disks are always refused; fixed disks need --force and the
+    /// device path typed back
+    fn check_target(&self, device: &UsbDevice, action: &str) -> Result<()> {
         if !super::is_device_safe(&device.path)? {
-            bail!("Device {} is not safe to write to.", device.path.display());
+            bail!("Device {} backs the running system. Refusing to {}.", device.path.display(), action);
+        }
+
+        if device.is_removable {
+            return Ok(());
+        }
+
+        if !self.force {
+            bail!(
+                "Device {} is not removable. Refusing to {} for safety; use --force to override.",
+                device.path.display(), action
+            );
         }
 
+        let prompt = Prompt::new(self.output.emoji_enabled);
+        let path = device.path.display().to_string();
+        let message = format!(
+            "{} ({}, {}) is NOT a removable disk. All data on it will be lost.",
+            path, device.name, device.format_size()
+        );
+        if !prompt.destructive_confirm(&message, &path)? {
+            bail!("Confirmation did not match; {} left untouched", path);
+        }
+        Ok(())
+    }
+
+    /// Write an ISO to a USB device (dd-style)
+    pub async fn write_iso(&self, iso_path: &Path, device: &UsbDevice, verify: bool) -> Result<()> {
+        // Safety checks
+        self.check_target(device, "write")?;
+
         // Check ISO exists and get size
         let iso_metadata = tokio::fs::metadata(iso_path).await
             .context("Failed to read ISO file")?;
@@ -182,13 +214,7 @@ impl UsbWriter {
 
     /// Erase a USB device completely
     pub async fn erase_device(&self, device: &UsbDevice, filesystem: &str) -> Result<()> {
-        if !device.is_removable {
-            bail!("Device {} is not removable. Refusing to erase for safety.", device.path.display());
-        }
-
-        if !super::is_device_safe(&device.path)? {
-            bail!("Device {} is not safe to erase.", device.path.display());
-        }
+        self.check_target(device, "erase")?;
 
         self.output.warn(&format!(
             "Erasing {} will permanently delete all data!",
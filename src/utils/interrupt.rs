@@ -0,0 +1,14 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by commands that stop cleanly on Ctrl-C themselves
+static HANDLED_BY_COMMAND: AtomicBool = AtomicBool::new(false);
+
+/// Keep the process-wide SIGINT handler from exiting with 130, because the
+/// running command listens for Ctrl-C and shuts down on its own
+pub fn handle_in_command() {
+    HANDLED_BY_COMMAND.store(true, Ordering::Relaxed);
+}
+
+pub fn handled_by_command() -> bool {
+    HANDLED_BY_COMMAND.load(Ordering::Relaxed)
+}
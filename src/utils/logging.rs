@@ -0,0 +1,222 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Output};
+use std::sync::{Mutex, RwLock};
+
+/// Values that must never reach the log file
+static SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Token shapes that are redacted even when not registered
+static TOKEN_PATTERNS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| vec![
+    (Regex::new(r"\b(ghp|gho|ghu|ghs|ghr)_[A-Za-z0-9]{20,}").unwrap(), "[REDACTED]"),
+    (Regex::new(r"\bgithub_pat_[A-Za-z0-9_]{20,}").unwrap(), "[REDACTED]"),
+    (Regex::new(r"(?i)(authorization:\s*(?:bearer|token)\s+)\S+").unwrap(), "${1}[REDACTED]"),
+]);
+
+/// Log file sink; every record goes through `redact`
+struct FileLogger {
+    file: Mutex<File>,
+    level: LevelFilter,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "{} {:<5} {}: {}\n",
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+            record.level(),
+            record.target(),
+            redact(&record.args().to_string())
+        );
+
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Level for the log file: --quiet keeps commands and errors, the default
+/// adds captured output, --verbose adds everything
+pub fn level_for(verbose: bool, quiet: bool) -> LevelFilter {
+    if verbose {
+        LevelFilter::Trace
+    } else if quiet {
+        LevelFilter::Info
+    } else {
+        LevelFilter::Debug
+    }
+}
+
+/// Start writing the log file; without --log-file logging stays disabled
+pub fn init(path: &Path, level: LevelFilter) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))?;
+
+    log::set_boxed_logger(Box::new(FileLogger { file: Mutex::new(file), level }))
+        .context("Logger already initialized")?;
+    log::set_max_level(level);
+
+    log::info!("pkmgr {} started: {}", env!("CARGO_PKG_VERSION"), std::env::args().collect::<Vec<_>>().join(" "));
+    Ok(())
+}
+
+/// Register a secret so it is masked wherever it appears in the log
+pub fn register_secret(secret: &str) {
+    let secret = secret.trim();
+    // Very short values would mask unrelated text
+    if secret.len() < 8 {
+        return;
+    }
+    if let Ok(mut secrets) = SECRETS.write() {
+        if !secrets.iter().any(|s| s == secret) {
+            secrets.push(secret.to_string());
+        }
+    }
+}
+
+/// Mask registered secrets and anything shaped like a token
+pub fn redact(message: &str) -> String {
+    let mut redacted = message.to_string();
+
+    if let Ok(secrets) = SECRETS.read() {
+        for secret in secrets.iter() {
+            redacted = redacted.replace(secret.as_str(), "[REDACTED]");
+        }
+    }
+
+    for (pattern, replacement) in TOKEN_PATTERNS.iter() {
+        redacted = pattern.replace_all(&redacted, *replacement).into_owned();
+    }
+
+    redacted
+}
+
+fn describe(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn log_output(level: Level, stream: &str, bytes: &[u8]) {
+    let text = String::from_utf8_lossy(bytes);
+    let text = text.trim_end();
+    if !text.is_empty() {
+        log::log!(level, "{}:\n{}", stream, text);
+    }
+}
+
+/// Run native commands with their command line, exit code and captured
+/// output recorded in the log file
+pub trait LoggedCommand {
+    fn logged_output(&mut self) -> io::Result<Output>;
+    fn logged_status(&mut self) -> io::Result<ExitStatus>;
+}
+
+/// Async counterpart of `LoggedCommand` for tokio commands
+#[async_trait]
+pub trait LoggedAsyncCommand {
+    async fn logged_output(&mut self) -> io::Result<Output>;
+}
+
+fn log_result(command: &str, result: &io::Result<Output>) {
+    match result {
+        Ok(output) => {
+            log::info!("exit: {} ({})", output.status, command);
+            let level = if output.status.success() { Level::Debug } else { Level::Info };
+            log_output(level, "stdout", &output.stdout);
+            log_output(level, "stderr", &output.stderr);
+        }
+        Err(e) => log::error!("failed to spawn {}: {}", command, e),
+    }
+}
+
+impl LoggedCommand for Command {
+    fn logged_output(&mut self) -> io::Result<Output> {
+        let command = describe(self);
+        log::info!("exec: {}", command);
+
+        let result = self.output();
+        log_result(&command, &result);
+        result
+    }
+
+    fn logged_status(&mut self) -> io::Result<ExitStatus> {
+        let command = describe(self);
+        log::info!("exec: {}", command);
+
+        // Output streams to the terminal, so only the exit code is recorded
+        match self.status() {
+            Ok(status) => {
+                log::info!("exit: {} ({})", status, command);
+                Ok(status)
+            }
+            Err(e) => {
+                log::error!("failed to spawn {}: {}", command, e);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LoggedAsyncCommand for tokio::process::Command {
+    async fn logged_output(&mut self) -> io::Result<Output> {
+        let command = describe(self.as_std());
+        log::info!("exec: {}", command);
+
+        let result = self.output().await;
+        log_result(&command, &result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_registered_secrets() {
+        register_secret("s3cr3t-value-for-test");
+        assert_eq!(redact("token=s3cr3t-value-for-test ok"), "token=[REDACTED] ok");
+    }
+
+    #[test]
+    fn redacts_token_shapes() {
+        let token = format!("ghp_{}", "a".repeat(36));
+        assert_eq!(redact(&format!("using {}", token)), "using [REDACTED]");
+        assert_eq!(
+            redact("Authorization: Bearer abc.def.ghi"),
+            "Authorization: Bearer [REDACTED]"
+        );
+    }
+}
@@ -1,4 +1,7 @@
 pub mod download;
 pub mod archive;
 pub mod crypto;
-pub mod fs;
\ No newline at end of file
+pub mod fs;
+pub mod interrupt;
+pub mod logging;
+